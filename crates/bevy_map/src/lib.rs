@@ -42,9 +42,9 @@
 //! - [`runtime`] - Map loading and rendering (requires `runtime` feature)
 
 // =============================================================================
-// Re-export derive macro at top level for ergonomics
+// Re-export derive macros at top level for ergonomics
 // =============================================================================
-pub use bevy_map_derive::MapEntity;
+pub use bevy_map_derive::{FromMapValue, MapEntity, ToMapValue};
 
 // =============================================================================
 // Core module - fundamental data structures
@@ -64,9 +64,9 @@ pub mod core {
 
 // Core type re-exports at crate root
 pub use bevy_map_core::{
-    CollisionData, CollisionShape, EditorProject, EntityInstance, Layer, LayerData, LayerType,
-    Level, MapProject, MapProjectBuilder, OneWayDirection, PhysicsBody, TileProperties, Tileset,
-    TilesetImage, Value, OCCUPIED_CELL,
+    CollisionData, CollisionShape, EditorProject, EntityInstance, FromMapValue, Layer, LayerData,
+    LayerType, Level, MapProject, MapProjectBuilder, OneWayDirection, PhysicsBody, TileProperties,
+    Tileset, TilesetImage, ToMapValue, Value, OCCUPIED_CELL,
 };
 
 // =============================================================================
@@ -88,8 +88,8 @@ pub use bevy_map_animation::{
     AnimatedSprite, AnimationCustomEvent, AnimationDef, AnimationEventExt, AnimationParticleEvent,
     AnimationSoundEvent, AnimationTrigger, AnimationTriggerEvent, AnimationTriggerRegistry,
     AnimationTriggerType, AnimationTriggered, AnimationWindow, AnimationWindowChanged,
-    AnimationWindowEvent, AnimationWindowRegistry, AnimationWindowType, LoopMode,
-    SpriteAnimationPlugin, SpriteData, TriggerPayload, WindowPhase, WindowTracker,
+    AnimationWindowEvent, AnimationWindowRegistry, AnimationWindowType, HitboxKind, LoopMode,
+    SpriteAnimationPlugin, SpriteData, TriggerPayload, WindowPhase, WindowShape, WindowTracker,
 };
 
 // =============================================================================
@@ -102,14 +102,19 @@ pub use bevy_map_animation::{
 /// - [`DialogueTree`] - Complete dialogue definition
 /// - [`DialogueNode`] - Individual dialogue nodes
 /// - [`DialogueRunner`] - Runtime dialogue state
+/// - [`DialogueVariables`] - Variables read by conditions and written by actions
 /// - [`DialoguePlugin`] - Bevy plugin for dialogue
+/// - [`DialogueUiPlugin`] - Optional ready-made dialogue box
+/// - [`StringTable`] / [`CurrentLocale`] - Localized text
 pub mod dialogue {
     pub use bevy_map_dialogue::*;
 }
 
 pub use bevy_map_dialogue::{
-    DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-    DialogueNodeType, DialoguePlugin, DialogueRunner, DialogueTree, StartDialogueEvent,
+    CurrentLocale, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle,
+    DialogueNode, DialogueNodeType, DialoguePlugin, DialogueRunner, DialogueSignalEvent,
+    DialogueSignalExt, DialogueSignalType, DialogueTree, DialogueUiPlugin, DialogueUiStyle,
+    DialogueValue, DialogueVariables, Localizer, StartDialogueEvent, StringTable,
 };
 
 // =============================================================================
@@ -163,11 +168,12 @@ pub mod runtime {
 
 #[cfg(feature = "runtime")]
 pub use bevy_map_runtime::{
-    attach_dialogues, complete_sprite_loads, spawn_map_project, spawn_sprite_components, Dialogue,
-    EntityProperties, EntityRegistry, MapCollider, MapCollisionPlugin, MapEntityExt,
-    MapEntityMarker, MapEntityType, MapHandle, MapLoadError, MapProjectLoader, MapRoot,
-    MapRuntimePlugin, MapSpawnedEvent, SpawnMapEvent, SpawnMapProjectEvent, SpriteSlot,
-    TilesetTextures,
+    apply_automap_rules, attach_dialogues, capture_map_entities, complete_sprite_loads,
+    spawn_map_project, spawn_sprite_components, AnimationHitbox, AnimationHitboxPlugin,
+    ApplyAutomapRulesEvent, Dialogue, EntityProperties, EntityRegistry, MapCollider,
+    MapCollisionPlugin, MapEntityExt, MapEntityMarker, MapEntityType, MapHandle, MapLoadError,
+    MapProjectLoader, MapRoot, MapRuntimePlugin, MapSpawnedEvent, SpawnMapEvent,
+    SpawnMapProjectEvent, SpriteSlot, TilesetTextures,
 };
 
 // =============================================================================
@@ -181,8 +187,8 @@ pub use bevy_map_runtime::{
 /// use bevy_map::prelude::*;
 /// ```
 pub mod prelude {
-    // Derive macro
-    pub use crate::MapEntity;
+    // Derive macros (FromMapValue and ToMapValue are also the traits)
+    pub use crate::{FromMapValue, MapEntity, ToMapValue};
 
     // Core types
     pub use crate::{
@@ -195,8 +201,10 @@ pub mod prelude {
 
     // Dialogue
     pub use crate::{
-        DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-        DialoguePlugin, DialogueRunner, DialogueTree, StartDialogueEvent,
+        CurrentLocale, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle,
+        DialogueNode, DialoguePlugin, DialogueRunner, DialogueSignalEvent, DialogueSignalExt,
+        DialogueTree, DialogueUiPlugin, DialogueVariables, Localizer, StartDialogueEvent,
+        StringTable,
     };
 
     // Autotile
@@ -205,7 +213,7 @@ pub mod prelude {
     // Runtime (if enabled)
     #[cfg(feature = "runtime")]
     pub use crate::{
-        spawn_map_project, EntityRegistry, MapEntityExt, MapHandle, MapRoot, MapRuntimePlugin,
-        SpawnMapEvent, SpawnMapProjectEvent, TilesetTextures,
+        spawn_map_project, ApplyAutomapRulesEvent, EntityRegistry, MapEntityExt, MapHandle,
+        MapRoot, MapRuntimePlugin, SpawnMapEvent, SpawnMapProjectEvent, TilesetTextures,
     };
 }
@@ -0,0 +1,135 @@
+//! Compiles the data code `bevy_map_codegen` generates for
+//! `fixtures/data_project.map.json` and loads the project's data through it
+//!
+//! The generated files are checked in under `generated/`. After changing the
+//! generators, run this test with `BLESS=1` to rewrite them.
+
+#![allow(dead_code)]
+
+// Kept exactly as generated, so rustfmt must not touch them
+#[rustfmt::skip]
+#[path = "generated/data.rs"]
+mod data;
+#[rustfmt::skip]
+#[path = "generated/enums.rs"]
+mod enums;
+
+use bevy::asset::AssetEvent;
+use bevy::prelude::*;
+use bevy_map::{EditorProject, Level, MapProject};
+use data::*;
+use enums::*;
+use uuid::Uuid;
+
+const PROJECT: &str = include_str!("fixtures/data_project.map.json");
+
+fn project() -> EditorProject {
+    serde_json::from_str(PROJECT).unwrap()
+}
+
+fn id(id: &str) -> Uuid {
+    Uuid::parse_str(id).unwrap()
+}
+
+#[test]
+fn test_generated_code_is_current() {
+    let schema = bevy_map_schema::parse_schema(&project().schema.unwrap().to_string()).unwrap();
+    let generated = [
+        ("enums.rs", bevy_map_codegen::enums::generate_enums(&schema)),
+        (
+            "data.rs",
+            bevy_map_codegen::data::generate_data_types(&schema),
+        ),
+    ];
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/generated");
+    for (file, code) in generated {
+        let code = code.unwrap();
+        let path = dir.join(file);
+        if std::env::var_os("BLESS").is_some() {
+            std::fs::write(&path, &code).unwrap();
+        }
+        let checked_in = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            checked_in == code,
+            "tests/generated/{} is out of date, rerun with BLESS=1",
+            file
+        );
+    }
+}
+
+#[test]
+fn test_deserialize_instance() {
+    let item: Item = serde_json::from_value(serde_json::json!({
+        "rarity": "rare",
+        "stats": { "damage": 3 },
+        "quest": "1b5e8d7a-43c2-4e6f-8d1a-0c9b2e7f4a10",
+    }))
+    .unwrap();
+    assert_eq!(item.display_name, "Sword");
+    assert_eq!(item.rarity, Rarity::Rare);
+    assert_eq!(item.stats.damage, 3);
+    assert!(item.tags.is_empty());
+    assert_eq!(
+        item.quest,
+        Some(DataId::new(id("1b5e8d7a-43c2-4e6f-8d1a-0c9b2e7f4a10")))
+    );
+
+    assert!(serde_json::from_value::<Item>(serde_json::json!("Sword")).is_err());
+}
+
+#[test]
+fn test_game_data_from_project() {
+    let data = GameData::from_json(PROJECT).unwrap();
+    assert_eq!(data.item.len(), 2);
+    assert_eq!(data.quest.len(), 1);
+
+    let sword_id = DataId::<Item>::new(id("6a1f3c52-0d3e-4f0b-9a43-2b1c7e0a9f01"));
+    let sword = data.get(sword_id).unwrap();
+    assert_eq!(sword.display_name, "Fire Sword");
+    assert_eq!(sword.tags, vec!["fire", "blade"]);
+
+    // Typed references resolve through GameData
+    let quest = data.get(sword.quest.unwrap()).unwrap();
+    assert_eq!(quest.title, "Forge the sword");
+    assert_eq!(quest.reward, 10);
+    let upgrade = data.get(sword.upgrades[0]).unwrap();
+    assert_eq!(upgrade.display_name, "Sword");
+    assert_eq!(upgrade.rarity, Rarity::Common);
+
+    assert_eq!(data.iter::<Quest>().count(), 1);
+    assert_eq!(<Item as GameDataType>::TYPE_NAME, "Item");
+}
+
+#[test]
+fn test_game_data_from_map() {
+    let map = project().to_map_project().unwrap();
+    let data = GameData::from_map(&map);
+    assert_eq!(data.item.len(), 2);
+    assert_eq!(data.quest.len(), 1);
+}
+
+#[test]
+fn test_load_game_data_system() {
+    let mut app = App::new();
+    app.init_resource::<Assets<MapProject>>()
+        .add_message::<AssetEvent<MapProject>>()
+        .init_resource::<GameData>()
+        .add_systems(Update, load_game_data);
+
+    let mut maps = app.world_mut().resource_mut::<Assets<MapProject>>();
+    let empty = maps.add(MapProject::new(
+        Level::new("Empty".to_string(), 1, 1),
+        Vec::new(),
+    ));
+    let id = maps.add(project().to_map_project().unwrap()).id();
+    app.world_mut()
+        .write_message(AssetEvent::LoadedWithDependencies { id });
+    app.update();
+    assert_eq!(app.world().resource::<GameData>().item.len(), 2);
+
+    // Maps without data leave the loaded data alone
+    app.world_mut()
+        .write_message(AssetEvent::LoadedWithDependencies { id: empty.id() });
+    app.update();
+    assert_eq!(app.world().resource::<GameData>().quest.len(), 1);
+}
@@ -0,0 +1,210 @@
+//! Tests for `#[derive(MapEntity)]`, `#[derive(FromMapValue)]` and
+//! `#[derive(ToMapValue)]`
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_map::prelude::*;
+use bevy_map::runtime::MapEntityType;
+
+#[derive(Debug, Default, PartialEq, FromMapValue, ToMapValue)]
+enum Mood {
+    #[default]
+    Calm,
+    BigBoss,
+}
+
+#[derive(Debug, Default, PartialEq, FromMapValue, ToMapValue)]
+struct Stats {
+    hp: i32,
+    #[map_prop(name = "spd", default = 1.5)]
+    speed: f32,
+    #[map_prop(default = "BigBoss")]
+    mood: Mood,
+}
+
+#[derive(Debug, Default, PartialEq, FromMapValue, ToMapValue)]
+struct Label(String);
+
+#[derive(Component, MapEntity, Debug, Default)]
+#[map_entity(type_name = "Enemy")]
+struct Enemy {
+    #[map_prop]
+    name: String,
+    #[map_prop(name = "hp", default = 100)]
+    health: i32,
+    #[map_prop]
+    mood: Mood,
+    #[map_prop]
+    stats: Stats,
+    #[map_sprite("sprite")]
+    sprite: Option<Handle<Image>>,
+    not_a_prop: u32,
+}
+
+/// Readable from a property, but with no `ToMapValue` impl
+#[derive(Debug, Default, PartialEq)]
+struct Cooldown(f32);
+
+impl bevy_map::core::FromMapValue for Cooldown {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_float().map(|secs| Cooldown(secs as f32))
+    }
+}
+
+#[derive(Component, MapEntity, Debug, Default)]
+#[map_entity(type_name = "Spawner")]
+struct Spawner {
+    #[map_prop]
+    count: u32,
+    #[map_prop(name = "every", skip_write)]
+    cooldown: Cooldown,
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_enum_matches_variant_and_snake_case() {
+    assert_eq!(Mood::from_map_value(&string("Calm")), Some(Mood::Calm));
+    assert_eq!(Mood::from_map_value(&string("calm")), Some(Mood::Calm));
+    assert_eq!(
+        Mood::from_map_value(&string("BigBoss")),
+        Some(Mood::BigBoss)
+    );
+    assert_eq!(
+        Mood::from_map_value(&string("big_boss")),
+        Some(Mood::BigBoss)
+    );
+    assert_eq!(Mood::from_map_value(&string("bigboss")), None);
+    assert_eq!(Mood::from_map_value(&Value::Int(1)), None);
+
+    // Written back as the variant name
+    assert_eq!(Mood::BigBoss.to_map_value(), string("BigBoss"));
+}
+
+#[test]
+fn test_struct_names_and_defaults() {
+    let value = Value::Object(HashMap::from([
+        ("hp".to_string(), Value::Int(10)),
+        ("spd".to_string(), Value::Float(2.5)),
+        ("mood".to_string(), string("calm")),
+    ]));
+    let stats = Stats::from_map_value(&value).unwrap();
+    assert_eq!(
+        stats,
+        Stats {
+            hp: 10,
+            speed: 2.5,
+            mood: Mood::Calm,
+        }
+    );
+
+    // Missing keys use the map_prop default, or the field's Default
+    let stats = Stats::from_map_value(&Value::Object(HashMap::new())).unwrap();
+    assert_eq!(
+        stats,
+        Stats {
+            hp: 0,
+            speed: 1.5,
+            mood: Mood::BigBoss,
+        }
+    );
+    assert_eq!(Stats::from_map_value(&string("not an object")), None);
+
+    // Round trip through the renamed keys
+    let stats = Stats {
+        hp: 3,
+        speed: 4.0,
+        mood: Mood::BigBoss,
+    };
+    let Value::Object(object) = stats.to_map_value() else {
+        panic!("struct should write an object");
+    };
+    assert_eq!(object.get("spd"), Some(&Value::Float(4.0)));
+    assert!(!object.contains_key("speed"));
+    assert_eq!(Stats::from_map_value(&Value::Object(object)), Some(stats));
+}
+
+#[test]
+fn test_newtype_reads_inner_value() {
+    assert_eq!(
+        Label::from_map_value(&string("boss")),
+        Some(Label("boss".to_string()))
+    );
+    assert_eq!(Label::from_map_value(&Value::Int(1)), None);
+    assert_eq!(Label("boss".to_string()).to_map_value(), string("boss"));
+}
+
+#[test]
+fn test_map_entity_from_instance() {
+    let mut instance = EntityInstance::new("Enemy".to_string(), [0.0, 0.0]);
+    instance.set_string("name", "Grunt".to_string());
+    instance
+        .properties
+        .insert("mood".to_string(), string("big_boss"));
+    instance.properties.insert(
+        "stats".to_string(),
+        Value::Object(HashMap::from([("hp".to_string(), Value::Int(7))])),
+    );
+
+    let enemy = Enemy::from_instance(&instance);
+    assert_eq!(Enemy::type_name(), "Enemy");
+    assert_eq!(enemy.name, "Grunt");
+    assert_eq!(enemy.health, 100);
+    assert_eq!(enemy.mood, Mood::BigBoss);
+    assert_eq!(enemy.stats.hp, 7);
+    assert_eq!(enemy.stats.speed, 1.5);
+    assert!(enemy.sprite.is_none());
+    assert_eq!(enemy.not_a_prop, 0);
+    assert_eq!(Enemy::sprite_properties(), &["sprite"]);
+
+    // Unconvertible values fall back to the default as well
+    instance.set_string("hp", "lots".to_string());
+    assert_eq!(Enemy::from_instance(&instance).health, 100);
+}
+
+#[test]
+fn test_map_entity_write_back() {
+    let enemy = Enemy {
+        name: "Grunt".to_string(),
+        health: 42,
+        mood: Mood::BigBoss,
+        ..Default::default()
+    };
+    let instance = enemy.to_instance();
+    assert_eq!(instance.type_name, "Enemy");
+    assert_eq!(instance.get_string("name"), Some("Grunt"));
+    assert_eq!(instance.get_int("hp"), Some(42));
+    assert_eq!(instance.get_string("mood"), Some("BigBoss"));
+    assert!(!instance.properties.contains_key("not_a_prop"));
+    assert!(!instance.properties.contains_key("sprite"));
+
+    let read_back = Enemy::from_instance(&instance);
+    assert_eq!(read_back.health, 42);
+    assert_eq!(read_back.mood, Mood::BigBoss);
+    assert_eq!(read_back.stats, enemy.stats);
+}
+
+#[test]
+fn test_skip_write() {
+    let mut instance = EntityInstance::new("Spawner".to_string(), [0.0, 0.0]);
+    instance.set_int("count", 3);
+    instance.set_float("every", 2.5);
+
+    let spawner = Spawner::from_instance(&instance);
+    assert_eq!(spawner.count, 3);
+    assert_eq!(spawner.cooldown, Cooldown(2.5));
+
+    // Skipped fields are read but never written back
+    let written = spawner.to_instance();
+    assert_eq!(written.get_int("count"), Some(3));
+    assert!(!written.properties.contains_key("every"));
+
+    let mut existing = instance.clone();
+    existing.set_int("count", 9);
+    spawner.write_properties(&mut existing);
+    assert_eq!(existing.get_int("count"), Some(3));
+    assert_eq!(existing.get_float("every"), Some(2.5));
+}
@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use bevy::asset::AssetEvent;
+use bevy::ecs::message::MessageReader;
+use bevy_map::prelude::*;
+use std::collections::HashMap;
+use super::enums::*;
+/// Typed id of a data instance, looked up with [`GameData::get`]
+pub struct DataId<T> {
+    pub id: uuid::Uuid,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+impl<T> DataId<T> {
+    pub const fn new(id: uuid::Uuid) -> Self {
+        Self {
+            id,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+impl<T> Clone for DataId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for DataId<T> {}
+impl<T> PartialEq for DataId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl<T> Eq for DataId<T> {}
+impl<T> std::hash::Hash for DataId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.id, state);
+    }
+}
+impl<T> std::fmt::Debug for DataId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DataId({})", self.id)
+    }
+}
+impl<T> FromMapValue for DataId<T> {
+    fn from_map_value(value: &bevy_map::core::Value) -> Option<Self> {
+        uuid::Uuid::from_map_value(value).map(Self::new)
+    }
+}
+impl<T> ToMapValue for DataId<T> {
+    fn to_map_value(&self) -> bevy_map::core::Value {
+        self.id.to_map_value()
+    }
+}
+/// A data type whose instances are stored in [`GameData`]
+pub trait GameDataType: Sized + 'static {
+    /// The type's name in the schema
+    const TYPE_NAME: &'static str;
+    /// The instances of this type, by id
+    fn instances(data: &GameData) -> &HashMap<uuid::Uuid, Self>;
+}
+/// Data type `Item`
+#[derive(Debug, Clone, Default, FromMapValue, ToMapValue, serde::Deserialize)]
+#[serde(try_from = "bevy_map::core::Value")]
+pub struct Item {
+    #[map_prop(name = "displayName", default = "Sword")]
+    pub display_name: String,
+    #[map_prop]
+    pub rarity: Rarity,
+    #[map_prop]
+    pub stats: Stats,
+    #[map_prop]
+    pub tags: Vec<String>,
+    #[map_prop]
+    pub upgrades: Vec<DataId<Item>>,
+    #[map_prop]
+    pub quest: Option<DataId<Quest>>,
+}
+impl TryFrom<bevy_map::core::Value> for Item {
+    type Error = String;
+    fn try_from(value: bevy_map::core::Value) -> Result<Self, Self::Error> {
+        Self::from_map_value(&value).ok_or_else(|| "Item must be an object".to_string())
+    }
+}
+/// Data type `Quest`
+#[derive(Debug, Clone, Default, FromMapValue, ToMapValue, serde::Deserialize)]
+#[serde(try_from = "bevy_map::core::Value")]
+pub struct Quest {
+    #[map_prop]
+    pub title: String,
+    #[map_prop(default = 10)]
+    pub reward: i32,
+}
+impl TryFrom<bevy_map::core::Value> for Quest {
+    type Error = String;
+    fn try_from(value: bevy_map::core::Value) -> Result<Self, Self::Error> {
+        Self::from_map_value(&value).ok_or_else(|| "Quest must be an object".to_string())
+    }
+}
+/// Embedded type `Stats`
+#[derive(Debug, Clone, Default, FromMapValue, ToMapValue, serde::Deserialize)]
+#[serde(try_from = "bevy_map::core::Value")]
+pub struct Stats {
+    #[map_prop]
+    pub damage: i32,
+}
+impl TryFrom<bevy_map::core::Value> for Stats {
+    type Error = String;
+    fn try_from(value: bevy_map::core::Value) -> Result<Self, Self::Error> {
+        Self::from_map_value(&value).ok_or_else(|| "Stats must be an object".to_string())
+    }
+}
+impl GameDataType for Item {
+    const TYPE_NAME: &'static str = "Item";
+    fn instances(data: &GameData) -> &HashMap<uuid::Uuid, Self> {
+        &data.item
+    }
+}
+impl GameDataType for Quest {
+    const TYPE_NAME: &'static str = "Quest";
+    fn instances(data: &GameData) -> &HashMap<uuid::Uuid, Self> {
+        &data.quest
+    }
+}
+/// The project's data instances, by type and id
+///
+/// Filled by [`load_game_data`] when a map loads, or build it with
+/// [`GameData::from_project`] or [`GameData::from_json`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct GameData {
+    /// `Item` instances by id
+    pub item: HashMap<uuid::Uuid, Item>,
+    /// `Quest` instances by id
+    pub quest: HashMap<uuid::Uuid, Quest>,
+}
+impl GameData {
+    /// Collect the instances of a project's `data` section
+    ///
+    /// Instances whose properties don't convert are skipped.
+    pub fn from_data(data: Option<&serde_json::Value>) -> Self {
+        let mut game_data = Self::default();
+        let instances = data
+            .and_then(|data| data.get("instances"))
+            .and_then(|instances| instances.as_object());
+        for (type_name, list) in instances.into_iter().flatten() {
+            for instance in list.as_array().into_iter().flatten() {
+                let Some(id) = instance
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .and_then(|id| uuid::Uuid::parse_str(id).ok()) else {
+                    continue;
+                };
+                let properties = bevy_map::core::Value::from_json(
+                    instance.get("properties").cloned().unwrap_or_default(),
+                );
+                match type_name.as_str() {
+                    "Item" => {
+                        if let Some(value) = Item::from_map_value(&properties) {
+                            game_data.item.insert(id, value);
+                        }
+                    }
+                    "Quest" => {
+                        if let Some(value) = Quest::from_map_value(&properties) {
+                            game_data.quest.insert(id, value);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        game_data
+    }
+    /// Collect the data instances of an editor project (`.map.json`)
+    pub fn from_project(project: &bevy_map::EditorProject) -> Self {
+        Self::from_data(project.data.as_ref())
+    }
+    /// Collect the data instances of a loaded map asset
+    pub fn from_map(map: &bevy_map::MapProject) -> Self {
+        Self::from_data(map.data.as_ref())
+    }
+    /// Parse an editor project (`.map.json`) and collect its data instances
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let project: bevy_map::EditorProject = serde_json::from_str(json)?;
+        Ok(Self::from_project(&project))
+    }
+    /// Look a data instance up by its typed id
+    pub fn get<T: GameDataType>(&self, id: DataId<T>) -> Option<&T> {
+        T::instances(self).get(&id.id)
+    }
+    /// All instances of a data type with their ids
+    pub fn iter<T: GameDataType>(&self) -> impl Iterator<Item = (DataId<T>, &T)> {
+        T::instances(self).iter().map(|(id, value)| (DataId::new(*id), value))
+    }
+}
+/// Replace [`GameData`] with the data of each map that loads or is hot-reloaded
+///
+/// Added by `GeneratedPlugin`. Maps without a data section are ignored.
+pub fn load_game_data(
+    mut events: MessageReader<AssetEvent<bevy_map::MapProject>>,
+    maps: Res<Assets<bevy_map::MapProject>>,
+    mut data: ResMut<GameData>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if let Some(map) = maps.get(*id).filter(|map| map.data.is_some()) {
+            *data = GameData::from_map(map);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::str::FromStr;
+use bevy_map::prelude::{FromMapValue, ToMapValue};
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, FromMapValue, ToMapValue)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Rare,
+}
+impl FromStr for Rarity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Common" | "common" => Ok(Rarity::Common),
+            "Rare" | "rare" => Ok(Rarity::Rare),
+            _ => Err(format!("Unknown Rarity variant: {}", s)),
+        }
+    }
+}
+impl std::fmt::Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rarity::Common => write!(f, "Common"),
+            Rarity::Rare => write!(f, "Rare"),
+        }
+    }
+}
+impl Rarity {
+    pub fn all() -> &'static [Rarity] {
+        &[Rarity::Common, Rarity::Rare]
+    }
+}
@@ -27,6 +27,10 @@ use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+mod shape;
+
+pub use shape::{CollisionShape, HitboxKind, WindowShape};
+
 /// Animation loop mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
 #[serde(rename_all = "lowercase")]
@@ -201,6 +205,10 @@ pub struct AnimationWindow {
     /// Editor display color (RGB), None = use default green
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub color: Option<[u8; 3]>,
+    /// Hitbox/hurtbox shapes active while this window is open
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[reflect(ignore)]
+    pub shapes: Vec<WindowShape>,
 }
 
 impl AnimationWindow {
@@ -213,6 +221,7 @@ impl AnimationWindow {
             end_ms,
             payload: TriggerPayload::None,
             color: None,
+            shapes: Vec::new(),
         }
     }
 
@@ -230,6 +239,7 @@ impl AnimationWindow {
             end_ms,
             payload,
             color: None,
+            shapes: Vec::new(),
         }
     }
 
@@ -242,6 +252,28 @@ impl AnimationWindow {
     pub fn duration_ms(&self) -> u32 {
         self.end_ms.saturating_sub(self.start_ms)
     }
+
+    /// Add a hitbox/hurtbox shape to this window
+    pub fn add_shape(&mut self, shape: WindowShape) {
+        self.shapes.push(shape);
+    }
+
+    /// Remove a shape by ID
+    pub fn remove_shape(&mut self, id: Uuid) -> bool {
+        let len = self.shapes.len();
+        self.shapes.retain(|s| s.id != id);
+        self.shapes.len() != len
+    }
+
+    /// Get a shape by ID
+    pub fn get_shape(&self, id: Uuid) -> Option<&WindowShape> {
+        self.shapes.iter().find(|s| s.id == id)
+    }
+
+    /// Get a mutable shape by ID
+    pub fn get_shape_mut(&mut self, id: Uuid) -> Option<&mut WindowShape> {
+        self.shapes.iter_mut().find(|s| s.id == id)
+    }
 }
 
 /// A single animation definition
@@ -288,13 +320,19 @@ impl AnimationDef {
 
     /// Get the frame index for a given time in milliseconds
     pub fn frame_at_time(&self, time_ms: u32) -> Option<usize> {
+        self.frame_position_at_time(time_ms)
+            .and_then(|pos| self.frames.get(pos).copied())
+    }
+
+    /// Get the position in `frames` (not the spritesheet index) for a given time
+    pub fn frame_position_at_time(&self, time_ms: u32) -> Option<usize> {
         if self.frames.is_empty() {
             return None;
         }
 
         let total_duration = self.total_duration_ms();
         if total_duration == 0 {
-            return self.frames.first().copied();
+            return Some(0);
         }
 
         let loop_time = match self.loop_mode {
@@ -312,9 +350,7 @@ impl AnimationDef {
         };
 
         let frame_index = (loop_time / self.frame_duration_ms) as usize;
-        self.frames
-            .get(frame_index.min(self.frames.len() - 1))
-            .copied()
+        Some(frame_index.min(self.frames.len() - 1))
     }
 
     /// Get all triggers that should fire between prev_ms (exclusive) and current_ms (inclusive)
@@ -516,11 +552,11 @@ impl SpriteData {
 
     /// Update grid dimensions from image size
     pub fn update_from_image_size(&mut self, image_width: u32, image_height: u32) {
-        if self.frame_width > 0 {
-            self.columns = image_width / self.frame_width;
+        if let Some(columns) = image_width.checked_div(self.frame_width) {
+            self.columns = columns;
         }
-        if self.frame_height > 0 {
-            self.rows = image_height / self.frame_height;
+        if let Some(rows) = image_height.checked_div(self.frame_height) {
+            self.rows = rows;
         }
     }
 }
@@ -1034,7 +1070,9 @@ impl Plugin for SpriteAnimationPlugin {
 }
 
 /// System to update animated sprites and fire animation events
-fn update_animated_sprites(
+///
+/// Public so systems reading the animation events can be ordered after it.
+pub fn update_animated_sprites(
     mut commands: Commands,
     time: Res<Time>,
     sprite_assets: Res<Assets<SpriteData>>,
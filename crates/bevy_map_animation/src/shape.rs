@@ -0,0 +1,224 @@
+//! Collider shapes for animation windows
+//!
+//! This module provides:
+//! - `CollisionShape` - Shape types shared with tile/entity collision (re-exported by `bevy_map_core`)
+//! - `HitboxKind` - Whether a window shape deals damage (hitbox) or receives it (hurtbox)
+//! - `WindowShape` - A shape attached to an `AnimationWindow`, with optional per-frame overrides
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Collision shape types supported by the editor
+///
+/// Coordinates are normalized (0-1) to the tile or sprite frame they belong to,
+/// with Y pointing down (top = 0), matching the editor's canvas.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum CollisionShape {
+    /// No collision
+    None,
+    /// Full tile/entity bounding box
+    Full,
+    /// Rectangle with optional offset and size
+    Rectangle {
+        /// Top-left corner [x, y] (0-1 normalized for tiles)
+        #[serde(default)]
+        offset: [f32; 2],
+        /// Size [width, height] (0-1 normalized for tiles)
+        #[serde(default = "default_full_size")]
+        size: [f32; 2],
+    },
+    /// Circle collider
+    Circle {
+        /// Center offset [x, y] (0-1 normalized)
+        #[serde(default)]
+        offset: [f32; 2],
+        /// Radius (0-1 normalized, 0.5 = full tile)
+        #[serde(default = "default_radius")]
+        radius: f32,
+    },
+    /// Polygon collider (convex hull)
+    Polygon {
+        /// Vertices in local coordinates (0-1 normalized)
+        points: Vec<[f32; 2]>,
+    },
+}
+
+fn default_full_size() -> [f32; 2] {
+    [1.0, 1.0]
+}
+
+fn default_radius() -> f32 {
+    0.5
+}
+
+impl Default for CollisionShape {
+    fn default() -> Self {
+        CollisionShape::None
+    }
+}
+
+impl CollisionShape {
+    /// Check if this shape has collision
+    pub fn has_collision(&self) -> bool {
+        !matches!(self, CollisionShape::None)
+    }
+
+    /// Create a rectangle shape with offset and size
+    pub fn rectangle(offset: [f32; 2], size: [f32; 2]) -> Self {
+        CollisionShape::Rectangle { offset, size }
+    }
+
+    /// Create a circle shape with offset and radius
+    pub fn circle(offset: [f32; 2], radius: f32) -> Self {
+        CollisionShape::Circle { offset, radius }
+    }
+
+    /// Create a polygon shape from points
+    pub fn polygon(points: Vec<[f32; 2]>) -> Self {
+        CollisionShape::Polygon { points }
+    }
+
+    /// Get the display name of this shape type
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollisionShape::None => "None",
+            CollisionShape::Full => "Full",
+            CollisionShape::Rectangle { .. } => "Rectangle",
+            CollisionShape::Circle { .. } => "Circle",
+            CollisionShape::Polygon { .. } => "Polygon",
+        }
+    }
+
+    /// Mirror this shape inside its normalized 0-1 cell
+    ///
+    /// Used to follow a sprite's `flip_x`/`flip_y` so hitboxes stay aligned
+    /// with the artwork.
+    pub fn flipped(&self, flip_x: bool, flip_y: bool) -> Self {
+        let mirror = |v: f32, flip: bool| if flip { 1.0 - v } else { v };
+        match self {
+            CollisionShape::None | CollisionShape::Full => self.clone(),
+            CollisionShape::Rectangle { offset, size } => CollisionShape::Rectangle {
+                // Offset is the top-left corner, so mirror the far edge instead
+                offset: [
+                    if flip_x {
+                        1.0 - offset[0] - size[0]
+                    } else {
+                        offset[0]
+                    },
+                    if flip_y {
+                        1.0 - offset[1] - size[1]
+                    } else {
+                        offset[1]
+                    },
+                ],
+                size: *size,
+            },
+            CollisionShape::Circle { offset, radius } => CollisionShape::Circle {
+                offset: [mirror(offset[0], flip_x), mirror(offset[1], flip_y)],
+                radius: *radius,
+            },
+            CollisionShape::Polygon { points } => CollisionShape::Polygon {
+                points: points
+                    .iter()
+                    .map(|p| [mirror(p[0], flip_x), mirror(p[1], flip_y)])
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Role of a shape attached to an animation window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HitboxKind {
+    /// Deals damage (e.g. a sword swing)
+    #[default]
+    Hitbox,
+    /// Receives damage (e.g. the character's body while dodging)
+    Hurtbox,
+}
+
+impl HitboxKind {
+    /// Get the display name for this kind
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            HitboxKind::Hitbox => "Hitbox",
+            HitboxKind::Hurtbox => "Hurtbox",
+        }
+    }
+
+    /// Get all available kinds
+    pub fn all() -> &'static [HitboxKind] {
+        &[HitboxKind::Hitbox, HitboxKind::Hurtbox]
+    }
+}
+
+/// A collider shape carried by an [`AnimationWindow`](crate::AnimationWindow)
+///
+/// The shape is positioned relative to the sprite frame (0-1 normalized, Y down).
+/// `frame_shapes` overrides the base shape on specific frames, keyed by the
+/// frame's position in the animation's `frames` list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowShape {
+    /// Unique identifier for this shape
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+    /// Display name (for editor UI)
+    #[serde(default)]
+    pub name: String,
+    /// Whether this shape is a hitbox or a hurtbox
+    #[serde(default)]
+    pub kind: HitboxKind,
+    /// Shape used on frames without an override
+    #[serde(default)]
+    pub shape: CollisionShape,
+    /// Per-frame shape overrides (frame position -> shape)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub frame_shapes: HashMap<usize, CollisionShape>,
+}
+
+impl WindowShape {
+    /// Create a new window shape
+    pub fn new(name: impl Into<String>, kind: HitboxKind, shape: CollisionShape) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            kind,
+            shape,
+            frame_shapes: HashMap::new(),
+        }
+    }
+
+    /// Get the shape in effect on a frame position
+    pub fn shape_at_frame(&self, frame_pos: usize) -> &CollisionShape {
+        self.frame_shapes.get(&frame_pos).unwrap_or(&self.shape)
+    }
+
+    /// Get the shape in effect on a frame position for editing
+    ///
+    /// Returns the frame override if one exists, otherwise the base shape.
+    pub fn shape_at_frame_mut(&mut self, frame_pos: usize) -> &mut CollisionShape {
+        match self.frame_shapes.get_mut(&frame_pos) {
+            Some(shape) => shape,
+            None => &mut self.shape,
+        }
+    }
+
+    /// Check if a frame position has its own shape override
+    pub fn has_frame_override(&self, frame_pos: usize) -> bool {
+        self.frame_shapes.contains_key(&frame_pos)
+    }
+
+    /// Add a frame override, seeded from the shape currently in effect
+    pub fn add_frame_override(&mut self, frame_pos: usize) {
+        let shape = self.shape_at_frame(frame_pos).clone();
+        self.frame_shapes.insert(frame_pos, shape);
+    }
+
+    /// Remove a frame override, falling back to the base shape
+    pub fn remove_frame_override(&mut self, frame_pos: usize) -> bool {
+        self.frame_shapes.remove(&frame_pos).is_some()
+    }
+}
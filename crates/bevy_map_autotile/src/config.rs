@@ -2,7 +2,10 @@
 //!
 //! This module contains configuration types and legacy 47-tile blob support.
 
+use crate::legacy::retag_legacy_tiles;
+use crate::rules::AutomapRuleSet;
 use crate::terrain::TerrainSet;
+use bevy_map_core::{LayerData, Level};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -15,6 +18,9 @@ pub struct AutotileConfig {
     /// Legacy terrain types (for backward compatibility - will be migrated)
     #[serde(default)]
     pub terrains: Vec<LegacyTerrainType>,
+    /// Automapping rule sets
+    #[serde(default)]
+    pub rule_sets: Vec<AutomapRuleSet>,
 }
 
 impl AutotileConfig {
@@ -22,6 +28,7 @@ impl AutotileConfig {
         Self {
             terrain_sets: Vec::new(),
             terrains: Vec::new(),
+            rule_sets: Vec::new(),
         }
     }
 
@@ -57,6 +64,16 @@ impl AutotileConfig {
             .collect()
     }
 
+    /// Get rule set by ID
+    pub fn get_rule_set(&self, id: Uuid) -> Option<&AutomapRuleSet> {
+        self.rule_sets.iter().find(|rs| rs.id == id)
+    }
+
+    /// Get mutable rule set by ID
+    pub fn get_rule_set_mut(&mut self, id: Uuid) -> Option<&mut AutomapRuleSet> {
+        self.rule_sets.iter_mut().find(|rs| rs.id == id)
+    }
+
     // Legacy compatibility methods
 
     /// Add a legacy terrain type (for backward compatibility)
@@ -77,6 +94,34 @@ impl AutotileConfig {
             None
         }
     }
+
+    /// Convert every legacy terrain to a Mixed terrain set
+    ///
+    /// Tiles painted with a legacy terrain in `levels` are re-resolved to the
+    /// new set (see [`retag_legacy_tiles`]), and the legacy terrains are
+    /// removed. Returns the IDs of the new terrain sets.
+    pub fn migrate_legacy_terrains(&mut self, levels: &mut [Level]) -> Vec<Uuid> {
+        let mut migrated = Vec::new();
+        for legacy in std::mem::take(&mut self.terrains) {
+            let terrain_set = legacy.to_terrain_set();
+            for level in levels.iter_mut() {
+                let (width, height) = (level.width, level.height);
+                for layer in &mut level.layers {
+                    if let LayerData::Tiles {
+                        tileset_id, tiles, ..
+                    } = &mut layer.data
+                    {
+                        if *tileset_id == legacy.tileset_id {
+                            retag_legacy_tiles(tiles, width, height, &legacy, &terrain_set);
+                        }
+                    }
+                }
+            }
+            migrated.push(terrain_set.id);
+            self.terrain_sets.push(terrain_set);
+        }
+        migrated
+    }
 }
 
 /// Legacy terrain type for backward compatibility with old 47-tile blob format
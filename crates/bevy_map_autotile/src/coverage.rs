@@ -0,0 +1,304 @@
+//! Terrain set coverage analysis
+//!
+//! Finds the terrain combinations a [`TerrainSet`] has no tile for, which is
+//! where [`WangFiller`](crate::WangFiller) has to fall back to a mismatched tile
+//! and seams show up. Missing combinations can be laid out as a template image
+//! for artists to draw over.
+
+use std::collections::HashSet;
+
+use crate::terrain::{TerrainSet, TerrainSetType};
+use crate::wang::{get_active_positions, WangFiller, WangId, WangPosition, TRANSFORMS};
+
+/// Largest number of combinations [`TerrainSet::coverage`] checks
+///
+/// A Mixed set has `terrains^8` combinations, so big sets are cut off here.
+pub const MAX_COVERAGE_COMBINATIONS: usize = 1 << 16;
+
+/// Which terrain combinations a terrain set has tiles for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TerrainCoverage {
+    /// Number of possible combinations (`terrains ^ positions`, saturating)
+    pub total: usize,
+    /// Number of combinations checked (less than `total` if truncated)
+    pub checked: usize,
+    /// Combinations no tile matches, in enumeration order
+    pub missing: Vec<WangId>,
+}
+
+impl TerrainCoverage {
+    /// Number of checked combinations that have a tile
+    pub fn covered(&self) -> usize {
+        self.checked - self.missing.len()
+    }
+
+    /// Whether only the first [`MAX_COVERAGE_COMBINATIONS`] were checked
+    pub fn is_truncated(&self) -> bool {
+        self.checked < self.total
+    }
+
+    /// Whether every combination has a tile
+    pub fn is_complete(&self) -> bool {
+        !self.is_truncated() && self.missing.is_empty()
+    }
+}
+
+/// An RGBA8 image laying out terrain combinations, one per tile
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageTemplate {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA8 pixels, top row first
+    pub pixels: Vec<u8>,
+}
+
+impl TerrainSet {
+    /// List the terrain combinations no tile in this set matches
+    ///
+    /// Every active position (corners, edges, or both) must have a terrain, so
+    /// a set with `n` terrains has `n^4` (Corner/Edge) or `n^8` (Mixed)
+    /// combinations. With `allow_transforms`, flipped and rotated tiles count.
+    pub fn coverage(&self) -> TerrainCoverage {
+        let active_positions = get_active_positions(self.set_type);
+        let terrain_count = self.terrains.len();
+        if terrain_count == 0 {
+            return TerrainCoverage::default();
+        }
+
+        let total = (terrain_count as u64)
+            .checked_pow(active_positions.len() as u32)
+            .map_or(usize::MAX, |n| n.min(usize::MAX as u64) as usize);
+        let checked = total.min(MAX_COVERAGE_COMBINATIONS);
+
+        let transforms = if self.allow_transforms {
+            &TRANSFORMS[..]
+        } else {
+            &TRANSFORMS[..1]
+        };
+        let filler = WangFiller::new(self);
+        let mut covered = HashSet::new();
+        for &tile in self.tile_terrains.keys() {
+            for &flags in transforms {
+                if let Some(wang) = filler.tile_wang_id(tile | flags) {
+                    covered.insert(wang);
+                }
+            }
+        }
+
+        let mut missing = Vec::new();
+        for combination in 0..checked {
+            // Base-n digits of the combination are the terrains at each position
+            let mut wang = WangId::WILDCARD;
+            let mut rest = combination;
+            for &i in active_positions {
+                wang.colors[i] = (rest % terrain_count + 1) as u8;
+                rest /= terrain_count;
+            }
+            if !covered.contains(&wang) {
+                missing.push(wang);
+            }
+        }
+
+        TerrainCoverage {
+            total,
+            checked,
+            missing,
+        }
+    }
+
+    /// Describe a combination by terrain name, e.g. "TopLeft: Grass, TopRight: Dirt, ..."
+    pub fn describe_wang_id(&self, wang: &WangId) -> String {
+        get_active_positions(self.set_type)
+            .iter()
+            .map(|&i| {
+                let terrain = match wang.colors[i] {
+                    0 => "-",
+                    color => self
+                        .terrains
+                        .get(color as usize - 1)
+                        .map_or("?", |t| t.name.as_str()),
+                };
+                format!("{:?}: {}", WangPosition::from_index(i), terrain)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Lay out combinations as a template image, `columns` tiles wide
+    ///
+    /// Each tile is filled with the terrain colors in the zones the tileset
+    /// editor uses: quadrants for Corner sets, triangles towards each edge for
+    /// Edge sets, and a 3x3 grid for Mixed sets (center left transparent).
+    /// Returns `None` if the image would be too large to address.
+    pub fn coverage_template(
+        &self,
+        combinations: &[WangId],
+        tile_size: u32,
+        columns: u32,
+    ) -> Option<CoverageTemplate> {
+        let columns = columns.max(1);
+        let rows = u32::try_from(combinations.len().div_ceil(columns as usize)).ok()?;
+        let width = columns.checked_mul(tile_size)?;
+        let height = rows.checked_mul(tile_size)?;
+        let byte_len = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(4)?;
+        let mut pixels = vec![0u8; byte_len];
+
+        for (n, wang) in combinations.iter().enumerate() {
+            let origin_x = (n as u32 % columns) * tile_size;
+            let origin_y = (n as u32 / columns) * tile_size;
+            for py in 0..tile_size {
+                for px in 0..tile_size {
+                    // Sample at the pixel center, in 0..1 with y down
+                    let u = (px as f32 + 0.5) / tile_size as f32;
+                    let v = (py as f32 + 0.5) / tile_size as f32;
+                    let Some(i) = template_zone(self.set_type, u, v) else {
+                        continue;
+                    };
+                    let Some(terrain) = (wang.colors[i] as usize)
+                        .checked_sub(1)
+                        .and_then(|t| self.terrains.get(t))
+                    else {
+                        continue;
+                    };
+
+                    let idx =
+                        ((origin_y + py) as usize * width as usize + (origin_x + px) as usize) * 4;
+                    let c = terrain.color;
+                    pixels[idx..idx + 4].copy_from_slice(&[
+                        (c.r.clamp(0.0, 1.0) * 255.0).round() as u8,
+                        (c.g.clamp(0.0, 1.0) * 255.0).round() as u8,
+                        (c.b.clamp(0.0, 1.0) * 255.0).round() as u8,
+                        (c.a.clamp(0.0, 1.0) * 255.0).round() as u8,
+                    ]);
+                }
+            }
+        }
+
+        Some(CoverageTemplate {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// WangId index drawn at `(u, v)` in a template tile (y down), if any
+fn template_zone(set_type: TerrainSetType, u: f32, v: f32) -> Option<usize> {
+    match set_type {
+        TerrainSetType::Corner => Some(match (u > 0.5, v > 0.5) {
+            (false, false) => WangPosition::TopLeft as usize,
+            (true, false) => WangPosition::TopRight as usize,
+            (false, true) => WangPosition::BottomLeft as usize,
+            (true, true) => WangPosition::BottomRight as usize,
+        }),
+        TerrainSetType::Edge => {
+            let dx = (u - 0.5).abs();
+            let dy = (v - 0.5).abs();
+            Some(if dy > dx {
+                if v < 0.5 {
+                    WangPosition::Top as usize
+                } else {
+                    WangPosition::Bottom as usize
+                }
+            } else if u < 0.5 {
+                WangPosition::Left as usize
+            } else {
+                WangPosition::Right as usize
+            })
+        }
+        TerrainSetType::Mixed => {
+            let zone = |t: f32| (t * 3.0).min(2.0) as usize;
+            const GRID: [[Option<WangPosition>; 3]; 3] = [
+                [
+                    Some(WangPosition::TopLeft),
+                    Some(WangPosition::Top),
+                    Some(WangPosition::TopRight),
+                ],
+                [Some(WangPosition::Left), None, Some(WangPosition::Right)],
+                [
+                    Some(WangPosition::BottomLeft),
+                    Some(WangPosition::Bottom),
+                    Some(WangPosition::BottomRight),
+                ],
+            ];
+            GRID[zone(v)][zone(u)].map(|pos| pos as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::Color;
+    use uuid::Uuid;
+
+    /// Two-terrain Corner set with a tile for every combination but `skip`.
+    /// Bit `p` of the tile index is the terrain at corner `p` (TL, TR, BL, BR).
+    fn corner_set(skip: &[u32]) -> TerrainSet {
+        let mut set = TerrainSet::new("Ground".to_string(), Uuid::new_v4(), TerrainSetType::Corner);
+        set.add_terrain("Grass".to_string(), Color::GREEN);
+        set.add_terrain("Dirt".to_string(), Color::RED);
+        for tile in (0..16u32).filter(|t| !skip.contains(t)) {
+            for corner in 0..4 {
+                set.set_tile_terrain(tile, corner, Some(((tile >> corner) & 1) as usize));
+            }
+        }
+        set
+    }
+
+    #[test]
+    fn test_coverage_lists_missing_combinations() {
+        let coverage = corner_set(&[]).coverage();
+        assert_eq!(coverage.total, 16);
+        assert!(coverage.is_complete());
+
+        // Missing the two tiles with dirt in exactly one of the right corners
+        let set = corner_set(&[0b0010, 0b1000]);
+        let coverage = set.coverage();
+        assert_eq!(coverage.covered(), 14);
+        assert_eq!(coverage.missing.len(), 2);
+        assert_eq!(
+            set.describe_wang_id(&coverage.missing[0]),
+            "TopRight: Dirt, BottomRight: Grass, BottomLeft: Grass, TopLeft: Grass"
+        );
+
+        // A flip of the one-corner tiles covers both
+        let mut set = set;
+        set.allow_transforms = true;
+        assert!(set.coverage().is_complete());
+
+        let mut mixed = corner_set(&[]);
+        mixed.set_type = TerrainSetType::Mixed;
+        for _ in 0..4 {
+            mixed.add_terrain("Extra".to_string(), Color::BLUE);
+        }
+        let coverage = mixed.coverage();
+        assert_eq!(coverage.total, 6usize.pow(8));
+        assert!(coverage.is_truncated());
+        assert_eq!(coverage.missing.len(), MAX_COVERAGE_COMBINATIONS);
+    }
+
+    #[test]
+    fn test_coverage_template_layout() {
+        let set = corner_set(&[0b0010, 0b1000]);
+        let missing = set.coverage().missing;
+        let template = set.coverage_template(&missing, 4, 8).unwrap();
+        assert_eq!((template.width, template.height), (32, 4));
+
+        let pixel = |x: u32, y: u32| {
+            let idx = ((y * template.width + x) * 4) as usize;
+            &template.pixels[idx..idx + 4]
+        };
+        // First missing tile: dirt top-right, grass elsewhere
+        assert_eq!(pixel(3, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(0, 0), [0, 255, 0, 255]);
+        assert_eq!(pixel(3, 3), [0, 255, 0, 255]);
+        // Unused slots stay transparent
+        assert_eq!(pixel(8, 0), [0, 0, 0, 0]);
+
+        // Sizes that don't fit are rejected instead of wrapping around
+        assert!(set.coverage_template(&missing, u32::MAX, 8).is_none());
+    }
+}
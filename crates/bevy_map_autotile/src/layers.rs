@@ -0,0 +1,517 @@
+//! Terrain-to-layer routing
+//!
+//! A terrain can be painted onto its own layer (water on "Water", cliffs on
+//! "Walls") instead of the selected one. For overlapping transitions, a
+//! terrain set can also stack layers: each stack layer is painted over what
+//! the layers below it show, and only keeps the tiles that show one of its own
+//! terrains (or a terrain from a higher layer). Painting a low terrain over a
+//! high one then pulls the high layer back, with matching transitions.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use bevy_map_core::{LayerData, Level};
+
+use crate::region::{fill_terrain_region, replace_terrain, resolve_terrain_layer};
+use crate::terrain::TerrainSet;
+use crate::wang::{get_affected_region, paint_terrain_at_targets, PaintTarget, WangFiller};
+
+/// Error resolving the layers a terrain is painted on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerrainLayerError {
+    /// The level has no layer with this name
+    MissingLayer(String),
+    /// The layer exists but isn't a tile layer
+    NotTileLayer(String),
+}
+
+impl fmt::Display for TerrainLayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerrainLayerError::MissingLayer(name) => write!(f, "Layer '{}' not found", name),
+            TerrainLayerError::NotTileLayer(name) => {
+                write!(f, "Layer '{}' is not a tile layer", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TerrainLayerError {}
+
+/// The layers of a level a terrain stroke involves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerrainLayers {
+    /// Layer indices, bottom of the stack first (one layer without a stack)
+    pub stack: Vec<usize>,
+    /// Position in `stack` of the painted terrain's layer. It and every layer
+    /// above it are painted; the layers below are only read.
+    pub start: usize,
+}
+
+impl TerrainLayers {
+    /// Indices of the layers a stroke writes to
+    pub fn painted(&self) -> &[usize] {
+        &self.stack[self.start..]
+    }
+}
+
+impl TerrainSet {
+    /// Position of a terrain's layer in `layer_stack`
+    ///
+    /// Terrains without a layer, or with one outside the stack, count as the
+    /// bottom of the stack.
+    pub fn stack_level(&self, terrain_index: usize) -> usize {
+        self.terrains
+            .get(terrain_index)
+            .and_then(|t| t.layer.as_ref())
+            .and_then(|name| self.layer_stack.iter().position(|l| l == name))
+            .unwrap_or(0)
+    }
+
+    /// Whether any terrain is routed to a layer other than the selected one
+    pub fn routes_layers(&self) -> bool {
+        !self.layer_stack.is_empty() || self.terrains.iter().any(|t| t.layer.is_some())
+    }
+
+    /// Find the layers a stroke of `terrain_index` involves in `level`
+    ///
+    /// A terrain without a layer goes on the bottom of the stack, or on
+    /// `selected_layer` if the set has no stack.
+    pub fn terrain_layers(
+        &self,
+        level: &Level,
+        selected_layer: usize,
+        terrain_index: usize,
+    ) -> Result<TerrainLayers, TerrainLayerError> {
+        let name = self
+            .terrains
+            .get(terrain_index)
+            .and_then(|t| t.layer.as_ref())
+            .or(self.layer_stack.first());
+
+        let Some(name) = name else {
+            let layer = level
+                .layers
+                .get(selected_layer)
+                .ok_or_else(|| TerrainLayerError::MissingLayer(selected_layer.to_string()))?;
+            return match layer.data {
+                LayerData::Tiles { .. } => Ok(TerrainLayers {
+                    stack: vec![selected_layer],
+                    start: 0,
+                }),
+                LayerData::Objects { .. } => {
+                    Err(TerrainLayerError::NotTileLayer(layer.name.clone()))
+                }
+            };
+        };
+
+        match self.layer_stack.iter().position(|l| l == name) {
+            Some(start) => Ok(TerrainLayers {
+                stack: self
+                    .layer_stack
+                    .iter()
+                    .map(|l| tile_layer_index(level, l))
+                    .collect::<Result<_, _>>()?,
+                start,
+            }),
+            None => Ok(TerrainLayers {
+                stack: vec![tile_layer_index(level, name)?],
+                start: 0,
+            }),
+        }
+    }
+
+    /// Routes covering every layer the set's terrains are painted on, each
+    /// written from the bottom up
+    ///
+    /// Used for whole-layer edits that touch all terrains, like re-resolving.
+    pub fn all_terrain_layers(
+        &self,
+        level: &Level,
+        selected_layer: usize,
+    ) -> Result<Vec<TerrainLayers>, TerrainLayerError> {
+        let mut routes: Vec<TerrainLayers> = Vec::new();
+        for terrain_index in 0..self.terrains.len() {
+            let mut route = self.terrain_layers(level, selected_layer, terrain_index)?;
+            route.start = 0;
+            if !routes.contains(&route) {
+                routes.push(route);
+            }
+        }
+        Ok(routes)
+    }
+}
+
+/// Paint terrain at targets on every layer the stroke writes to
+///
+/// Each painted layer is painted over a composite of itself and the stack
+/// layers below it, so its transitions match what's drawn underneath. Changed
+/// tiles that only show terrains of lower stack layers are then cleared, since
+/// those layers already draw them.
+pub fn paint_terrain_layers(
+    level: &mut Level,
+    layers: &TerrainLayers,
+    targets: &[PaintTarget],
+    terrain_set: &TerrainSet,
+    terrain_index: usize,
+) {
+    let mut stack = stack_tiles(level, layers);
+    paint_stack(
+        &mut stack,
+        layers.start,
+        level.width,
+        level.height,
+        targets,
+        terrain_set,
+        terrain_index,
+    );
+
+    write_stack(level, layers, stack);
+}
+
+/// Terrain bucket fill on every layer a route writes to
+///
+/// Like [`fill_terrain_region`], with the region taken from what each layer
+/// shows over the stack layers below it.
+pub fn fill_terrain_region_layers(
+    level: &mut Level,
+    layers: &TerrainLayers,
+    x: i32,
+    y: i32,
+    terrain_set: &TerrainSet,
+    terrain_index: usize,
+) {
+    let (width, height) = (level.width, level.height);
+    edit_terrain_layers(level, layers, terrain_set, |tiles| {
+        fill_terrain_region(tiles, width, height, x, y, terrain_set, terrain_index);
+    });
+}
+
+/// [`replace_terrain`] on every layer a route writes to
+pub fn replace_terrain_layers(
+    level: &mut Level,
+    layers: &TerrainLayers,
+    terrain_set: &TerrainSet,
+    from_terrain: usize,
+    to_terrain: usize,
+) {
+    let (width, height) = (level.width, level.height);
+    edit_terrain_layers(level, layers, terrain_set, |tiles| {
+        replace_terrain(tiles, width, height, terrain_set, from_terrain, to_terrain);
+    });
+}
+
+/// [`resolve_terrain_layer`] on every layer a route writes to
+pub fn resolve_terrain_layers(
+    level: &mut Level,
+    layers: &TerrainLayers,
+    terrain_set: &TerrainSet,
+    seed: u64,
+) {
+    let (width, height) = (level.width, level.height);
+    edit_terrain_layers(level, layers, terrain_set, |tiles| {
+        resolve_terrain_layer(tiles, width, height, terrain_set, seed);
+    });
+}
+
+/// Calculate preview tiles for [`paint_terrain_layers`] without modifying the level
+///
+/// Returns the brush footprint as it would look afterwards: the top-most tile
+/// of the stack in each cell.
+pub fn preview_terrain_layers(
+    level: &Level,
+    layers: &TerrainLayers,
+    targets: &[PaintTarget],
+    terrain_set: &TerrainSet,
+    terrain_index: usize,
+) -> Vec<((i32, i32), u32)> {
+    let (width, height) = (level.width, level.height);
+    let mut footprint: HashSet<(i32, i32)> = HashSet::new();
+    for target in targets {
+        footprint.extend(get_affected_region(
+            *target,
+            width,
+            height,
+            terrain_set.set_type,
+        ));
+    }
+    if footprint.is_empty() {
+        return Vec::new();
+    }
+
+    let mut stack = stack_tiles(level, layers);
+    paint_stack(
+        &mut stack,
+        layers.start,
+        width,
+        height,
+        targets,
+        terrain_set,
+        terrain_index,
+    );
+
+    footprint
+        .into_iter()
+        .filter_map(|(x, y)| {
+            let idx = (y as u32 * width + x as u32) as usize;
+            stack
+                .iter()
+                .rev()
+                .find_map(|tiles| tiles.get(idx).copied().flatten())
+                .map(|tile| ((x, y), tile))
+        })
+        .collect()
+}
+
+/// Copy the tiles of each stack layer (empty for missing layers)
+fn stack_tiles(level: &Level, layers: &TerrainLayers) -> Vec<Vec<Option<u32>>> {
+    let cells = (level.width * level.height) as usize;
+    layers
+        .stack
+        .iter()
+        .map(|&idx| match level.layers.get(idx).map(|l| &l.data) {
+            Some(LayerData::Tiles { tiles, .. }) => tiles.clone(),
+            _ => vec![None; cells],
+        })
+        .collect()
+}
+
+/// Paint the stack layers from `start` up, each over the layers below it
+fn paint_stack(
+    stack: &mut [Vec<Option<u32>>],
+    start: usize,
+    width: u32,
+    height: u32,
+    targets: &[PaintTarget],
+    terrain_set: &TerrainSet,
+    terrain_index: usize,
+) {
+    edit_stack(stack, start, terrain_set, |composite| {
+        paint_terrain_at_targets(
+            composite,
+            width,
+            height,
+            targets,
+            terrain_set,
+            terrain_index,
+        );
+    });
+}
+
+/// Run `edit` on each stack layer from `start` up, over the layers below it
+///
+/// `edit` gets the layer composited over the layers below it. Changed tiles
+/// that only show terrains of lower stack layers are cleared, since those
+/// layers already draw them.
+fn edit_stack(
+    stack: &mut [Vec<Option<u32>>],
+    start: usize,
+    terrain_set: &TerrainSet,
+    mut edit: impl FnMut(&mut [Option<u32>]),
+) {
+    let filler = WangFiller::new(terrain_set);
+    for level in start..stack.len() {
+        let (below, rest) = stack.split_at_mut(level);
+        let layer = &mut rest[0];
+
+        let before: Vec<Option<u32>> = layer
+            .iter()
+            .enumerate()
+            .map(|(idx, tile)| tile.or_else(|| below.iter().rev().find_map(|l| l[idx])))
+            .collect();
+        let mut composite = before.clone();
+        edit(&mut composite);
+
+        for (idx, tile) in composite.into_iter().enumerate() {
+            if tile == before[idx] {
+                continue;
+            }
+            let shown_below = tile.is_some_and(|tile| {
+                filler.tile_wang_id(tile).is_some_and(|wang| {
+                    let mut colors = wang.colors.iter().filter(|&&c| c > 0).peekable();
+                    colors.peek().is_some()
+                        && colors.all(|&c| terrain_set.stack_level(c as usize - 1) < level)
+                })
+            });
+            layer[idx] = if shown_below { None } else { tile };
+        }
+    }
+}
+
+/// Run `edit` on every layer a route writes to (see [`edit_stack`])
+fn edit_terrain_layers(
+    level: &mut Level,
+    layers: &TerrainLayers,
+    terrain_set: &TerrainSet,
+    edit: impl FnMut(&mut [Option<u32>]),
+) {
+    let mut stack = stack_tiles(level, layers);
+    edit_stack(&mut stack, layers.start, terrain_set, edit);
+    write_stack(level, layers, stack);
+}
+
+/// Store the painted stack layers back in the level
+fn write_stack(level: &mut Level, layers: &TerrainLayers, stack: Vec<Vec<Option<u32>>>) {
+    for (&layer_idx, painted) in layers.stack.iter().zip(stack).skip(layers.start) {
+        if let Some(LayerData::Tiles { tiles, .. }) =
+            level.layers.get_mut(layer_idx).map(|l| &mut l.data)
+        {
+            *tiles = painted;
+        }
+    }
+}
+
+fn tile_layer_index(level: &Level, name: &str) -> Result<usize, TerrainLayerError> {
+    let idx = level
+        .layers
+        .iter()
+        .position(|layer| layer.name == name)
+        .ok_or_else(|| TerrainLayerError::MissingLayer(name.to_string()))?;
+    match level.layers[idx].data {
+        LayerData::Tiles { .. } => Ok(idx),
+        LayerData::Objects { .. } => Err(TerrainLayerError::NotTileLayer(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::{Color, TerrainSetType};
+    use bevy_map_core::Layer;
+    use uuid::Uuid;
+
+    const GRASS: usize = 0;
+    const WATER: usize = 1;
+
+    /// Grass/Water Corner set on a "Ground" + "Water" stack, with a tile for
+    /// every combination. Bit `p` of the tile index is water at corner `p`.
+    fn stacked_set() -> TerrainSet {
+        let mut set = TerrainSet::new("Ground".to_string(), Uuid::new_v4(), TerrainSetType::Corner);
+        set.add_terrain("Grass".to_string(), Color::GREEN);
+        set.add_terrain("Water".to_string(), Color::BLUE);
+        for tile in 0..16u32 {
+            for corner in 0..4 {
+                set.set_tile_terrain(tile, corner, Some(((tile >> corner) & 1) as usize));
+            }
+        }
+        set.terrains[GRASS].layer = Some("Ground".to_string());
+        set.terrains[WATER].layer = Some("Water".to_string());
+        set.layer_stack = vec!["Ground".to_string(), "Water".to_string()];
+        set
+    }
+
+    fn level(tileset_id: Uuid) -> Level {
+        let mut level = Level::new("Test".to_string(), 4, 4);
+        let mut ground = Layer::new_tile_layer("Ground".to_string(), tileset_id, 4, 4);
+        if let LayerData::Tiles { tiles, .. } = &mut ground.data {
+            tiles.fill(Some(0));
+        }
+        level.add_layer(ground);
+        level.add_layer(Layer::new_object_layer("Objects".to_string()));
+        level.add_layer(Layer::new_tile_layer("Water".to_string(), tileset_id, 4, 4));
+        level
+    }
+
+    fn layer_tiles(level: &Level, idx: usize) -> &[Option<u32>] {
+        match &level.layers[idx].data {
+            LayerData::Tiles { tiles, .. } => tiles,
+            LayerData::Objects { .. } => panic!("not a tile layer"),
+        }
+    }
+
+    #[test]
+    fn test_terrain_layers_resolve() {
+        let mut set = stacked_set();
+        let level = level(set.tileset_id);
+
+        let water = set.terrain_layers(&level, 0, WATER).unwrap();
+        assert_eq!(water.stack, vec![0, 2]);
+        assert_eq!(water.painted(), &[2]);
+        assert_eq!(
+            set.terrain_layers(&level, 2, GRASS).unwrap().painted(),
+            &[0, 2]
+        );
+
+        // Without a stack, only the terrain's own layer is painted
+        set.layer_stack.clear();
+        set.terrains[GRASS].layer = None;
+        assert_eq!(set.terrain_layers(&level, 2, GRASS).unwrap().stack, vec![2]);
+        assert_eq!(set.terrain_layers(&level, 0, WATER).unwrap().stack, vec![2]);
+        assert_eq!(
+            set.terrain_layers(&level, 1, GRASS),
+            Err(TerrainLayerError::NotTileLayer("Objects".to_string()))
+        );
+
+        set.terrains[WATER].layer = Some("Lava".to_string());
+        assert_eq!(
+            set.terrain_layers(&level, 0, WATER),
+            Err(TerrainLayerError::MissingLayer("Lava".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_paint_terrain_layers_stack() {
+        let set = stacked_set();
+        let mut level = level(set.tileset_id);
+        let center = [PaintTarget::Corner {
+            corner_x: 2,
+            corner_y: 2,
+        }];
+
+        // Water goes on the Water layer, around the painted corner only
+        let water = set.terrain_layers(&level, 0, WATER).unwrap();
+        paint_terrain_layers(&mut level, &water, &center, &set, WATER);
+        assert!(layer_tiles(&level, 0).iter().all(|t| *t == Some(0)));
+        let filler = WangFiller::new(&set);
+        for y in 0..4 {
+            for x in 0..4 {
+                let tile = layer_tiles(&level, 2)[y * 4 + x];
+                let around_corner = (1..=2).contains(&x) && (1..=2).contains(&y);
+                assert_eq!(tile.is_some(), around_corner, "cell ({}, {})", x, y);
+                if let Some(tile) = tile {
+                    assert!(filler.tile_wang_id(tile).unwrap().colors.contains(&2));
+                }
+            }
+        }
+
+        let preview = preview_terrain_layers(&level, &water, &center, &set, WATER);
+        assert_eq!(preview.len(), 4);
+
+        // Painting grass back pulls the water layer back without touching the ground
+        let grass = set.terrain_layers(&level, 0, GRASS).unwrap();
+        paint_terrain_layers(&mut level, &grass, &center, &set, GRASS);
+        assert!(layer_tiles(&level, 0).iter().all(|t| *t == Some(0)));
+        assert!(layer_tiles(&level, 2).iter().all(|t| t.is_none()));
+    }
+
+    #[test]
+    fn test_region_edits_follow_layers() {
+        let set = stacked_set();
+        let mut level = level(set.tileset_id);
+
+        // A water bucket fill floods the Water layer over the untouched ground
+        let water = set.terrain_layers(&level, 0, WATER).unwrap();
+        fill_terrain_region_layers(&mut level, &water, 1, 1, &set, WATER);
+        assert!(layer_tiles(&level, 0).iter().all(|t| *t == Some(0)));
+        assert!(layer_tiles(&level, 2).iter().all(|t| *t == Some(15)));
+
+        // Replacing water with grass empties the Water layer again
+        let grass = set.terrain_layers(&level, 0, GRASS).unwrap();
+        replace_terrain_layers(&mut level, &grass, &set, WATER, GRASS);
+        assert!(layer_tiles(&level, 0).iter().all(|t| *t == Some(0)));
+        assert!(layer_tiles(&level, 2).iter().all(|t| t.is_none()));
+
+        // The whole stack is one route for re-resolving
+        let routes = set.all_terrain_layers(&level, 0).unwrap();
+        assert_eq!(
+            routes,
+            vec![TerrainLayers {
+                stack: vec![0, 2],
+                start: 0
+            }]
+        );
+        resolve_terrain_layers(&mut level, &routes[0], &set, 0);
+        assert!(layer_tiles(&level, 0).iter().all(|t| *t == Some(0)));
+        assert!(layer_tiles(&level, 2).iter().all(|t| t.is_none()));
+    }
+}
@@ -1,9 +1,15 @@
 //! Legacy 47-tile blob autotile support
 //!
 //! This module provides backward compatibility for the old 47-tile blob format
-//! used in earlier versions.
+//! used in earlier versions, and converts blob terrains to Wang terrain sets.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy_map_core::tile_index;
 
 use crate::config::LegacyTerrainType;
+use crate::terrain::{Color, TerrainSet, TerrainSetType, TileTerrainData};
+use crate::wang::{WangFiller, WangId, NEIGHBOR_OFFSETS};
 
 /// Legacy neighbor direction flags for bitmask calculation
 pub mod neighbors {
@@ -184,3 +190,233 @@ pub fn erase_autotile<F>(
         is_terrain_tile,
     );
 }
+
+// =============================================================================
+// Migration to Wang terrain sets
+// =============================================================================
+
+/// Terrain data for a blob tile, as a single-terrain Mixed tile
+///
+/// Each edge and corner the bitmask connects to gets `terrain_index`; the rest
+/// are left empty, and the center is always the terrain. Bits mean the same
+/// neighbors as in [`calculate_bitmask`]: `N` is the cell at `y - 1`, which is
+/// the Wang Bottom edge (Wang Top is `y + 1`), so painted maps keep their tiles.
+pub fn bitmask_terrain_data(bitmask: u8, terrain_index: usize) -> TileTerrainData {
+    use neighbors::*;
+
+    let mut data = TileTerrainData::new();
+    // Mixed positions: TL, Top, TR, Right, BR, Bottom, BL, Left
+    for (position, bit) in [SW, S, SE, E, NE, N, NW, W].into_iter().enumerate() {
+        if bitmask & bit != 0 {
+            data.set(position, Some(terrain_index));
+        }
+    }
+    data.set(8, Some(terrain_index));
+    data
+}
+
+impl LegacyTerrainType {
+    /// All tile indices painted by this terrain
+    pub fn tiles(&self) -> HashSet<u32> {
+        self.tile_mapping
+            .values()
+            .copied()
+            .chain(std::iter::once(self.base_tile))
+            .collect()
+    }
+
+    /// Convert to a Mixed [`TerrainSet`] with one terrain
+    ///
+    /// Each mapped tile is tagged from its bitmask (see [`bitmask_terrain_data`]).
+    /// Bitmasks that can't occur after [`optimize_bitmask`] are skipped, and a
+    /// tile mapped more than once keeps its lowest bitmask.
+    pub fn to_terrain_set(&self) -> TerrainSet {
+        let mut terrain_set =
+            TerrainSet::new(self.name.clone(), self.tileset_id, TerrainSetType::Mixed);
+        let terrain_index = terrain_set.add_terrain(self.name.clone(), Color::GREEN);
+
+        let mut bitmasks: Vec<_> = self
+            .tile_mapping
+            .iter()
+            .filter(|(&bitmask, _)| optimize_bitmask(bitmask) == bitmask)
+            .collect();
+        bitmasks.sort();
+        for (&bitmask, &tile) in bitmasks {
+            terrain_set
+                .tile_terrains
+                .entry(tile)
+                .or_insert_with(|| bitmask_terrain_data(bitmask, terrain_index));
+        }
+
+        // The base tile is the fully surrounded tile unless mapped otherwise
+        terrain_set
+            .tile_terrains
+            .entry(self.base_tile)
+            .or_insert_with(|| bitmask_terrain_data(u8::MAX, terrain_index));
+
+        terrain_set
+    }
+}
+
+/// Re-resolve tiles painted with a legacy terrain to the tiles of its converted
+/// terrain set
+///
+/// Each cell painted with one of `legacy`'s tiles gets the tile whose edges and
+/// corners match its painted neighbors (corners need both adjacent edges, as in
+/// the blob layout). Cells with no exactly matching tile are left alone.
+/// Returns the number of changed cells.
+pub fn retag_legacy_tiles(
+    tiles: &mut [Option<u32>],
+    width: u32,
+    height: u32,
+    legacy: &LegacyTerrainType,
+    terrain_set: &TerrainSet,
+) -> usize {
+    let legacy_tiles = legacy.tiles();
+    let is_painted: Vec<bool> = tiles
+        .iter()
+        .map(|tile| tile.is_some_and(|t| legacy_tiles.contains(&tile_index(t))))
+        .collect();
+    let painted_at = |x: i32, y: i32| {
+        x >= 0
+            && y >= 0
+            && x < width as i32
+            && y < height as i32
+            && is_painted[(y as u32 * width + x as u32) as usize]
+    };
+
+    // Lowest tile index wins for duplicate WangIds
+    let filler = WangFiller::new(terrain_set);
+    let mut keys: Vec<u32> = terrain_set.tile_terrains.keys().copied().collect();
+    keys.sort_unstable();
+    let mut by_wang = HashMap::new();
+    for tile in keys {
+        if let Some(wang) = filler.tile_wang_id(tile) {
+            by_wang.entry(wang).or_insert(tile);
+        }
+    }
+
+    let color = 1;
+    let mut changed = 0;
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            if !painted_at(x, y) {
+                continue;
+            }
+
+            let mut wang = WangId::WILDCARD;
+            for (i, &(dx, dy)) in NEIGHBOR_OFFSETS.iter().enumerate() {
+                let connected = if WangId::is_corner(i) {
+                    painted_at(x + dx, y + dy) && painted_at(x + dx, y) && painted_at(x, y + dy)
+                } else {
+                    painted_at(x + dx, y + dy)
+                };
+                if connected {
+                    wang.colors[i] = color;
+                }
+            }
+
+            let idx = (y as u32 * width + x as u32) as usize;
+            if let Some(&tile) = by_wang.get(&wang) {
+                if tiles[idx] != Some(tile) {
+                    tiles[idx] = Some(tile);
+                    changed += 1;
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neighbors::*;
+    use uuid::Uuid;
+
+    fn legacy_terrain(mapping: &[(u8, u32)]) -> LegacyTerrainType {
+        let mut terrain = LegacyTerrainType::new("Wall".to_string(), Uuid::new_v4(), 0);
+        terrain.tile_mapping = mapping.iter().copied().collect();
+        terrain.base_tile = mapping[0].1;
+        terrain
+    }
+
+    #[test]
+    fn test_to_terrain_set() {
+        // NE without N and E can't happen, so it's skipped
+        let legacy = legacy_terrain(&[(0, 10), (N | E | NE, 11), (NE, 12)]);
+        let set = legacy.to_terrain_set();
+        assert_eq!(set.set_type, TerrainSetType::Mixed);
+        assert_eq!(set.terrains.len(), 1);
+        assert_eq!(set.tile_terrains.len(), 2);
+
+        let data = set.get_tile_terrain(11).unwrap();
+        let painted: Vec<usize> = (0..8).filter(|&p| data.get(p).is_some()).collect();
+        assert_eq!(painted, [3, 4, 5]); // Right, BR, Bottom (N is y - 1)
+        assert_eq!(data.get(8), Some(0));
+    }
+
+    #[test]
+    fn test_retag_legacy_tiles() {
+        let legacy = legacy_terrain(&[(0, 10), (E, 11), (W, 12), (E | W, 13), (N, 20), (S, 21)]);
+        let set = legacy.to_terrain_set();
+
+        // A row of three isolated tiles next to a tile from another terrain
+        let mut tiles = vec![Some(10), Some(10), Some(10), Some(99)];
+        assert_eq!(retag_legacy_tiles(&mut tiles, 4, 1, &legacy, &set), 3);
+        assert_eq!(tiles, [Some(11), Some(13), Some(12), Some(99)]);
+
+        // As in calculate_bitmask, N is the neighbor at y - 1
+        let mut tiles = vec![Some(10), Some(10)];
+        retag_legacy_tiles(&mut tiles, 1, 2, &legacy, &set);
+        assert_eq!(tiles, [Some(21), Some(20)]);
+    }
+
+    #[test]
+    fn test_retag_keeps_legacy_painted_tiles() {
+        // One tile per reachable blob bitmask
+        let mapping: Vec<(u8, u32)> = (0..=255u8)
+            .filter(|&b| optimize_bitmask(b) == b)
+            .map(|b| (b, 100 + b as u32))
+            .collect();
+        assert_eq!(mapping.len(), 47);
+        let legacy = legacy_terrain(&mapping);
+        let legacy_tiles = legacy.tiles();
+
+        // An irregular blob with holes, notches and diagonal contacts
+        let shape = [
+            "..####..", //
+            ".######.", //
+            "##.##.##", //
+            "########", //
+            ".#..##.#", //
+            "##..#..#",
+        ];
+        let (width, height) = (8, shape.len() as u32);
+        let mut tiles: Vec<Option<u32>> = shape
+            .iter()
+            .flat_map(|row| row.chars().map(|c| (c == '#').then_some(legacy.base_tile)))
+            .collect();
+        apply_autotile_to_region(
+            &mut tiles,
+            width,
+            height,
+            0,
+            0,
+            width as i32,
+            height as i32,
+            &legacy,
+            |tile| tile.is_some_and(|t| legacy_tiles.contains(&t)),
+        );
+        let painted = tiles.clone();
+        assert!(painted.iter().flatten().any(|&t| t != legacy.base_tile));
+
+        let set = legacy.to_terrain_set();
+        assert_eq!(
+            retag_legacy_tiles(&mut tiles, width, height, &legacy, &set),
+            0
+        );
+        assert_eq!(tiles, painted);
+    }
+}
@@ -5,9 +5,13 @@
 //!
 //! # Features
 //! - Corner, Edge, and Mixed terrain set types
-//! - Tiled-compatible Wang tile matching
+//! - Tiled-compatible Wang tile matching, optionally with flipped/rotated tiles
 //! - Runtime terrain modification support
-//! - Legacy 47-tile blob format support
+//! - Terrain bucket fill, whole-layer terrain replacement and re-resolving
+//! - Routing terrains to their own layers, or a stack of layers
+//! - Coverage reports and template images for missing terrain combinations
+//! - Rule-based automapping (pattern rules over one or more layers)
+//! - Legacy 47-tile blob format support, with migration to Mixed terrain sets
 //!
 //! # Example
 //!
@@ -39,12 +43,23 @@
 //! ```
 
 pub mod config;
+pub mod coverage;
+pub mod layers;
 pub mod legacy;
+pub mod region;
+pub mod rules;
 pub mod terrain;
 pub mod wang;
 
 // Re-export main types at crate root
 pub use config::{AutotileConfig, LegacyTerrainType, TerrainBrush, TerrainType};
+pub use coverage::{CoverageTemplate, TerrainCoverage, MAX_COVERAGE_COMBINATIONS};
+pub use layers::{
+    fill_terrain_region_layers, paint_terrain_layers, preview_terrain_layers,
+    replace_terrain_layers, resolve_terrain_layers, TerrainLayerError, TerrainLayers,
+};
+pub use region::{fill_terrain_region, replace_terrain, resolve_terrain_layer, terrain_region};
+pub use rules::{AutomapError, AutomapRule, AutomapRuleSet, CellMatch, RuleInput, RuleOutput};
 pub use terrain::{Color, Terrain, TerrainSet, TerrainSetType, TileConstraints, TileTerrainData};
 pub use wang::{
     get_paint_target, paint_terrain, paint_terrain_at_target, paint_terrain_at_target_with_debug,
@@ -55,8 +70,8 @@ pub use wang::{
 
 // Re-export legacy module contents for backward compatibility
 pub use legacy::{
-    apply_autotile_to_region, calculate_bitmask, erase_autotile, neighbors, optimize_bitmask,
-    paint_autotile,
+    apply_autotile_to_region, bitmask_terrain_data, calculate_bitmask, erase_autotile, neighbors,
+    optimize_bitmask, paint_autotile, retag_legacy_tiles,
 };
 
 // Re-export bevy_map_core
@@ -0,0 +1,465 @@
+//! Region-based terrain painting
+//!
+//! Bucket fill, whole-layer terrain replacement and whole-layer re-resolving
+//! for [`TerrainSet`]s. Each hard-constrains every affected cell and lets a
+//! single [`WangFiller`] pass resolve the transitions along the region boundary.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy_map_core::tile_index;
+
+use crate::terrain::TerrainSet;
+use crate::wang::{get_active_positions, WangFiller, NEIGHBOR_OFFSETS};
+
+/// What makes two neighboring cells part of the same fill region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegionKey {
+    /// A tile with the same terrain at every position
+    Terrain(usize),
+    /// Any other tile (transition tiles, tiles outside the set, empty cells)
+    Tile(Option<u32>),
+}
+
+fn region_key(terrain_set: &TerrainSet, tile: Option<u32>) -> RegionKey {
+    let position_count = terrain_set.set_type.position_count();
+    tile.and_then(|tile| terrain_set.get_tile_terrain(tile_index(tile)))
+        .and_then(|data| data.is_uniform(position_count))
+        .map(RegionKey::Terrain)
+        .unwrap_or(RegionKey::Tile(tile))
+}
+
+/// Find the contiguous region containing `(x, y)`
+///
+/// Cells are connected 4-way. Tiles that are entirely one terrain match any
+/// other tile of that terrain, so variants of a fill tile form one region;
+/// every other tile only matches the same tile index. Returns an empty list if
+/// the start cell is out of bounds.
+pub fn terrain_region(
+    tiles: &[Option<u32>],
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+    terrain_set: &TerrainSet,
+) -> Vec<(i32, i32)> {
+    // Cells past the end of a short `tiles` slice count as out of bounds
+    let in_bounds = |x: i32, y: i32| {
+        x >= 0
+            && y >= 0
+            && x < width as i32
+            && y < height as i32
+            && ((y as u32 * width + x as u32) as usize) < tiles.len()
+    };
+    let tile_at = |x: i32, y: i32| {
+        tiles
+            .get((y as u32 * width + x as u32) as usize)
+            .copied()
+            .flatten()
+    };
+
+    if !in_bounds(x, y) {
+        return Vec::new();
+    }
+
+    let key = region_key(terrain_set, tile_at(x, y));
+    let mut region = Vec::new();
+    let mut visited = HashSet::from([(x, y)]);
+    let mut stack = vec![(x, y)];
+
+    while let Some((cx, cy)) = stack.pop() {
+        region.push((cx, cy));
+        for (nx, ny) in [(cx - 1, cy), (cx + 1, cy), (cx, cy - 1), (cx, cy + 1)] {
+            if in_bounds(nx, ny)
+                && !visited.contains(&(nx, ny))
+                && region_key(terrain_set, tile_at(nx, ny)) == key
+            {
+                visited.insert((nx, ny));
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    region
+}
+
+/// Cells sharing wang position `i` of a tile, as offsets from that tile
+///
+/// An edge is shared with one neighbor, a corner with three.
+fn shared_with(i: usize) -> Vec<(i32, i32)> {
+    let (dx, dy) = NEIGHBOR_OFFSETS[i];
+    if dx != 0 && dy != 0 {
+        vec![(dx, 0), (0, dy), (dx, dy)]
+    } else {
+        vec![(dx, dy)]
+    }
+}
+
+/// Flood fill the region containing `(x, y)` with one terrain (terrain bucket fill)
+///
+/// The region (see [`terrain_region`]) becomes solid `terrain_index`. Tiles
+/// around it keep their other terrains and get the matching transition on the
+/// sides they share with the region. Does nothing if the start cell already
+/// has that terrain.
+pub fn fill_terrain_region(
+    tiles: &mut [Option<u32>],
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+    terrain_set: &TerrainSet,
+    terrain_index: usize,
+) {
+    let region = terrain_region(tiles, width, height, x, y, terrain_set);
+    let Some(&(x, y)) = region.first() else {
+        return;
+    };
+    let start_tile = tiles
+        .get((y as u32 * width + x as u32) as usize)
+        .copied()
+        .flatten();
+    if region_key(terrain_set, start_tile) == RegionKey::Terrain(terrain_index) {
+        return;
+    }
+
+    let color = (terrain_index + 1) as u8;
+    let active_positions = get_active_positions(terrain_set.set_type);
+    let region_set: HashSet<(i32, i32)> = region.iter().copied().collect();
+
+    // Seed based on the start position for deterministic results
+    let seed = (x as u64) << 32 | (y as u64);
+    let mut filler = WangFiller::with_seed(terrain_set, seed);
+
+    for &(cx, cy) in &region {
+        let cell = filler.get_cell_mut(cx, cy);
+        for &i in active_positions {
+            cell.set_constraint_at_index(i, color);
+        }
+    }
+
+    // Painted tiles around the region are re-resolved as well, constrained
+    // only where they touch it
+    let mut fill_region = region.clone();
+    let mut boundary = HashSet::new();
+    for &(cx, cy) in &region {
+        for &(dx, dy) in &NEIGHBOR_OFFSETS {
+            let (nx, ny) = (cx + dx, cy + dy);
+            if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+                continue;
+            }
+            let painted = tiles
+                .get((ny as u32 * width + nx as u32) as usize)
+                .copied()
+                .flatten()
+                .is_some_and(|tile| terrain_set.get_tile_terrain(tile_index(tile)).is_some());
+            if painted && !region_set.contains(&(nx, ny)) && boundary.insert((nx, ny)) {
+                fill_region.push((nx, ny));
+            }
+        }
+    }
+    for &(bx, by) in &boundary {
+        let cell = filler.get_cell_mut(bx, by);
+        for &i in active_positions {
+            let touches_region = shared_with(i)
+                .into_iter()
+                .any(|(dx, dy)| region_set.contains(&(bx + dx, by + dy)));
+            if touches_region {
+                cell.set_constraint_at_index(i, color);
+            }
+        }
+    }
+
+    filler.apply(tiles, width, height, &fill_region);
+}
+
+/// Repaint every occurrence of one terrain as another across a whole layer
+///
+/// Each position painted `from_terrain` becomes `to_terrain`, including the
+/// corners and edges of transition tiles, so borders with other terrains are
+/// kept. Other positions prefer their current terrain.
+pub fn replace_terrain(
+    tiles: &mut [Option<u32>],
+    width: u32,
+    height: u32,
+    terrain_set: &TerrainSet,
+    from_terrain: usize,
+    to_terrain: usize,
+) {
+    if from_terrain == to_terrain {
+        return;
+    }
+
+    let from_color = (from_terrain + 1) as u8;
+    let to_color = (to_terrain + 1) as u8;
+    let active_positions = get_active_positions(terrain_set.set_type);
+
+    let mut filler = WangFiller::new(terrain_set);
+    let mut region = Vec::new();
+
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let idx = (y as u32 * width + x as u32) as usize;
+            let Some(wang) = tiles
+                .get(idx)
+                .copied()
+                .flatten()
+                .and_then(|tile| filler.tile_wang_id(tile))
+            else {
+                continue;
+            };
+
+            let mut matched = false;
+            for &i in active_positions {
+                if wang.colors[i] == from_color {
+                    filler
+                        .get_cell_mut(x, y)
+                        .set_constraint_at_index(i, to_color);
+                    matched = true;
+                }
+            }
+            if matched {
+                region.push((x, y));
+            }
+        }
+    }
+
+    if !region.is_empty() {
+        filler.apply(tiles, width, height, &region);
+    }
+}
+
+/// Re-resolve every painted cell of a layer against the terrain set's current
+/// tile assignments
+///
+/// Use after changing which terrains a set's tiles have: the terrain at each
+/// corner/edge is inferred from the tiles currently sharing it (majority vote,
+/// lowest terrain on ties), then the whole layer is refilled in one seeded
+/// [`WangFiller`] pass, so the same input always gives the same tiles. Cells
+/// with no tile for their inferred terrains are left alone. Returns the
+/// number of changed cells.
+pub fn resolve_terrain_layer(
+    tiles: &mut [Option<u32>],
+    width: u32,
+    height: u32,
+    terrain_set: &TerrainSet,
+    seed: u64,
+) -> usize {
+    let active_positions = get_active_positions(terrain_set.set_type);
+    let mut filler = WangFiller::with_seed(terrain_set, seed);
+
+    // Corners and edges in doubled coordinates, so the cells sharing one agree
+    // on its key: cell (x, y) is centered on (2x + 1, 2y + 1)
+    let key = |x: i32, y: i32, i: usize| {
+        let (dx, dy) = NEIGHBOR_OFFSETS[i];
+        (2 * x + 1 + dx, 2 * y + 1 + dy)
+    };
+
+    let mut region = Vec::new();
+    let mut votes: HashMap<(i32, i32), HashMap<u8, usize>> = HashMap::new();
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let idx = (y as u32 * width + x as u32) as usize;
+            let Some(wang) = tiles
+                .get(idx)
+                .copied()
+                .flatten()
+                .and_then(|tile| filler.tile_wang_id(tile))
+            else {
+                continue;
+            };
+            region.push((x, y));
+            for &i in active_positions {
+                if wang.colors[i] != 0 {
+                    *votes
+                        .entry(key(x, y, i))
+                        .or_default()
+                        .entry(wang.colors[i])
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let winners: HashMap<(i32, i32), u8> = votes
+        .into_iter()
+        .filter_map(|(k, counts)| {
+            counts
+                .into_iter()
+                .max_by_key(|&(color, count)| (count, std::cmp::Reverse(color)))
+                .map(|(color, _)| (k, color))
+        })
+        .collect();
+
+    for &(x, y) in &region {
+        let cell = filler.get_cell_mut(x, y);
+        for &i in active_positions {
+            if let Some(&color) = winners.get(&key(x, y, i)) {
+                cell.set_constraint_at_index(i, color);
+            }
+        }
+    }
+
+    let before = tiles.to_vec();
+    filler.apply(tiles, width, height, &region);
+    tiles
+        .iter()
+        .zip(&before)
+        .filter(|(after, before)| after != before)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terrain::{Color, TerrainSetType, TileTerrainData};
+    use crate::wang::paint_terrain;
+    use uuid::Uuid;
+
+    const GRASS: usize = 0;
+    const DIRT: usize = 1;
+    const SAND: usize = 2;
+
+    /// Corner set with three terrains and all 81 corner combinations. Digit
+    /// `p` of the tile index in base 3 is the terrain at corner `p` (TL, TR,
+    /// BL, BR), so tile 0 is all grass.
+    fn corner_set() -> TerrainSet {
+        let mut set = TerrainSet::new("Ground".to_string(), Uuid::new_v4(), TerrainSetType::Corner);
+        set.add_terrain("Grass".to_string(), Color::GREEN);
+        set.add_terrain("Dirt".to_string(), Color::rgb(0.6, 0.4, 0.2));
+        set.add_terrain("Sand".to_string(), Color::rgb(0.9, 0.8, 0.5));
+        for tile in 0..81u32 {
+            let mut data = TileTerrainData::new();
+            for corner in 0..4 {
+                data.set(corner, Some(digit(tile, corner)));
+            }
+            set.tile_terrains.insert(tile, data);
+        }
+        set
+    }
+
+    fn digit(tile: u32, corner: usize) -> usize {
+        (tile / 3u32.pow(corner as u32) % 3) as usize
+    }
+
+    /// Tile with terrain `from` replaced by `to` at every corner
+    fn swap(tile: u32, from: usize, to: usize) -> u32 {
+        (0..4)
+            .map(|corner| {
+                let terrain = digit(tile, corner);
+                let terrain = if terrain == from { to } else { terrain };
+                terrain as u32 * 3u32.pow(corner as u32)
+            })
+            .sum()
+    }
+
+    /// 4x4 grass map with dirt painted at the center corner
+    fn dirt_patch(set: &TerrainSet) -> Vec<Option<u32>> {
+        let mut tiles = vec![Some(0); 16];
+        paint_terrain(&mut tiles, 4, 4, 2, 2, set, DIRT);
+        tiles
+    }
+
+    #[test]
+    fn test_terrain_region_stops_at_other_tiles() {
+        let set = corner_set();
+        // 4x2 map of grass split by an empty column
+        let tiles = vec![
+            Some(0),
+            None,
+            Some(0),
+            Some(0),
+            Some(0),
+            None,
+            Some(0),
+            Some(0),
+        ];
+
+        let mut left = terrain_region(&tiles, 4, 2, 0, 0, &set);
+        left.sort();
+        assert_eq!(left, [(0, 0), (0, 1)]);
+        assert_eq!(terrain_region(&tiles, 4, 2, 3, 1, &set).len(), 4);
+        assert_eq!(terrain_region(&tiles, 4, 2, 1, 0, &set).len(), 2);
+        assert!(terrain_region(&tiles, 4, 2, 4, 0, &set).is_empty());
+        // A short slice never yields cells past its end
+        assert_eq!(terrain_region(&tiles[..6], 4, 2, 3, 0, &set).len(), 2);
+
+        // The dirt transitions split off from the surrounding grass
+        assert_eq!(
+            terrain_region(&dirt_patch(&set), 4, 4, 0, 0, &set).len(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_fill_terrain_region() {
+        let set = corner_set();
+        let mut tiles = vec![Some(0); 16];
+        fill_terrain_region(&mut tiles, 4, 4, 1, 1, &set, SAND);
+        assert!(tiles.iter().all(|&t| t == Some(80)));
+
+        // Filling the grass around a dirt patch keeps the dirt corner
+        let painted = dirt_patch(&set);
+        let mut tiles = painted.clone();
+        fill_terrain_region(&mut tiles, 4, 4, 0, 0, &set, SAND);
+        let expected: Vec<_> = painted
+            .iter()
+            .map(|t| t.map(|t| swap(t, GRASS, SAND)))
+            .collect();
+        assert_eq!(tiles, expected);
+
+        // Already that terrain: nothing to do
+        fill_terrain_region(&mut tiles, 4, 4, 0, 0, &set, SAND);
+        assert_eq!(tiles, expected);
+
+        // Layer data shorter than the level doesn't panic
+        let mut short = vec![Some(0); 10];
+        fill_terrain_region(&mut short, 4, 4, 0, 0, &set, SAND);
+        assert!(short.iter().all(|&t| t == Some(80)));
+    }
+
+    #[test]
+    fn test_replace_terrain_keeps_transitions() {
+        let set = corner_set();
+        let painted = dirt_patch(&set);
+
+        let mut tiles = painted.clone();
+        replace_terrain(&mut tiles, 4, 4, &set, GRASS, SAND);
+        let expected: Vec<_> = painted
+            .iter()
+            .map(|t| t.map(|t| swap(t, GRASS, SAND)))
+            .collect();
+        assert_eq!(tiles, expected);
+
+        let mut tiles = painted;
+        replace_terrain(&mut tiles, 4, 4, &set, DIRT, GRASS);
+        assert!(tiles.iter().all(|&t| t == Some(0)));
+    }
+
+    #[test]
+    fn test_resolve_terrain_layer() {
+        let set = corner_set();
+
+        // Consistent tiles stay as they are
+        let painted = dirt_patch(&set);
+        let mut tiles = painted.clone();
+        assert_eq!(resolve_terrain_layer(&mut tiles, 4, 4, &set, 7), 0);
+        assert_eq!(tiles, painted);
+
+        // A solid sand tile in the grass, e.g. after its tile was retagged:
+        // the grass neighbors outvote it everywhere but the map corner it
+        // doesn't share, and ties go to the lower terrain
+        let mut tiles = vec![Some(0); 16];
+        tiles[0] = Some(80);
+        assert_eq!(resolve_terrain_layer(&mut tiles, 4, 4, &set, 7), 1);
+        let bottom_left_sand = 2 * 3u32.pow(2);
+        assert_eq!(tiles[0], Some(bottom_left_sand));
+        assert!(tiles[1..].iter().all(|&t| t == Some(0)));
+
+        // Resolving again changes nothing
+        assert_eq!(resolve_terrain_layer(&mut tiles, 4, 4, &set, 7), 0);
+
+        // Layer data shorter than the level doesn't panic
+        let mut short = dirt_patch(&set);
+        short.truncate(10);
+        resolve_terrain_layer(&mut short, 4, 4, &set, 7);
+    }
+}
@@ -0,0 +1,679 @@
+//! Rule-based automapping
+//!
+//! Complements Wang terrains with LDtk/Tiled-style rules: each rule matches
+//! patterns on one or more input layers around a cell and writes one of its
+//! output tiles to the rule set's output layer. Typical uses are scattering
+//! decorations with a chance, placing shadows under overhangs, and deriving a
+//! collision or visual layer from a layer of marker tiles.
+//!
+//! Rules read a snapshot of the level taken before the rule set runs, so the
+//! result doesn't depend on cell order. Random choices are hashed from the
+//! seed and cell position, which keeps re-running a rule set after an edit
+//! stable everywhere except around the edit.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy_map_core::{tile_index, LayerData, Level, TILE_FLIP_DIAGONAL, TILE_FLIP_X, TILE_FLIP_Y};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a pattern cell requires of the tile under it
+///
+/// Tile indices are compared without flip flags. Cells outside the level only
+/// match [`CellMatch::Any`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CellMatch {
+    /// Matches anything
+    #[default]
+    Any,
+    /// Matches an empty cell
+    Empty,
+    /// Matches any tile
+    NotEmpty,
+    /// Matches one of these tiles
+    Tiles(Vec<u32>),
+    /// Matches an empty cell or a tile not in this list
+    NotTiles(Vec<u32>),
+}
+
+impl CellMatch {
+    fn matches(&self, tile: Option<u32>) -> bool {
+        let tile = tile.map(tile_index);
+        match self {
+            CellMatch::Any => true,
+            CellMatch::Empty => tile.is_none(),
+            CellMatch::NotEmpty => tile.is_some(),
+            CellMatch::Tiles(tiles) => tile.is_some_and(|t| tiles.contains(&t)),
+            CellMatch::NotTiles(tiles) => tile.map_or(true, |t| !tiles.contains(&t)),
+        }
+    }
+}
+
+/// A pattern over one input layer, centered on the cell being evaluated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleInput {
+    /// Name of the layer to read
+    pub layer: String,
+    /// Pattern size in cells (odd, so the pattern has a center)
+    pub size: u32,
+    /// Row-major cells, top row first
+    pub cells: Vec<CellMatch>,
+}
+
+impl RuleInput {
+    /// Create a pattern of `size` x `size` cells that matches anything
+    pub fn new(layer: impl Into<String>, size: u32) -> Self {
+        let size = size.max(1) | 1;
+        Self {
+            layer: layer.into(),
+            size,
+            cells: vec![CellMatch::Any; (size * size) as usize],
+        }
+    }
+
+    /// Get a cell by offset from the center (`dy` grows downward)
+    ///
+    /// Offsets outside the pattern (or its stored cells) are [`CellMatch::Any`].
+    pub fn cell(&self, dx: i32, dy: i32) -> &CellMatch {
+        const ANY: &CellMatch = &CellMatch::Any;
+        self.cell_index(dx, dy)
+            .and_then(|idx| self.cells.get(idx))
+            .unwrap_or(ANY)
+    }
+
+    /// Set a cell by offset from the center (`dy` grows downward)
+    ///
+    /// Offsets outside the pattern are ignored.
+    pub fn set_cell(&mut self, dx: i32, dy: i32, cell: CellMatch) {
+        if let Some(slot) = self
+            .cell_index(dx, dy)
+            .and_then(|idx| self.cells.get_mut(idx))
+        {
+            *slot = cell;
+        }
+    }
+
+    fn cell_index(&self, dx: i32, dy: i32) -> Option<usize> {
+        let half = (self.size / 2) as i32;
+        if dx.abs() > half || dy.abs() > half {
+            return None;
+        }
+        Some(((dy + half) as u32 * self.size + (dx + half) as u32) as usize)
+    }
+
+    /// Whether `size` is odd and `cells` holds exactly `size` x `size` cells
+    ///
+    /// Hand-edited or corrupted project files can break this.
+    pub fn is_valid(&self) -> bool {
+        self.size % 2 == 1 && self.cells.len() as u64 == self.size as u64 * self.size as u64
+    }
+
+    /// Change the pattern size, keeping cells around the center
+    pub fn resize(&mut self, size: u32) {
+        let mut resized = RuleInput::new(self.layer.clone(), size);
+        let half = (self.size.min(resized.size) / 2) as i32;
+        for dy in -half..=half {
+            for dx in -half..=half {
+                resized.set_cell(dx, dy, self.cell(dx, dy).clone());
+            }
+        }
+        *self = resized;
+    }
+}
+
+/// A tile a rule can write, with its relative weight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleOutput {
+    /// Tile to write (`None` erases the cell)
+    pub tile: Option<u32>,
+    pub weight: f32,
+}
+
+/// A single automapping rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomapRule {
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Patterns that must all match
+    pub inputs: Vec<RuleInput>,
+    /// Tiles to pick from, weighted
+    pub outputs: Vec<RuleOutput>,
+    /// Chance (0-1) that the rule applies where it matches
+    #[serde(default = "default_chance")]
+    pub chance: f32,
+    /// Also match the pattern mirrored horizontally (output gets `TILE_FLIP_X`)
+    #[serde(default)]
+    pub flip_x: bool,
+    /// Also match the pattern mirrored vertically (output gets `TILE_FLIP_Y`)
+    #[serde(default)]
+    pub flip_y: bool,
+    /// Also match the pattern rotated by 90, 180 and 270 degrees (output gets
+    /// Tiled-style flip flags)
+    #[serde(default)]
+    pub rotate: bool,
+    /// Stop evaluating later rules for a cell once this rule writes it
+    #[serde(default = "default_true")]
+    pub break_on_match: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_chance() -> f32 {
+    1.0
+}
+
+impl AutomapRule {
+    /// Create an enabled rule with no inputs or outputs
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            enabled: true,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            chance: 1.0,
+            flip_x: false,
+            flip_y: false,
+            rotate: false,
+            break_on_match: true,
+        }
+    }
+
+    /// Transforms to try, identity first, as (diagonal, flip x, flip y) flags
+    fn variants(&self) -> Vec<Transform> {
+        let mut variants = vec![Transform::IDENTITY];
+        let mut add = |t: Transform| {
+            if !variants.contains(&t) {
+                variants.push(t);
+            }
+        };
+        if self.flip_x {
+            add(Transform::new(false, true, false));
+        }
+        if self.flip_y {
+            add(Transform::new(false, false, true));
+        }
+        if self.flip_x && self.flip_y {
+            add(Transform::new(false, true, true));
+        }
+        if self.rotate {
+            // Tiled's rotations: 90 = D+X, 180 = X+Y, 270 = D+Y
+            for t in [
+                Transform::new(true, true, false),
+                Transform::new(false, true, true),
+                Transform::new(true, false, true),
+            ] {
+                add(t);
+            }
+            if self.flip_x || self.flip_y {
+                // Mirrored rotations complete the eight orientations
+                for t in [
+                    Transform::new(true, false, false),
+                    Transform::new(false, true, false),
+                    Transform::new(false, false, true),
+                    Transform::new(true, true, true),
+                ] {
+                    add(t);
+                }
+            }
+        }
+        variants
+    }
+}
+
+/// Orientation of a pattern and its output tile, using Tiled's flip flags:
+/// the diagonal flip (transpose) applies first, then the horizontal and
+/// vertical flips
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transform {
+    diagonal: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl Transform {
+    const IDENTITY: Transform = Transform::new(false, false, false);
+
+    const fn new(diagonal: bool, flip_x: bool, flip_y: bool) -> Self {
+        Self {
+            diagonal,
+            flip_x,
+            flip_y,
+        }
+    }
+
+    /// Map a pattern offset (`dy` grows downward) to its transformed offset
+    fn apply(self, dx: i32, dy: i32) -> (i32, i32) {
+        let (mut x, mut y) = if self.diagonal { (dy, dx) } else { (dx, dy) };
+        if self.flip_x {
+            x = -x;
+        }
+        if self.flip_y {
+            y = -y;
+        }
+        (x, y)
+    }
+
+    fn flags(self) -> u32 {
+        let mut flags = 0;
+        if self.diagonal {
+            flags |= TILE_FLIP_DIAGONAL;
+        }
+        if self.flip_x {
+            flags |= TILE_FLIP_X;
+        }
+        if self.flip_y {
+            flags |= TILE_FLIP_Y;
+        }
+        flags
+    }
+}
+
+/// An ordered list of rules writing to one output layer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomapRuleSet {
+    pub id: Uuid,
+    pub name: String,
+    /// Name of the tile layer the rules write to
+    pub output_layer: String,
+    /// Rules in priority order (later rules overwrite earlier ones unless an
+    /// earlier rule breaks on match)
+    pub rules: Vec<AutomapRule>,
+    /// Clear output cells that no rule writes, so the layer is fully generated
+    #[serde(default = "default_true")]
+    pub clear_output: bool,
+    /// Re-run in the editor whenever an input layer changes
+    #[serde(default)]
+    pub auto_apply: bool,
+    /// Seed for chances and weighted output picks
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Error applying an [`AutomapRuleSet`] to a level
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutomapError {
+    /// The level has no layer with this name
+    MissingLayer(String),
+    /// The layer exists but isn't a tile layer
+    NotTileLayer(String),
+    /// A rule's pattern has an even size or the wrong number of cells
+    InvalidPattern { rule: String, layer: String },
+}
+
+impl fmt::Display for AutomapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AutomapError::MissingLayer(name) => write!(f, "Layer '{}' not found", name),
+            AutomapError::NotTileLayer(name) => write!(f, "Layer '{}' is not a tile layer", name),
+            AutomapError::InvalidPattern { rule, layer } => {
+                write!(
+                    f,
+                    "Rule '{}' has an invalid pattern for layer '{}'",
+                    rule, layer
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AutomapError {}
+
+impl AutomapRuleSet {
+    /// Create an empty rule set writing to `output_layer`
+    pub fn new(name: impl Into<String>, output_layer: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            output_layer: output_layer.into(),
+            rules: Vec::new(),
+            clear_output: true,
+            auto_apply: false,
+            seed: 0,
+        }
+    }
+
+    /// Names of the layers read by enabled rules
+    pub fn input_layers(&self) -> Vec<&str> {
+        let mut layers: Vec<&str> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.enabled) {
+            for input in &rule.inputs {
+                if !layers.contains(&input.layer.as_str()) {
+                    layers.push(&input.layer);
+                }
+            }
+        }
+        layers
+    }
+
+    /// Run the rules on a level, returning the number of output cells changed
+    ///
+    /// Layers whose tile data is shorter than the level read as empty past
+    /// their end, and such output cells are left alone.
+    pub fn apply(&self, level: &mut Level) -> Result<usize, AutomapError> {
+        let output_idx = tile_layer_index(level, &self.output_layer)?;
+        for rule in self.rules.iter().filter(|r| r.enabled) {
+            if let Some(input) = rule.inputs.iter().find(|input| !input.is_valid()) {
+                return Err(AutomapError::InvalidPattern {
+                    rule: rule.name.clone(),
+                    layer: input.layer.clone(),
+                });
+            }
+        }
+
+        // Snapshot the inputs so rules never see their own output
+        let mut inputs: HashMap<&str, Vec<Option<u32>>> = HashMap::new();
+        for name in self.input_layers() {
+            let idx = tile_layer_index(level, name)?;
+            if let LayerData::Tiles { tiles, .. } = &level.layers[idx].data {
+                inputs.insert(name, tiles.clone());
+            }
+        }
+
+        let (width, height) = (level.width, level.height);
+        let LayerData::Tiles { tiles: output, .. } = &mut level.layers[output_idx].data else {
+            return Err(AutomapError::NotTileLayer(self.output_layer.clone()));
+        };
+
+        let rules: Vec<(usize, &AutomapRule, Vec<Transform>)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.enabled && !rule.outputs.is_empty())
+            .map(|(i, rule)| (i, rule, rule.variants()))
+            .collect();
+
+        let mut changed = 0;
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                let mut result = None;
+                for (rule_idx, rule, variants) in &rules {
+                    let Some(transform) = variants
+                        .iter()
+                        .find(|t| rule_matches(rule, **t, &inputs, width, height, x, y))
+                    else {
+                        continue;
+                    };
+                    if cell_random(self.seed, *rule_idx, 0, x, y) >= rule.chance {
+                        continue;
+                    }
+                    let pick = cell_random(self.seed, *rule_idx, 1, x, y);
+                    let tile = pick_output(&rule.outputs, pick)
+                        .map(|tile| tile_index(tile) | transform.flags());
+                    result = Some(tile);
+                    if rule.break_on_match {
+                        break;
+                    }
+                }
+
+                let idx = (y as u32 * width + x as u32) as usize;
+                let new_tile = match result {
+                    Some(tile) => tile,
+                    None if self.clear_output => None,
+                    None => continue,
+                };
+                if let Some(cell) = output.get_mut(idx).filter(|cell| **cell != new_tile) {
+                    *cell = new_tile;
+                    changed += 1;
+                }
+            }
+        }
+
+        Ok(changed)
+    }
+}
+
+fn tile_layer_index(level: &Level, name: &str) -> Result<usize, AutomapError> {
+    let idx = level
+        .layers
+        .iter()
+        .position(|layer| layer.name == name)
+        .ok_or_else(|| AutomapError::MissingLayer(name.to_string()))?;
+    match level.layers[idx].data {
+        LayerData::Tiles { .. } => Ok(idx),
+        LayerData::Objects { .. } => Err(AutomapError::NotTileLayer(name.to_string())),
+    }
+}
+
+/// Whether every input pattern of a rule matches around `(x, y)`
+fn rule_matches(
+    rule: &AutomapRule,
+    transform: Transform,
+    inputs: &HashMap<&str, Vec<Option<u32>>>,
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+) -> bool {
+    rule.inputs.iter().all(|input| {
+        let Some(tiles) = inputs.get(input.layer.as_str()) else {
+            return false;
+        };
+        let half = (input.size / 2) as i32;
+        (-half..=half).all(|py| {
+            (-half..=half).all(|px| {
+                let cell = input.cell(px, py);
+                if *cell == CellMatch::Any {
+                    return true;
+                }
+                // Pattern rows go down, level rows go up
+                let (dx, dy) = transform.apply(px, py);
+                let (cx, cy) = (x + dx, y - dy);
+                if cx < 0 || cy < 0 || cx >= width as i32 || cy >= height as i32 {
+                    return false;
+                }
+                let tile = tiles.get((cy as u32 * width + cx as u32) as usize);
+                cell.matches(tile.copied().flatten())
+            })
+        })
+    })
+}
+
+/// Pick an output by weight, `roll` in `[0, 1)`
+fn pick_output(outputs: &[RuleOutput], roll: f32) -> Option<u32> {
+    let total: f32 = outputs.iter().map(|o| o.weight.max(0.0)).sum();
+    if total <= 0.0 {
+        return outputs.first().and_then(|o| o.tile);
+    }
+    let mut remaining = roll * total;
+    for output in outputs {
+        remaining -= output.weight.max(0.0);
+        if remaining < 0.0 {
+            return output.tile;
+        }
+    }
+    outputs.last().and_then(|o| o.tile)
+}
+
+/// Deterministic random number in `[0, 1)` for a cell (SplitMix64 hash)
+fn cell_random(seed: u64, rule: usize, stream: u64, x: i32, y: i32) -> f32 {
+    let mut z = seed
+        ^ (rule as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ stream.wrapping_mul(0xD1B5_4A32_D192_ED03)
+        ^ ((x as u32 as u64) << 32 | y as u32 as u64);
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_core::Layer;
+
+    const GROUND: u32 = 1;
+    const WALL: u32 = 2;
+    const SHADOW: u32 = 10;
+    const GRASS: u32 = 20;
+
+    /// Level with "Ground" filled from `rows` (top row first, '#' = wall,
+    /// '.' = ground, ' ' = empty) and an empty "Decor" layer
+    fn level(rows: &[&str]) -> Level {
+        let height = rows.len() as u32;
+        let width = rows[0].len() as u32;
+        let mut level = Level::new("Test".to_string(), width, height);
+        level.add_layer(Layer::new_tile_layer(
+            "Ground".to_string(),
+            Uuid::nil(),
+            width,
+            height,
+        ));
+        level.add_layer(Layer::new_tile_layer(
+            "Decor".to_string(),
+            Uuid::nil(),
+            width,
+            height,
+        ));
+        for (row, line) in rows.iter().enumerate() {
+            let y = height - 1 - row as u32;
+            for (x, c) in line.chars().enumerate() {
+                let tile = match c {
+                    '#' => Some(WALL),
+                    '.' => Some(GROUND),
+                    _ => None,
+                };
+                level.set_tile(0, x as u32, y, tile);
+            }
+        }
+        level
+    }
+
+    /// The "Decor" layer as rows, top row first
+    fn decor(level: &Level) -> Vec<Vec<Option<u32>>> {
+        (0..level.height)
+            .rev()
+            .map(|y| (0..level.width).map(|x| level.get_tile(1, x, y)).collect())
+            .collect()
+    }
+
+    /// Rule writing `tile` on ground cells whose neighbor above is a wall
+    fn shadow_rule() -> AutomapRule {
+        let mut input = RuleInput::new("Ground", 3);
+        input.set_cell(0, 0, CellMatch::Tiles(vec![GROUND]));
+        input.set_cell(0, -1, CellMatch::Tiles(vec![WALL]));
+        let mut rule = AutomapRule::new("Shadow");
+        rule.inputs.push(input);
+        rule.outputs.push(RuleOutput {
+            tile: Some(SHADOW),
+            weight: 1.0,
+        });
+        rule
+    }
+
+    #[test]
+    fn test_pattern_matches_and_clears() {
+        let mut level = level(&["###", "...", "..."]);
+        let mut rule_set = AutomapRuleSet::new("Shadows", "Decor");
+        rule_set.rules.push(shadow_rule());
+
+        assert_eq!(rule_set.apply(&mut level), Ok(3));
+        let s = Some(SHADOW);
+        assert_eq!(
+            decor(&level),
+            [
+                vec![None, None, None],
+                vec![s, s, s],
+                vec![None, None, None]
+            ]
+        );
+
+        // Re-running is stable; removing the wall clears the generated tiles
+        assert_eq!(rule_set.apply(&mut level), Ok(0));
+        level.set_tile(0, 1, 2, Some(GROUND));
+        assert_eq!(rule_set.apply(&mut level), Ok(1));
+        assert_eq!(decor(&level)[1], [s, None, s]);
+    }
+
+    #[test]
+    fn test_rotated_variants_set_flip_flags() {
+        // A wall to the right of ground only matches the pattern rotated 90 degrees
+        let mut level = level(&[".#"]);
+        let mut rule_set = AutomapRuleSet::new("Shadows", "Decor");
+        rule_set.rules.push(shadow_rule());
+        assert_eq!(rule_set.apply(&mut level), Ok(0));
+
+        rule_set.rules[0].rotate = true;
+        assert_eq!(rule_set.apply(&mut level), Ok(1));
+        assert_eq!(
+            level.get_tile(1, 0, 0),
+            Some(SHADOW | TILE_FLIP_DIAGONAL | TILE_FLIP_X)
+        );
+    }
+
+    #[test]
+    fn test_chance_and_missing_layers() {
+        let rows = vec!["........"; 8];
+        let mut level = level(&rows);
+
+        let mut input = RuleInput::new("Ground", 1);
+        input.set_cell(0, 0, CellMatch::NotEmpty);
+        let mut rule = AutomapRule::new("Grass");
+        rule.inputs.push(input);
+        rule.outputs.push(RuleOutput {
+            tile: Some(GRASS),
+            weight: 1.0,
+        });
+        rule.chance = 0.25;
+
+        let mut rule_set = AutomapRuleSet::new("Decor", "Decor");
+        rule_set.rules.push(rule);
+        let placed = rule_set.apply(&mut level).unwrap();
+        assert!(placed > 0 && placed < 40, "placed {}", placed);
+
+        // Same seed, same result
+        let mut again = level.clone();
+        assert_eq!(rule_set.apply(&mut again), Ok(0));
+
+        rule_set.output_layer = "Missing".to_string();
+        assert_eq!(
+            rule_set.apply(&mut level),
+            Err(AutomapError::MissingLayer("Missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_short_layers_and_malformed_patterns() {
+        let mut level = level(&["###", "...", "..."]);
+        let mut rule_set = AutomapRuleSet::new("Shadows", "Decor");
+        rule_set.rules.push(shadow_rule());
+
+        let truncate = |level: &mut Level, layer: usize, len: usize| {
+            if let LayerData::Tiles { tiles, .. } = &mut level.layers[layer].data {
+                tiles.truncate(len);
+            }
+        };
+
+        // Missing input cells read as empty: only (0, 0) sees a wall above
+        truncate(&mut level, 0, 4);
+        level.set_tile(0, 0, 1, Some(WALL));
+        assert_eq!(rule_set.apply(&mut level), Ok(1));
+        assert_eq!(level.get_tile(1, 0, 0), Some(SHADOW));
+
+        // Missing output cells aren't written
+        truncate(&mut level, 1, 0);
+        assert_eq!(rule_set.apply(&mut level), Ok(0));
+
+        // Patterns loaded with the wrong cell count or an even size are rejected
+        let input = &mut rule_set.rules[0].inputs[0];
+        input.cells.pop();
+        assert_eq!(input.cell(1, 1), &CellMatch::Any);
+        input.set_cell(1, 1, CellMatch::Empty);
+        assert_eq!(input.cell(5, 0), &CellMatch::Any);
+        let invalid = Err(AutomapError::InvalidPattern {
+            rule: "Shadow".to_string(),
+            layer: "Ground".to_string(),
+        });
+        assert_eq!(rule_set.apply(&mut level), invalid);
+        let input = &mut rule_set.rules[0].inputs[0];
+        input.size = 2;
+        input.cells = vec![CellMatch::Any; 4];
+        assert_eq!(rule_set.apply(&mut level), invalid);
+        rule_set.rules[0].inputs[0].size = 0;
+        assert_eq!(rule_set.apply(&mut level), invalid);
+    }
+}
@@ -103,6 +103,10 @@ pub struct Terrain {
     pub color: Color,
     /// Representative tile for this terrain (shown in UI)
     pub icon_tile: Option<u32>,
+    /// Name of the layer this terrain is painted on (`None` paints on the
+    /// selected layer, or the bottom of the set's layer stack)
+    #[serde(default)]
+    pub layer: Option<String>,
 }
 
 impl Terrain {
@@ -112,6 +116,7 @@ impl Terrain {
             name,
             color,
             icon_tile: None,
+            layer: None,
         }
     }
 }
@@ -262,6 +267,16 @@ pub struct TerrainSet {
     /// Used by WangFiller to bias tile selection toward certain variants
     #[serde(default)]
     pub tile_probabilities: HashMap<u32, f32>,
+    /// Let WangFiller also place flipped and rotated tiles (Tiled's
+    /// "allow flipping/rotation"). Untransformed tiles are preferred when
+    /// they match equally well.
+    #[serde(default)]
+    pub allow_transforms: bool,
+    /// Layer names, bottom first, for overlapping transitions. Each terrain
+    /// belongs to the stack layer named by its `layer`, and a stack layer
+    /// only keeps tiles showing its own terrains or ones from higher layers.
+    #[serde(default)]
+    pub layer_stack: Vec<String>,
 }
 
 impl TerrainSet {
@@ -274,6 +289,8 @@ impl TerrainSet {
             terrains: Vec::new(),
             tile_terrains: HashMap::new(),
             tile_probabilities: HashMap::new(),
+            allow_transforms: false,
+            layer_stack: Vec::new(),
         }
     }
 
@@ -356,6 +373,8 @@ impl TerrainSet {
     /// Find the best tile match using Tiled-style penalty scoring
     /// Returns (tile_index, penalty_score) where lower score = better match
     /// Returns None only if no tiles have terrain data
+    ///
+    /// Only considers tiles as authored; `WangFiller` handles `allow_transforms`.
     pub fn find_best_tile(&self, constraints: &TileConstraints) -> Option<(u32, f32)> {
         let position_count = self.set_type.position_count();
         let mut best_tile: Option<(u32, f32)> = None;
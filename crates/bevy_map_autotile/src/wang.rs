@@ -11,6 +11,7 @@
 //! 3. **Corrections**: Fix edge neighbors that violate constraints (single pass)
 
 use crate::terrain::{TerrainSet, TerrainSetType, TileTerrainData};
+use bevy_map_core::{tile_index, TILE_FLIP_DIAGONAL, TILE_FLIP_MASK, TILE_FLIP_X, TILE_FLIP_Y};
 use rand::prelude::*;
 use rand::rngs::SmallRng;
 use std::collections::{HashMap, HashSet};
@@ -158,6 +159,33 @@ impl WangId {
     pub fn has_any_terrain(&self) -> bool {
         self.colors.iter().any(|&c| c != 0)
     }
+
+    /// WangId of a tile drawn with the given flip flags
+    ///
+    /// Flags follow Tiled's order: diagonal (transpose) first, then X, then Y.
+    /// So rotating 90° clockwise is diagonal + X.
+    pub fn transformed(&self, flags: u32) -> Self {
+        let mut wang = *self;
+        if flags & TILE_FLIP_DIAGONAL != 0 {
+            wang = wang.mirrored(14); // Top <-> Left, Right <-> Bottom
+        }
+        if flags & TILE_FLIP_X != 0 {
+            wang = wang.mirrored(8); // Left <-> Right
+        }
+        if flags & TILE_FLIP_Y != 0 {
+            wang = wang.mirrored(12); // Top <-> Bottom
+        }
+        wang
+    }
+
+    /// Reflect across an axis, moving index `i` to `(axis - i) % 8`
+    fn mirrored(&self, axis: usize) -> Self {
+        let mut wang = WangId::WILDCARD;
+        for (i, &color) in self.colors.iter().enumerate() {
+            wang.colors[(axis - i) % 8] = color;
+        }
+        wang
+    }
 }
 
 // =============================================================================
@@ -230,7 +258,7 @@ impl CellInfo {
 // =============================================================================
 
 /// Neighbor offsets in Y-UP coordinate system, indexed by WangPosition
-const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
+pub(crate) const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
     (0, 1),   // 0 = Top
     (1, 1),   // 1 = TopRight
     (1, 0),   // 2 = Right
@@ -247,7 +275,7 @@ const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
 /// - Corner mode: only corner positions (odd indices 1,3,5,7)
 /// - Edge mode: only edge positions (even indices 0,2,4,6)
 /// - Mixed mode: all 8 positions
-fn get_active_positions(set_type: TerrainSetType) -> &'static [usize] {
+pub(crate) fn get_active_positions(set_type: TerrainSetType) -> &'static [usize] {
     match set_type {
         TerrainSetType::Corner => &[1, 3, 5, 7], // TopRight, BottomRight, BottomLeft, TopLeft
         TerrainSetType::Edge => &[0, 2, 4, 6],   // Top, Right, Bottom, Left
@@ -255,6 +283,19 @@ fn get_active_positions(set_type: TerrainSetType) -> &'static [usize] {
     }
 }
 
+/// Flip flag combinations a tile can be placed with (the 8 flips/rotations
+/// of a square), untransformed first
+pub(crate) const TRANSFORMS: [u32; 8] = [
+    0,
+    TILE_FLIP_X,
+    TILE_FLIP_Y,
+    TILE_FLIP_X | TILE_FLIP_Y,
+    TILE_FLIP_DIAGONAL,
+    TILE_FLIP_DIAGONAL | TILE_FLIP_X,
+    TILE_FLIP_DIAGONAL | TILE_FLIP_Y,
+    TILE_FLIP_DIAGONAL | TILE_FLIP_X | TILE_FLIP_Y,
+];
+
 // =============================================================================
 // WangFiller - Main fill algorithm (Tiled-compatible)
 // =============================================================================
@@ -309,6 +350,17 @@ impl<'a> WangFiller<'a> {
         self.cells.entry((x, y)).or_default()
     }
 
+    /// Get the WangId of a placed tile, following its flip flags
+    ///
+    /// Returns None if the tile has no terrain data in this set.
+    pub(crate) fn tile_wang_id(&self, tile: u32) -> Option<WangId> {
+        let data = self.terrain_set.get_tile_terrain(tile_index(tile))?;
+        Some(
+            self.tile_terrain_to_wang_id(data)
+                .transformed(tile & TILE_FLIP_MASK),
+        )
+    }
+
     /// Convert TileTerrainData to WangId
     fn tile_terrain_to_wang_id(&self, data: &TileTerrainData) -> WangId {
         let mut wang = WangId::WILDCARD;
@@ -397,8 +449,7 @@ impl<'a> WangFiller<'a> {
             if nx >= 0 && ny >= 0 && nx < width as i32 && ny < height as i32 {
                 let nidx = (ny as u32 * width + nx as u32) as usize;
                 if let Some(tile) = tiles.get(nidx).copied().flatten() {
-                    if let Some(terrain_data) = self.terrain_set.get_tile_terrain(tile) {
-                        let neighbor_wang = self.tile_terrain_to_wang_id(terrain_data);
+                    if let Some(neighbor_wang) = self.tile_wang_id(tile) {
                         // Get the opposite position's color from the neighbor
                         let opp_idx = WangId::opposite_index(i);
                         let color = neighbor_wang.colors[opp_idx];
@@ -492,44 +543,73 @@ impl<'a> WangFiller<'a> {
         let mut best_penalty = f32::MAX;
         let mut rejected_count = 0;
 
-        for (&tile_id, tile_terrain) in &self.terrain_set.tile_terrains {
+        let transforms = if self.terrain_set.allow_transforms {
+            &TRANSFORMS[..]
+        } else {
+            &TRANSFORMS[..1]
+        };
+
+        for (&base_tile, tile_terrain) in &self.terrain_set.tile_terrains {
             if !tile_terrain.has_any_terrain() {
                 continue;
             }
 
-            let tile_wang = self.tile_terrain_to_wang_id(tile_terrain);
+            let base_wang = self.tile_terrain_to_wang_id(tile_terrain);
+            let mut seen = Vec::with_capacity(transforms.len());
 
-            if let Some(penalty) = self.score_tile(cell, &tile_wang) {
-                if self.debug {
-                    log::info!(
-                        "  Tile {}: ACCEPTED (penalty: {}, wang: {:?})",
-                        tile_id,
-                        penalty,
-                        tile_wang.colors
-                    );
-                }
-                if penalty < best_penalty {
-                    best_penalty = penalty;
-                    candidates.clear();
-                }
-                if (penalty - best_penalty).abs() < f32::EPSILON {
-                    // Weight by both inverse penalty AND per-tile probability
-                    let tile_prob = self.terrain_set.get_tile_probability(tile_id);
-                    let weight = tile_prob / (1.0 + penalty);
-                    candidates.push((tile_id, weight));
+            for &flags in transforms {
+                let tile_wang = base_wang.transformed(flags);
+                // Symmetric tiles look the same under several transforms; count
+                // each distinct variant once so they don't get extra weight
+                if seen.contains(&tile_wang) {
+                    continue;
                 }
-            } else {
-                rejected_count += 1;
-                if self.debug {
-                    log::info!(
-                        "  Tile {}: REJECTED (wang: {:?})",
-                        tile_id,
-                        tile_wang.colors
-                    );
+                seen.push(tile_wang);
+                let tile_id = base_tile | flags;
+
+                if let Some(penalty) = self.score_tile(cell, &tile_wang) {
+                    if self.debug {
+                        log::info!(
+                            "  Tile {}: ACCEPTED (penalty: {}, wang: {:?})",
+                            tile_id,
+                            penalty,
+                            tile_wang.colors
+                        );
+                    }
+                    if penalty < best_penalty {
+                        best_penalty = penalty;
+                        candidates.clear();
+                    }
+                    if (penalty - best_penalty).abs() < f32::EPSILON {
+                        // Weight by both inverse penalty AND per-tile probability
+                        let tile_prob = self.terrain_set.get_tile_probability(base_tile);
+                        let weight = tile_prob / (1.0 + penalty);
+                        candidates.push((tile_id, weight));
+                    }
+                } else {
+                    rejected_count += 1;
+                    if self.debug {
+                        log::info!(
+                            "  Tile {}: REJECTED (wang: {:?})",
+                            tile_id,
+                            tile_wang.colors
+                        );
+                    }
                 }
             }
         }
 
+        // Like Tiled's "prefer untransformed", only use a flipped or rotated
+        // tile when no authored tile matches as well
+        if candidates
+            .iter()
+            .any(|&(tile, _)| tile & TILE_FLIP_MASK == 0)
+        {
+            candidates.retain(|&(tile, _)| tile & TILE_FLIP_MASK == 0);
+        }
+        // Tile terrains are a HashMap; sort so a seeded filler picks the same tile every run
+        candidates.sort_by_key(|&(tile, _)| tile);
+
         if self.debug {
             log::info!(
                 "find_best_match: {} candidates, {} rejected",
@@ -623,8 +703,7 @@ impl<'a> WangFiller<'a> {
             // This matches Tiled's behavior - existing tiles influence selection
             // but don't force specific outcomes
             if let Some(tile_id) = tiles.get(idx).copied().flatten() {
-                if let Some(terrain_data) = self.terrain_set.get_tile_terrain(tile_id) {
-                    let existing = self.tile_terrain_to_wang_id(terrain_data);
+                if let Some(existing) = self.tile_wang_id(tile_id) {
                     let cell = self.get_cell_mut(x, y);
 
                     for i in 0..8 {
@@ -665,12 +744,9 @@ impl<'a> WangFiller<'a> {
                 tiles[idx] = Some(chosen_tile);
 
                 // Get the WangId of the chosen tile
-                let chosen_wang =
-                    if let Some(terrain_data) = self.terrain_set.get_tile_terrain(chosen_tile) {
-                        self.tile_terrain_to_wang_id(terrain_data)
-                    } else {
-                        continue;
-                    };
+                let Some(chosen_wang) = self.tile_wang_id(chosen_tile) else {
+                    continue;
+                };
 
                 // Propagate to neighbors
                 for (dir_idx, &(dx, dy)) in NEIGHBOR_OFFSETS.iter().enumerate() {
@@ -702,11 +778,7 @@ impl<'a> WangFiller<'a> {
                         if outside {
                             // Check if neighbor violates the new constraint
                             if let Some(neighbor_tile) = tiles.get(nidx).copied().flatten() {
-                                if let Some(neighbor_terrain) =
-                                    self.terrain_set.get_tile_terrain(neighbor_tile)
-                                {
-                                    let neighbor_wang =
-                                        self.tile_terrain_to_wang_id(neighbor_terrain);
+                                if let Some(neighbor_wang) = self.tile_wang_id(neighbor_tile) {
                                     if let Some(cell) = self.cells.get(&(nx, ny)) {
                                         if self.cell_violates_constraints(cell, &neighbor_wang) {
                                             if !self.corrections.contains(&(nx, ny)) {
@@ -741,9 +813,7 @@ impl<'a> WangFiller<'a> {
             let idx = (y as u32 * width + x as u32) as usize;
 
             if let Some(orig_tile) = tiles.get(idx).copied().flatten() {
-                if let Some(tile_terrain) = self.terrain_set.get_tile_terrain(orig_tile) {
-                    let current_wang = self.tile_terrain_to_wang_id(tile_terrain);
-
+                if let Some(current_wang) = self.tile_wang_id(orig_tile) {
                     if let Some(cell) = self.cells.get(&(x, y)).cloned() {
                         // Check if actually violates constraints
                         if self.cell_violates_constraints(&cell, &current_wang) {
@@ -1323,7 +1393,7 @@ pub fn update_tile_with_neighbors(
 // =============================================================================
 
 /// Get affected region for a paint target
-fn get_affected_region(
+pub(crate) fn get_affected_region(
     target: PaintTarget,
     width: u32,
     height: u32,
@@ -1476,3 +1546,94 @@ pub fn preview_terrain_at_targets(
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region::replace_terrain;
+    use crate::terrain::Color;
+    use uuid::Uuid;
+
+    const GRASS: usize = 0;
+    const DIRT: usize = 1;
+    /// Grass tile with dirt in the top-left corner
+    const DIRT_TL: u32 = 2;
+
+    /// Corner set with solid grass (0), solid dirt (1) and one transition tile
+    fn single_corner_set(allow_transforms: bool) -> TerrainSet {
+        let mut set = TerrainSet::new("Ground".to_string(), Uuid::new_v4(), TerrainSetType::Corner);
+        set.add_terrain("Grass".to_string(), Color::GREEN);
+        set.add_terrain("Dirt".to_string(), Color::rgb(0.6, 0.4, 0.2));
+        for corner in 0..4 {
+            set.set_tile_terrain(0, corner, Some(GRASS));
+            set.set_tile_terrain(1, corner, Some(DIRT));
+            set.set_tile_terrain(DIRT_TL, corner, Some(GRASS));
+        }
+        set.set_tile_terrain(DIRT_TL, 0, Some(DIRT));
+        set.allow_transforms = allow_transforms;
+        set
+    }
+
+    #[test]
+    fn test_wang_id_transformed() {
+        let mut wang = WangId::WILDCARD;
+        wang.set_color(WangPosition::TopLeft, 1);
+        wang.set_color(WangPosition::Top, 2);
+
+        let moved = |flags: u32| {
+            let t = wang.transformed(flags);
+            (
+                WangPosition::from_index(t.colors.iter().position(|&c| c == 1).unwrap()),
+                WangPosition::from_index(t.colors.iter().position(|&c| c == 2).unwrap()),
+            )
+        };
+
+        assert_eq!(moved(0), (WangPosition::TopLeft, WangPosition::Top));
+        assert_eq!(
+            moved(TILE_FLIP_X),
+            (WangPosition::TopRight, WangPosition::Top)
+        );
+        assert_eq!(
+            moved(TILE_FLIP_Y),
+            (WangPosition::BottomLeft, WangPosition::Bottom)
+        );
+        assert_eq!(
+            moved(TILE_FLIP_DIAGONAL),
+            (WangPosition::TopLeft, WangPosition::Left)
+        );
+        // Diagonal + X is a clockwise quarter turn
+        assert_eq!(
+            moved(TILE_FLIP_DIAGONAL | TILE_FLIP_X),
+            (WangPosition::TopRight, WangPosition::Right)
+        );
+    }
+
+    #[test]
+    fn test_fill_uses_transformed_tiles() {
+        let set = single_corner_set(true);
+        let mut tiles = vec![Some(0); 16];
+        paint_terrain(&mut tiles, 4, 4, 2, 2, &set, DIRT);
+
+        // The corner at (2, 2) is shared by the tiles at (1..=2, 1..=2)
+        assert_eq!(tiles[5], Some(DIRT_TL | TILE_FLIP_X)); // dirt top-right
+        assert_eq!(tiles[6], Some(DIRT_TL));
+        assert_eq!(tiles[9], Some(DIRT_TL | TILE_FLIP_X | TILE_FLIP_Y));
+        assert_eq!(tiles[10], Some(DIRT_TL | TILE_FLIP_Y)); // dirt bottom-left
+        let changed = [5, 6, 9, 10];
+        for (i, tile) in tiles.iter().enumerate() {
+            if !changed.contains(&i) {
+                assert_eq!(*tile, Some(0), "untransformed tile preferred at {}", i);
+            }
+        }
+
+        // Flipped tiles are read back with their flips applied
+        replace_terrain(&mut tiles, 4, 4, &set, DIRT, GRASS);
+        assert!(tiles.iter().all(|&t| t == Some(0)));
+
+        // Without the flag, only authored tiles are placed
+        let set = single_corner_set(false);
+        let mut tiles = vec![Some(0); 16];
+        paint_terrain(&mut tiles, 4, 4, 2, 2, &set, DIRT);
+        assert!(tiles.iter().flatten().all(|&t| t & TILE_FLIP_MASK == 0));
+    }
+}
@@ -41,7 +41,7 @@ pub fn generate_behaviors(
 
     // Generate behaviors for each entity type with input config
     for (type_name, type_def) in &schema.data_types {
-        if !type_def.placeable {
+        if !type_def.is_placeable() {
             continue;
         }
 
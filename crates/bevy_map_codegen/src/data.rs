@@ -0,0 +1,459 @@
+//! Data type struct code generation
+//!
+//! Generates serde-deserializable Rust structs for data types that aren't
+//! placed in levels (Items, Quests, ...) and for the embedded types they use.
+//! Values are read through `FromMapValue`, so they parse exactly like
+//! `#[derive(MapEntity)]` properties, with the schema defaults for missing keys.
+//!
+//! Data instances are collected into a `GameData` resource, looked up by
+//! typed `DataId<T>` handles. `Ref` fields pointing at data types are
+//! generated as such handles. The generated `load_game_data` system fills the
+//! resource from each map asset that loads.
+
+use bevy_map_schema::{PropType, PropertyDef, Schema, TypeDef};
+use codegen::Scope;
+
+use crate::entities::{map_prop_attr, prop_type_to_rust};
+use crate::{format_code, to_snake_case, CodegenError};
+
+/// Generate structs for all non-placeable data types and all embedded types,
+/// plus the `GameData` resource holding the data instances
+pub fn generate_data_types(schema: &Schema) -> Result<String, CodegenError> {
+    let mut scope = Scope::new();
+
+    // Add imports (codegen puts these at the top automatically)
+    scope.import("bevy::prelude", "*");
+    scope.import("bevy::asset", "AssetEvent");
+    scope.import("bevy::ecs::message", "MessageReader");
+    scope.import("bevy_map::prelude", "*");
+    scope.import("std::collections", "HashMap");
+    if !schema.enums.is_empty() {
+        scope.import("super::enums", "*");
+    }
+
+    // Add comment (after imports in output)
+    scope.raw("");
+    scope.raw("// Auto-generated data type structs from schema");
+    scope.raw("// This file is regenerated when you save your map project.");
+    scope.raw("// Do not edit manually - your changes will be overwritten!");
+    scope.raw("");
+
+    generate_data_id(&mut scope);
+
+    for name in data_struct_names(schema) {
+        if let Some(type_def) = schema.get_type(name) {
+            generate_data_struct(&mut scope, name, type_def, schema);
+        }
+    }
+
+    generate_game_data(&mut scope, &stored_type_names(schema));
+    generate_load_system(&mut scope);
+
+    let code = scope.to_string();
+    format_code(&code)
+}
+
+/// Names of the types whose instances are stored in `GameData`, sorted
+///
+/// Non-placeable, non-abstract data types.
+pub(crate) fn stored_type_names(schema: &Schema) -> Vec<&str> {
+    schema
+        .data_type_names()
+        .into_iter()
+        .filter(|name| is_stored(schema, name))
+        .collect()
+}
+
+/// Names of the types that get a data struct, sorted
+///
+/// The stored data types, then non-abstract embedded types.
+pub(crate) fn data_struct_names(schema: &Schema) -> Vec<&str> {
+    let mut embedded: Vec<&str> = schema
+        .embedded_types
+        .iter()
+        .filter(|(_, def)| !def.is_abstract)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    embedded.sort();
+
+    let mut names = stored_type_names(schema);
+    names.extend(embedded);
+    names
+}
+
+fn is_stored(schema: &Schema, name: &str) -> bool {
+    schema
+        .data_types
+        .get(name)
+        .is_some_and(|def| !def.is_placeable() && !def.is_abstract)
+}
+
+/// Rust type of a data struct field
+///
+/// References to stored data types become `DataId` handles; everything else
+/// matches the entity field types.
+fn data_field_type(prop: &PropertyDef, schema: &Schema) -> String {
+    match (prop.prop_type, &prop.ref_type, &prop.item_type) {
+        (PropType::Ref, Some(target), _) if is_stored(schema, target) => {
+            format!("Option<DataId<{}>>", target)
+        }
+        (PropType::Array, _, Some(target)) if is_stored(schema, target) => {
+            format!("Vec<DataId<{}>>", target)
+        }
+        _ => prop_type_to_rust(&prop.prop_type, prop, schema),
+    }
+}
+
+/// Generate the `DataId<T>` handle
+///
+/// Trait impls are written out so they don't require `T` to implement them.
+fn generate_data_id(scope: &mut Scope) {
+    scope.raw(
+        r#"/// Typed id of a data instance, looked up with [`GameData::get`]
+pub struct DataId<T> {
+    pub id: uuid::Uuid,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> DataId<T> {
+    pub const fn new(id: uuid::Uuid) -> Self {
+        Self { id, marker: std::marker::PhantomData }
+    }
+}
+
+impl<T> Clone for DataId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DataId<T> {}
+
+impl<T> PartialEq for DataId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for DataId<T> {}
+
+impl<T> std::hash::Hash for DataId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::hash::Hash::hash(&self.id, state);
+    }
+}
+
+impl<T> std::fmt::Debug for DataId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DataId({})", self.id)
+    }
+}
+
+impl<T> FromMapValue for DataId<T> {
+    fn from_map_value(value: &bevy_map::core::Value) -> Option<Self> {
+        uuid::Uuid::from_map_value(value).map(Self::new)
+    }
+}
+
+impl<T> ToMapValue for DataId<T> {
+    fn to_map_value(&self) -> bevy_map::core::Value {
+        self.id.to_map_value()
+    }
+}
+
+/// A data type whose instances are stored in [`GameData`]
+pub trait GameDataType: Sized + 'static {
+    /// The type's name in the schema
+    const TYPE_NAME: &'static str;
+
+    /// The instances of this type, by id
+    fn instances(data: &GameData) -> &HashMap<uuid::Uuid, Self>;
+}
+"#,
+    );
+}
+
+/// Generate a single data struct
+///
+/// The struct reads from a properties object (`FromMapValue`) and
+/// deserializes from one by converting through `bevy_map::core::Value`.
+fn generate_data_struct(scope: &mut Scope, name: &str, type_def: &TypeDef, schema: &Schema) {
+    let kind = if schema.embedded_types.contains_key(name) {
+        "Embedded"
+    } else {
+        "Data"
+    };
+    scope.raw(format!("/// {} type `{}`", kind, name));
+    if let Some(base) = &type_def.extends {
+        scope.raw("///");
+        scope.raw(format!(
+            "/// Extends `{}` (inherited fields are included)",
+            base
+        ));
+    }
+    scope.raw("#[derive(Debug, Clone, Default, FromMapValue, ToMapValue, serde::Deserialize)]");
+    scope.raw("#[serde(try_from = \"bevy_map::core::Value\")]");
+    scope.raw(format!("pub struct {} {{", name));
+
+    for prop in schema.all_properties(name) {
+        let constraints = prop.constraint_descriptions();
+        if !constraints.is_empty() {
+            scope.raw(format!("    /// Constraints: {}", constraints.join(", ")));
+        }
+        scope.raw(format!("    {}", map_prop_attr(prop, schema)));
+        scope.raw(format!(
+            "    pub {}: {},",
+            to_snake_case(&prop.name),
+            data_field_type(prop, schema)
+        ));
+    }
+
+    scope.raw("}");
+    scope.raw("");
+
+    scope
+        .new_impl(name)
+        .impl_trait("TryFrom<bevy_map::core::Value>")
+        .associate_type("Error", "String")
+        .new_fn("try_from")
+        .arg("value", "bevy_map::core::Value")
+        .ret("Result<Self, Self::Error>")
+        .line(format!(
+            "Self::from_map_value(&value).ok_or_else(|| \"{} must be an object\".to_string())",
+            name
+        ));
+}
+
+/// Generate the `GameData` resource and a `GameDataType` impl per stored type
+fn generate_game_data(scope: &mut Scope, stored: &[&str]) {
+    for name in stored {
+        scope
+            .new_impl(name)
+            .impl_trait("GameDataType")
+            .associate_const("TYPE_NAME", "&'static str", format!("\"{}\"", name), "")
+            .new_fn("instances")
+            .arg("data", "&GameData")
+            .ret("&HashMap<uuid::Uuid, Self>")
+            .line(format!("&data.{}", to_snake_case(name)));
+    }
+
+    scope.raw("/// The project's data instances, by type and id");
+    scope.raw("///");
+    scope.raw("/// Filled by [`load_game_data`] when a map loads, or build it with");
+    scope.raw("/// [`GameData::from_project`] or [`GameData::from_json`].");
+    scope.raw("#[derive(Resource, Debug, Clone, Default)]");
+    scope.raw("pub struct GameData {");
+    for name in stored {
+        scope.raw(format!("    /// `{}` instances by id", name));
+        scope.raw(format!(
+            "    pub {}: HashMap<uuid::Uuid, {}>,",
+            to_snake_case(name),
+            name
+        ));
+    }
+    scope.raw("}");
+    scope.raw("");
+
+    let load = if stored.is_empty() {
+        "Self::default()".to_string()
+    } else {
+        let mut load = String::from("let mut game_data = Self::default();\n");
+        load.push_str(
+            r#"let instances = data
+    .and_then(|data| data.get("instances"))
+    .and_then(|instances| instances.as_object());
+for (type_name, list) in instances.into_iter().flatten() {
+    for instance in list.as_array().into_iter().flatten() {
+        let Some(id) = instance
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+        else {
+            continue;
+        };
+        let properties = bevy_map::core::Value::from_json(
+            instance.get("properties").cloned().unwrap_or_default(),
+        );
+        match type_name.as_str() {
+"#,
+        );
+        for name in stored {
+            load.push_str(&format!(
+                "\"{name}\" => {{ if let Some(value) = {name}::from_map_value(&properties) {{ game_data.{field}.insert(id, value); }} }}\n",
+                name = name,
+                field = to_snake_case(name)
+            ));
+        }
+        load.push_str("_ => {}\n}\n}\n}\ngame_data");
+        load
+    };
+
+    let game_data = scope.new_impl("GameData");
+    game_data
+        .new_fn("from_data")
+        .doc("Collect the instances of a project's `data` section\n\nInstances whose properties don't convert are skipped.")
+        .vis("pub")
+        .arg(if stored.is_empty() { "_data" } else { "data" }, "Option<&serde_json::Value>")
+        .ret("Self")
+        .line(load);
+    game_data
+        .new_fn("from_project")
+        .doc("Collect the data instances of an editor project (`.map.json`)")
+        .vis("pub")
+        .arg("project", "&bevy_map::EditorProject")
+        .ret("Self")
+        .line("Self::from_data(project.data.as_ref())");
+    game_data
+        .new_fn("from_map")
+        .doc("Collect the data instances of a loaded map asset")
+        .vis("pub")
+        .arg("map", "&bevy_map::MapProject")
+        .ret("Self")
+        .line("Self::from_data(map.data.as_ref())");
+    game_data
+        .new_fn("from_json")
+        .doc("Parse an editor project (`.map.json`) and collect its data instances")
+        .vis("pub")
+        .arg("json", "&str")
+        .ret("Result<Self, serde_json::Error>")
+        .line("let project: bevy_map::EditorProject = serde_json::from_str(json)?;")
+        .line("Ok(Self::from_project(&project))");
+    game_data
+        .new_fn("get")
+        .doc("Look a data instance up by its typed id")
+        .vis("pub")
+        .generic("T: GameDataType")
+        .arg_ref_self()
+        .arg("id", "DataId<T>")
+        .ret("Option<&T>")
+        .line("T::instances(self).get(&id.id)");
+    game_data
+        .new_fn("iter")
+        .doc("All instances of a data type with their ids")
+        .vis("pub")
+        .generic("T: GameDataType")
+        .arg_ref_self()
+        .ret("impl Iterator<Item = (DataId<T>, &T)>")
+        .line("T::instances(self).iter().map(|(id, value)| (DataId::new(*id), value))");
+}
+
+/// Generate the system that fills `GameData` from loaded map assets
+fn generate_load_system(scope: &mut Scope) {
+    scope.raw(
+        r#"/// Replace [`GameData`] with the data of each map that loads or is hot-reloaded
+///
+/// Added by `GeneratedPlugin`. Maps without a data section are ignored.
+pub fn load_game_data(
+    mut events: MessageReader<AssetEvent<bevy_map::MapProject>>,
+    maps: Res<Assets<bevy_map::MapProject>>,
+    mut data: ResMut<GameData>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if let Some(map) = maps.get(*id).filter(|map| map.data.is_some()) {
+            *data = GameData::from_map(map);
+        }
+    }
+}
+"#,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_test_schema() -> Schema {
+        bevy_map_schema::parse_schema(
+            r##"{
+                "version": 1,
+                "project": { "name": "Test" },
+                "enums": { "Rarity": ["Common", "Rare"] },
+                "data_types": {
+                    "Item": {
+                        "properties": [
+                            { "name": "displayName", "type": "string", "default": "Sword" },
+                            { "name": "rarity", "type": "enum", "enumType": "Rarity" },
+                            { "name": "stats", "type": "embedded", "embeddedType": "Stats" },
+                            { "name": "tags", "type": "array", "itemType": "String" },
+                            { "name": "upgrades", "type": "array", "itemType": "Item" },
+                            { "name": "quest", "type": "ref", "refType": "Quest" },
+                            { "name": "spawner", "type": "ref", "refType": "Enemy" }
+                        ]
+                    },
+                    "Quest": { "properties": [{ "name": "title", "type": "string" }] },
+                    "Enemy": {
+                        "placeable": true,
+                        "properties": [{ "name": "drop", "type": "ref", "refType": "Item" }]
+                    }
+                },
+                "embedded_types": {
+                    "Stats": { "properties": [{ "name": "damage", "type": "int" }] }
+                }
+            }"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_data_types() {
+        let schema = make_test_schema();
+        let code = generate_data_types(&schema).unwrap();
+
+        assert!(code.contains("use super::enums::*;"));
+        assert!(code.contains("pub struct Item {"));
+        assert!(code.contains("pub struct Stats {"));
+        // Placeable types are entities, not data structs
+        assert!(!code.contains("pub struct Enemy"));
+        assert!(code.contains("serde::Deserialize"));
+        assert!(code.contains("#[serde(try_from = \"bevy_map::core::Value\")]"));
+        assert!(code.contains("#[map_prop(name = \"displayName\", default = \"Sword\")]"));
+        assert!(code.contains("pub display_name: String"));
+        assert!(code.contains("pub rarity: Rarity"));
+        assert!(code.contains("pub stats: Stats"));
+        assert!(code.contains("pub tags: Vec<String>"));
+        assert!(code.contains("impl TryFrom<bevy_map::core::Value> for Item"));
+    }
+
+    #[test]
+    fn test_generate_game_data() {
+        let schema = make_test_schema();
+        let code = generate_data_types(&schema).unwrap();
+
+        // References to stored data types are typed handles
+        assert!(code.contains("pub struct DataId<T>"));
+        assert!(code.contains("pub upgrades: Vec<DataId<Item>>"));
+        assert!(code.contains("pub quest: Option<DataId<Quest>>"));
+        // Placeable types aren't stored, so their refs stay plain ids
+        assert!(code.contains("pub spawner: Option<uuid::Uuid>"));
+
+        assert!(code.contains("#[derive(Resource, Debug, Clone, Default)]"));
+        assert!(code.contains("pub item: HashMap<uuid::Uuid, Item>"));
+        assert!(code.contains("pub quest: HashMap<uuid::Uuid, Quest>"));
+        assert!(!code.contains("HashMap<uuid::Uuid, Stats>"));
+        assert!(code.contains("impl GameDataType for Quest"));
+        assert!(code.contains("const TYPE_NAME: &'static str = \"Quest\";"));
+        assert!(code.contains("\"Item\" => {"));
+        assert!(code.contains("pub fn from_project(project: &bevy_map::EditorProject) -> Self"));
+        assert!(code.contains("pub fn from_map(map: &bevy_map::MapProject) -> Self"));
+        assert!(code.contains("pub fn load_game_data("));
+        assert!(code.contains("pub fn get<T: GameDataType>(&self, id: DataId<T>) -> Option<&T>"));
+    }
+
+    #[test]
+    fn test_generate_empty_game_data() {
+        let code = generate_data_types(&Schema::default()).unwrap();
+        assert!(code.contains("pub struct GameData {}"));
+        assert!(code.contains("pub fn from_data(_data: Option<&serde_json::Value>) -> Self"));
+    }
+
+    #[test]
+    fn test_data_struct_names() {
+        let schema = make_test_schema();
+        assert_eq!(stored_type_names(&schema), vec!["Item", "Quest"]);
+        assert_eq!(data_struct_names(&schema), vec!["Item", "Quest", "Stats"]);
+    }
+}
@@ -14,7 +14,7 @@ pub fn generate_entities(schema: &Schema) -> Result<String, CodegenError> {
 
     // Add imports (codegen puts these at the top automatically)
     scope.import("bevy::prelude", "*");
-    scope.import("bevy_map_runtime::prelude", "*");
+    scope.import("bevy_map::prelude", "*");
 
     // Add comment (after imports in output)
     scope.raw("");
@@ -25,7 +25,7 @@ pub fn generate_entities(schema: &Schema) -> Result<String, CodegenError> {
 
     // Generate structs for each placeable data type
     for (name, type_def) in &schema.data_types {
-        if type_def.placeable {
+        if type_def.is_placeable() {
             generate_entity_struct(&mut scope, name, type_def, schema);
         }
     }
@@ -39,22 +39,27 @@ fn generate_entity_struct(scope: &mut Scope, name: &str, type_def: &TypeDef, sch
     // Generate struct manually using raw to support field-level attributes
     // The codegen crate's Struct::field() doesn't support per-field attributes
 
-    scope.raw("#[derive(Component, Debug, Clone, Default)]");
+    if let Some(base) = &type_def.extends {
+        scope.raw(format!(
+            "/// Extends `{}` (inherited fields are included)",
+            base
+        ));
+    }
+    scope.raw("#[derive(Component, MapEntity, Debug, Clone, Default)]");
     scope.raw(format!("#[map_entity(type_name = \"{}\")]", name));
     scope.raw(format!("pub struct {} {{", name));
 
-    // Add fields for each property
-    for prop in &type_def.properties {
+    // Add fields for each property, inherited ones first
+    for prop in schema.all_properties(name) {
         let rust_type = prop_type_to_rust(&prop.prop_type, prop, schema);
         let field_name = to_snake_case(&prop.name);
 
-        // Add map_prop attribute
-        if let Some(ref default) = prop.default {
-            let default_str = format_default_value(default, &prop.prop_type);
-            scope.raw(format!("    #[map_prop(default = {})]", default_str));
-        } else {
-            scope.raw("    #[map_prop]");
+        let constraints = prop.constraint_descriptions();
+        if !constraints.is_empty() {
+            scope.raw(format!("    /// Constraints: {}", constraints.join(", ")));
         }
+
+        scope.raw(format!("    {}", map_prop_attr(prop, schema)));
         scope.raw(format!("    pub {}: {},", field_name, rust_type));
     }
 
@@ -62,14 +67,44 @@ fn generate_entity_struct(scope: &mut Scope, name: &str, type_def: &TypeDef, sch
     scope.raw("");
 }
 
+/// The `#[map_prop]` attribute of a property's field
+///
+/// Adds the property name when it differs from the snake_case field name,
+/// and the schema default if there is one.
+pub(crate) fn map_prop_attr(prop: &PropertyDef, schema: &Schema) -> String {
+    let mut args = Vec::new();
+    if to_snake_case(&prop.name) != prop.name {
+        args.push(format!("name = \"{}\"", prop.name));
+    }
+    if let Some(ref default) = prop.default {
+        let default_str = match flags_type(prop, schema) {
+            Some(flags) => format_flags_default(default, &flags, prop),
+            None => format_default_value(default, &prop.prop_type),
+        };
+        args.push(format!("default = {}", default_str));
+    }
+    if args.is_empty() {
+        "#[map_prop]".to_string()
+    } else {
+        format!("#[map_prop({})]", args.join(", "))
+    }
+}
+
 /// Convert a schema property type to a Rust type string
-fn prop_type_to_rust(prop_type: &PropType, prop: &PropertyDef, schema: &Schema) -> String {
+pub(crate) fn prop_type_to_rust(
+    prop_type: &PropType,
+    prop: &PropertyDef,
+    schema: &Schema,
+) -> String {
     #[allow(deprecated)]
     match prop_type {
         PropType::String | PropType::Multiline => "String".to_string(),
         PropType::Int => "i32".to_string(),
         PropType::Float => "f32".to_string(),
         PropType::Bool => "bool".to_string(),
+        PropType::Enum if prop.is_flags() => {
+            flags_type(prop, schema).unwrap_or_else(|| "Vec<String>".to_string())
+        }
         PropType::Enum => {
             if let Some(ref enum_type) = prop.enum_type {
                 // Check if enum exists in schema
@@ -85,7 +120,7 @@ fn prop_type_to_rust(prop_type: &PropType, prop: &PropertyDef, schema: &Schema)
         PropType::Ref => "Option<uuid::Uuid>".to_string(),
         PropType::Array => {
             if let Some(ref item_type) = prop.item_type {
-                format!("Vec<{}>", item_type)
+                format!("Vec<{}>", item_type_to_rust(item_type, schema))
             } else {
                 "Vec<serde_json::Value>".to_string()
             }
@@ -104,6 +139,41 @@ fn prop_type_to_rust(prop_type: &PropType, prop: &PropertyDef, schema: &Schema)
     }
 }
 
+/// Convert an array item type to a Rust type string
+fn item_type_to_rust(item_type: &str, schema: &Schema) -> String {
+    match item_type {
+        "String" => "String".to_string(),
+        "Int" => "i32".to_string(),
+        "Float" => "f32".to_string(),
+        "Bool" => "bool".to_string(),
+        // Items of data types are references to instances
+        _ if schema.data_types.contains_key(item_type) => "uuid::Uuid".to_string(),
+        // Embedded types and enums are generated under their own names
+        _ => item_type.to_string(),
+    }
+}
+
+/// The generated flag set type of a flags property, if its enum exists
+fn flags_type(prop: &PropertyDef, schema: &Schema) -> Option<String> {
+    let enum_type = prop.enum_type.as_ref()?;
+    (prop.is_flags() && schema.enums.contains_key(enum_type)).then(|| format!("{}Flags", enum_type))
+}
+
+/// Format a flags default (an array of value names) as a flag set expression
+fn format_flags_default(value: &serde_json::Value, flags: &str, prop: &PropertyDef) -> String {
+    let enum_type = prop.enum_type.as_deref().unwrap_or_default();
+    let mut expr = format!("{}::EMPTY", flags);
+    for name in value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+    {
+        expr.push_str(&format!(".with({}::{})", enum_type, name));
+    }
+    expr
+}
+
 /// Format a default value for use in an attribute
 fn format_default_value(value: &serde_json::Value, prop_type: &PropType) -> String {
     match value {
@@ -127,8 +197,8 @@ fn format_default_value(value: &serde_json::Value, prop_type: &PropType) -> Stri
                 format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
             }
         }
-        serde_json::Value::Array(_) => "[]".to_string(),
-        serde_json::Value::Object(_) => "{}".to_string(),
+        serde_json::Value::Array(_) => "Vec::new()".to_string(),
+        serde_json::Value::Object(_) => "Default::default()".to_string(),
     }
 }
 
@@ -173,6 +243,14 @@ mod tests {
                 ref_type: None,
                 item_type: None,
                 embedded_type: None,
+                localized: false,
+                pattern: None,
+                min_length: None,
+                max_length: None,
+                min_items: None,
+                max_items: None,
+                unique: None,
+                flags: false,
             },
             PropertyDef {
                 name: "name".to_string(),
@@ -186,6 +264,14 @@ mod tests {
                 ref_type: None,
                 item_type: None,
                 embedded_type: None,
+                localized: false,
+                pattern: None,
+                min_length: None,
+                max_length: None,
+                min_items: None,
+                max_items: None,
+                unique: None,
+                flags: false,
             },
         ];
         schema.data_types.insert("Player".to_string(), player_type);
@@ -204,6 +290,47 @@ mod tests {
         assert!(code.contains("health"));
         assert!(code.contains("name"));
         assert!(code.contains("#[map_entity"));
+        assert!(code.contains("derive(Component, MapEntity"));
+    }
+
+    #[test]
+    fn test_generate_inherited_entities() {
+        let mut schema = make_test_schema();
+        let mut actor = schema.data_types["Player"].clone();
+        actor.is_abstract = true;
+        schema.data_types.insert("Actor".to_string(), actor);
+
+        let player = schema.data_types.get_mut("Player").unwrap();
+        player.extends = Some("Actor".to_string());
+        player.properties.truncate(1);
+        player.properties[0].default = Some(serde_json::json!(50));
+
+        let code = generate_entities(&schema).unwrap();
+        assert!(!code.contains("struct Actor"));
+        assert!(code.contains("Extends `Actor`"));
+        assert!(code.contains("default = 50"));
+        assert!(!code.contains("default = 100"));
+        assert!(code.contains("pub name: String"));
+    }
+
+    #[test]
+    fn test_generate_constrained_entities() {
+        let mut schema = make_test_schema();
+        let player = schema.data_types.get_mut("Player").unwrap();
+        player.properties[1].max_length = Some(16);
+        player.properties[1].unique = Some(bevy_map_schema::UniqueScope::Level);
+        let mut slots = player.properties[0].clone();
+        slots.name = "slots".to_string();
+        slots.prop_type = PropType::Enum;
+        slots.enum_type = Some("ItemType".to_string());
+        slots.flags = true;
+        slots.default = Some(serde_json::json!(["Weapon", "Armor"]));
+        player.properties.push(slots);
+
+        let code = generate_entities(&schema).unwrap();
+        assert!(code.contains("/// Constraints: <= 16 chars, unique per level"));
+        assert!(code.contains("pub slots: ItemTypeFlags"));
+        assert!(code.contains("ItemTypeFlags::EMPTY.with(ItemType::Weapon).with(ItemType::Armor)"));
     }
 
     #[test]
@@ -221,6 +348,14 @@ mod tests {
             ref_type: None,
             item_type: None,
             embedded_type: None,
+            localized: false,
+            pattern: None,
+            min_length: None,
+            max_length: None,
+            min_items: None,
+            max_items: None,
+            unique: None,
+            flags: false,
         };
 
         assert_eq!(prop_type_to_rust(&PropType::Int, &prop, &schema), "i32");
@@ -18,6 +18,8 @@ pub fn generate_enums(schema: &Schema) -> Result<String, CodegenError> {
 
     // Add imports (codegen puts these at the top automatically)
     scope.import("std::str", "FromStr");
+    scope.import("bevy_map::prelude", "FromMapValue");
+    scope.import("bevy_map::prelude", "ToMapValue");
 
     // Add comments (after imports in output)
     scope.raw("");
@@ -34,6 +36,11 @@ pub fn generate_enums(schema: &Schema) -> Result<String, CodegenError> {
         generate_enum(&mut scope, name, variants);
     }
 
+    // Flag sets for enums used by multi-select (flags) properties
+    for name in schema.flag_enum_names() {
+        generate_flags(&mut scope, name);
+    }
+
     let code = scope.to_string();
     format_code(&code)
 }
@@ -43,13 +50,16 @@ fn generate_enum(scope: &mut Scope, name: &str, variants: &[String]) {
     // Create the enum
     let e = scope
         .new_enum(name)
+        .vis("pub")
         .derive("Debug")
         .derive("Clone")
         .derive("Copy")
         .derive("PartialEq")
         .derive("Eq")
         .derive("Hash")
-        .derive("Default");
+        .derive("Default")
+        .derive("FromMapValue")
+        .derive("ToMapValue");
 
     // Add variants - first variant is default
     for (i, variant) in variants.iter().enumerate() {
@@ -123,6 +133,92 @@ fn generate_enum(scope: &mut Scope, name: &str, variants: &[String]) {
         .line(all_body);
 }
 
+/// Generate a `{Name}Flags` bit set for an enum (bit i = i-th variant)
+///
+/// Stored in map files as an array of variant names, like the editor does.
+fn generate_flags(scope: &mut Scope, name: &str) {
+    let flags = format!("{}Flags", name);
+    let bit = "1 << value as u64";
+
+    scope
+        .new_struct(&flags)
+        .doc(&format!(
+            "Set of `{}` values, one bit per variant in declaration order",
+            name
+        ))
+        .vis("pub")
+        .derive("Debug")
+        .derive("Clone")
+        .derive("Copy")
+        .derive("PartialEq")
+        .derive("Eq")
+        .derive("Hash")
+        .derive("Default")
+        .tuple_field("pub u64");
+
+    let flags_impl = scope.new_impl(&flags);
+    flags_impl.associate_const("EMPTY", &flags, format!("{}(0)", flags), "pub");
+    flags_impl
+        .new_fn("with")
+        .vis("pub const")
+        .arg_self()
+        .arg("value", name)
+        .ret("Self")
+        .line(format!("Self(self.0 | {})", bit));
+    flags_impl
+        .new_fn("contains")
+        .vis("pub")
+        .arg_ref_self()
+        .arg("value", name)
+        .ret("bool")
+        .line(format!("self.0 & ({}) != 0", bit));
+    flags_impl
+        .new_fn("insert")
+        .vis("pub")
+        .arg_mut_self()
+        .arg("value", name)
+        .line(format!("self.0 |= {};", bit));
+    flags_impl
+        .new_fn("remove")
+        .vis("pub")
+        .arg_mut_self()
+        .arg("value", name)
+        .line(format!("self.0 &= !({});", bit));
+    flags_impl
+        .new_fn("is_empty")
+        .vis("pub")
+        .arg_ref_self()
+        .ret("bool")
+        .line("self.0 == 0");
+    flags_impl
+        .new_fn("iter")
+        .vis("pub")
+        .arg_ref_self()
+        .ret(format!("impl Iterator<Item = {}> + '_", name))
+        .line(format!(
+            "{}::all().iter().copied().filter(move |v| self.contains(*v))",
+            name
+        ));
+
+    scope
+        .new_impl(&flags)
+        .impl_trait("FromMapValue")
+        .new_fn("from_map_value")
+        .arg("value", "&bevy_map::core::Value")
+        .ret("Option<Self>")
+        .line("value.as_array()?.iter().try_fold(Self::EMPTY, |flags, item| {")
+        .line("    Some(flags.with(item.as_string()?.parse().ok()?))")
+        .line("})");
+
+    scope
+        .new_impl(&flags)
+        .impl_trait("ToMapValue")
+        .new_fn("to_map_value")
+        .arg_ref_self()
+        .ret("bevy_map::core::Value")
+        .line("bevy_map::core::Value::Array(self.iter().map(|v| v.to_map_value()).collect())");
+}
+
 /// Generate an empty enums module when there are no enums
 fn generate_empty_enums_module() -> String {
     r#"//! Auto-generated enum definitions from schema
@@ -165,14 +261,35 @@ mod tests {
         assert!(result.is_ok());
 
         let code = result.unwrap();
-        assert!(code.contains("enum ItemType"));
+        assert!(code.contains("pub enum ItemType"));
         assert!(code.contains("enum Direction"));
         assert!(code.contains("Weapon"));
         assert!(code.contains("Armor"));
         assert!(code.contains("impl FromStr"));
+        assert!(code.contains("FromMapValue"));
+        assert!(code.contains("ToMapValue"));
         assert!(code.contains("fn all()"));
     }
 
+    #[test]
+    fn test_generate_flags() {
+        let mut schema = make_test_schema();
+        let prop: bevy_map_schema::PropertyDef = serde_json::from_value(serde_json::json!({
+            "name": "slots", "type": "enum", "enumType": "ItemType", "flags": true
+        }))
+        .unwrap();
+        let mut chest = bevy_map_schema::TypeDef::default();
+        chest.properties.push(prop);
+        schema.data_types.insert("Chest".to_string(), chest);
+
+        let code = generate_enums(&schema).unwrap();
+        assert!(code.contains("pub struct ItemTypeFlags(pub u64);"));
+        assert!(code.contains("pub const EMPTY: ItemTypeFlags = ItemTypeFlags(0);"));
+        assert!(code.contains("pub const fn with(self, value: ItemType) -> Self"));
+        assert!(code.contains("impl FromMapValue for ItemTypeFlags"));
+        assert!(!code.contains("DirectionFlags"));
+    }
+
     #[test]
     fn test_generate_empty_enums() {
         let schema = Schema::default();
@@ -10,6 +10,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::behaviors::{generate_behaviors, generate_health_module, generate_patrol_module};
+use crate::data::generate_data_types;
 use crate::entities::generate_entities;
 use crate::enums::generate_enums;
 use crate::stubs::generate_stubs;
@@ -27,6 +28,9 @@ pub struct CodegenConfig {
     /// Whether to generate enum definitions
     pub generate_enums: bool,
 
+    /// Whether to generate serde-deserializable data type structs
+    pub generate_data_types: bool,
+
     /// Whether to generate stub systems
     pub generate_stubs: bool,
 
@@ -47,6 +51,7 @@ impl CodegenConfig {
             output_dir: output_dir.into(),
             generate_entities: true,
             generate_enums: true,
+            generate_data_types: false, // Optional module
             generate_stubs: true,
             generate_behaviors: true,
             generate_health: false, // Optional module
@@ -66,6 +71,12 @@ impl CodegenConfig {
         self
     }
 
+    /// Enable data type struct generation
+    pub fn with_data_types(mut self) -> Self {
+        self.generate_data_types = true;
+        self
+    }
+
     /// Disable stub generation
     pub fn without_stubs(mut self) -> Self {
         self.generate_stubs = false;
@@ -130,6 +141,7 @@ impl CodegenResult {
 /// This is the main entry point for code generation. It creates:
 /// - `entities.rs` - Entity structs with MapEntity derives
 /// - `enums.rs` - Enum definitions with FromStr
+/// - `data.rs` - Data type structs and the `GameData` resource (optional)
 /// - `stubs.rs` - Stub systems for each placeable type
 /// - `behaviors.rs` - Movement and AI systems based on input profiles
 /// - `mod.rs` - Module exports and plugin registration
@@ -159,6 +171,14 @@ pub fn generate_all(
         result.add_file(path);
     }
 
+    // Generate data types
+    if config.generate_data_types {
+        let data_code = generate_data_types(schema)?;
+        let path = config.output_dir.join("data.rs");
+        fs::write(&path, &data_code)?;
+        result.add_file(path);
+    }
+
     // Generate stubs
     if config.generate_stubs {
         let stubs_code = generate_stubs(schema)?;
@@ -231,6 +251,9 @@ fn generate_mod_rs(schema: &Schema, config: &CodegenConfig) -> String {
     if config.generate_enums && !schema.enums.is_empty() {
         lines.push("mod enums;".to_string());
     }
+    if config.generate_data_types {
+        lines.push("mod data;".to_string());
+    }
     if config.generate_stubs {
         lines.push("mod stubs;".to_string());
     }
@@ -250,6 +273,9 @@ fn generate_mod_rs(schema: &Schema, config: &CodegenConfig) -> String {
     if config.generate_enums && !schema.enums.is_empty() {
         lines.push("pub use enums::*;".to_string());
     }
+    if config.generate_data_types {
+        lines.push("pub use data::*;".to_string());
+    }
     if config.generate_stubs {
         lines.push("pub use stubs::StubsPlugin;".to_string());
     }
@@ -270,12 +296,17 @@ fn generate_mod_rs(schema: &Schema, config: &CodegenConfig) -> String {
     if config.generate_entities {
         // Register each placeable type as a component
         for (name, type_def) in &schema.data_types {
-            if type_def.placeable {
+            if type_def.is_placeable() {
                 lines.push(format!("        app.register_type::<{}>();", name));
             }
         }
     }
 
+    if config.generate_data_types {
+        lines.push("        app.init_resource::<GameData>();".to_string());
+        lines.push("        app.add_systems(Update, load_game_data);".to_string());
+    }
+
     lines.push("".to_string());
     lines.push("        // Add plugins".to_string());
 
@@ -325,6 +356,11 @@ pub fn preview_enums(schema: &Schema) -> Result<String, CodegenError> {
     generate_enums(schema)
 }
 
+/// Preview generated data types without writing to disk
+pub fn preview_data_types(schema: &Schema) -> Result<String, CodegenError> {
+    generate_data_types(schema)
+}
+
 /// Preview generated stubs without writing to disk
 pub fn preview_stubs(schema: &Schema) -> Result<String, CodegenError> {
     generate_stubs(schema)
@@ -391,20 +427,35 @@ mod tests {
         assert!(temp.join("enums.rs").exists());
         assert!(temp.join("stubs.rs").exists());
         assert!(temp.join("behaviors.rs").exists());
+        assert!(!temp.join("data.rs").exists());
 
         // Clean up
         let _ = fs::remove_dir_all(&temp);
     }
 
+    #[test]
+    fn test_generate_mod_rs_data_types() {
+        let (schema, _) = make_test_schema_and_configs();
+        let config = CodegenConfig::new("src/gen").with_data_types();
+
+        let code = generate_mod_rs(&schema, &config);
+        assert!(code.contains("mod data;"));
+        assert!(code.contains("pub use data::*;"));
+        assert!(code.contains("app.init_resource::<GameData>();"));
+        assert!(code.contains("app.add_systems(Update, load_game_data);"));
+    }
+
     #[test]
     fn test_codegen_config() {
         let config = CodegenConfig::new("src/gen")
             .without_stubs()
+            .with_data_types()
             .with_health()
             .with_patrol();
 
         assert_eq!(config.output_dir, PathBuf::from("src/gen"));
         assert!(!config.generate_stubs);
+        assert!(config.generate_data_types);
         assert!(config.generate_health);
         assert!(config.generate_patrol);
     }
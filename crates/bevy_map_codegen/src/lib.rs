@@ -5,6 +5,8 @@
 //!
 //! - **Project validation** - Validate game project structure
 //! - **Entity structs** - Auto-generate `#[derive(MapEntity)]` structs from schema types
+//! - **Data structs** - Serde-deserializable structs for data and embedded types, and a
+//!   `GameData` resource with typed lookups (opt-in)
 //! - **Behavior stubs** - Generate empty system function signatures per entity type
 //! - **Behavior systems** - Pre-built systems for common 2D patterns (movement, combat, AI)
 //!
@@ -25,6 +27,7 @@
 //! ```
 
 pub mod behaviors;
+pub mod data;
 pub mod entities;
 pub mod enums;
 pub mod generator;
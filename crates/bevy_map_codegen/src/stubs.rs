@@ -13,7 +13,7 @@ pub fn generate_stubs(schema: &Schema) -> Result<String, CodegenError> {
     let placeable_types: Vec<_> = schema
         .data_types
         .iter()
-        .filter(|(_, def)| def.placeable)
+        .filter(|(_, def)| def.is_placeable())
         .collect();
 
     if placeable_types.is_empty() {
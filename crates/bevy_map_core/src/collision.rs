@@ -8,85 +8,9 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Collision shape types supported by the editor
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "type")]
-pub enum CollisionShape {
-    /// No collision
-    None,
-    /// Full tile/entity bounding box
-    Full,
-    /// Rectangle with optional offset and size
-    Rectangle {
-        /// Offset from center [x, y] (0-1 normalized for tiles)
-        #[serde(default)]
-        offset: [f32; 2],
-        /// Size [width, height] (0-1 normalized for tiles)
-        #[serde(default = "default_full_size")]
-        size: [f32; 2],
-    },
-    /// Circle collider
-    Circle {
-        /// Center offset [x, y] (0-1 normalized)
-        #[serde(default)]
-        offset: [f32; 2],
-        /// Radius (0-1 normalized, 0.5 = full tile)
-        #[serde(default = "default_radius")]
-        radius: f32,
-    },
-    /// Polygon collider (convex hull)
-    Polygon {
-        /// Vertices in local coordinates (0-1 normalized)
-        points: Vec<[f32; 2]>,
-    },
-}
-
-fn default_full_size() -> [f32; 2] {
-    [1.0, 1.0]
-}
-
-fn default_radius() -> f32 {
-    0.5
-}
-
-impl Default for CollisionShape {
-    fn default() -> Self {
-        CollisionShape::None
-    }
-}
-
-impl CollisionShape {
-    /// Check if this shape has collision
-    pub fn has_collision(&self) -> bool {
-        !matches!(self, CollisionShape::None)
-    }
-
-    /// Create a rectangle shape with offset and size
-    pub fn rectangle(offset: [f32; 2], size: [f32; 2]) -> Self {
-        CollisionShape::Rectangle { offset, size }
-    }
-
-    /// Create a circle shape with offset and radius
-    pub fn circle(offset: [f32; 2], radius: f32) -> Self {
-        CollisionShape::Circle { offset, radius }
-    }
-
-    /// Create a polygon shape from points
-    pub fn polygon(points: Vec<[f32; 2]>) -> Self {
-        CollisionShape::Polygon { points }
-    }
-
-    /// Get the display name of this shape type
-    pub fn name(&self) -> &'static str {
-        match self {
-            CollisionShape::None => "None",
-            CollisionShape::Full => "Full",
-            CollisionShape::Rectangle { .. } => "Rectangle",
-            CollisionShape::Circle { .. } => "Circle",
-            CollisionShape::Polygon { .. } => "Polygon",
-        }
-    }
-}
+// `CollisionShape` lives in `bevy_map_animation` so animation windows can carry
+// hitbox shapes without a dependency cycle; this is its canonical path.
+pub use bevy_map_animation::CollisionShape;
 
 /// Physics body type
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -273,4 +197,22 @@ mod tests {
         let parsed: CollisionShape = serde_json::from_str(&json).unwrap();
         assert_eq!(shape, parsed);
     }
+
+    #[test]
+    fn test_collision_shape_flipped() {
+        let rect = CollisionShape::rectangle([0.125, 0.25], [0.25, 0.5]);
+        assert_eq!(
+            rect.flipped(true, false),
+            CollisionShape::rectangle([0.625, 0.25], [0.25, 0.5])
+        );
+
+        let circle = CollisionShape::circle([0.25, 0.5], 0.1);
+        assert_eq!(
+            circle.flipped(true, true),
+            CollisionShape::circle([0.75, 0.5], 0.1)
+        );
+
+        let poly = CollisionShape::polygon(vec![[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]]);
+        assert_eq!(poly.flipped(false, false), poly);
+    }
 }
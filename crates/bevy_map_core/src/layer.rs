@@ -37,6 +37,12 @@ pub fn tile_flip_y(tile: u32) -> bool {
     tile & TILE_FLIP_Y != 0
 }
 
+/// Check if a tile has diagonal flip (set by 90° rotations)
+#[inline]
+pub fn tile_flip_diagonal(tile: u32) -> bool {
+    tile & TILE_FLIP_DIAGONAL != 0
+}
+
 /// Create a tile value with flip flags
 #[inline]
 pub fn tile_with_flips(index: u32, flip_x: bool, flip_y: bool) -> u32 {
@@ -29,12 +29,13 @@ pub use entity_type_config::{
     SpriteConfig,
 };
 pub use layer::{
-    tile_flip_x, tile_flip_y, tile_index, tile_with_flips, toggle_flip_x, toggle_flip_y, Layer,
-    LayerData, LayerType, OCCUPIED_CELL, TILE_FLIP_MASK, TILE_FLIP_X, TILE_FLIP_Y, TILE_INDEX_MASK,
+    tile_flip_diagonal, tile_flip_x, tile_flip_y, tile_index, tile_with_flips, toggle_flip_x,
+    toggle_flip_y, Layer, LayerData, LayerType, OCCUPIED_CELL, TILE_FLIP_DIAGONAL, TILE_FLIP_MASK,
+    TILE_FLIP_X, TILE_FLIP_Y, TILE_INDEX_MASK,
 };
 pub use level::Level;
 pub use physics_layers::{PhysicsLayerSet, PhysicsLayers};
 pub use project::{EditorProject, MapProject, MapProjectBuilder};
 pub use tileset::{TileProperties, Tileset, TilesetImage};
-pub use value::Value;
+pub use value::{FromMapValue, ToMapValue, Value};
 pub use world::{ConnectionDirection, LevelConnection, WorldConfig, WorldLayout};
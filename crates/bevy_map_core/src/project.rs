@@ -102,6 +102,8 @@ impl EditorProject {
             sprite_sheets,
             dialogues,
             entity_type_configs: self.entity_type_configs.clone(),
+            data: self.data.clone(),
+            autotile_config: self.autotile_config.clone(),
         })
     }
 
@@ -142,6 +144,14 @@ pub struct MapProject {
     /// Entity type component configurations (physics, input, sprite per type)
     #[serde(default)]
     pub entity_type_configs: HashMap<String, EntityTypeConfig>,
+    /// Data instances of the editor project (`{ "instances": { type: [...] } }`),
+    /// read by the generated `GameData` resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// Autotile configuration of the editor project, whose automapping rule
+    /// sets can be re-run at runtime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autotile_config: Option<serde_json::Value>,
 }
 
 impl MapProject {
@@ -155,6 +165,8 @@ impl MapProject {
             sprite_sheets: HashMap::new(),
             dialogues: HashMap::new(),
             entity_type_configs: HashMap::new(),
+            data: None,
+            autotile_config: None,
         }
     }
 
@@ -173,6 +185,8 @@ impl MapProject {
             sprite_sheets: sprite_sheet_map,
             dialogues: HashMap::new(),
             entity_type_configs: HashMap::new(),
+            data: None,
+            autotile_config: None,
         }
     }
 
@@ -160,6 +160,344 @@ impl From<bool> for Value {
     }
 }
 
+/// Conversion from a property [`Value`] into a Rust type
+///
+/// Used by `#[derive(MapEntity)]` to read `#[map_prop]` fields, and derivable
+/// with `#[derive(FromMapValue)]` for unit enums (matched by variant name) and
+/// embedded structs (read from an object). Returns `None` when the value has
+/// the wrong shape, in which case the derive falls back to the field default.
+pub trait FromMapValue: Sized {
+    fn from_map_value(value: &Value) -> Option<Self>;
+}
+
+impl FromMapValue for Value {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+impl FromMapValue for serde_json::Value {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        Some(value.to_json())
+    }
+}
+
+impl FromMapValue for String {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_string().map(str::to_string)
+    }
+}
+
+impl FromMapValue for bool {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+macro_rules! impl_from_map_value_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromMapValue for $ty {
+                fn from_map_value(value: &Value) -> Option<Self> {
+                    value.as_int().and_then(|i| <$ty>::try_from(i).ok())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_map_value_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromMapValue for f32 {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_float().map(|f| f as f32)
+    }
+}
+
+impl FromMapValue for f64 {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_float()
+    }
+}
+
+/// `Null` reads as `None`; anything else must convert to `T`
+impl<T: FromMapValue> FromMapValue for Option<T> {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            value => T::from_map_value(value).map(Some),
+        }
+    }
+}
+
+/// Reads an array; fails if any element doesn't convert
+impl<T: FromMapValue> FromMapValue for Vec<T> {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value.as_array()?.iter().map(T::from_map_value).collect()
+    }
+}
+
+impl<T: FromMapValue> FromMapValue for HashMap<String, T> {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value
+            .as_object()?
+            .iter()
+            .map(|(k, v)| Some((k.clone(), T::from_map_value(v)?)))
+            .collect()
+    }
+}
+
+impl FromMapValue for uuid::Uuid {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        value
+            .as_string()
+            .and_then(|s| uuid::Uuid::parse_str(s).ok())
+    }
+}
+
+/// Points, stored by the editor as `{"x": .., "y": ..}` (arrays also work)
+impl FromMapValue for [f32; 2] {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        let [x, y] = float_components(value, ["x", "y"])?;
+        Some([x, y])
+    }
+}
+
+impl FromMapValue for [f32; 3] {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        float_components(value, ["x", "y", "z"])
+    }
+}
+
+/// Colors as RGBA, stored by the editor as `"#rrggbb"` (`"#rgb"`,
+/// `"#rrggbbaa"` and `[r, g, b]`/`[r, g, b, a]` arrays also work)
+impl FromMapValue for [f32; 4] {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => parse_hex_color(s),
+            Value::Array(a) if a.len() == 3 || a.len() == 4 => {
+                let mut rgba = [1.0; 4];
+                for (c, v) in rgba.iter_mut().zip(a) {
+                    *c = v.as_float()? as f32;
+                }
+                Some(rgba)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl FromMapValue for bevy::math::Vec2 {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        <[f32; 2]>::from_map_value(value).map(Self::from)
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl FromMapValue for bevy::math::Vec3 {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        <[f32; 3]>::from_map_value(value).map(Self::from)
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl FromMapValue for bevy::color::Color {
+    fn from_map_value(value: &Value) -> Option<Self> {
+        let [r, g, b, a] = <[f32; 4]>::from_map_value(value)?;
+        Some(Self::srgba(r, g, b, a))
+    }
+}
+
+/// Conversion from a Rust type into a property [`Value`]
+///
+/// The reverse of [`FromMapValue`], producing the format the editor stores
+/// (points as `{"x", "y"}` objects, colors as hex strings). Used by
+/// `#[derive(MapEntity)]` to write `#[map_prop]` fields back to an
+/// `EntityInstance`, and derivable with `#[derive(ToMapValue)]`.
+pub trait ToMapValue {
+    fn to_map_value(&self) -> Value;
+}
+
+impl ToMapValue for Value {
+    fn to_map_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl ToMapValue for serde_json::Value {
+    fn to_map_value(&self) -> Value {
+        Value::from_json(self.clone())
+    }
+}
+
+impl ToMapValue for String {
+    fn to_map_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl ToMapValue for bool {
+    fn to_map_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_map_value_int {
+    ($($ty:ty),*) => {
+        $(
+            impl ToMapValue for $ty {
+                fn to_map_value(&self) -> Value {
+                    // Only u64/usize values past i64::MAX saturate
+                    Value::Int(i64::try_from(*self).unwrap_or(i64::MAX))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_map_value_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToMapValue for f32 {
+    fn to_map_value(&self) -> Value {
+        Value::Float(*self as f64)
+    }
+}
+
+impl ToMapValue for f64 {
+    fn to_map_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+impl<T: ToMapValue> ToMapValue for Option<T> {
+    fn to_map_value(&self) -> Value {
+        self.as_ref().map_or(Value::Null, T::to_map_value)
+    }
+}
+
+impl<T: ToMapValue> ToMapValue for Vec<T> {
+    fn to_map_value(&self) -> Value {
+        Value::Array(self.iter().map(T::to_map_value).collect())
+    }
+}
+
+impl<T: ToMapValue> ToMapValue for HashMap<String, T> {
+    fn to_map_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (k.clone(), v.to_map_value()))
+                .collect(),
+        )
+    }
+}
+
+impl ToMapValue for uuid::Uuid {
+    fn to_map_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl ToMapValue for [f32; 2] {
+    fn to_map_value(&self) -> Value {
+        float_object(["x", "y"], *self)
+    }
+}
+
+impl ToMapValue for [f32; 3] {
+    fn to_map_value(&self) -> Value {
+        float_object(["x", "y", "z"], *self)
+    }
+}
+
+/// Writes `"#rrggbb"`, or `"#rrggbbaa"` if the color isn't opaque
+impl ToMapValue for [f32; 4] {
+    fn to_map_value(&self) -> Value {
+        let [r, g, b, a] = self.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        Value::String(if a == 255 {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        })
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl ToMapValue for bevy::math::Vec2 {
+    fn to_map_value(&self) -> Value {
+        self.to_array().to_map_value()
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl ToMapValue for bevy::math::Vec3 {
+    fn to_map_value(&self) -> Value {
+        self.to_array().to_map_value()
+    }
+}
+
+#[cfg(feature = "bevy")]
+impl ToMapValue for bevy::color::Color {
+    fn to_map_value(&self) -> Value {
+        use bevy::color::ColorToComponents;
+        self.to_srgba().to_f32_array().to_map_value()
+    }
+}
+
+fn float_object<const N: usize>(names: [&str; N], values: [f32; N]) -> Value {
+    Value::Object(
+        names
+            .iter()
+            .zip(values)
+            .map(|(name, v)| (name.to_string(), Value::Float(v as f64)))
+            .collect(),
+    )
+}
+
+/// Read named object fields, or array elements in order, as floats
+fn float_components<const N: usize>(value: &Value, names: [&str; N]) -> Option<[f32; N]> {
+    let mut out = [0.0; N];
+    match value {
+        Value::Object(obj) => {
+            for (c, name) in out.iter_mut().zip(names) {
+                *c = obj.get(name)?.as_float()? as f32;
+            }
+        }
+        Value::Array(a) if a.len() == N => {
+            for (c, v) in out.iter_mut().zip(a) {
+                *c = v.as_float()? as f32;
+            }
+        }
+        _ => return None,
+    }
+    Some(out)
+}
+
+/// Parse `#rgb`, `#rrggbb` or `#rrggbbaa` (the `#` is optional) into RGBA
+fn parse_hex_color(s: &str) -> Option<[f32; 4]> {
+    let hex = s.trim().trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize, len: usize| {
+        let digits = &hex[i * len..(i + 1) * len];
+        let v = u8::from_str_radix(digits, 16).ok()?;
+        // Single digits expand as in CSS (#f80 = #ff8800)
+        Some(if len == 1 { v * 17 } else { v } as f32 / 255.0)
+    };
+    match hex.len() {
+        3 => Some([channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, 1.0]),
+        6 => Some([channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 1.0]),
+        8 => Some([
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            channel(3, 2)?,
+        ]),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +518,84 @@ mod tests {
         let parsed: Value = serde_json::from_str(&json).unwrap();
         assert_eq!(value, parsed);
     }
+
+    #[test]
+    fn test_from_map_value() {
+        assert_eq!(i32::from_map_value(&Value::Int(7)), Some(7));
+        assert_eq!(u8::from_map_value(&Value::Int(-1)), None);
+        assert_eq!(f32::from_map_value(&Value::Int(2)), Some(2.0));
+        assert_eq!(String::from_map_value(&Value::Int(2)), None);
+
+        assert_eq!(Option::<i32>::from_map_value(&Value::Null), Some(None));
+        assert_eq!(
+            Vec::<i64>::from_map_value(&Value::Array(vec![Value::Int(1), Value::Int(2)])),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            Vec::<i64>::from_map_value(&Value::Array(vec![Value::Int(1), Value::Bool(true)])),
+            None
+        );
+
+        let point = Value::Object(
+            [
+                ("x".to_string(), Value::Float(1.5)),
+                ("y".to_string(), Value::Int(-2)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(<[f32; 2]>::from_map_value(&point), Some([1.5, -2.0]));
+
+        assert_eq!(
+            <[f32; 4]>::from_map_value(&Value::from("#ff0000")),
+            Some([1.0, 0.0, 0.0, 1.0])
+        );
+        assert_eq!(
+            <[f32; 4]>::from_map_value(&Value::from("#0f08")),
+            None,
+            "4-digit hex isn't supported"
+        );
+        assert_eq!(
+            <[f32; 4]>::from_map_value(&Value::from("00ff0080")),
+            Some([0.0, 1.0, 0.0, 128.0 / 255.0])
+        );
+
+        let id = uuid::Uuid::new_v4();
+        assert_eq!(
+            uuid::Uuid::from_map_value(&Value::String(id.to_string())),
+            Some(id)
+        );
+    }
+
+    #[test]
+    fn test_to_map_value_roundtrip() {
+        fn roundtrip<T: FromMapValue + ToMapValue + PartialEq + std::fmt::Debug>(value: T) {
+            assert_eq!(
+                T::from_map_value(&value.to_map_value()).as_ref(),
+                Some(&value)
+            );
+        }
+
+        roundtrip(42i32);
+        roundtrip(-1.5f32);
+        roundtrip("hi".to_string());
+        roundtrip(Some(3u8));
+        roundtrip(Option::<String>::None);
+        roundtrip(vec![true, false]);
+        roundtrip([1.0f32, -2.0]);
+        roundtrip([0.0f32, 1.0, 2.0]);
+        roundtrip([1.0f32, 0.0, 0.2, 1.0]);
+        roundtrip(uuid::Uuid::new_v4());
+
+        // Opaque colors are written the way the editor does
+        assert_eq!(
+            [1.0f32, 0.0, 0.0, 1.0].to_map_value(),
+            Value::from("#ff0000")
+        );
+        assert_eq!(
+            [1.0f32, 0.0, 0.0, 0.5].to_map_value(),
+            Value::from("#ff000080")
+        );
+        assert_eq!(u64::MAX.to_map_value(), Value::Int(i64::MAX));
+    }
 }
@@ -1,7 +1,9 @@
 //! Derive macros for bevy_map_editor entity spawning
 //!
 //! This crate provides the `#[derive(MapEntity)]` macro for automatically
-//! implementing entity spawning from map data.
+//! implementing entity spawning from map data (and writing it back), and
+//! `#[derive(FromMapValue)]`/`#[derive(ToMapValue)]` for enums and embedded
+//! structs used as property fields.
 //!
 //! # Example
 //!
@@ -23,7 +25,8 @@
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::{
     parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, Meta, Type,
 };
@@ -39,8 +42,13 @@ use syn::{
 /// - `#[map_prop]` - Mark a field as coming from entity properties
 /// - `#[map_prop(name = "property_name")]` - Use a different property name than the field name
 /// - `#[map_prop(default = value)]` - Default value if property is missing
+/// - `#[map_prop(skip_write)]` - Read the field but leave it out of `write_properties`
 /// - `#[map_sprite]` - Mark a field to receive sprite handle injection (field must be `Option<Handle<Image>>`)
 /// - `#[map_sprite("property_name")]` - Use a different property name than the field name
+///
+/// `#[map_prop]` fields are also written back by the generated
+/// `write_properties`, so field types must implement `ToMapValue` as well
+/// unless the field is marked `skip_write`.
 #[proc_macro_derive(MapEntity, attributes(map_entity, map_prop, map_sprite))]
 pub fn derive_map_entity(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -50,6 +58,246 @@ pub fn derive_map_entity(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Derive macro for reading a type from a property `Value`
+///
+/// Implements `FromMapValue`, so the type can be used in `#[map_prop]` fields.
+///
+/// - Enums with unit variants read from a string: the variant name or its
+///   snake_case form (`"BigBoss"` or `"big_boss"`)
+/// - Structs with named fields read from an object, one key per field. Fields
+///   accept `#[map_prop(name = "...", default = ...)]`; missing keys use the
+///   default (fields must implement `Default`)
+/// - Tuple structs with one field read the inner value
+#[proc_macro_derive(FromMapValue, attributes(map_prop))]
+pub fn derive_from_map_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_from_map_value(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn impl_from_map_value(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "FromMapValue can only be derived for enums with unit variants",
+                    ));
+                }
+                let ident = &variant.ident;
+                let variant_name = ident.to_string();
+                let snake = to_snake_case(&variant_name);
+                arms.push(if snake == variant_name {
+                    quote! { #variant_name => Some(Self::#ident), }
+                } else {
+                    quote! { #variant_name | #snake => Some(Self::#ident), }
+                });
+            }
+            quote! {
+                match value.as_string()? {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let field_inits = fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        let field_name = field.ident.as_ref().unwrap();
+                        let (prop_name, default_value) = match field
+                            .attrs
+                            .iter()
+                            .find(|attr| attr.path().is_ident("map_prop"))
+                        {
+                            Some(attr) => {
+                                let prop = parse_map_prop_attr(attr, field_name)?;
+                                (prop.name, prop.default)
+                            }
+                            None => (field_name.to_string(), None),
+                        };
+                        generate_field_init(
+                            field_name,
+                            &field.ty,
+                            quote! { object.get(#prop_name) },
+                            default_value,
+                        )
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! {
+                    let object = value.as_object()?;
+                    Some(Self {
+                        #(#field_inits),*
+                    })
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field_type = &fields.unnamed[0].ty;
+                let convert = quote_spanned! {field_type.span()=>
+                    <#field_type as bevy_map::core::FromMapValue>::from_map_value
+                };
+                quote! {
+                    #convert(value).map(Self)
+                }
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "FromMapValue can only be derived for structs with named fields or a single unnamed field",
+                ))
+            }
+        },
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "FromMapValue can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics bevy_map::core::FromMapValue for #name #ty_generics #where_clause {
+            fn from_map_value(value: &bevy_map::core::Value) -> Option<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// Convert a PascalCase variant name to snake_case
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Derive macro for writing a type to a property `Value`
+///
+/// Implements `ToMapValue`, the reverse of `#[derive(FromMapValue)]`: unit
+/// enums write their variant name, structs with named fields write an object
+/// (keys from `#[map_prop(name = "...")]` or the field name, skipping
+/// `skip_write` fields), and single-field tuple structs write the inner value.
+#[proc_macro_derive(ToMapValue, attributes(map_prop))]
+pub fn derive_to_map_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_to_map_value(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn impl_to_map_value(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "ToMapValue can only be derived for enums with unit variants",
+                    ));
+                }
+                let ident = &variant.ident;
+                let variant_name = ident.to_string();
+                arms.push(quote! {
+                    Self::#ident => #variant_name,
+                });
+            }
+            quote! {
+                bevy_map::core::Value::String(
+                    match self {
+                        #(#arms)*
+                    }
+                    .to_string(),
+                )
+            }
+        }
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let mut writes = Vec::new();
+                for field in &fields.named {
+                    let field_name = field.ident.as_ref().unwrap();
+                    let prop_name = match field
+                        .attrs
+                        .iter()
+                        .find(|attr| attr.path().is_ident("map_prop"))
+                    {
+                        Some(attr) => {
+                            let prop = parse_map_prop_attr(attr, field_name)?;
+                            if prop.skip_write {
+                                continue;
+                            }
+                            prop.name
+                        }
+                        None => field_name.to_string(),
+                    };
+                    writes.push(generate_field_write(
+                        field_name,
+                        &field.ty,
+                        quote! { object },
+                        &prop_name,
+                    ));
+                }
+                quote! {
+                    let mut object = std::collections::HashMap::new();
+                    #(#writes)*
+                    bevy_map::core::Value::Object(object)
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let field_type = &fields.unnamed[0].ty;
+                let convert = quote_spanned! {field_type.span()=>
+                    <#field_type as bevy_map::core::ToMapValue>::to_map_value
+                };
+                quote! {
+                    #convert(&self.0)
+                }
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "ToMapValue can only be derived for structs with named fields or a single unnamed field",
+                ))
+            }
+        },
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "ToMapValue can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics bevy_map::core::ToMapValue for #name #ty_generics #where_clause {
+            fn to_map_value(&self) -> bevy_map::core::Value {
+                #body
+            }
+        }
+    })
+}
+
 fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let name = &input.ident;
 
@@ -77,6 +325,8 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
 
     // Collect sprite fields: (field_name, property_name)
     let mut sprite_fields: Vec<(Ident, String)> = Vec::new();
+    // Property writes for write_properties, one per #[map_prop] field
+    let mut property_writes: Vec<TokenStream2> = Vec::new();
 
     // Generate field initialization code
     let field_inits: Vec<TokenStream2> = fields
@@ -106,8 +356,22 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 .find(|attr| attr.path().is_ident("map_prop"));
 
             if let Some(attr) = map_prop_attr {
-                let (prop_name, default_value) = parse_map_prop_attr(attr, field_name)?;
-                generate_field_init(field_name, field_type, &prop_name, default_value)
+                let prop = parse_map_prop_attr(attr, field_name)?;
+                let prop_name = &prop.name;
+                if !prop.skip_write {
+                    property_writes.push(generate_field_write(
+                        field_name,
+                        field_type,
+                        quote! { instance.properties },
+                        prop_name,
+                    ));
+                }
+                generate_field_init(
+                    field_name,
+                    field_type,
+                    quote! { instance.properties.get(#prop_name) },
+                    prop.default,
+                )
             } else {
                 // Field without #[map_prop] - use Default::default()
                 Ok(quote! {
@@ -159,6 +423,21 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
         }
     };
 
+    // Generate write_properties (the reverse of from_instance)
+    let write_properties_impl = if property_writes.is_empty() {
+        quote! {
+            fn write_properties(&self, _instance: &mut bevy_map::core::EntityInstance) {
+                // No map_prop fields
+            }
+        }
+    } else {
+        quote! {
+            fn write_properties(&self, instance: &mut bevy_map::core::EntityInstance) {
+                #(#property_writes)*
+            }
+        }
+    };
+
     // Use bevy_map paths if available, otherwise fall back to direct crate paths
     // This allows both `bevy_map` umbrella crate users and direct crate users to work
     let expanded = quote! {
@@ -173,6 +452,8 @@ fn impl_map_entity(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 }
             }
 
+            #write_properties_impl
+
             #sprite_properties_impl
 
             #inject_sprite_impl
@@ -211,12 +492,20 @@ fn parse_type_name(attrs: &[Attribute]) -> syn::Result<String> {
     ))
 }
 
-fn parse_map_prop_attr(
-    attr: &Attribute,
-    field_name: &Ident,
-) -> syn::Result<(String, Option<TokenStream2>)> {
-    let mut prop_name = field_name.to_string();
-    let mut default_value = None;
+/// Options of a `#[map_prop(...)]` attribute
+struct MapProp {
+    name: String,
+    default: Option<TokenStream2>,
+    /// Leave the field out of write-back (`#[map_prop(skip_write)]`)
+    skip_write: bool,
+}
+
+fn parse_map_prop_attr(attr: &Attribute, field_name: &Ident) -> syn::Result<MapProp> {
+    let mut prop = MapProp {
+        name: field_name.to_string(),
+        default: None,
+        skip_write: false,
+    };
 
     // Handle both #[map_prop] and #[map_prop(...)]
     match &attr.meta {
@@ -228,18 +517,23 @@ fn parse_map_prop_attr(
                 list.parse_args_with(syn::punctuated::Punctuated::parse_terminated)?;
 
             for meta in nested {
-                if let Meta::NameValue(nv) = meta {
-                    if nv.path.is_ident("name") {
+                match meta {
+                    Meta::NameValue(nv) if nv.path.is_ident("name") => {
                         if let Expr::Lit(ExprLit {
                             lit: Lit::Str(lit_str),
                             ..
                         }) = &nv.value
                         {
-                            prop_name = lit_str.value();
+                            prop.name = lit_str.value();
                         }
-                    } else if nv.path.is_ident("default") {
-                        default_value = Some(nv.value.to_token_stream());
                     }
+                    Meta::NameValue(nv) if nv.path.is_ident("default") => {
+                        prop.default = Some(nv.value.to_token_stream());
+                    }
+                    Meta::Path(path) if path.is_ident("skip_write") => {
+                        prop.skip_write = true;
+                    }
+                    _ => {}
                 }
             }
         }
@@ -251,7 +545,7 @@ fn parse_map_prop_attr(
         }
     }
 
-    Ok((prop_name, default_value))
+    Ok(prop)
 }
 
 /// Parse #[map_sprite] or #[map_sprite("property_name")] attribute
@@ -279,93 +573,126 @@ fn parse_map_sprite_attr(attr: &Attribute, field_name: &Ident) -> syn::Result<St
     Ok(prop_name)
 }
 
+/// Read a field from an optional `&Value` expression through `FromMapValue`
+///
+/// The trait bound is spanned to the field type, so unsupported types report
+/// a missing `FromMapValue` impl on the field itself.
 fn generate_field_init(
     field_name: &Ident,
     field_type: &Type,
-    prop_name: &str,
+    value: TokenStream2,
     default_value: Option<TokenStream2>,
 ) -> syn::Result<TokenStream2> {
-    let type_str = quote!(#field_type).to_string();
+    let convert = quote_spanned! {field_type.span()=>
+        <#field_type as bevy_map::core::FromMapValue>::from_map_value
+    };
 
-    // Generate the appropriate getter based on field type
-    let getter = if type_str.contains("String") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_string(#prop_name)
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| #default.to_string())
-            }
-        } else {
-            quote! {
-                instance.get_string(#prop_name)
-                    .map(|s| s.to_string())
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("i32")
-        || type_str.contains("i64")
-        || type_str.contains("u32")
-        || type_str.contains("u64")
-        || type_str.contains("usize")
-    {
-        // All integer types use get_int and cast to the target type
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_int(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_int(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("f32") || type_str.contains("f64") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_float(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_float(#prop_name)
-                    .map(|v| v as #field_type)
-                    .unwrap_or_default()
-            }
-        }
-    } else if type_str.contains("bool") {
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_bool(#prop_name)
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_bool(#prop_name)
-                    .unwrap_or_default()
-            }
-        }
-    } else {
-        // For custom types, try FromStr or Default
-        if let Some(default) = default_value {
-            quote! {
-                instance.get_string(#prop_name)
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(#default)
-            }
-        } else {
-            quote! {
-                instance.get_string(#prop_name)
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or_default()
-            }
-        }
+    let getter = match default_value {
+        None => quote! {
+            #value.and_then(#convert).unwrap_or_default()
+        },
+        // String defaults go through the same conversion, so they work for
+        // Strings as well as enums, colors and refs
+        Some(default) if syn::parse2::<syn::LitStr>(default.clone()).is_ok() => quote! {
+            #value
+                .and_then(#convert)
+                .or_else(|| #convert(&bevy_map::core::Value::String(#default.to_string())))
+                .unwrap_or_default()
+        },
+        Some(default) => quote! {
+            #value.and_then(#convert).unwrap_or_else(|| #default)
+        },
     };
 
     Ok(quote! {
         #field_name: #getter
     })
 }
+
+/// Write a field into a `HashMap<String, Value>` expression through `ToMapValue`
+fn generate_field_write(
+    field_name: &Ident,
+    field_type: &Type,
+    properties: TokenStream2,
+    prop_name: &str,
+) -> TokenStream2 {
+    let convert = quote_spanned! {field_type.span()=>
+        <#field_type as bevy_map::core::ToMapValue>::to_map_value
+    };
+    quote! {
+        #properties.insert(#prop_name.to_string(), #convert(&self.#field_name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(result: syn::Result<TokenStream2>) -> String {
+        result.expect_err("derive should be rejected").to_string()
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(to_snake_case("Idle"), "idle");
+        assert_eq!(to_snake_case("BigBoss"), "big_boss");
+        assert_eq!(to_snake_case("lower"), "lower");
+    }
+
+    #[test]
+    fn test_rejects_unsupported_shapes() {
+        let data_enum: DeriveInput = parse_quote! {
+            enum Shape { Circle(f32), Square }
+        };
+        assert_eq!(
+            error(impl_from_map_value(&data_enum)),
+            "FromMapValue can only be derived for enums with unit variants"
+        );
+        assert_eq!(
+            error(impl_to_map_value(&data_enum)),
+            "ToMapValue can only be derived for enums with unit variants"
+        );
+
+        let tuple: DeriveInput = parse_quote! { struct Pair(i32, i32); };
+        assert!(error(impl_from_map_value(&tuple)).contains("a single unnamed field"));
+        assert!(error(impl_to_map_value(&tuple)).contains("a single unnamed field"));
+
+        let unit: DeriveInput = parse_quote! { struct Marker; };
+        assert!(error(impl_from_map_value(&unit)).contains("named fields"));
+
+        let union: DeriveInput = parse_quote! { union Bits { a: u32, b: f32 } };
+        assert_eq!(
+            error(impl_from_map_value(&union)),
+            "FromMapValue can't be derived for unions"
+        );
+
+        let enum_entity: DeriveInput = parse_quote! {
+            #[map_entity(type_name = "Door")]
+            enum Door { Open, Closed }
+        };
+        assert_eq!(
+            error(impl_map_entity(&enum_entity)),
+            "MapEntity can only be derived for structs"
+        );
+
+        let untyped: DeriveInput = parse_quote! { struct Npc { #[map_prop] name: String } };
+        assert!(error(impl_map_entity(&untyped)).contains("type_name"));
+
+        let bad_attr: DeriveInput = parse_quote! {
+            #[map_entity(type_name = "Npc")]
+            struct Npc { #[map_prop = "name"] name: String }
+        };
+        assert!(error(impl_map_entity(&bad_attr)).contains("Expected #[map_prop]"));
+    }
+
+    #[test]
+    fn test_enum_arms_accept_snake_case() {
+        let input: DeriveInput = parse_quote! {
+            enum Mood { Calm, BigBoss }
+        };
+        let code = impl_from_map_value(&input).unwrap().to_string();
+        assert!(code.contains("\"BigBoss\" | \"big_boss\" => Some (Self :: BigBoss)"));
+        assert!(code.contains("\"Calm\" | \"calm\" => Some (Self :: Calm)"));
+    }
+}
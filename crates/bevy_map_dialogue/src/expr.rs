@@ -0,0 +1,992 @@
+//! Expression language for dialogue conditions and actions
+//!
+//! Conditions are single expressions that must evaluate to a boolean:
+//!
+//! ```text
+//! gold >= 10 && !met_king
+//! name == "Aria" || reputation > 2.5
+//! ```
+//!
+//! Actions are `;`-separated statements that assign variables or emit signals:
+//!
+//! ```text
+//! gold -= 10; has_key = true; give_item("rusty_key", 1)
+//! ```
+//!
+//! Supported operators, from lowest to highest precedence:
+//! `||`, `&&`, `==` `!=`, `<` `<=` `>` `>=`, `+` `-`, `*` `/` `%`, unary `!` `-`.
+//! Literals are integers, floats, `"strings"`, `true` and `false`.
+//! Undefined variables read as `false`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy::prelude::*;
+
+/// A value stored in [`DialogueVariables`] or produced by an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DialogueValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Default for DialogueValue {
+    fn default() -> Self {
+        DialogueValue::Bool(false)
+    }
+}
+
+impl DialogueValue {
+    /// Get the type name of this value (for error messages)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DialogueValue::Bool(_) => "bool",
+            DialogueValue::Int(_) => "int",
+            DialogueValue::Float(_) => "float",
+            DialogueValue::String(_) => "string",
+        }
+    }
+
+    /// Get as a boolean, if this is a bool
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            DialogueValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Get as an integer, if this is an int
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            DialogueValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Get as a float (ints are widened)
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            DialogueValue::Int(i) => Some(*i as f64),
+            DialogueValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Get as a string slice, if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DialogueValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The zero value of the same type (used for compound assignment on unset variables)
+    fn zero_like(&self) -> DialogueValue {
+        match self {
+            DialogueValue::Bool(_) => DialogueValue::Bool(false),
+            DialogueValue::Int(_) => DialogueValue::Int(0),
+            DialogueValue::Float(_) => DialogueValue::Float(0.0),
+            DialogueValue::String(_) => DialogueValue::String(String::new()),
+        }
+    }
+}
+
+impl fmt::Display for DialogueValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialogueValue::Bool(b) => write!(f, "{}", b),
+            DialogueValue::Int(i) => write!(f, "{}", i),
+            DialogueValue::Float(v) => write!(f, "{}", v),
+            DialogueValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<bool> for DialogueValue {
+    fn from(v: bool) -> Self {
+        DialogueValue::Bool(v)
+    }
+}
+
+impl From<i64> for DialogueValue {
+    fn from(v: i64) -> Self {
+        DialogueValue::Int(v)
+    }
+}
+
+impl From<i32> for DialogueValue {
+    fn from(v: i32) -> Self {
+        DialogueValue::Int(v as i64)
+    }
+}
+
+impl From<f64> for DialogueValue {
+    fn from(v: f64) -> Self {
+        DialogueValue::Float(v)
+    }
+}
+
+impl From<&str> for DialogueValue {
+    fn from(v: &str) -> Self {
+        DialogueValue::String(v.to_string())
+    }
+}
+
+impl From<String> for DialogueValue {
+    fn from(v: String) -> Self {
+        DialogueValue::String(v)
+    }
+}
+
+/// Variables read by dialogue conditions and written by dialogue actions
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DialogueVariables {
+    values: HashMap<String, DialogueValue>,
+}
+
+impl DialogueVariables {
+    /// Create an empty variable set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a variable
+    pub fn get(&self, name: &str) -> Option<&DialogueValue> {
+        self.values.get(name)
+    }
+
+    /// Set a variable
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<DialogueValue>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Remove a variable
+    pub fn remove(&mut self, name: &str) -> Option<DialogueValue> {
+        self.values.remove(name)
+    }
+
+    /// Get a boolean variable (unset or non-bool reads as false)
+    pub fn get_bool(&self, name: &str) -> bool {
+        self.get(name).and_then(|v| v.as_bool()).unwrap_or(false)
+    }
+
+    /// Get an integer variable
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        self.get(name).and_then(|v| v.as_int())
+    }
+
+    /// Get a float variable (ints are widened)
+    pub fn get_float(&self, name: &str) -> Option<f64> {
+        self.get(name).and_then(|v| v.as_float())
+    }
+
+    /// Get a string variable
+    pub fn get_string(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|v| v.as_str())
+    }
+
+    /// Iterate over all variables
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DialogueValue)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Remove all variables
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// Error from parsing or evaluating an expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    /// Human-readable description
+    pub message: String,
+    /// Byte offset in the source where the error was detected (parse errors only)
+    pub position: Option<usize>,
+}
+
+impl ExprError {
+    fn at(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    fn eval(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(pos) => write!(f, "{} (at column {})", self.message, pos + 1),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Unary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+/// Binary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+/// Parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(DialogueValue),
+    Var(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+/// Assignment operator in an action statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignOp {
+    Set,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Parsed action statement
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    /// `name = expr`, `name += expr`, ...
+    Assign {
+        name: String,
+        op: AssignOp,
+        value: Expr,
+    },
+    /// `name(args...)` - emits a [`DialogueSignal`]
+    Emit { name: String, args: Vec<Expr> },
+}
+
+/// A signal emitted by an action statement such as `give_item("sword", 1)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueSignal {
+    /// Signal name (the called identifier)
+    pub name: String,
+    /// Evaluated arguments
+    pub args: Vec<DialogueValue>,
+}
+
+// ============================================================================
+// Tokenizer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Op(&'static str),
+    Eof,
+}
+
+const OPERATORS: &[&str] = &[
+    "&&", "||", "==", "!=", "<=", ">=", "+=", "-=", "*=", "/=", "(", ")", ",", ";", "!", "<", ">",
+    "+", "-", "*", "/", "%", "=",
+];
+
+fn tokenize(src: &str) -> Result<Vec<(Token, usize)>, ExprError> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let is_float = i + 1 < bytes.len() && bytes[i] == b'.' && bytes[i + 1].is_ascii_digit();
+            if is_float {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let value = src[start..i]
+                    .parse()
+                    .map_err(|_| ExprError::at(start, "invalid number"))?;
+                tokens.push((Token::Float(value), start));
+            } else {
+                let value = src[start..i]
+                    .parse()
+                    .map_err(|_| ExprError::at(start, "integer out of range"))?;
+                tokens.push((Token::Int(value), start));
+            }
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            let start = i;
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'.')
+            {
+                i += 1;
+            }
+            tokens.push((Token::Ident(src[start..i].to_string()), start));
+        } else if c == b'"' {
+            let start = i;
+            i += 1;
+            let mut value = String::new();
+            loop {
+                let Some(ch) = src[i..].chars().next() else {
+                    return Err(ExprError::at(start, "unterminated string"));
+                };
+                i += ch.len_utf8();
+                match ch {
+                    '"' => break,
+                    '\\' => {
+                        let Some(escaped) = src[i..].chars().next() else {
+                            return Err(ExprError::at(start, "unterminated string"));
+                        };
+                        i += escaped.len_utf8();
+                        value.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            other => other,
+                        });
+                    }
+                    other => value.push(other),
+                }
+            }
+            tokens.push((Token::Str(value), start));
+        } else if let Some(op) = OPERATORS.iter().find(|op| src[i..].starts_with(**op)) {
+            tokens.push((Token::Op(op), i));
+            i += op.len();
+        } else {
+            let ch = src[i..].chars().next().unwrap_or('?');
+            return Err(ExprError::at(i, format!("unexpected character '{}'", ch)));
+        }
+    }
+
+    tokens.push((Token::Eof, src.len()));
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Maximum nesting of parentheses, unary operators and chained binary
+/// operators, so hostile input can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn new(src: &str) -> Result<Self, ExprError> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+            pos: 0,
+            depth: 0,
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens[self.pos].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Token::Op(o) if *o == op) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), ExprError> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(ExprError::at(self.offset(), format!("expected '{}'", op)))
+        }
+    }
+
+    fn expect_eof(&self) -> Result<(), ExprError> {
+        match self.peek() {
+            Token::Eof => Ok(()),
+            _ => Err(ExprError::at(self.offset(), "unexpected trailing input")),
+        }
+    }
+
+    /// Run `f` one nesting level deeper, failing past `MAX_DEPTH`
+    fn nested<T>(
+        &mut self,
+        offset: usize,
+        f: impl FnOnce(&mut Self) -> Result<T, ExprError>,
+    ) -> Result<T, ExprError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ExprError::at(offset, "expression is nested too deeply"));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn binary_level(
+        &mut self,
+        ops: &[(&str, BinaryOp)],
+        next: fn(&mut Self) -> Result<Expr, ExprError>,
+    ) -> Result<Expr, ExprError> {
+        let depth = self.depth;
+        let result = self.binary_chain(ops, next);
+        self.depth = depth;
+        result
+    }
+
+    /// Parse `next (op next)*` left-associatively; each operator nests the
+    /// tree one level deeper, so it counts against `MAX_DEPTH`
+    fn binary_chain(
+        &mut self,
+        ops: &[(&str, BinaryOp)],
+        next: fn(&mut Self) -> Result<Expr, ExprError>,
+    ) -> Result<Expr, ExprError> {
+        let mut lhs = next(self)?;
+        'outer: loop {
+            for (symbol, op) in ops {
+                let offset = self.offset();
+                if self.eat(symbol) {
+                    if self.depth >= MAX_DEPTH {
+                        return Err(ExprError::at(offset, "expression is nested too deeply"));
+                    }
+                    self.depth += 1;
+                    let rhs = next(self)?;
+                    lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+                    continue 'outer;
+                }
+            }
+            return Ok(lhs);
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(&[("||", BinaryOp::Or)], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(&[("&&", BinaryOp::And)], Self::equality)
+    }
+
+    fn equality(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(
+            &[("==", BinaryOp::Eq), ("!=", BinaryOp::Ne)],
+            Self::comparison,
+        )
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(
+            &[
+                ("<=", BinaryOp::Le),
+                (">=", BinaryOp::Ge),
+                ("<", BinaryOp::Lt),
+                (">", BinaryOp::Gt),
+            ],
+            Self::additive,
+        )
+    }
+
+    fn additive(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            Self::multiplicative,
+        )
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, ExprError> {
+        self.binary_level(
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Rem),
+            ],
+            Self::unary,
+        )
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        let offset = self.offset();
+        if self.eat("!") {
+            let operand = self.nested(offset, Self::unary)?;
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(operand)));
+        }
+        if self.eat("-") {
+            let operand = self.nested(offset, Self::unary)?;
+            return Ok(Expr::Unary(UnaryOp::Neg, Box::new(operand)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExprError> {
+        let offset = self.offset();
+        match self.advance() {
+            Token::Int(i) => Ok(Expr::Literal(DialogueValue::Int(i))),
+            Token::Float(f) => Ok(Expr::Literal(DialogueValue::Float(f))),
+            Token::Str(s) => Ok(Expr::Literal(DialogueValue::String(s))),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(DialogueValue::Bool(true))),
+                "false" => Ok(Expr::Literal(DialogueValue::Bool(false))),
+                _ if matches!(self.peek(), Token::Op("(")) => Err(ExprError::at(
+                    offset,
+                    format!("'{}(...)' can only be used as an action", name),
+                )),
+                _ => Ok(Expr::Var(name)),
+            },
+            Token::Op("(") => {
+                let inner = self.nested(offset, Self::expr)?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Token::Eof => Err(ExprError::at(offset, "unexpected end of expression")),
+            Token::Op(op) => Err(ExprError::at(offset, format!("unexpected '{}'", op))),
+        }
+    }
+
+    fn statement(&mut self) -> Result<Statement, ExprError> {
+        let offset = self.offset();
+        let Token::Ident(name) = self.advance() else {
+            return Err(ExprError::at(
+                offset,
+                "expected a variable assignment or signal call",
+            ));
+        };
+        if name == "true" || name == "false" {
+            return Err(ExprError::at(
+                offset,
+                format!("cannot assign to '{}'", name),
+            ));
+        }
+
+        if self.eat("(") {
+            let mut args = Vec::new();
+            if !self.eat(")") {
+                loop {
+                    args.push(self.expr()?);
+                    if self.eat(")") {
+                        break;
+                    }
+                    self.expect(",")?;
+                }
+            }
+            return Ok(Statement::Emit { name, args });
+        }
+
+        let op = match self.peek() {
+            Token::Op("=") => AssignOp::Set,
+            Token::Op("+=") => AssignOp::Add,
+            Token::Op("-=") => AssignOp::Sub,
+            Token::Op("*=") => AssignOp::Mul,
+            Token::Op("/=") => AssignOp::Div,
+            _ => {
+                return Err(ExprError::at(
+                    self.offset(),
+                    format!("expected '=' or '(' after '{}'", name),
+                ))
+            }
+        };
+        self.advance();
+        let value = self.expr()?;
+        Ok(Statement::Assign { name, op, value })
+    }
+}
+
+/// Parse a condition expression
+pub fn parse_condition(src: &str) -> Result<Expr, ExprError> {
+    let mut parser = Parser::new(src)?;
+    let expr = parser.expr()?;
+    parser.expect_eof()?;
+    Ok(expr)
+}
+
+/// Parse an action (one or more `;`-separated statements)
+pub fn parse_action(src: &str) -> Result<Vec<Statement>, ExprError> {
+    let mut parser = Parser::new(src)?;
+    let mut statements = Vec::new();
+    while !matches!(parser.peek(), Token::Eof) {
+        statements.push(parser.statement()?);
+        if !parser.eat(";") {
+            break;
+        }
+    }
+    // Allow a trailing ';' but require the rest to be consumed
+    parser.expect_eof()?;
+    Ok(statements)
+}
+
+// ============================================================================
+// Evaluation
+// ============================================================================
+
+impl Expr {
+    /// Evaluate this expression against a set of variables
+    pub fn eval(&self, vars: &DialogueVariables) -> Result<DialogueValue, ExprError> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Var(name) => Ok(vars.get(name).cloned().unwrap_or_default()),
+            Expr::Unary(op, inner) => {
+                let value = inner.eval(vars)?;
+                match (op, &value) {
+                    (UnaryOp::Not, DialogueValue::Bool(b)) => Ok(DialogueValue::Bool(!b)),
+                    (UnaryOp::Neg, DialogueValue::Int(i)) => i
+                        .checked_neg()
+                        .map(DialogueValue::Int)
+                        .ok_or_else(|| ExprError::eval("integer overflow")),
+                    (UnaryOp::Neg, DialogueValue::Float(f)) => Ok(DialogueValue::Float(-f)),
+                    (UnaryOp::Not, _) => Err(ExprError::eval(format!(
+                        "cannot apply '!' to {}",
+                        value.type_name()
+                    ))),
+                    (UnaryOp::Neg, _) => Err(ExprError::eval(format!(
+                        "cannot negate {}",
+                        value.type_name()
+                    ))),
+                }
+            }
+            Expr::Binary(BinaryOp::And, lhs, rhs) => {
+                if !expect_bool(lhs.eval(vars)?, "&&")? {
+                    return Ok(DialogueValue::Bool(false));
+                }
+                Ok(DialogueValue::Bool(expect_bool(rhs.eval(vars)?, "&&")?))
+            }
+            Expr::Binary(BinaryOp::Or, lhs, rhs) => {
+                if expect_bool(lhs.eval(vars)?, "||")? {
+                    return Ok(DialogueValue::Bool(true));
+                }
+                Ok(DialogueValue::Bool(expect_bool(rhs.eval(vars)?, "||")?))
+            }
+            Expr::Binary(op, lhs, rhs) => binary(*op, lhs.eval(vars)?, rhs.eval(vars)?),
+        }
+    }
+
+    /// Evaluate this expression as a condition
+    pub fn eval_bool(&self, vars: &DialogueVariables) -> Result<bool, ExprError> {
+        let value = self.eval(vars)?;
+        value.as_bool().ok_or_else(|| {
+            ExprError::eval(format!(
+                "condition must be a bool, got {}",
+                value.type_name()
+            ))
+        })
+    }
+}
+
+fn expect_bool(value: DialogueValue, op: &str) -> Result<bool, ExprError> {
+    value.as_bool().ok_or_else(|| {
+        ExprError::eval(format!(
+            "'{}' expects bool operands, got {}",
+            op,
+            value.type_name()
+        ))
+    })
+}
+
+fn binary(
+    op: BinaryOp,
+    lhs: DialogueValue,
+    rhs: DialogueValue,
+) -> Result<DialogueValue, ExprError> {
+    use DialogueValue::*;
+
+    let type_error = |lhs: &DialogueValue, rhs: &DialogueValue| {
+        ExprError::eval(format!(
+            "cannot apply {:?} to {} and {}",
+            op,
+            lhs.type_name(),
+            rhs.type_name()
+        ))
+    };
+
+    match op {
+        BinaryOp::Eq | BinaryOp::Ne => {
+            let equal = match (&lhs, &rhs) {
+                (Int(_), Float(_)) | (Float(_), Int(_)) => lhs.as_float() == rhs.as_float(),
+                _ => lhs == rhs,
+            };
+            Ok(Bool(if op == BinaryOp::Eq { equal } else { !equal }))
+        }
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let ordering = match (&lhs, &rhs) {
+                (Int(a), Int(b)) => a.partial_cmp(b),
+                (String(a), String(b)) => a.partial_cmp(b),
+                _ => match (lhs.as_float(), rhs.as_float()) {
+                    (Some(a), Some(b)) => a.partial_cmp(&b),
+                    _ => return Err(type_error(&lhs, &rhs)),
+                },
+            };
+            let Some(ordering) = ordering else {
+                return Ok(Bool(false));
+            };
+            Ok(Bool(match op {
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOp::Add => match (&lhs, &rhs) {
+            (String(a), String(b)) => Ok(String(format!("{}{}", a, b))),
+            _ => arithmetic(op, &lhs, &rhs).ok_or_else(|| type_error(&lhs, &rhs))?,
+        },
+        _ => arithmetic(op, &lhs, &rhs).ok_or_else(|| type_error(&lhs, &rhs))?,
+    }
+}
+
+/// Numeric arithmetic; `None` if the operand types don't support it
+fn arithmetic(
+    op: BinaryOp,
+    lhs: &DialogueValue,
+    rhs: &DialogueValue,
+) -> Option<Result<DialogueValue, ExprError>> {
+    if let (DialogueValue::Int(a), DialogueValue::Int(b)) = (lhs, rhs) {
+        let result = match op {
+            BinaryOp::Add => a.checked_add(*b),
+            BinaryOp::Sub => a.checked_sub(*b),
+            BinaryOp::Mul => a.checked_mul(*b),
+            BinaryOp::Div | BinaryOp::Rem if *b == 0 => {
+                return Some(Err(ExprError::eval("division by zero")))
+            }
+            BinaryOp::Div => a.checked_div(*b),
+            BinaryOp::Rem => a.checked_rem(*b),
+            _ => return None,
+        };
+        return Some(
+            result
+                .map(DialogueValue::Int)
+                .ok_or_else(|| ExprError::eval("integer overflow")),
+        );
+    }
+
+    let (a, b) = (lhs.as_float()?, rhs.as_float()?);
+    let result = match op {
+        BinaryOp::Add => a + b,
+        BinaryOp::Sub => a - b,
+        BinaryOp::Mul => a * b,
+        BinaryOp::Div => a / b,
+        BinaryOp::Rem => a % b,
+        _ => return None,
+    };
+    Some(Ok(DialogueValue::Float(result)))
+}
+
+impl Statement {
+    /// Execute this statement, returning a signal if it emits one
+    pub fn execute(
+        &self,
+        vars: &mut DialogueVariables,
+    ) -> Result<Option<DialogueSignal>, ExprError> {
+        match self {
+            Statement::Assign { name, op, value } => {
+                let rhs = value.eval(vars)?;
+                let new_value = match op {
+                    AssignOp::Set => rhs,
+                    _ => {
+                        let current = vars.get(name).cloned().unwrap_or_else(|| rhs.zero_like());
+                        let bin_op = match op {
+                            AssignOp::Add => BinaryOp::Add,
+                            AssignOp::Sub => BinaryOp::Sub,
+                            AssignOp::Mul => BinaryOp::Mul,
+                            _ => BinaryOp::Div,
+                        };
+                        binary(bin_op, current, rhs)?
+                    }
+                };
+                vars.set(name.clone(), new_value);
+                Ok(None)
+            }
+            Statement::Emit { name, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.eval(vars))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(DialogueSignal {
+                    name: name.clone(),
+                    args,
+                }))
+            }
+        }
+    }
+}
+
+/// Parse and evaluate a condition string
+pub fn evaluate_condition(src: &str, vars: &DialogueVariables) -> Result<bool, ExprError> {
+    parse_condition(src)?.eval_bool(vars)
+}
+
+/// Parse and execute an action string, returning the signals it emitted
+///
+/// Statements run in order; on error, earlier statements keep their effects.
+pub fn execute_action(
+    src: &str,
+    vars: &mut DialogueVariables,
+) -> Result<Vec<DialogueSignal>, ExprError> {
+    let mut signals = Vec::new();
+    for statement in parse_action(src)? {
+        if let Some(signal) = statement.execute(vars)? {
+            signals.push(signal);
+        }
+    }
+    Ok(signals)
+}
+
+/// Quote a string as an expression literal, escaping quotes, backslashes and newlines
+pub(crate) fn quote_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> DialogueVariables {
+        let mut vars = DialogueVariables::new();
+        vars.set("gold", 15);
+        vars.set("name", "Aria");
+        vars.set("met_king", true);
+        vars.set("reputation", 2.5);
+        vars
+    }
+
+    #[test]
+    fn test_condition_precedence() {
+        let vars = vars();
+        assert!(evaluate_condition("gold >= 10 && met_king", &vars).unwrap());
+        assert!(evaluate_condition("gold > 100 || name == \"Aria\"", &vars).unwrap());
+        assert!(!evaluate_condition("!(gold + 5 * 2 == 25)", &vars).unwrap());
+        assert!(evaluate_condition("reputation > 2 && gold % 2 == 1", &vars).unwrap());
+    }
+
+    #[test]
+    fn test_undefined_variable_is_false() {
+        let vars = vars();
+        assert!(evaluate_condition("!has_key", &vars).unwrap());
+        assert!(!evaluate_condition("has_key && gold > 0", &vars).unwrap());
+    }
+
+    #[test]
+    fn test_condition_type_errors() {
+        let vars = vars();
+        assert!(evaluate_condition("gold", &vars).is_err());
+        assert!(evaluate_condition("name < 3", &vars).is_err());
+        assert!(evaluate_condition("gold / 0 == 1", &vars).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_condition("gold >=").is_err());
+        assert!(parse_condition("(gold > 1").is_err());
+        assert!(parse_condition("\"open").is_err());
+        assert!(parse_condition("gold > 1 gold").is_err());
+        assert!(parse_action("gold 5").is_err());
+        assert!(parse_action("true = 1").is_err());
+        assert_eq!(parse_condition("a @ b").unwrap_err().position, Some(2));
+    }
+
+    #[test]
+    fn test_nesting_depth_limit() {
+        let nested = |n: usize| format!("{}gold{}", "(".repeat(n), ")".repeat(n));
+        assert!(parse_condition(&nested(MAX_DEPTH)).is_ok());
+        assert!(parse_condition(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(parse_condition(&"!".repeat(100_000)).is_err());
+        assert!(parse_condition(&"(".repeat(100_000)).is_err());
+        assert!(parse_action(&format!("gold = {}1", "-".repeat(100_000))).is_err());
+
+        // Long operator chains build left-deep trees
+        let chain = |op: &str, n: usize| vec!["gold"; n].join(op);
+        assert!(parse_condition(&chain(" + ", 32)).is_ok());
+        assert!(parse_condition(&chain(" + ", 100_000)).is_err());
+        assert!(parse_condition(&chain(" && ", 100_000)).is_err());
+        assert!(parse_action(&format!("gold = {}", chain(" * ", 100_000))).is_err());
+    }
+
+    #[test]
+    fn test_action_assignments_and_signals() {
+        let mut vars = vars();
+        let signals = execute_action(
+            "gold -= 10; has_key = true; visits += 1; give_item(\"key\", gold);",
+            &mut vars,
+        )
+        .unwrap();
+
+        assert_eq!(vars.get_int("gold"), Some(5));
+        assert!(vars.get_bool("has_key"));
+        assert_eq!(vars.get_int("visits"), Some(1));
+        assert_eq!(
+            signals,
+            vec![DialogueSignal {
+                name: "give_item".to_string(),
+                args: vec!["key".into(), 5.into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_empty_action() {
+        let mut vars = vars();
+        assert!(execute_action("", &mut vars).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,712 @@
+//! Best-effort importer for compiled Ink stories (`.ink.json`)
+//!
+//! Walks the story's JSON runtime format and rebuilds it as a dialogue tree:
+//! - Each output line becomes a Text node (`Name: text` lines set the speaker)
+//! - Choice points become a Choice node, using the line before them as the prompt
+//! - Conditional diverts (`{ cond: ... }`) become Condition nodes
+//! - Variable assignments and external function calls become Action nodes
+//! - `END` becomes an End node
+//!
+//! Not supported: tunnels, threads, functions, lists, sequences/shuffles,
+//! visit counts (read as `0`), once-only choices and global variable
+//! declarations. Unsupported content is skipped rather than rejected.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::expr::quote_str;
+use crate::yarn::split_speaker;
+use crate::{DialogueChoice, DialogueNode, DialogueNodeType, DialogueTree};
+use uuid::Uuid;
+
+/// Error produced while importing an Ink story
+#[derive(Debug, Clone, PartialEq)]
+pub struct InkError {
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for InkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for InkError {}
+
+/// Choice point flags (see ink's `ChoicePoint`)
+const FLAG_CONDITION: u64 = 0x1;
+const FLAG_START_CONTENT: u64 = 0x2;
+const FLAG_CHOICE_ONLY_CONTENT: u64 = 0x4;
+const FLAG_INVISIBLE_DEFAULT: u64 = 0x8;
+
+/// Import a compiled Ink story (the `.ink.json` produced by inklecate or Inky)
+pub fn import_ink_json(source: &str, name: impl Into<String>) -> Result<DialogueTree, InkError> {
+    let json: Value = serde_json::from_str(source).map_err(|e| InkError {
+        message: format!("Invalid JSON: {}", e),
+    })?;
+    let root = json
+        .get("root")
+        .filter(|root| root.is_array())
+        .ok_or_else(|| InkError {
+            message: "Missing 'root' container (is this a compiled .ink.json?)".to_string(),
+        })?;
+
+    let mut importer = Importer {
+        root,
+        tree: DialogueTree::empty(name),
+        memo: HashMap::new(),
+        counter: 0,
+    };
+    let mut flow = Flow::default();
+    importer.divert(&mut flow, Vec::new(), 0);
+
+    let start = importer
+        .memo
+        .get(&position_key(&[], 0))
+        .cloned()
+        .flatten()
+        .ok_or_else(|| InkError {
+            message: "Story has no content".to_string(),
+        })?;
+    let mut tree = importer.tree;
+    tree.start_node = start;
+    tree.auto_layout();
+    Ok(tree)
+}
+
+type Path = Vec<String>;
+
+fn position_key(container: &[String], index: usize) -> String {
+    format!("{}#{}", container.join("."), index)
+}
+
+/// An output of a node that should link to the next node created
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Port {
+    Next,
+    False,
+    Choice(usize),
+}
+
+struct PendingChoice {
+    text: String,
+    condition: Option<String>,
+    target: Option<(Path, usize)>,
+    invisible_default: bool,
+}
+
+/// State of one path through the story
+#[derive(Default)]
+struct Flow {
+    /// Outputs waiting for the next node
+    pending: Vec<(String, Port)>,
+    /// Position keys that map to the next node created
+    waiters: Vec<String>,
+    text: String,
+    /// Evaluation stack of expressions, with whether each needs parentheses
+    stack: Vec<(String, bool)>,
+    string: Option<String>,
+    in_tag: bool,
+    choices: Vec<PendingChoice>,
+    /// Text node created last whose `next_node` is still pending (a choice prompt candidate)
+    last_text: Option<String>,
+    /// Action node created last whose `next_node` is still pending (merged with following actions)
+    last_action: Option<String>,
+}
+
+impl Flow {
+    fn continuing_from(id: &str, port: Port) -> Self {
+        Self {
+            pending: vec![(id.to_string(), port)],
+            ..Default::default()
+        }
+    }
+
+    fn pop(&mut self) -> String {
+        self.stack.pop().map(|(expr, _)| expr).unwrap_or_default()
+    }
+
+    /// Pop an operand, parenthesizing compound expressions
+    fn pop_operand(&mut self) -> String {
+        match self.stack.pop() {
+            Some((expr, true)) => format!("({})", expr),
+            Some((expr, false)) => expr,
+            None => "false".to_string(),
+        }
+    }
+
+    fn push(&mut self, expr: impl Into<String>) {
+        self.stack.push((expr.into(), false));
+    }
+}
+
+enum Step {
+    Continue,
+    Stop,
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+    tree: DialogueTree,
+    /// Position key -> first node created from there (`None` while still walking)
+    memo: HashMap<String, Option<String>>,
+    counter: usize,
+}
+
+impl<'a> Importer<'a> {
+    /// Get the container at a path
+    fn container(&self, path: &[String]) -> Option<&'a Vec<Value>> {
+        let mut current = self.root.as_array()?;
+        for comp in path {
+            current = match comp.parse::<usize>() {
+                Ok(index) => current.get(index)?.as_array()?,
+                Err(_) => named_child(current, comp)?.1.as_array()?,
+            };
+        }
+        Some(current)
+    }
+
+    /// Resolve a divert target to a container and index
+    ///
+    /// Relative targets (`.^.name`) are resolved from `origin`, the position of
+    /// the object holding the target.
+    fn resolve(&self, target: &str, origin: (&[String], usize)) -> Option<(Path, usize)> {
+        let comps: Vec<String> = match target.strip_prefix('.') {
+            Some(relative) => {
+                let mut comps: Path = origin.0.to_vec();
+                comps.push(origin.1.to_string());
+                for comp in relative.split('.') {
+                    if comp == "^" {
+                        comps.pop()?;
+                    } else {
+                        comps.push(comp.to_string());
+                    }
+                }
+                comps
+            }
+            None => target.split('.').map(str::to_string).collect(),
+        };
+
+        // Walk the path, using indices for named inline containers so keys stay canonical
+        let mut path = Path::new();
+        let mut current = self.root.as_array()?;
+        for (i, comp) in comps.iter().enumerate() {
+            if let Ok(index) = comp.parse::<usize>() {
+                match current.get(index)? {
+                    Value::Array(child) => {
+                        current = child;
+                        path.push(comp.clone());
+                    }
+                    _ if i + 1 == comps.len() => return Some((path, index)),
+                    _ => return None,
+                }
+            } else {
+                let (key, child) = named_child(current, comp)?;
+                current = child.as_array()?;
+                path.push(key);
+            }
+        }
+        Some((path, 0))
+    }
+
+    /// Concatenate the text of a container (used for choice start content)
+    fn collect_text(&self, path: &[String]) -> String {
+        let mut text = String::new();
+        if let Some(content) = self.container(path) {
+            for item in content {
+                match item {
+                    Value::String(s) if s.starts_with('^') => text.push_str(&s[1..]),
+                    Value::Object(o) if o.contains_key("->") => break,
+                    _ => {}
+                }
+            }
+        }
+        text
+    }
+
+    /// Create a node, linking everything waiting in the flow to it
+    fn emit(&mut self, flow: &mut Flow, mut node: DialogueNode) -> String {
+        self.counter += 1;
+        node.id = format!("ink_{}", self.counter);
+        let id = node.id.clone();
+        self.tree.nodes.insert(id.clone(), node);
+        self.link(flow, &id);
+        flow.last_text = None;
+        flow.last_action = None;
+        id
+    }
+
+    /// Link the flow's pending outputs and waiting positions to an existing node
+    fn link(&mut self, flow: &mut Flow, id: &str) {
+        for (source, port) in flow.pending.drain(..) {
+            if let Some(node) = self.tree.nodes.get_mut(&source) {
+                let link = match port {
+                    Port::Next => &mut node.next_node,
+                    Port::False => &mut node.false_node,
+                    Port::Choice(i) => match node.choices.get_mut(i) {
+                        Some(choice) => &mut choice.next_node,
+                        None => continue,
+                    },
+                };
+                *link = Some(id.to_string());
+            }
+        }
+        for key in flow.waiters.drain(..) {
+            self.memo.insert(key, Some(id.to_string()));
+        }
+    }
+
+    fn flush_text(&mut self, flow: &mut Flow) {
+        let text = std::mem::take(&mut flow.text);
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let (speaker, text) = split_speaker(text);
+        let id = self.emit(flow, DialogueNode::new_text(speaker, text));
+        flow.pending = vec![(id.clone(), Port::Next)];
+        flow.last_text = Some(id);
+    }
+
+    fn emit_action(&mut self, flow: &mut Flow, action: String) {
+        self.flush_text(flow);
+        if let Some(id) = &flow.last_action {
+            if let Some(node) = self.tree.nodes.get_mut(id) {
+                let merged = match node.action.take() {
+                    Some(existing) => format!("{}; {}", existing, action),
+                    None => action,
+                };
+                node.action = Some(merged);
+                return;
+            }
+        }
+        let id = self.emit(flow, DialogueNode::new_action(action));
+        flow.pending = vec![(id.clone(), Port::Next)];
+        flow.last_action = Some(id);
+    }
+
+    /// Continue the flow at a position, reusing nodes already built from there
+    fn divert(&mut self, flow: &mut Flow, container: Path, index: usize) {
+        self.flush_text(flow);
+        let key = position_key(&container, index);
+        match self.memo.get(&key) {
+            Some(Some(id)) => {
+                let id = id.clone();
+                self.link(flow, &id);
+            }
+            // Still being walked without producing a node: a loop with no content
+            Some(None) => {}
+            None => {
+                self.memo.insert(key.clone(), None);
+                flow.waiters.push(key);
+                self.walk_from(flow, container, index, false);
+            }
+        }
+    }
+
+    /// Walk from a position, falling out of inline containers into their parents
+    fn walk_from(&mut self, flow: &mut Flow, mut container: Path, mut index: usize, check: bool) {
+        let mut check_first = check;
+        loop {
+            if let Step::Stop = self.walk(flow, &container, index, check_first) {
+                return;
+            }
+            // Only indexed (inline) containers fall through to their parent
+            match container.last().and_then(|c| c.parse::<usize>().ok()) {
+                Some(child_index) => {
+                    container.pop();
+                    index = child_index + 1;
+                    check_first = true;
+                }
+                None => {
+                    self.present_choices(flow, false);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Walk a container's content from `start`
+    fn walk(&mut self, flow: &mut Flow, container: &Path, start: usize, check_first: bool) -> Step {
+        let Some(content) = self.container(container) else {
+            return Step::Stop;
+        };
+        // The last element is the container's terminator (null or named content)
+        let end = match content.last() {
+            Some(Value::Null | Value::Object(_)) => content.len() - 1,
+            _ => content.len(),
+        };
+
+        for (index, item) in content.iter().enumerate().take(end).skip(start) {
+            // Reached content another flow already converted: link to it
+            if index != start || check_first {
+                if let Some(entry) = self.memo.get(&position_key(container, index)) {
+                    if let Some(id) = entry.clone() {
+                        self.flush_text(flow);
+                        self.link(flow, &id);
+                    }
+                    return Step::Stop;
+                }
+            }
+
+            match item {
+                Value::String(s) => {
+                    if let Step::Stop = self.control(flow, s) {
+                        return Step::Stop;
+                    }
+                }
+                Value::Number(n) => {
+                    if let Some(i) = n.as_i64() {
+                        flow.push(i.to_string());
+                    } else if let Some(f) = n.as_f64() {
+                        flow.push(format!("{:?}", f));
+                    }
+                }
+                Value::Bool(b) => flow.push(b.to_string()),
+                Value::Array(_) => {
+                    let mut child = container.clone();
+                    child.push(index.to_string());
+                    if let Step::Stop = self.walk(flow, &child, 0, true) {
+                        return Step::Stop;
+                    }
+                }
+                Value::Object(object) => {
+                    if let Step::Stop = self.command(flow, object, (container, index)) {
+                        return Step::Stop;
+                    }
+                }
+                Value::Null => {}
+            }
+        }
+        Step::Continue
+    }
+
+    /// Handle a string entry: text or a control command
+    fn control(&mut self, flow: &mut Flow, s: &str) -> Step {
+        if let Some(text) = s.strip_prefix('^') {
+            if flow.in_tag {
+                return Step::Continue;
+            }
+            match &mut flow.string {
+                Some(buffer) => buffer.push_str(text),
+                None => flow.text.push_str(text),
+            }
+            return Step::Continue;
+        }
+
+        match s {
+            "\n" if flow.string.is_none() && !flow.in_tag => self.flush_text(flow),
+            "str" => flow.string = Some(String::new()),
+            "/str" => {
+                let text = flow.string.take().unwrap_or_default();
+                flow.push(quote_str(&text));
+            }
+            "#" => flow.in_tag = true,
+            "/#" => flow.in_tag = false,
+            "out" => {
+                let value = flow.pop();
+                match unquote(&value) {
+                    Some(text) => flow.text.push_str(&text),
+                    None => flow.text.push_str(&format!("{{{}}}", value)),
+                }
+            }
+            "pop" => {
+                flow.pop();
+            }
+            "du" => {
+                if let Some(top) = flow.stack.last().cloned() {
+                    flow.stack.push(top);
+                }
+            }
+            "done" => {
+                self.present_choices(flow, false);
+                return Step::Stop;
+            }
+            "end" => {
+                self.present_choices(flow, true);
+                return Step::Stop;
+            }
+            "->->" | "~ret" => {
+                self.flush_text(flow);
+                return Step::Stop;
+            }
+            "==" | "!=" | "<" | ">" | "<=" | ">=" | "+" | "-" | "*" | "/" | "%" | "&&" | "||" => {
+                let rhs = flow.pop_operand();
+                let lhs = flow.pop_operand();
+                flow.stack.push((format!("{} {} {}", lhs, s, rhs), true));
+            }
+            "!" => {
+                let operand = flow.pop_operand();
+                flow.push(format!("!{}", operand));
+            }
+            "_" => {
+                let operand = flow.pop_operand();
+                flow.push(format!("-{}", operand));
+            }
+            "MIN" | "MAX" | "POW" => {
+                flow.pop();
+                flow.pop();
+                flow.push("0");
+            }
+            // "ev", "/ev", glue, "nop", "void", ... don't affect the converted tree
+            _ => {}
+        }
+        Step::Continue
+    }
+
+    /// Handle an object entry: diverts, choices, variables and calls
+    fn command(
+        &mut self,
+        flow: &mut Flow,
+        object: &serde_json::Map<String, Value>,
+        origin: (&Path, usize),
+    ) -> Step {
+        let origin_ref = (origin.0.as_slice(), origin.1);
+
+        if let Some(target) = object.get("->").and_then(Value::as_str) {
+            if object.get("var").and_then(Value::as_bool) == Some(true) {
+                return Step::Stop;
+            }
+            // Choice start content: inline its text into the choice label
+            if let Some(buffer) = flow.string.as_mut() {
+                if let Some((path, _)) = self.resolve(target, origin_ref) {
+                    buffer.push_str(&self.collect_text(&path));
+                }
+                return Step::Continue;
+            }
+            // A choice body replaying its start content: the label isn't repeated as a line
+            if target.ends_with(".s") {
+                return Step::Continue;
+            }
+            let Some((container, index)) = self.resolve(target, origin_ref) else {
+                return Step::Stop;
+            };
+
+            if object.get("c").and_then(Value::as_bool) == Some(true) {
+                let condition = flow.pop();
+                self.flush_text(flow);
+                let id = self.emit(flow, DialogueNode::new_condition(condition));
+                let mut branch = Flow::continuing_from(&id, Port::Next);
+                self.divert(&mut branch, container, index);
+                flow.pending = vec![(id, Port::False)];
+                return Step::Continue;
+            }
+
+            self.divert(flow, container, index);
+            return Step::Stop;
+        }
+
+        if let Some(target) = object.get("*").and_then(Value::as_str) {
+            let flags = object.get("flg").and_then(Value::as_u64).unwrap_or(0);
+            let choice_only = if flags & FLAG_CHOICE_ONLY_CONTENT != 0 {
+                flow.pop()
+            } else {
+                String::new()
+            };
+            let start = if flags & FLAG_START_CONTENT != 0 {
+                flow.pop()
+            } else {
+                String::new()
+            };
+            let condition = (flags & FLAG_CONDITION != 0).then(|| flow.pop());
+            let text = format!(
+                "{}{}",
+                unquote(&start).unwrap_or(start.clone()),
+                unquote(&choice_only).unwrap_or(choice_only.clone())
+            );
+            flow.choices.push(PendingChoice {
+                text: text.trim().to_string(),
+                condition,
+                target: self.resolve(target, origin_ref),
+                invisible_default: flags & FLAG_INVISIBLE_DEFAULT != 0,
+            });
+            return Step::Continue;
+        }
+
+        if let Some(name) = object.get("VAR=").and_then(Value::as_str) {
+            let value = flow.pop();
+            self.emit_action(flow, format!("{} = {}", name, value));
+        } else if object.contains_key("temp=") {
+            flow.pop();
+        } else if let Some(name) = object.get("VAR?").and_then(Value::as_str) {
+            flow.push(name);
+        } else if object.contains_key("CNT?") || object.contains_key("^->") {
+            flow.push("0");
+        } else if let Some(name) = object.get("x()").and_then(Value::as_str) {
+            let count = object.get("exArgs").and_then(Value::as_u64).unwrap_or(0) as usize;
+            let split = flow.stack.len().saturating_sub(count);
+            let args: Vec<String> = flow.stack.drain(split..).map(|(arg, _)| arg).collect();
+            self.emit_action(flow, format!("{}({})", name, args.join(", ")));
+            flow.push("false");
+        } else if object.contains_key("f()") {
+            flow.push("false");
+        }
+        Step::Continue
+    }
+
+    /// Turn the flow's collected choices into a Choice node (or an End node)
+    fn present_choices(&mut self, flow: &mut Flow, end: bool) {
+        self.flush_text(flow);
+        let choices = std::mem::take(&mut flow.choices);
+
+        if choices.iter().all(|c| c.invisible_default) {
+            if let Some((container, index)) = choices.into_iter().find_map(|c| c.target) {
+                // Only a fallback choice: ink takes it automatically
+                self.divert(flow, container, index);
+            } else if end {
+                self.emit(flow, DialogueNode::new_end());
+            }
+            return;
+        }
+
+        let choices: Vec<PendingChoice> = choices
+            .into_iter()
+            .filter(|c| !c.invisible_default)
+            .collect();
+
+        // Use the line right before the choices as the prompt
+        let prompt = flow
+            .last_text
+            .clone()
+            .filter(|id| flow.pending == [(id.clone(), Port::Next)]);
+        let id = match prompt {
+            Some(id) => {
+                flow.pending.clear();
+                id
+            }
+            None => self.emit(flow, DialogueNode::new_choice("", "")),
+        };
+        flow.last_text = None;
+
+        if let Some(node) = self.tree.nodes.get_mut(&id) {
+            node.node_type = DialogueNodeType::Choice;
+            node.choices = choices
+                .iter()
+                .map(|c| DialogueChoice {
+                    id: Uuid::new_v4().to_string(),
+                    text: c.text.clone(),
+                    next_node: None,
+                    condition: c.condition.clone(),
+                })
+                .collect();
+        }
+        for (i, choice) in choices.into_iter().enumerate() {
+            if let Some((container, index)) = choice.target {
+                let mut branch = Flow::continuing_from(&id, Port::Choice(i));
+                self.divert(&mut branch, container, index);
+            }
+        }
+    }
+}
+
+/// Find a named child container: in the terminator dict, or an indexed child with `#n`
+fn named_child<'v>(content: &'v [Value], name: &str) -> Option<(String, &'v Value)> {
+    if let Some(child) = content
+        .last()
+        .and_then(Value::as_object)
+        .and_then(|named| named.get(name))
+    {
+        return Some((name.to_string(), child));
+    }
+    content.iter().enumerate().find_map(|(i, child)| {
+        let terminator = child.as_array()?.last()?.as_object()?;
+        (terminator.get("#n")?.as_str()? == name).then(|| (i.to_string(), child))
+    })
+}
+
+/// Get the text of a quoted string expression
+fn unquote(expr: &str) -> Option<String> {
+    let inner = expr.strip_prefix('"')?.strip_suffix('"')?;
+    Some(
+        inner
+            .replace("\\\"", "\"")
+            .replace("\\n", "\n")
+            .replace("\\\\", "\\"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DialogueVariables;
+
+    /// Compiled from:
+    ///
+    /// ```ink
+    /// VAR gold = 15
+    /// Merchant: Welcome!
+    /// * [Buy a sword]
+    ///     ~ gold = gold - 10
+    ///     Merchant: Enjoy.
+    /// * {gold > 100} [Buy the shop]
+    ///     Merchant: Deal.
+    /// - Merchant: Goodbye.
+    /// -> END
+    /// ```
+    const STORY: &str = r##"{"inkVersion":21,"root":[[
+        "^Merchant: Welcome!","\n",
+        "ev","str","^Buy a sword","/str","/ev",{"*":"0.c-0","flg":20},
+        "ev",{"VAR?":"gold"},100,">","/ev","ev","str","^Buy the shop","/str","/ev",{"*":"0.c-1","flg":21},
+        {"c-0":["\n","ev",{"VAR?":"gold"},10,"-","/ev",{"VAR=":"gold","re":true},"^Merchant: Enjoy.","\n",{"->":"0.g-0"},{"#f":5}],
+         "c-1":["\n","^Merchant: Deal.","\n",{"->":"0.g-0"},{"#f":5}],
+         "g-0":["^Merchant: Goodbye.","\n","end",null]}],
+        "done",{"global decl":["ev",15,{"VAR=":"gold"},"/ev","end",null]}],"listDefs":{}}"##;
+
+    #[test]
+    fn test_import_structure() {
+        let tree = import_ink_json(STORY, "Merchant").unwrap();
+        assert!(tree.validate().is_empty());
+
+        let choice = tree.get_start_node().unwrap();
+        assert_eq!(choice.node_type, DialogueNodeType::Choice);
+        assert_eq!(choice.speaker, "Merchant");
+        assert_eq!(choice.text, "Welcome!");
+        assert_eq!(choice.choices.len(), 2);
+        assert_eq!(choice.choices[0].text, "Buy a sword");
+        assert_eq!(choice.choices[1].condition.as_deref(), Some("gold > 100"));
+
+        let action = tree
+            .get_node(choice.choices[0].next_node.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(action.action.as_deref(), Some("gold = gold - 10"));
+
+        // Both branches rejoin at the same gather node
+        let enjoy = tree.get_node(action.next_node.as_ref().unwrap()).unwrap();
+        let deal = tree
+            .get_node(choice.choices[1].next_node.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(enjoy.text, "Enjoy.");
+        assert_eq!(deal.text, "Deal.");
+        assert_eq!(enjoy.next_node, deal.next_node);
+        let goodbye = tree.get_node(enjoy.next_node.as_ref().unwrap()).unwrap();
+        assert_eq!(goodbye.text, "Goodbye.");
+        let end = tree.get_node(goodbye.next_node.as_ref().unwrap()).unwrap();
+        assert_eq!(end.node_type, DialogueNodeType::End);
+    }
+
+    #[test]
+    fn test_import_runs() {
+        let tree = import_ink_json(STORY, "Merchant").unwrap();
+        let mut vars = DialogueVariables::new();
+        vars.set("gold", 15);
+        let mut signals = Vec::new();
+
+        let choice = tree
+            .resolve(Some(tree.start_node.as_str()), &mut vars, &mut signals)
+            .unwrap();
+        assert_eq!(tree.continue_from(&choice, 1, &vars), None);
+        let next = tree.continue_from(&choice, 0, &vars).unwrap();
+        tree.resolve(next, &mut vars, &mut signals).unwrap();
+        assert_eq!(vars.get_int("gold"), Some(5));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(import_ink_json("not json", "x").is_err());
+        assert!(import_ink_json(r#"{"inkVersion":21}"#, "x").is_err());
+        assert!(import_ink_json(r#"{"root":[["done",null],"done",null]}"#, "x").is_err());
+    }
+}
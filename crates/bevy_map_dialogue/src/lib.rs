@@ -4,8 +4,12 @@
 //! - Multiple node types (Text, Choice, Condition, Action, End)
 //! - Player choices with optional conditions
 //! - Speaker assignments
-//! - Conditional branching
-//! - Action triggers
+//! - Conditional branching and choice visibility driven by [`DialogueVariables`]
+//! - Actions that set variables or emit signals (see [`expr`] for the syntax)
+//! - An optional ready-made dialogue box ([`DialogueUiPlugin`])
+//! - Per-locale string tables for translations (see [`locale`])
+//! - Graph validation with per-node errors and warnings (see [`validate`])
+//! - Import/export of Yarn Spinner scripts ([`yarn`]) and import of compiled Ink stories ([`ink`])
 //!
 //! # Usage
 //!
@@ -30,11 +34,33 @@
 //! tree.add_node(choice_node);
 //! ```
 
+pub mod expr;
+pub mod ink;
+pub mod locale;
+pub mod ui;
+pub mod validate;
+pub mod yarn;
+
+pub use expr::{
+    evaluate_condition, execute_action, parse_action, parse_condition, DialogueSignal,
+    DialogueValue, DialogueVariables, ExprError,
+};
+pub use locale::{
+    CurrentLocale, LocaleError, Localizer, StringTable, StringTableCsvLoader,
+    StringTableFluentLoader,
+};
+pub use ui::{DialogueUiFocus, DialogueUiPlugin, DialogueUiStyle, HideDialogueUi};
+pub use validate::{DialogueIssue, DialogueSeverity};
+
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
 use uuid::Uuid;
 
+/// Maximum number of Condition/Action nodes followed in one step (guards against loops)
+const MAX_AUTO_STEPS: usize = 256;
+
 /// Type of dialogue node
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
 #[serde(rename_all = "lowercase")]
@@ -44,7 +70,7 @@ pub enum DialogueNodeType {
     Text,
     /// Player chooses from multiple options
     Choice,
-    /// Check a condition and branch
+    /// Check a condition and branch (`next_node` if true, `false_node` if false)
     Condition,
     /// Execute an action (give item, start quest, etc.)
     Action,
@@ -90,11 +116,16 @@ impl DialogueNodeType {
 /// A player choice option in a dialogue
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Reflect)]
 pub struct DialogueChoice {
+    /// Stable identifier used in translation keys (see [`locale`])
+    ///
+    /// Empty for choices saved before ids existed; those are keyed by index.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     /// Display text for this choice
     pub text: String,
     /// Node to go to when this choice is selected
     pub next_node: Option<String>,
-    /// Condition required to show this choice (script/expression string)
+    /// Condition required to show this choice (see [`expr`] for the syntax)
     pub condition: Option<String>,
 }
 
@@ -102,6 +133,7 @@ impl DialogueChoice {
     /// Create a new choice with text and target node
     pub fn new(text: impl Into<String>, next_node: impl Into<String>) -> Self {
         Self {
+            id: Uuid::new_v4().to_string(),
             text: text.into(),
             next_node: Some(next_node.into()),
             condition: None,
@@ -113,6 +145,17 @@ impl DialogueChoice {
         self.condition = Some(condition.into());
         self
     }
+
+    /// Check whether this choice should be shown
+    ///
+    /// Choices without a condition are always visible. A condition that fails
+    /// to evaluate hides the choice.
+    pub fn is_visible(&self, vars: &DialogueVariables) -> bool {
+        match self.condition.as_deref().map(str::trim) {
+            None | Some("") => true,
+            Some(condition) => evaluate_condition(condition, vars).unwrap_or(false),
+        }
+    }
 }
 
 fn default_position() -> (f32, f32) {
@@ -136,12 +179,18 @@ pub struct DialogueNode {
     /// Choices available to the player (for choice nodes)
     #[serde(default)]
     pub choices: Vec<DialogueChoice>,
-    /// Next node to go to (for linear flow)
+    /// Next node to go to (for linear flow, or the true branch of a condition node)
     pub next_node: Option<String>,
-    /// Condition to check before showing this node (script/expression string)
+    /// Node to go to when a condition node evaluates to false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub false_node: Option<String>,
+    /// Condition evaluated by condition nodes (see [`expr`] for the syntax)
     pub condition: Option<String>,
-    /// Action to execute when entering this node (script/expression string)
+    /// Action to execute when entering this node (see [`expr`] for the syntax)
     pub action: Option<String>,
+    /// Base string key for translations (defaults to `<tree id>.<node id>`, see [`locale`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loc_key: Option<String>,
     /// Position in the editor (x, y)
     #[serde(default = "default_position")]
     pub position: (f32, f32),
@@ -156,8 +205,10 @@ impl Default for DialogueNode {
             text: String::new(),
             choices: Vec::new(),
             next_node: None,
+            false_node: None,
             condition: None,
             action: None,
+            loc_key: None,
             position: (0.0, 0.0),
         }
     }
@@ -215,6 +266,12 @@ impl DialogueNode {
         self
     }
 
+    /// Set the node to go to when a condition node evaluates to false
+    pub fn with_false(mut self, false_node: impl Into<String>) -> Self {
+        self.false_node = Some(false_node.into());
+        self
+    }
+
     /// Add a choice
     pub fn with_choice(mut self, choice: DialogueChoice) -> Self {
         self.choices.push(choice);
@@ -226,6 +283,26 @@ impl DialogueNode {
         self.position = (x, y);
         self
     }
+
+    /// Get the IDs of all nodes this node links to (next, false branch, then choices)
+    pub fn targets(&self) -> impl Iterator<Item = &str> {
+        self.next_node
+            .iter()
+            .chain(self.false_node.iter())
+            .chain(self.choices.iter().filter_map(|c| c.next_node.as_ref()))
+            .map(String::as_str)
+    }
+
+    /// Get the choices whose conditions pass, with their index in `choices`
+    pub fn visible_choices<'a>(
+        &'a self,
+        vars: &'a DialogueVariables,
+    ) -> impl Iterator<Item = (usize, &'a DialogueChoice)> + 'a {
+        self.choices
+            .iter()
+            .enumerate()
+            .filter(move |(_, choice)| choice.is_visible(vars))
+    }
 }
 
 fn default_dialogue_id() -> String {
@@ -264,8 +341,10 @@ impl DialogueTree {
                 text: "Hello!".to_string(),
                 choices: Vec::new(),
                 next_node: None,
+                false_node: None,
                 condition: None,
                 action: None,
+                loc_key: None,
                 position: (100.0, 100.0),
             },
         );
@@ -302,6 +381,9 @@ impl DialogueTree {
             if node.next_node.as_deref() == Some(id) {
                 node.next_node = None;
             }
+            if node.false_node.as_deref() == Some(id) {
+                node.false_node = None;
+            }
             node.choices.retain(|c| c.next_node.as_deref() != Some(id));
         }
         if self.start_node == id {
@@ -334,41 +416,158 @@ impl DialogueTree {
         self.nodes.keys().map(|s| s.as_str())
     }
 
-    /// Check if the tree is valid (has start node and all references are valid)
-    pub fn validate(&self) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
+    /// Give choices without an id their current index as id
+    ///
+    /// Translation keys of existing choices stay the same, but no longer
+    /// change when choices are reordered. Returns the number of choices updated.
+    pub fn assign_choice_ids(&mut self) -> usize {
+        let mut assigned = 0;
+        for node in self.nodes.values_mut() {
+            for (index, choice) in node.choices.iter_mut().enumerate() {
+                if choice.id.is_empty() {
+                    choice.id = index.to_string();
+                    assigned += 1;
+                }
+            }
+        }
+        assigned
+    }
+
+    /// Arrange nodes left-to-right by their distance from the start node
+    ///
+    /// Used after importing trees that have no editor layout. Nodes that can't
+    /// be reached from the start node are placed in a final column.
+    pub fn auto_layout(&mut self) {
+        const ORIGIN: (f32, f32) = (100.0, 100.0);
+        const COLUMN_SPACING: f32 = 280.0;
+        const ROW_SPACING: f32 = 160.0;
+
+        let mut columns: Vec<Vec<String>> = Vec::new();
+        let mut placed: HashSet<&str> = HashSet::new();
+        let mut frontier: Vec<&str> = Vec::new();
+        if self.nodes.contains_key(&self.start_node) {
+            placed.insert(&self.start_node);
+            frontier.push(&self.start_node);
+        }
+        while !frontier.is_empty() {
+            let mut next_frontier = Vec::new();
+            for id in &frontier {
+                for target in self.nodes[*id].targets() {
+                    if self.nodes.contains_key(target) && placed.insert(target) {
+                        next_frontier.push(target);
+                    }
+                }
+            }
+            columns.push(frontier.iter().map(|id| id.to_string()).collect());
+            frontier = next_frontier;
+        }
 
-        if self.start_node.is_empty() {
-            errors.push("No start node defined".to_string());
-        } else if !self.nodes.contains_key(&self.start_node) {
-            errors.push(format!("Start node '{}' not found", self.start_node));
+        let mut unreachable: Vec<String> = self
+            .nodes
+            .keys()
+            .filter(|id| !placed.contains(id.as_str()))
+            .cloned()
+            .collect();
+        unreachable.sort();
+        if !unreachable.is_empty() {
+            columns.push(unreachable);
         }
 
-        for (id, node) in &self.nodes {
-            if let Some(next) = &node.next_node {
-                if !self.nodes.contains_key(next) {
-                    errors.push(format!(
-                        "Node '{}' references non-existent node '{}'",
-                        id, next
-                    ));
+        for (col, ids) in columns.iter().enumerate() {
+            for (row, id) in ids.iter().enumerate() {
+                if let Some(node) = self.nodes.get_mut(id) {
+                    node.position = (
+                        ORIGIN.0 + col as f32 * COLUMN_SPACING,
+                        ORIGIN.1 + row as f32 * ROW_SPACING,
+                    );
                 }
             }
-            for choice in &node.choices {
-                if let Some(next) = &choice.next_node {
-                    if !self.nodes.contains_key(next) {
-                        errors.push(format!(
-                            "Choice '{}' in node '{}' references non-existent node '{}'",
-                            choice.text, id, next
-                        ));
-                    }
+        }
+    }
+
+    /// Enter a node, running Action nodes and branching on Condition nodes
+    ///
+    /// Follows links until reaching a node that waits for the player (Text or
+    /// Choice), returning its ID. Returns `None` when the dialogue ends: an End
+    /// node, a missing link, or too many automatic steps (a loop of
+    /// Condition/Action nodes). Signals emitted by actions are appended to `signals`.
+    pub fn resolve(
+        &self,
+        node_id: Option<&str>,
+        vars: &mut DialogueVariables,
+        signals: &mut Vec<DialogueSignal>,
+    ) -> Option<String> {
+        let mut current = node_id?;
+        for _ in 0..MAX_AUTO_STEPS {
+            let Some(node) = self.get_node(current) else {
+                warn!("Dialogue '{}': node '{}' not found", self.name, current);
+                return None;
+            };
+
+            if let Some(action) = node.action.as_deref().filter(|a| !a.trim().is_empty()) {
+                match execute_action(action, vars) {
+                    Ok(emitted) => signals.extend(emitted),
+                    Err(err) => warn!("Dialogue node '{}' action '{}': {}", node.id, action, err),
                 }
             }
+
+            let next = match node.node_type {
+                DialogueNodeType::Text | DialogueNodeType::Choice => return Some(node.id.clone()),
+                DialogueNodeType::End => return None,
+                DialogueNodeType::Action => node.next_node.as_deref(),
+                DialogueNodeType::Condition => {
+                    let condition = node.condition.as_deref().unwrap_or("");
+                    let result = evaluate_condition(condition, vars).unwrap_or_else(|err| {
+                        warn!(
+                            "Dialogue node '{}' condition '{}': {}",
+                            node.id, condition, err
+                        );
+                        false
+                    });
+                    if result {
+                        node.next_node.as_deref()
+                    } else {
+                        node.false_node.as_deref()
+                    }
+                }
+            };
+            current = next?;
         }
 
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+        warn!(
+            "Dialogue '{}': stopped after {} condition/action steps without reaching a text or choice node",
+            self.name, MAX_AUTO_STEPS
+        );
+        None
+    }
+
+    /// Get the link followed when the player continues from a node
+    ///
+    /// For Text nodes any `choice_index` continues to `next_node`. For Choice
+    /// nodes the choice must exist and be visible; if every choice is hidden,
+    /// any `choice_index` falls through to the node's `next_node` (ending the
+    /// dialogue if it has none). Returns `None` if the input doesn't apply,
+    /// `Some(None)` if the dialogue should end.
+    pub fn continue_from(
+        &self,
+        node_id: &str,
+        choice_index: usize,
+        vars: &DialogueVariables,
+    ) -> Option<Option<&str>> {
+        let node = self.get_node(node_id)?;
+        match node.node_type {
+            DialogueNodeType::Text => Some(node.next_node.as_deref()),
+            DialogueNodeType::Choice => {
+                if node.visible_choices(vars).next().is_none() {
+                    return Some(node.next_node.as_deref());
+                }
+                let choice = node.choices.get(choice_index)?;
+                if !choice.is_visible(vars) {
+                    return None;
+                }
+                Some(choice.next_node.as_deref())
+            }
+            _ => Some(None),
         }
     }
 }
@@ -385,6 +584,10 @@ impl DialogueHandle {
 }
 
 /// Message to start a dialogue (sent via MessageWriter, read via MessageReader)
+///
+/// The dialogue runs in a [`DialogueRunner`] on `speaker_entity`, which is
+/// inserted if missing. Starting a dialogue on an entity that is already
+/// running one replaces it.
 #[derive(Message, Debug, Clone)]
 pub struct StartDialogueEvent {
     /// The entity that owns the dialogue (e.g., NPC)
@@ -396,7 +599,10 @@ pub struct StartDialogueEvent {
 /// Message sent when the player makes a choice
 #[derive(Message, Debug, Clone)]
 pub struct DialogueChoiceEvent {
-    /// Index of the choice selected
+    /// The entity whose [`DialogueRunner`] should advance
+    pub speaker_entity: Entity,
+    /// Index into the current node's `choices` (ignored for text nodes and
+    /// choice nodes whose choices are all hidden)
     pub choice_index: usize,
 }
 
@@ -407,24 +613,180 @@ pub struct DialogueEndEvent {
     pub speaker_entity: Entity,
 }
 
-/// Current state of an active dialogue
-#[derive(Resource, Debug, Clone, Default)]
+/// Message sent when a dialogue action emits a signal, e.g. `give_item("sword", 1)`
+#[derive(Message, Debug, Clone)]
+pub struct DialogueSignalEvent {
+    /// The entity that owns the dialogue
+    pub speaker_entity: Entity,
+    /// Signal name
+    pub name: String,
+    /// Evaluated arguments
+    pub args: Vec<DialogueValue>,
+}
+
+// ============================================================================
+// Custom Signal Type System
+// ============================================================================
+
+/// Trait for types that can be emitted by dialogue actions.
+///
+/// Registered types are triggered as entity events on the speaker entity when
+/// an action calls a signal with a matching name.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use bevy::prelude::*;
+/// use bevy_map_dialogue::{DialogueSignalType, DialogueValue};
+///
+/// #[derive(EntityEvent, Clone)]
+/// pub struct GiveItem {
+///     pub entity: Entity,
+///     pub item: String,
+///     pub count: i64,
+/// }
+///
+/// impl DialogueSignalType for GiveItem {
+///     fn signal_name() -> &'static str { "give_item" }
+///
+///     fn from_args(entity: Entity, args: &[DialogueValue]) -> Option<Self> {
+///         Some(Self {
+///             entity,
+///             item: args.first()?.as_str()?.to_string(),
+///             count: args.get(1).and_then(|v| v.as_int()).unwrap_or(1),
+///         })
+///     }
+/// }
+/// ```
+pub trait DialogueSignalType: EntityEvent + Clone + Send + Sync + 'static
+where
+    for<'a> <Self as Event>::Trigger<'a>: Default,
+{
+    /// The signal name as used in dialogue actions
+    fn signal_name() -> &'static str;
+
+    /// Create an instance targeting `entity` from the call arguments.
+    /// Return None if required arguments are missing or invalid.
+    fn from_args(entity: Entity, args: &[DialogueValue]) -> Option<Self>;
+}
+
+// Internal trait for type-erased signal dispatch
+trait SignalDispatcher: Send + Sync {
+    fn dispatch(&self, commands: &mut Commands, entity: Entity, args: &[DialogueValue]);
+}
+
+struct TypedSignalDispatcher<T: DialogueSignalType>
+where
+    for<'a> <T as Event>::Trigger<'a>: Default,
+{
+    _marker: PhantomData<T>,
+}
+
+impl<T: DialogueSignalType> SignalDispatcher for TypedSignalDispatcher<T>
+where
+    for<'a> <T as Event>::Trigger<'a>: Default,
+{
+    fn dispatch(&self, commands: &mut Commands, entity: Entity, args: &[DialogueValue]) {
+        match T::from_args(entity, args) {
+            Some(event) => commands.trigger(event),
+            None => warn!(
+                "Dialogue signal '{}' called with invalid arguments: {:?}",
+                T::signal_name(),
+                args
+            ),
+        }
+    }
+}
+
+/// Registry for custom dialogue signal types.
+#[derive(Resource, Default)]
+pub struct DialogueSignalRegistry {
+    dispatchers: HashMap<String, Box<dyn SignalDispatcher>>,
+}
+
+impl DialogueSignalRegistry {
+    /// Register a custom signal type
+    pub fn register<T: DialogueSignalType>(&mut self)
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default,
+    {
+        self.dispatchers.insert(
+            T::signal_name().to_string(),
+            Box::new(TypedSignalDispatcher::<T> {
+                _marker: PhantomData,
+            }),
+        );
+    }
+
+    /// Check if a signal name is registered
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.dispatchers.contains_key(name)
+    }
+
+    /// Dispatch a signal to its registered type, if any
+    pub fn dispatch(&self, commands: &mut Commands, entity: Entity, signal: &DialogueSignal) {
+        if let Some(dispatcher) = self.dispatchers.get(&signal.name) {
+            dispatcher.dispatch(commands, entity, &signal.args);
+        }
+    }
+}
+
+/// Extension trait for registering dialogue signal types.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// App::new()
+///     .add_plugins(DialoguePlugin)
+///     .register_dialogue_signal::<GiveItem>()
+///     .run();
+/// ```
+pub trait DialogueSignalExt {
+    /// Register a custom signal type for type-safe event handling
+    fn register_dialogue_signal<T: DialogueSignalType>(&mut self) -> &mut Self
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default;
+}
+
+impl DialogueSignalExt for App {
+    fn register_dialogue_signal<T: DialogueSignalType>(&mut self) -> &mut Self
+    where
+        for<'a> <T as Event>::Trigger<'a>: Default,
+    {
+        // Ensure registry exists
+        if !self.world().contains_resource::<DialogueSignalRegistry>() {
+            self.insert_resource(DialogueSignalRegistry::default());
+        }
+
+        self.world_mut()
+            .resource_mut::<DialogueSignalRegistry>()
+            .register::<T>();
+
+        self
+    }
+}
+
+/// State of a dialogue running on an entity
+///
+/// Each speaker (or a dedicated runner entity) carries its own runner, so
+/// several dialogues can run at once, e.g. a conversation with the player
+/// while nearby NPCs play ambient barks. Inserted by [`StartDialogueEvent`];
+/// it stays on the entity, inactive, after the dialogue ends.
+#[derive(Component, Debug, Clone, Default, Reflect)]
 pub struct DialogueRunner {
     /// Whether a dialogue is currently active
     pub active: bool,
-    /// The entity that started this dialogue
-    pub speaker_entity: Option<Entity>,
     /// Handle to the current dialogue tree
+    #[reflect(ignore)]
     pub dialogue_handle: Option<Handle<DialogueTree>>,
-    /// Current node ID
+    /// Current node ID (always a Text or Choice node while active)
     pub current_node_id: Option<String>,
 }
 
 impl DialogueRunner {
     /// Start a new dialogue
-    pub fn start(&mut self, speaker: Entity, dialogue: Handle<DialogueTree>, start_node: String) {
+    pub fn start(&mut self, dialogue: Handle<DialogueTree>, start_node: String) {
         self.active = true;
-        self.speaker_entity = Some(speaker);
         self.dialogue_handle = Some(dialogue);
         self.current_node_id = Some(start_node);
     }
@@ -432,7 +794,6 @@ impl DialogueRunner {
     /// End the current dialogue
     pub fn end(&mut self) {
         self.active = false;
-        self.speaker_entity = None;
         self.dialogue_handle = None;
         self.current_node_id = None;
     }
@@ -446,6 +807,18 @@ impl DialogueRunner {
     pub fn is_active(&self) -> bool {
         self.active
     }
+
+    /// Get the current node, if the dialogue is active and its tree is loaded
+    pub fn current_node<'a>(
+        &self,
+        dialogues: &'a Assets<DialogueTree>,
+    ) -> Option<&'a DialogueNode> {
+        if !self.active {
+            return None;
+        }
+        let tree = dialogues.get(self.dialogue_handle.as_ref()?)?;
+        tree.get_node(self.current_node_id.as_deref()?)
+    }
 }
 
 /// Plugin for dialogue support
@@ -454,46 +827,125 @@ pub struct DialoguePlugin;
 impl Plugin for DialoguePlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<DialogueTree>()
+            .init_asset::<StringTable>()
+            .init_asset_loader::<StringTableCsvLoader>()
+            .init_asset_loader::<StringTableFluentLoader>()
             .register_type::<DialogueNodeType>()
             .register_type::<DialogueChoice>()
             .register_type::<DialogueNode>()
             .register_type::<DialogueTree>()
             .register_type::<DialogueHandle>()
-            .init_resource::<DialogueRunner>()
+            .register_type::<DialogueRunner>()
+            .register_type::<CurrentLocale>()
+            .init_resource::<DialogueVariables>()
+            .init_resource::<CurrentLocale>()
+            .init_resource::<DialogueSignalRegistry>()
             .init_resource::<Messages<StartDialogueEvent>>()
             .init_resource::<Messages<DialogueChoiceEvent>>()
             .init_resource::<Messages<DialogueEndEvent>>()
+            .init_resource::<Messages<DialogueSignalEvent>>()
             .add_systems(Update, (handle_start_dialogue, handle_dialogue_choice));
     }
 }
 
+/// Move the runner into `node_id`, resolving Condition/Action nodes and
+/// ending the dialogue if no Text or Choice node is reached
+fn enter_node(
+    commands: &mut Commands,
+    speaker: Entity,
+    runner: &mut DialogueRunner,
+    tree: &DialogueTree,
+    node_id: Option<&str>,
+    vars: &mut DialogueVariables,
+    registry: &DialogueSignalRegistry,
+    signal_events: &mut MessageWriter<DialogueSignalEvent>,
+    end_events: &mut MessageWriter<DialogueEndEvent>,
+) {
+    let mut signals = Vec::new();
+    let next = tree.resolve(node_id, vars, &mut signals);
+
+    for signal in signals {
+        registry.dispatch(commands, speaker, &signal);
+        signal_events.write(DialogueSignalEvent {
+            speaker_entity: speaker,
+            name: signal.name,
+            args: signal.args,
+        });
+    }
+
+    match next {
+        Some(id) => runner.advance_to(id),
+        None => {
+            runner.end();
+            end_events.write(DialogueEndEvent {
+                speaker_entity: speaker,
+            });
+        }
+    }
+}
+
 /// System to handle starting dialogues
 fn handle_start_dialogue(
+    mut commands: Commands,
     mut events: MessageReader<StartDialogueEvent>,
-    mut runner: ResMut<DialogueRunner>,
+    mut signal_events: MessageWriter<DialogueSignalEvent>,
+    mut end_events: MessageWriter<DialogueEndEvent>,
+    mut runners: Query<&mut DialogueRunner>,
+    mut vars: ResMut<DialogueVariables>,
+    registry: Res<DialogueSignalRegistry>,
     dialogues: Res<Assets<DialogueTree>>,
 ) {
     for event in events.read() {
-        if let Some(tree) = dialogues.get(&event.dialogue) {
-            if !tree.start_node.is_empty() {
-                runner.start(
-                    event.speaker_entity,
-                    event.dialogue.clone(),
-                    tree.start_node.clone(),
-                );
-            }
+        let Some(tree) = dialogues.get(&event.dialogue) else {
+            continue;
+        };
+        if tree.start_node.is_empty() {
+            continue;
+        }
+
+        // Reuse the speaker's runner, or create one to insert afterwards
+        let mut new_runner = None;
+        let runner = match runners.get_mut(event.speaker_entity) {
+            Ok(runner) => runner.into_inner(),
+            Err(_) => new_runner.insert(DialogueRunner::default()),
+        };
+
+        runner.start(event.dialogue.clone(), tree.start_node.clone());
+        enter_node(
+            &mut commands,
+            event.speaker_entity,
+            runner,
+            tree,
+            Some(&tree.start_node),
+            &mut vars,
+            &registry,
+            &mut signal_events,
+            &mut end_events,
+        );
+
+        if let Some(runner) = new_runner {
+            commands.entity(event.speaker_entity).try_insert(runner);
         }
     }
 }
 
 /// System to handle dialogue choices
+///
+/// `choice_index` indexes the current node's `choices`; hidden choices are ignored.
 fn handle_dialogue_choice(
+    mut commands: Commands,
     mut choice_events: MessageReader<DialogueChoiceEvent>,
+    mut signal_events: MessageWriter<DialogueSignalEvent>,
     mut end_events: MessageWriter<DialogueEndEvent>,
-    mut runner: ResMut<DialogueRunner>,
+    mut runners: Query<&mut DialogueRunner>,
+    mut vars: ResMut<DialogueVariables>,
+    registry: Res<DialogueSignalRegistry>,
     dialogues: Res<Assets<DialogueTree>>,
 ) {
     for event in choice_events.read() {
+        let Ok(mut runner) = runners.get_mut(event.speaker_entity) else {
+            continue;
+        };
         if !runner.active {
             continue;
         }
@@ -507,57 +959,51 @@ fn handle_dialogue_choice(
         let Some(current_id) = &runner.current_node_id else {
             continue;
         };
-        let Some(node) = tree.get_node(current_id) else {
+        let Some(next) = tree.continue_from(current_id, event.choice_index, &vars) else {
             continue;
         };
 
-        // Handle based on node type
-        match node.node_type {
-            DialogueNodeType::Text => {
-                // Advance to next node or end
-                if let Some(next) = &node.next_node {
-                    runner.advance_to(next.clone());
-                } else {
-                    let speaker = runner.speaker_entity;
-                    runner.end();
-                    if let Some(entity) = speaker {
-                        end_events.write(DialogueEndEvent {
-                            speaker_entity: entity,
-                        });
-                    }
-                }
-            }
-            DialogueNodeType::Choice => {
-                // Select the choice and advance
-                if let Some(choice) = node.choices.get(event.choice_index) {
-                    if let Some(next) = &choice.next_node {
-                        runner.advance_to(next.clone());
-                    } else {
-                        let speaker = runner.speaker_entity;
-                        runner.end();
-                        if let Some(entity) = speaker {
-                            end_events.write(DialogueEndEvent {
-                                speaker_entity: entity,
-                            });
-                        }
-                    }
-                }
-            }
-            DialogueNodeType::End => {
-                let speaker = runner.speaker_entity;
-                runner.end();
-                if let Some(entity) = speaker {
-                    end_events.write(DialogueEndEvent {
-                        speaker_entity: entity,
-                    });
-                }
-            }
-            _ => {
-                // For condition/action nodes, advance to next
-                if let Some(next) = &node.next_node {
-                    runner.advance_to(next.clone());
-                }
-            }
-        }
+        enter_node(
+            &mut commands,
+            event.speaker_entity,
+            &mut runner,
+            tree,
+            next,
+            &mut vars,
+            &registry,
+            &mut signal_events,
+            &mut end_events,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continue_from_hidden_choices() {
+        let mut tree = DialogueTree::empty("hidden");
+        let mut ask = DialogueNode::new_choice("A", "?").with_next("fallback");
+        ask.id = "ask".to_string();
+        ask.choices = vec![
+            DialogueChoice::new("Rich", "rich").with_condition("gold > 100"),
+            DialogueChoice::new("Noble", "noble").with_condition("title == \"lord\""),
+        ];
+        tree.add_node(ask);
+
+        let mut vars = DialogueVariables::new();
+        // All choices hidden: continue to the node's own link
+        assert_eq!(tree.continue_from("ask", 0, &vars), Some(Some("fallback")));
+
+        vars.set("gold", 500);
+        assert_eq!(tree.continue_from("ask", 0, &vars), Some(Some("rich")));
+        // A hidden choice can't be picked while another is visible
+        assert_eq!(tree.continue_from("ask", 1, &vars), None);
+
+        // Without a link the dialogue ends
+        tree.get_node_mut("ask").unwrap().next_node = None;
+        vars.set("gold", 0);
+        assert_eq!(tree.continue_from("ask", 0, &vars), Some(None));
     }
 }
@@ -0,0 +1,687 @@
+//! Localization tables for dialogue and string properties
+//!
+//! Dialogue trees and schema properties keep their source-language text inline.
+//! Translations live in per-locale [`StringTable`] assets keyed by string keys:
+//!
+//! | Text                       | Key                                    |
+//! |----------------------------|----------------------------------------|
+//! | Node text                  | `<node key>.text`                      |
+//! | Choice text                | `<node key>.choice.<choice id>`        |
+//! | Speaker name               | `speaker.<speaker>`                    |
+//! | Localized string property  | `<type name>.<instance id>.<property>` |
+//!
+//! A node's key is its `loc_key` if set, otherwise `<tree id>.<node id>`. Choices
+//! saved without an id use their index in place of the id.
+//!
+//! Two formats are supported:
+//! - `*.strings.csv`: a `key` column, an optional `context` column, and one column
+//!   per locale (e.g. `key,context,en,fr,de`)
+//! - `*.ftl`: a subset of Fluent (`key = text`, indented continuation lines and
+//!   `.attribute = text` lines, which become `key.attribute`). The locale is the
+//!   last dotted segment of the file stem (`dialogue.fr.ftl` -> `fr`).
+//!
+//! At runtime, set [`CurrentLocale`] and read text through [`Localizer`]. Missing
+//! translations fall back to the inline text.
+//!
+//! ```rust,ignore
+//! #[derive(Resource)]
+//! struct Translations(Handle<StringTable>);
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     // Keep the handle alive so the table stays loaded
+//!     commands.insert_resource(Translations(asset_server.load("lang/game.strings.csv")));
+//!     commands.insert_resource(CurrentLocale::new("fr"));
+//! }
+//! ```
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{DialogueNode, DialogueTree};
+
+/// Error produced while parsing a string table
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleError {
+    /// Human-readable description of the problem
+    pub message: String,
+    /// 1-based line the error was found on, if known
+    pub line: Option<usize>,
+}
+
+impl LocaleError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+        }
+    }
+
+    fn at(message: impl Into<String>, line: usize) -> Self {
+        Self {
+            message: message.into(),
+            line: Some(line),
+        }
+    }
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} (line {})", self.message, line),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for LocaleError {}
+
+/// Translated strings for one or more locales
+///
+/// Keys keep their insertion order so exported files stay stable and readable.
+#[derive(Debug, Clone, Default, Asset, TypePath)]
+pub struct StringTable {
+    /// Keys in insertion order
+    keys: Vec<String>,
+    /// Same keys, for fast membership checks
+    key_set: HashSet<String>,
+    /// Locale -> key -> text
+    strings: HashMap<String, HashMap<String, String>>,
+    /// Translator notes per key (the CSV `context` column)
+    contexts: HashMap<String, String>,
+    /// Locales in the order they were added
+    locales: Vec<String>,
+}
+
+impl StringTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the text for a key in a locale
+    pub fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        self.strings.get(locale)?.get(key).map(String::as_str)
+    }
+
+    /// Set the text for a key in a locale
+    pub fn insert(
+        &mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        text: impl Into<String>,
+    ) {
+        let locale = locale.into();
+        let key = key.into();
+        self.add_locale(locale.clone());
+        self.add_key(key.clone());
+        self.strings
+            .entry(locale)
+            .or_default()
+            .insert(key, text.into());
+    }
+
+    /// Register a locale without any strings (so it gets a CSV column)
+    pub fn add_locale(&mut self, locale: impl Into<String>) {
+        let locale = locale.into();
+        if !self.locales.contains(&locale) {
+            self.locales.push(locale);
+        }
+    }
+
+    fn add_key(&mut self, key: String) -> bool {
+        if self.key_set.insert(key.clone()) {
+            self.keys.push(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Get the translator note for a key
+    pub fn context(&self, key: &str) -> Option<&str> {
+        self.contexts.get(key).map(String::as_str)
+    }
+
+    /// Set the translator note for a key
+    pub fn set_context(&mut self, key: impl Into<String>, context: impl Into<String>) {
+        let key = key.into();
+        self.add_key(key.clone());
+        self.contexts.insert(key, context.into());
+    }
+
+    /// All keys, in insertion order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.keys.iter().map(String::as_str)
+    }
+
+    /// All locales, in the order they were added
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.iter().map(String::as_str)
+    }
+
+    /// Check if the table has no keys
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Merge another table into this one, overwriting existing strings
+    pub fn merge(&mut self, other: &StringTable) {
+        for locale in &other.locales {
+            self.add_locale(locale.clone());
+        }
+        for key in &other.keys {
+            if let Some(context) = other.contexts.get(key) {
+                self.set_context(key.clone(), context.clone());
+            }
+            for locale in &other.locales {
+                if let Some(text) = other.get(locale, key) {
+                    self.insert(locale.clone(), key.clone(), text);
+                }
+            }
+        }
+    }
+
+    /// Parse a CSV table with a `key` column, an optional `context` column and
+    /// one column per locale
+    ///
+    /// Empty cells are treated as missing translations.
+    pub fn from_csv(source: &str) -> Result<Self, LocaleError> {
+        let mut rows = parse_csv(source)?.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Ok(Self::default());
+        };
+
+        let mut key_col = None;
+        let mut context_col = None;
+        let mut locale_cols = Vec::new();
+        for (col, name) in header.iter().enumerate() {
+            let name = name.trim();
+            match name.to_ascii_lowercase().as_str() {
+                "key" | "id" => key_col = Some(col),
+                "context" | "comment" | "notes" => context_col = Some(col),
+                "" => {}
+                _ => locale_cols.push((col, name.to_string())),
+            }
+        }
+        let key_col = key_col.ok_or_else(|| LocaleError::at("Missing 'key' column", 1))?;
+
+        let mut table = Self::default();
+        for (_, locale) in &locale_cols {
+            table.add_locale(locale.clone());
+        }
+        for (line, row) in rows {
+            let Some(key) = row.get(key_col).map(|k| k.trim()) else {
+                continue;
+            };
+            if key.is_empty() {
+                continue;
+            }
+            if !table.add_key(key.to_string()) {
+                return Err(LocaleError::at(format!("Duplicate key '{}'", key), line));
+            }
+            if let Some(context) = context_col.and_then(|col| row.get(col)) {
+                if !context.is_empty() {
+                    table.contexts.insert(key.to_string(), context.clone());
+                }
+            }
+            for (col, locale) in &locale_cols {
+                if let Some(text) = row.get(*col).filter(|text| !text.is_empty()) {
+                    table
+                        .strings
+                        .entry(locale.clone())
+                        .or_default()
+                        .insert(key.to_string(), text.clone());
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Write the table as CSV (`key,context,<locales...>`)
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let mut header = vec!["key", "context"];
+        header.extend(self.locales.iter().map(String::as_str));
+        write_csv_row(&mut out, &header);
+
+        for key in &self.keys {
+            let mut row = vec![key.as_str(), self.context(key).unwrap_or("")];
+            row.extend(self.locales.iter().map(|l| self.get(l, key).unwrap_or("")));
+            write_csv_row(&mut out, &row);
+        }
+        out
+    }
+
+    /// Parse a Fluent (`.ftl`) file for a single locale
+    ///
+    /// Supports plain messages and terms, multiline values and attributes.
+    /// Placeables (`{ $name }`) are kept verbatim; selectors are not evaluated.
+    pub fn from_fluent(locale: impl Into<String>, source: &str) -> Result<Self, LocaleError> {
+        let locale = locale.into();
+        let mut table = Self::default();
+        table.add_locale(locale.clone());
+
+        // Key of the message or attribute currently collecting continuation lines
+        let mut message: Option<String> = None;
+        let mut current: Option<(String, String)> = None;
+
+        let flush = |table: &mut StringTable, current: &mut Option<(String, String)>| {
+            if let Some((key, text)) = current.take() {
+                let text = text.trim_end().to_string();
+                if !text.is_empty() {
+                    table.insert(locale.clone(), key, text);
+                }
+            }
+        };
+
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            if raw.trim().is_empty() {
+                continue;
+            }
+
+            let indented = raw.starts_with(' ') || raw.starts_with('\t');
+            if !indented {
+                flush(&mut table, &mut current);
+                message = None;
+                if raw.starts_with('#') {
+                    continue;
+                }
+                let (key, value) = raw
+                    .split_once('=')
+                    .ok_or_else(|| LocaleError::at("Expected 'key = value'", line))?;
+                let key = key.trim();
+                if key.is_empty() || key.contains(char::is_whitespace) {
+                    return Err(LocaleError::at(format!("Invalid key '{}'", key), line));
+                }
+                message = Some(key.to_string());
+                current = Some((key.to_string(), value.trim().to_string()));
+                continue;
+            }
+
+            let trimmed = raw.trim();
+            if let Some(attribute) = trimmed.strip_prefix('.') {
+                let Some(parent) = &message else {
+                    return Err(LocaleError::at("Attribute outside of a message", line));
+                };
+                let (name, value) = attribute
+                    .split_once('=')
+                    .ok_or_else(|| LocaleError::at("Expected '.attribute = value'", line))?;
+                flush(&mut table, &mut current);
+                current = Some((
+                    format!("{}.{}", parent, name.trim()),
+                    value.trim().to_string(),
+                ));
+            } else if let Some((_, text)) = &mut current {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(trimmed);
+            } else {
+                return Err(LocaleError::at("Unexpected indented line", line));
+            }
+        }
+        flush(&mut table, &mut current);
+        Ok(table)
+    }
+}
+
+/// Parse CSV into rows, returning each row with the 1-based line it starts on
+fn parse_csv(source: &str) -> Result<Vec<(usize, Vec<String>)>, LocaleError> {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                } else {
+                    row.clear();
+                }
+                line += 1;
+                row_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(LocaleError::at("Unterminated quoted field", row_line));
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
+
+fn write_csv_row(out: &mut String, fields: &[&str]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push('\n');
+}
+
+/// Get the base key of a dialogue node (its `loc_key`, or `<tree id>.<node id>`)
+pub fn node_key(tree: &DialogueTree, node: &DialogueNode) -> String {
+    match node.loc_key.as_deref().map(str::trim) {
+        Some(key) if !key.is_empty() => key.to_string(),
+        _ => format!("{}.{}", tree.id, node.id),
+    }
+}
+
+/// Get the key of a dialogue node's text
+pub fn node_text_key(tree: &DialogueTree, node: &DialogueNode) -> String {
+    format!("{}.text", node_key(tree, node))
+}
+
+/// Get the key of the text of a node's choice at `index`
+pub fn choice_key(tree: &DialogueTree, node: &DialogueNode, index: usize) -> String {
+    match node.choices.get(index).map(|choice| choice.id.as_str()) {
+        Some(id) if !id.is_empty() => format!("{}.choice.{}", node_key(tree, node), id),
+        _ => format!("{}.choice.{}", node_key(tree, node), index),
+    }
+}
+
+/// Get the key of a speaker name
+pub fn speaker_key(speaker: &str) -> String {
+    format!("speaker.{}", speaker)
+}
+
+/// Get the key of a localized string property on an entity or data instance
+pub fn property_key(type_name: &str, instance_id: impl fmt::Display, property: &str) -> String {
+    format!("{}.{}.{}", type_name, instance_id, property)
+}
+
+/// The locale text is resolved in
+///
+/// Changing it takes effect immediately for text read through [`Localizer`].
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct CurrentLocale(pub String);
+
+impl CurrentLocale {
+    /// Create a locale resource
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self(locale.into())
+    }
+}
+
+impl Default for CurrentLocale {
+    fn default() -> Self {
+        Self("en".to_string())
+    }
+}
+
+/// System parameter that resolves text in the [`CurrentLocale`]
+///
+/// Looks the key up in every loaded [`StringTable`] and falls back to the
+/// inline text if no table has a translation.
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    locale: Res<'w, CurrentLocale>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl<'w> Localizer<'w> {
+    /// Get the current locale
+    pub fn locale(&self) -> &str {
+        &self.locale.0
+    }
+
+    /// Get the translation for a key, if any table has one
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tables
+            .iter()
+            .find_map(|(_, table)| table.get(&self.locale.0, key))
+    }
+
+    /// Get the translation for a key, or `fallback` if there is none
+    pub fn text<'a>(&'a self, key: &str, fallback: &'a str) -> &'a str {
+        self.get(key).unwrap_or(fallback)
+    }
+
+    /// Get a dialogue node's text
+    pub fn node_text<'a>(&'a self, tree: &DialogueTree, node: &'a DialogueNode) -> &'a str {
+        self.text(&node_text_key(tree, node), &node.text)
+    }
+
+    /// Get a dialogue node's speaker name
+    pub fn node_speaker<'a>(&'a self, node: &'a DialogueNode) -> &'a str {
+        if node.speaker.is_empty() {
+            return &node.speaker;
+        }
+        self.text(&speaker_key(&node.speaker), &node.speaker)
+    }
+
+    /// Get the text of a node's choice
+    pub fn choice_text<'a>(
+        &'a self,
+        tree: &DialogueTree,
+        node: &'a DialogueNode,
+        index: usize,
+    ) -> &'a str {
+        let fallback = node.choices.get(index).map_or("", |c| c.text.as_str());
+        self.text(&choice_key(tree, node, index), fallback)
+    }
+
+    /// Get a localized string property of an entity or data instance
+    pub fn property<'a>(
+        &'a self,
+        type_name: &str,
+        instance_id: impl fmt::Display,
+        property: &str,
+        fallback: &'a str,
+    ) -> &'a str {
+        self.text(&property_key(type_name, instance_id, property), fallback)
+    }
+}
+
+/// Asset loader for CSV string tables (`*.strings.csv`)
+#[derive(Default, TypePath)]
+pub struct StringTableCsvLoader;
+
+impl AssetLoader for StringTableCsvLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = LocaleError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let source = read_source(reader).await?;
+        StringTable::from_csv(&source)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["strings.csv"]
+    }
+}
+
+/// Asset loader for Fluent string tables (`*.ftl`)
+///
+/// The locale is taken from the file name: `fr.ftl` and `dialogue.fr.ftl` are both `fr`.
+#[derive(Default, TypePath)]
+pub struct StringTableFluentLoader;
+
+impl AssetLoader for StringTableFluentLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = LocaleError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let source = read_source(reader).await?;
+        let locale = load_context
+            .path()
+            .path()
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit('.').next())
+            .ok_or_else(|| LocaleError::new("Cannot determine locale from file name"))?
+            .to_string();
+        StringTable::from_fluent(locale, &source)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+async fn read_source(reader: &mut dyn Reader) -> Result<String, LocaleError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(|e| LocaleError::new(format!("Failed to read file: {}", e)))?;
+    String::from_utf8(bytes).map_err(|_| LocaleError::new("File is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DialogueChoice;
+
+    #[test]
+    fn test_csv_roundtrip() {
+        let source = "key,context,en,fr\n\
+                      greet.text,Shopkeeper,\"Hello, traveler!\",\"Bonjour, voyageur !\"\n\
+                      quote.text,,\"She said \"\"hi\"\"\",\n\
+                      multi.text,,\"line one\nline two\",deux\n";
+        let table = StringTable::from_csv(source).unwrap();
+
+        assert_eq!(table.get("en", "greet.text"), Some("Hello, traveler!"));
+        assert_eq!(table.get("fr", "greet.text"), Some("Bonjour, voyageur !"));
+        assert_eq!(table.get("en", "quote.text"), Some("She said \"hi\""));
+        assert_eq!(table.get("fr", "quote.text"), None);
+        assert_eq!(table.get("en", "multi.text"), Some("line one\nline two"));
+        assert_eq!(table.context("greet.text"), Some("Shopkeeper"));
+
+        let reparsed = StringTable::from_csv(&table.to_csv()).unwrap();
+        assert_eq!(
+            reparsed.keys().collect::<Vec<_>>(),
+            table.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(reparsed.get("en", "multi.text"), Some("line one\nline two"));
+        assert_eq!(reparsed.get("en", "quote.text"), Some("She said \"hi\""));
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        assert!(StringTable::from_csv("en,fr\nhello,bonjour\n").is_err());
+        assert!(StringTable::from_csv("key,en\na,1\na,2\n").is_err());
+        assert!(StringTable::from_csv("key,en\na,\"open\n").is_err());
+    }
+
+    #[test]
+    fn test_fluent() {
+        let source = "# Merchant\n\
+                      merchant-greeting = Welcome!\n\
+                      \x20   Take a look around.\n\
+                      shop =\n\
+                      \x20   .text = What would you like?\n\
+                      \x20   .choice.0 = Buy\n\
+                      speaker.Merchant = Marchand\n";
+        let table = StringTable::from_fluent("fr", source).unwrap();
+
+        assert_eq!(
+            table.get("fr", "merchant-greeting"),
+            Some("Welcome!\nTake a look around.")
+        );
+        assert!(table.get("fr", "shop").is_none());
+        assert_eq!(table.get("fr", "shop.text"), Some("What would you like?"));
+        assert_eq!(table.get("fr", "shop.choice.0"), Some("Buy"));
+        assert_eq!(table.get("fr", "speaker.Merchant"), Some("Marchand"));
+    }
+
+    #[test]
+    fn test_node_keys() {
+        let tree = DialogueTree::empty("Test");
+        let mut node = DialogueNode::new_text("Merchant", "Hello");
+        let default_key = format!("{}.{}.text", tree.id, node.id);
+        assert_eq!(node_text_key(&tree, &node), default_key);
+
+        node.loc_key = Some("merchant.greet".to_string());
+        assert_eq!(node_text_key(&tree, &node), "merchant.greet.text");
+        assert_eq!(choice_key(&tree, &node, 2), "merchant.greet.choice.2");
+    }
+
+    #[test]
+    fn test_choice_keys_follow_ids() {
+        let mut tree = DialogueTree::empty("Test");
+        let mut node = DialogueNode::new_choice("Merchant", "?");
+        node.id = "ask".to_string();
+        node.loc_key = Some("shop".to_string());
+        node.choices = vec![
+            DialogueChoice {
+                text: "Buy".to_string(),
+                ..default()
+            },
+            DialogueChoice {
+                text: "Sell".to_string(),
+                ..default()
+            },
+        ];
+        tree.add_node(node);
+
+        // Choices without ids keep their index keys when ids are assigned
+        assert_eq!(tree.assign_choice_ids(), 2);
+        assert_eq!(tree.assign_choice_ids(), 0);
+        let node = tree.get_node_mut("ask").unwrap();
+        node.choices.swap(0, 1);
+        node.choices.push(DialogueChoice::new("Leave", "end"));
+        let node = tree.get_node("ask").unwrap();
+        assert_eq!(choice_key(&tree, node, 0), "shop.choice.1");
+        assert_eq!(choice_key(&tree, node, 1), "shop.choice.0");
+        assert_eq!(
+            choice_key(&tree, node, 2),
+            format!("shop.choice.{}", node.choices[2].id)
+        );
+    }
+}
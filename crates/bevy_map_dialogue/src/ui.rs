@@ -0,0 +1,478 @@
+//! Ready-made dialogue box UI
+//!
+//! `DialogueUiPlugin` renders the dialogue running on the focused speaker:
+//! - Speaker name and typewriter text reveal (confirming skips the reveal)
+//! - Text resolved in the [`CurrentLocale`](crate::CurrentLocale)
+//! - Choice buttons, navigable with the mouse, keyboard (Up/Down arrows or
+//!   W/S, 1-9, Space/Enter to confirm) and gamepad (D-pad, South to confirm)
+//!
+//! The box follows the most recently started dialogue. Add [`HideDialogueUi`]
+//! to speakers whose dialogues should run without it (e.g. ambient barks).
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_dialogue::{DialoguePlugin, DialogueUiPlugin, DialogueUiStyle};
+//!
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins((DialoguePlugin, DialogueUiPlugin))
+//!     .insert_resource(DialogueUiStyle {
+//!         chars_per_second: 60.0,
+//!         ..default()
+//!     })
+//!     .run();
+//! ```
+
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::prelude::*;
+
+use crate::{
+    DialogueChoiceEvent, DialogueNodeType, DialogueRunner, DialogueTree, DialogueVariables,
+    Localizer, StartDialogueEvent,
+};
+
+/// Plugin that shows a dialogue box for the focused [`DialogueRunner`]
+///
+/// Requires `DialoguePlugin` and a camera to render UI.
+pub struct DialogueUiPlugin;
+
+impl Plugin for DialogueUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DialogueUiStyle>()
+            .init_resource::<DialogueUiFocus>()
+            .init_resource::<DialogueUiState>()
+            .add_systems(
+                Update,
+                (
+                    spawn_dialogue_ui.run_if(resource_changed::<DialogueUiStyle>),
+                    track_dialogue_focus,
+                    update_dialogue_ui,
+                    dialogue_ui_input,
+                    highlight_choices,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Look of the dialogue box
+///
+/// Changing this resource rebuilds the box.
+#[derive(Resource, Debug, Clone)]
+pub struct DialogueUiStyle {
+    /// Font for all dialogue text (Bevy's default font if not set)
+    pub font: Handle<Font>,
+    /// Font size of the dialogue text and choices
+    pub text_size: f32,
+    /// Font size of the speaker name
+    pub speaker_size: f32,
+    /// Color of the dialogue text
+    pub text_color: Color,
+    /// Color of the speaker name
+    pub speaker_color: Color,
+    /// Background of the dialogue box
+    pub background_color: Color,
+    /// Text color of unselected choices
+    pub choice_color: Color,
+    /// Background of unselected choices
+    pub choice_background: Color,
+    /// Text color of the selected choice
+    pub selected_choice_color: Color,
+    /// Background of the selected choice
+    pub selected_choice_background: Color,
+    /// Typewriter speed in characters per second (0 = show text instantly)
+    pub chars_per_second: f32,
+    /// Minimum height of the box in pixels
+    pub min_height: f32,
+    /// Distance from the screen edges in pixels
+    pub margin: f32,
+    /// Inner padding in pixels
+    pub padding: f32,
+}
+
+impl Default for DialogueUiStyle {
+    fn default() -> Self {
+        Self {
+            font: Handle::default(),
+            text_size: 20.0,
+            speaker_size: 22.0,
+            text_color: Color::WHITE,
+            speaker_color: Color::srgb(1.0, 0.85, 0.4),
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.9),
+            choice_color: Color::srgb(0.8, 0.8, 0.8),
+            choice_background: Color::NONE,
+            selected_choice_color: Color::WHITE,
+            selected_choice_background: Color::srgba(1.0, 1.0, 1.0, 0.15),
+            chars_per_second: 40.0,
+            min_height: 160.0,
+            margin: 20.0,
+            padding: 16.0,
+        }
+    }
+}
+
+/// The speaker whose dialogue the box shows
+///
+/// Set automatically when a dialogue starts on an entity without
+/// [`HideDialogueUi`]; set it yourself to switch between running dialogues.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DialogueUiFocus {
+    pub speaker: Option<Entity>,
+}
+
+/// Marker for speakers whose dialogues don't take over the dialogue box
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct HideDialogueUi;
+
+/// Reveal and selection state of the node being shown
+#[derive(Resource, Default)]
+struct DialogueUiState {
+    speaker: Option<Entity>,
+    node_id: Option<String>,
+    /// Locale the speaker name and choices were built in
+    locale: String,
+    /// Characters revealed so far (fractional for smooth typewriter timing)
+    revealed: f32,
+    text_len: usize,
+    /// Indices into the node's `choices` of the visible choices, in display order
+    choices: Vec<usize>,
+    /// Position of the selected choice in `choices`
+    selected: usize,
+}
+
+impl DialogueUiState {
+    fn fully_revealed(&self) -> bool {
+        self.revealed >= self.text_len as f32
+    }
+}
+
+#[derive(Component)]
+struct DialogueUiRoot;
+
+#[derive(Component)]
+struct DialogueUiSpeaker;
+
+#[derive(Component)]
+struct DialogueUiText;
+
+#[derive(Component)]
+struct DialogueUiChoices;
+
+/// A choice button, holding its position in `DialogueUiState::choices`
+#[derive(Component)]
+struct DialogueUiChoice(usize);
+
+/// System that (re)builds the dialogue box from the style
+fn spawn_dialogue_ui(
+    mut commands: Commands,
+    style: Res<DialogueUiStyle>,
+    mut state: ResMut<DialogueUiState>,
+    roots: Query<Entity, With<DialogueUiRoot>>,
+) {
+    for root in roots.iter() {
+        commands.entity(root).despawn();
+    }
+    // Force the current node to be rebuilt into the new box
+    state.node_id = None;
+
+    commands
+        .spawn((
+            DialogueUiRoot,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                left: Val::Px(style.margin),
+                right: Val::Px(style.margin),
+                bottom: Val::Px(style.margin),
+                min_height: Val::Px(style.min_height),
+                padding: UiRect::all(Val::Px(style.padding)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(style.padding / 2.0),
+                ..default()
+            },
+            BackgroundColor(style.background_color),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                DialogueUiSpeaker,
+                Text::default(),
+                TextFont {
+                    font: style.font.clone(),
+                    font_size: style.speaker_size,
+                    ..default()
+                },
+                TextColor(style.speaker_color),
+            ));
+            parent.spawn((
+                DialogueUiText,
+                Text::default(),
+                TextFont {
+                    font: style.font.clone(),
+                    font_size: style.text_size,
+                    ..default()
+                },
+                TextColor(style.text_color),
+            ));
+            parent.spawn((
+                DialogueUiChoices,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// System that focuses the box on newly started dialogues
+fn track_dialogue_focus(
+    mut events: MessageReader<StartDialogueEvent>,
+    mut focus: ResMut<DialogueUiFocus>,
+    hidden: Query<(), With<HideDialogueUi>>,
+) {
+    for event in events.read() {
+        if !hidden.contains(event.speaker_entity) {
+            focus.speaker = Some(event.speaker_entity);
+        }
+    }
+}
+
+/// System that shows the focused runner's node and advances the typewriter
+fn update_dialogue_ui(
+    mut commands: Commands,
+    time: Res<Time>,
+    style: Res<DialogueUiStyle>,
+    focus: Res<DialogueUiFocus>,
+    vars: Res<DialogueVariables>,
+    dialogues: Res<Assets<DialogueTree>>,
+    localizer: Localizer,
+    runners: Query<&DialogueRunner>,
+    mut state: ResMut<DialogueUiState>,
+    mut roots: Query<&mut Node, (With<DialogueUiRoot>, Without<DialogueUiChoices>)>,
+    mut choice_lists: Query<
+        (Entity, &mut Node),
+        (With<DialogueUiChoices>, Without<DialogueUiRoot>),
+    >,
+    mut speakers: Query<&mut Text, (With<DialogueUiSpeaker>, Without<DialogueUiText>)>,
+    mut bodies: Query<&mut Text, (With<DialogueUiText>, Without<DialogueUiSpeaker>)>,
+) {
+    let Ok(mut root) = roots.single_mut() else {
+        return;
+    };
+    let Ok((choice_list, mut choice_list_node)) = choice_lists.single_mut() else {
+        return;
+    };
+
+    let runner = focus.speaker.and_then(|speaker| runners.get(speaker).ok());
+    let tree = runner
+        .and_then(|runner| runner.dialogue_handle.as_ref())
+        .and_then(|handle| dialogues.get(handle));
+    let node = runner.zip(tree).and_then(|(runner, tree)| {
+        runner
+            .current_node_id
+            .as_deref()
+            .and_then(|id| tree.get_node(id))
+    });
+    let (Some(tree), Some(node)) = (tree, node) else {
+        root.display = Display::None;
+        state.node_id = None;
+        return;
+    };
+    root.display = Display::Flex;
+
+    // Entered a new node: reset the reveal and choice selection
+    let entered =
+        state.node_id.as_deref() != Some(node.id.as_str()) || state.speaker != focus.speaker;
+    if entered {
+        state.speaker = focus.speaker;
+        state.node_id = Some(node.id.clone());
+        state.revealed = 0.0;
+        state.selected = 0;
+        state.choices = if node.node_type == DialogueNodeType::Choice {
+            node.visible_choices(&vars).map(|(i, _)| i).collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    // (Re)build the localized speaker name and choice buttons
+    if entered || state.locale != localizer.locale() {
+        state.locale = localizer.locale().to_string();
+        state.text_len = localizer.node_text(tree, node).chars().count();
+        state.revealed = state.revealed.min(state.text_len as f32);
+
+        if let Ok(mut speaker) = speakers.single_mut() {
+            speaker.0 = localizer.node_speaker(node).to_string();
+        }
+
+        commands.entity(choice_list).despawn_children();
+        commands.entity(choice_list).with_children(|parent| {
+            for (pos, &index) in state.choices.iter().enumerate() {
+                parent
+                    .spawn((
+                        Button,
+                        DialogueUiChoice(pos),
+                        Node {
+                            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                            ..default()
+                        },
+                        BackgroundColor(style.choice_background),
+                    ))
+                    .with_child((
+                        Text::new(format!(
+                            "{}. {}",
+                            pos + 1,
+                            localizer.choice_text(tree, node, index)
+                        )),
+                        TextFont {
+                            font: style.font.clone(),
+                            font_size: style.text_size,
+                            ..default()
+                        },
+                        TextColor(style.choice_color),
+                    ));
+            }
+        });
+    }
+
+    // Typewriter reveal
+    if !state.fully_revealed() {
+        state.revealed = if style.chars_per_second > 0.0 {
+            (state.revealed + time.delta_secs() * style.chars_per_second).min(state.text_len as f32)
+        } else {
+            state.text_len as f32
+        };
+    }
+    if let Ok(mut body) = bodies.single_mut() {
+        let shown: String = localizer
+            .node_text(tree, node)
+            .chars()
+            .take(state.revealed as usize)
+            .collect();
+        if body.0 != shown {
+            body.0 = shown;
+        }
+    }
+
+    choice_list_node.display = if state.fully_revealed() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+}
+
+/// System that handles skipping, choice navigation and confirmation
+fn dialogue_ui_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    interactions: Query<(&Interaction, &DialogueUiChoice), Changed<Interaction>>,
+    focus: Res<DialogueUiFocus>,
+    mut state: ResMut<DialogueUiState>,
+    mut choice_events: MessageWriter<DialogueChoiceEvent>,
+) {
+    let Some(speaker) = focus.speaker else {
+        return;
+    };
+    if state.node_id.is_none() {
+        return;
+    }
+
+    let gamepad_pressed =
+        |button: GamepadButton| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+    let up = keyboard.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW])
+        || gamepad_pressed(GamepadButton::DPadUp);
+    let down = keyboard.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS])
+        || gamepad_pressed(GamepadButton::DPadDown);
+    let confirm = keyboard.any_just_pressed([KeyCode::Space, KeyCode::Enter])
+        || gamepad_pressed(GamepadButton::South);
+
+    if !state.fully_revealed() {
+        // Confirming while text is still typing shows it all at once
+        if confirm {
+            state.revealed = state.text_len as f32;
+        }
+        return;
+    }
+
+    let count = state.choices.len();
+    let mut chosen = None;
+
+    for (interaction, choice) in interactions.iter() {
+        match interaction {
+            Interaction::Hovered => state.selected = choice.0,
+            Interaction::Pressed => chosen = Some(choice.0),
+            Interaction::None => {}
+        }
+    }
+
+    const NUMBER_KEYS: [KeyCode; 9] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+    for (pos, key) in NUMBER_KEYS.iter().enumerate().take(count) {
+        if keyboard.just_pressed(*key) {
+            chosen = Some(pos);
+        }
+    }
+
+    if count > 0 {
+        if up {
+            state.selected = (state.selected + count - 1) % count;
+        }
+        if down {
+            state.selected = (state.selected + 1) % count;
+        }
+    }
+
+    let choice_index = match (chosen, confirm) {
+        (Some(pos), _) => state.choices.get(pos).copied(),
+        (None, true) if count > 0 => state.choices.get(state.selected).copied(),
+        // Text nodes, and choice nodes with every choice hidden, continue on
+        // any index
+        (None, true) => Some(0),
+        (None, false) => None,
+    };
+
+    if let Some(choice_index) = choice_index {
+        choice_events.write(DialogueChoiceEvent {
+            speaker_entity: speaker,
+            choice_index,
+        });
+        // Rebuild on the next frame, even if the runner stays on this node
+        state.node_id = None;
+    }
+}
+
+/// System that colors choice buttons by selection
+fn highlight_choices(
+    style: Res<DialogueUiStyle>,
+    state: Res<DialogueUiState>,
+    mut buttons: Query<(&DialogueUiChoice, &mut BackgroundColor, &Children)>,
+    mut texts: Query<&mut TextColor>,
+) {
+    for (choice, mut background, children) in buttons.iter_mut() {
+        let selected = choice.0 == state.selected;
+        background.set_if_neq(BackgroundColor(if selected {
+            style.selected_choice_background
+        } else {
+            style.choice_background
+        }));
+        for child in children.iter() {
+            if let Ok(mut color) = texts.get_mut(child) {
+                color.set_if_neq(TextColor(if selected {
+                    style.selected_choice_color
+                } else {
+                    style.choice_color
+                }));
+            }
+        }
+    }
+}
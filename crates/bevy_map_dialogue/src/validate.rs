@@ -0,0 +1,397 @@
+//! Graph analysis for dialogue trees
+//!
+//! [`DialogueTree::validate`] reports structured [`DialogueIssue`]s so tools can
+//! point at the offending node:
+//! - Errors: missing start node, links to missing nodes, Choice nodes without
+//!   choices, invalid conditions/actions, and loops that can never reach an end
+//! - Warnings: unreachable nodes, dead ends (links that end the dialogue without
+//!   an End node), Condition nodes missing a branch, and duplicate choice text
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::expr::{parse_action, parse_condition};
+use crate::{DialogueNode, DialogueNodeType, DialogueTree};
+
+/// How serious a [`DialogueIssue`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialogueSeverity {
+    /// The dialogue can't run as written
+    Error,
+    /// The dialogue runs, but probably not as intended
+    Warning,
+}
+
+/// A problem found by [`DialogueTree::validate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueIssue {
+    /// Node the issue is about (`None` for issues with the tree itself)
+    pub node_id: Option<String>,
+    pub severity: DialogueSeverity,
+    pub message: String,
+}
+
+impl DialogueIssue {
+    fn tree(severity: DialogueSeverity, message: impl Into<String>) -> Self {
+        Self {
+            node_id: None,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    fn node(node_id: &str, severity: DialogueSeverity, message: impl Into<String>) -> Self {
+        Self {
+            node_id: Some(node_id.to_string()),
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Whether this issue is an error
+    pub fn is_error(&self) -> bool {
+        self.severity == DialogueSeverity::Error
+    }
+}
+
+impl fmt::Display for DialogueIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.node_id {
+            Some(id) => write!(f, "Node '{}': {}", id, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Links followed at runtime for a node's type (`None` ends the dialogue)
+fn runtime_links(node: &DialogueNode) -> Vec<Option<&str>> {
+    match node.node_type {
+        DialogueNodeType::Text | DialogueNodeType::Action => vec![node.next_node.as_deref()],
+        DialogueNodeType::Condition => {
+            vec![node.next_node.as_deref(), node.false_node.as_deref()]
+        }
+        DialogueNodeType::Choice => {
+            let mut links: Vec<_> = node
+                .choices
+                .iter()
+                .map(|c| c.next_node.as_deref())
+                .collect();
+            // With every choice hidden the node continues to `next_node`
+            let all_conditional = node.choices.iter().all(|c| {
+                c.condition
+                    .as_deref()
+                    .is_some_and(|condition| !condition.trim().is_empty())
+            });
+            if all_conditional {
+                links.push(node.next_node.as_deref());
+            }
+            links
+        }
+        DialogueNodeType::End => Vec::new(),
+    }
+}
+
+impl DialogueTree {
+    /// Analyze the tree, returning every issue found (empty if the tree is clean)
+    ///
+    /// Tree-level issues come first, then node issues ordered by node ID.
+    pub fn validate(&self) -> Vec<DialogueIssue> {
+        use DialogueSeverity::{Error, Warning};
+
+        let mut issues = Vec::new();
+
+        if self.start_node.is_empty() {
+            issues.push(DialogueIssue::tree(Error, "No start node defined"));
+        } else if !self.nodes.contains_key(&self.start_node) {
+            issues.push(DialogueIssue::tree(
+                Error,
+                format!("Start node '{}' not found", self.start_node),
+            ));
+        }
+
+        let reachable = self.reachable_nodes();
+        let can_end = self.nodes_that_can_end();
+
+        let mut ids: Vec<&String> = self.nodes.keys().collect();
+        ids.sort();
+        for id in ids {
+            let node = &self.nodes[id];
+            let mut push = |severity, message: String| {
+                issues.push(DialogueIssue::node(id, severity, message));
+            };
+
+            // Links to missing nodes
+            for next in node.next_node.iter().chain(node.false_node.iter()) {
+                if !self.nodes.contains_key(next) {
+                    push(Error, format!("Links to missing node '{}'", next));
+                }
+            }
+            for (i, choice) in node.choices.iter().enumerate() {
+                if let Some(next) = &choice.next_node {
+                    if !self.nodes.contains_key(next) {
+                        push(
+                            Error,
+                            format!(
+                                "Choice {} ('{}') links to missing node '{}'",
+                                i + 1,
+                                choice.text,
+                                next
+                            ),
+                        );
+                    }
+                }
+            }
+
+            // Expressions
+            if node.node_type == DialogueNodeType::Condition {
+                if let Err(err) = parse_condition(node.condition.as_deref().unwrap_or("")) {
+                    push(Error, format!("Invalid condition: {}", err));
+                }
+            }
+            if let Some(action) = node.action.as_deref().filter(|a| !a.trim().is_empty()) {
+                if let Err(err) = parse_action(action) {
+                    push(Error, format!("Invalid action: {}", err));
+                }
+            }
+            for (i, choice) in node.choices.iter().enumerate() {
+                let condition = choice.condition.as_deref().unwrap_or("").trim();
+                if condition.is_empty() {
+                    continue;
+                }
+                if let Err(err) = parse_condition(condition) {
+                    push(Error, format!("Choice {} condition: {}", i + 1, err));
+                }
+            }
+
+            // Node-type specific checks
+            match node.node_type {
+                DialogueNodeType::Text | DialogueNodeType::Action if node.next_node.is_none() => {
+                    push(
+                        Warning,
+                        "Dead end: no next node (link an End node to finish here)".to_string(),
+                    );
+                }
+                DialogueNodeType::Choice if node.choices.is_empty() => {
+                    push(Error, "Choice node has no choices".to_string());
+                }
+                DialogueNodeType::Choice => {
+                    let mut seen = HashSet::new();
+                    let mut reported = HashSet::new();
+                    for (i, choice) in node.choices.iter().enumerate() {
+                        if choice.next_node.is_none() {
+                            push(
+                                Warning,
+                                format!(
+                                    "Dead end: choice {} ('{}') has no target",
+                                    i + 1,
+                                    choice.text
+                                ),
+                            );
+                        }
+                        let text = choice.text.trim();
+                        if !seen.insert(text) && reported.insert(text) {
+                            push(Warning, format!("Duplicate choice text '{}'", text));
+                        }
+                    }
+                }
+                DialogueNodeType::Condition => {
+                    if node.next_node.is_none() {
+                        push(Warning, "Condition has no true branch".to_string());
+                    }
+                    if node.false_node.is_none() {
+                        push(Warning, "Condition has no false branch".to_string());
+                    }
+                }
+                _ => {}
+            }
+
+            // Graph shape
+            if let Some(reachable) = &reachable {
+                if !reachable.contains(id.as_str()) {
+                    push(Warning, "Unreachable from the start node".to_string());
+                } else if !can_end.contains(id.as_str()) {
+                    push(
+                        Error,
+                        "Loop without exit: no path from here reaches an end".to_string(),
+                    );
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Nodes reachable from the start node (`None` if the start node is missing)
+    fn reachable_nodes(&self) -> Option<HashSet<&str>> {
+        let start = self.nodes.get_key_value(&self.start_node)?.0.as_str();
+        let mut reachable = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            for next in runtime_links(&self.nodes[id]).into_iter().flatten() {
+                if let Some((key, _)) = self.nodes.get_key_value(next) {
+                    if reachable.insert(key.as_str()) {
+                        queue.push_back(key.as_str());
+                    }
+                }
+            }
+        }
+        Some(reachable)
+    }
+
+    /// Nodes with at least one path that finishes the dialogue
+    ///
+    /// A path finishes at an End node, an empty or missing link, or a Choice
+    /// node without choices (reported separately).
+    fn nodes_that_can_end(&self) -> HashSet<&str> {
+        let mut incoming: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        let mut can_end = HashSet::new();
+
+        for (id, node) in &self.nodes {
+            let links = runtime_links(node);
+            let ends = links.is_empty()
+                || links
+                    .iter()
+                    .any(|link| link.map_or(true, |next| !self.nodes.contains_key(next)));
+            if ends {
+                can_end.insert(id.as_str());
+                queue.push_back(id.as_str());
+            }
+            for next in links.into_iter().flatten() {
+                incoming.entry(next).or_default().push(id.as_str());
+            }
+        }
+
+        while let Some(id) = queue.pop_front() {
+            for &source in incoming.get(id).into_iter().flatten() {
+                if can_end.insert(source) {
+                    queue.push_back(source);
+                }
+            }
+        }
+        can_end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DialogueChoice;
+
+    fn node(id: &str, mut node: DialogueNode) -> DialogueNode {
+        node.id = id.to_string();
+        node
+    }
+
+    fn issues_for<'a>(issues: &'a [DialogueIssue], id: &str) -> Vec<&'a str> {
+        issues
+            .iter()
+            .filter(|i| i.node_id.as_deref() == Some(id))
+            .map(|i| i.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_tree() {
+        let mut tree = DialogueTree::empty("clean");
+        tree.add_node(node(
+            "start",
+            DialogueNode::new_text("A", "Hi").with_next("end"),
+        ));
+        tree.add_node(node("end", DialogueNode::new_end()));
+        tree.start_node = "start".to_string();
+        assert!(tree.validate().is_empty());
+    }
+
+    #[test]
+    fn test_graph_issues() {
+        let mut tree = DialogueTree::empty("issues");
+        let mut choice = node("ask", DialogueNode::new_choice("A", "?"));
+        choice.choices = vec![
+            DialogueChoice::new("Yes", "check"),
+            DialogueChoice::new("Yes", "end"),
+            DialogueChoice::new("Loop", "loop_a"),
+        ];
+        tree.add_node(choice);
+        tree.add_node(node(
+            "check",
+            DialogueNode::new_condition("gold > 1").with_next("end"),
+        ));
+        tree.add_node(node(
+            "loop_a",
+            DialogueNode::new_text("A", "a").with_next("loop_b"),
+        ));
+        tree.add_node(node(
+            "loop_b",
+            DialogueNode::new_text("A", "b").with_next("loop_a"),
+        ));
+        tree.add_node(node("orphan", DialogueNode::new_text("A", "lost")));
+        tree.add_node(node("empty", DialogueNode::new_choice("A", "?")));
+        tree.add_node(node("end", DialogueNode::new_end()));
+        tree.start_node = "ask".to_string();
+
+        let issues = tree.validate();
+        assert_eq!(issues_for(&issues, "ask"), ["Duplicate choice text 'Yes'"]);
+        assert_eq!(
+            issues_for(&issues, "check"),
+            ["Condition has no false branch"]
+        );
+        assert!(issues_for(&issues, "loop_a")[0].starts_with("Loop without exit"));
+        assert!(issues_for(&issues, "loop_b")[0].starts_with("Loop without exit"));
+        assert_eq!(
+            issues_for(&issues, "orphan"),
+            [
+                "Dead end: no next node (link an End node to finish here)",
+                "Unreachable from the start node"
+            ]
+        );
+        assert!(issues_for(&issues, "empty").contains(&"Choice node has no choices"));
+        assert!(issues_for(&issues, "end").is_empty());
+
+        // Loops are errors, the rest here are warnings
+        let errors: Vec<_> = issues.iter().filter(|i| i.is_error()).collect();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn test_structural_errors() {
+        let mut tree = DialogueTree::empty("broken");
+        tree.add_node(node(
+            "a",
+            DialogueNode::new_text("A", "Hi").with_next("missing"),
+        ));
+        tree.add_node(node("b", DialogueNode::new_action("gold +=")));
+        assert_eq!(tree.validate()[0].message, "No start node defined");
+
+        tree.start_node = "a".to_string();
+        let issues = tree.validate();
+        assert_eq!(
+            issues_for(&issues, "a"),
+            ["Links to missing node 'missing'"]
+        );
+        assert!(issues_for(&issues, "b")[0].starts_with("Invalid action"));
+    }
+
+    #[test]
+    fn test_hidden_choices_fallback() {
+        let mut tree = DialogueTree::empty("fallback");
+        let mut choice = node("ask", DialogueNode::new_choice("A", "?"));
+        choice.choices = vec![DialogueChoice::new("Pay", "end").with_condition("gold > 10")];
+        choice.next_node = Some("broke".to_string());
+        tree.add_node(choice);
+        tree.add_node(node(
+            "broke",
+            DialogueNode::new_text("A", "No gold?").with_next("end"),
+        ));
+        tree.add_node(node("end", DialogueNode::new_end()));
+        tree.start_node = "ask".to_string();
+        assert!(tree.validate().is_empty());
+
+        // An unconditional choice is always shown, so the fallback is never taken
+        tree.nodes.get_mut("ask").unwrap().choices[0].condition = None;
+        assert_eq!(
+            issues_for(&tree.validate(), "broke"),
+            ["Unreachable from the start node"]
+        );
+    }
+}
@@ -0,0 +1,1120 @@
+//! Yarn Spinner (`.yarn`) import and export
+//!
+//! Importing turns each Yarn node into a chain of dialogue nodes:
+//! - `Speaker: text` lines become Text nodes (a `#line:` tag becomes the node's `loc_key`)
+//! - `->` options become a Choice node, using the line before them as the prompt
+//! - `<<if>>`/`<<elseif>>`/`<<else>>` blocks become Condition nodes
+//! - `<<set>>` and other commands become Action nodes (commands are emitted as signals)
+//! - `<<jump>>` links to another Yarn node and `<<stop>>` becomes an End node
+//!
+//! Node positions are generated with [`DialogueTree::auto_layout`]. The Yarn node
+//! titled `Start` (or the first node) becomes the start node.
+//!
+//! Exporting writes one Yarn node per branch target (start node, choice and condition
+//! targets, and nodes with several incoming links); linear runs of Text and Action
+//! nodes are written inline. Multi-line text becomes consecutive Yarn lines.
+//!
+//! ```rust,ignore
+//! let tree = bevy_map_dialogue::yarn::import_yarn(&source, "Merchant")?;
+//! let source = bevy_map_dialogue::yarn::export_yarn(&tree);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Write as _;
+
+use crate::expr::{
+    parse_action, parse_condition, quote_str, AssignOp, BinaryOp, Expr, Statement, UnaryOp,
+};
+use crate::{DialogueChoice, DialogueNode, DialogueNodeType, DialogueTree, DialogueValue};
+use uuid::Uuid;
+
+/// Prefix marking an unresolved `<<jump>>` target while building the tree
+const JUMP_PREFIX: &str = "\u{1}jump:";
+
+/// Error produced while importing a Yarn script
+#[derive(Debug, Clone, PartialEq)]
+pub struct YarnError {
+    /// Human-readable description of the problem
+    pub message: String,
+    /// 1-based line the error was found on, if known
+    pub line: Option<usize>,
+}
+
+impl YarnError {
+    fn at(message: impl Into<String>, line: usize) -> Self {
+        Self {
+            message: message.into(),
+            line: Some(line),
+        }
+    }
+}
+
+impl fmt::Display for YarnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} (line {})", self.message, line),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for YarnError {}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// A non-empty body line with comments stripped
+struct SourceLine {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+struct YarnNode {
+    title: String,
+    line: usize,
+    body: Vec<SourceLine>,
+}
+
+enum Stmt {
+    Line {
+        speaker: String,
+        text: String,
+        line_id: Option<String>,
+    },
+    Options(Vec<YarnOption>),
+    /// Branches in order; `None` is the `<<else>>` branch
+    If(Vec<(Option<String>, Vec<Stmt>)>),
+    /// A single action statement in `expr` syntax
+    Action(String),
+    Jump(String),
+    Stop,
+}
+
+struct YarnOption {
+    text: String,
+    condition: Option<String>,
+    body: Vec<Stmt>,
+}
+
+/// Import a Yarn script as a dialogue tree
+pub fn import_yarn(source: &str, name: impl Into<String>) -> Result<DialogueTree, YarnError> {
+    let yarn_nodes = split_nodes(source)?;
+    if yarn_nodes.is_empty() {
+        return Err(YarnError {
+            message: "No Yarn nodes found".to_string(),
+            line: None,
+        });
+    }
+
+    let mut builder = Builder::default();
+    let mut entries: HashMap<String, Option<String>> = HashMap::new();
+    for yarn_node in &yarn_nodes {
+        if entries.contains_key(&yarn_node.title) {
+            return Err(YarnError::at(
+                format!("Duplicate node title '{}'", yarn_node.title),
+                yarn_node.line,
+            ));
+        }
+        let mut pos = 0;
+        let stmts = parse_block(&yarn_node.body, &mut pos, 0)?;
+        if let Some(line) = yarn_node.body.get(pos) {
+            return Err(YarnError::at(
+                format!("Unexpected '{}'", line.text),
+                line.number,
+            ));
+        }
+        builder.prefix = sanitize_title(&yarn_node.title);
+        let entry = builder.compile(&stmts, None);
+        entries.insert(yarn_node.title.clone(), entry);
+    }
+
+    // Resolve `<<jump>>` placeholders (following jumps to nodes that only jump)
+    let resolve = |target: &str| -> Result<Option<String>, YarnError> {
+        let mut current = target.to_string();
+        for _ in 0..=entries.len() {
+            let Some(title) = current.strip_prefix(JUMP_PREFIX) else {
+                return Ok(Some(current));
+            };
+            match entries.get(title) {
+                Some(Some(entry)) => current = entry.clone(),
+                Some(None) => return Ok(None),
+                None => {
+                    return Err(YarnError {
+                        message: format!("Jump to unknown node '{}'", title),
+                        line: None,
+                    })
+                }
+            }
+        }
+        Err(YarnError {
+            message: "Jump loop without any content".to_string(),
+            line: None,
+        })
+    };
+    let resolve_link = |link: &mut Option<String>| -> Result<(), YarnError> {
+        if let Some(target) = link.take() {
+            *link = resolve(&target)?;
+        }
+        Ok(())
+    };
+
+    let mut tree = DialogueTree::empty(name);
+    for mut node in builder.nodes {
+        resolve_link(&mut node.next_node)?;
+        resolve_link(&mut node.false_node)?;
+        for choice in &mut node.choices {
+            resolve_link(&mut choice.next_node)?;
+        }
+        tree.nodes.insert(node.id.clone(), node);
+    }
+
+    let start_title = yarn_nodes
+        .iter()
+        .find(|n| n.title == "Start")
+        .unwrap_or(&yarn_nodes[0])
+        .title
+        .clone();
+    tree.start_node = match resolve(&format!("{}{}", JUMP_PREFIX, start_title))? {
+        Some(id) => id,
+        None => tree.add_node(DialogueNode::new_end()),
+    };
+    tree.auto_layout();
+    Ok(tree)
+}
+
+/// Split a script into nodes (`header --- body ===`)
+fn split_nodes(source: &str) -> Result<Vec<YarnNode>, YarnError> {
+    let mut nodes = Vec::new();
+    let mut title: Option<String> = None;
+    let mut header_line = 1;
+    let mut in_body = false;
+    let mut body = Vec::new();
+
+    for (index, raw) in source.lines().enumerate() {
+        let number = index + 1;
+        let line = strip_comment(raw);
+        let trimmed = line.trim();
+
+        if !in_body {
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed == "---" {
+                in_body = true;
+                continue;
+            }
+            if title.is_none() {
+                header_line = number;
+            }
+            if let Some((key, value)) = trimmed.split_once(':') {
+                if key.trim() == "title" {
+                    title = Some(value.trim().to_string());
+                }
+            }
+            continue;
+        }
+
+        if trimmed == "===" {
+            let title = title
+                .take()
+                .ok_or_else(|| YarnError::at("Node has no title", header_line))?;
+            nodes.push(YarnNode {
+                title,
+                line: header_line,
+                body: std::mem::take(&mut body),
+            });
+            in_body = false;
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum();
+        body.push(SourceLine {
+            number,
+            indent,
+            text: trimmed.to_string(),
+        });
+    }
+
+    if in_body {
+        return Err(YarnError::at(
+            "Node is missing its closing '==='",
+            header_line,
+        ));
+    }
+    Ok(nodes)
+}
+
+/// Remove a `//` comment, ignoring `//` inside string literals
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut prev = '\0';
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' if prev != '\\' => in_string = !in_string,
+            '/' if !in_string && prev == '/' => return &line[..i - 1],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+/// Parse statements until the indentation drops below `min_indent` or an
+/// `<<elseif>>`/`<<else>>`/`<<endif>>` is reached (left unconsumed)
+fn parse_block(
+    lines: &[SourceLine],
+    pos: &mut usize,
+    min_indent: usize,
+) -> Result<Vec<Stmt>, YarnError> {
+    let mut stmts = Vec::new();
+
+    while let Some(line) = lines.get(*pos) {
+        if line.indent < min_indent {
+            break;
+        }
+
+        if let Some(command) = parse_command(&line.text) {
+            let (keyword, rest) = split_word(command);
+            match keyword {
+                "elseif" | "else" | "endif" => break,
+                "if" => {
+                    *pos += 1;
+                    stmts.push(parse_if(lines, pos, min_indent, rest, line.number)?);
+                }
+                "set" => {
+                    stmts.push(Stmt::Action(convert_set(rest, line.number)?));
+                    *pos += 1;
+                }
+                "declare" => *pos += 1,
+                "jump" => {
+                    let target = rest.trim().trim_matches('"');
+                    if target.is_empty() {
+                        return Err(YarnError::at("Jump without a target", line.number));
+                    }
+                    stmts.push(Stmt::Jump(target.to_string()));
+                    *pos += 1;
+                }
+                "stop" => {
+                    stmts.push(Stmt::Stop);
+                    *pos += 1;
+                }
+                _ => {
+                    stmts.push(Stmt::Action(convert_command(keyword, rest)));
+                    *pos += 1;
+                }
+            }
+            continue;
+        }
+
+        if line.text.starts_with("->") {
+            let indent = line.indent;
+            let mut options = Vec::new();
+            while let Some(option_line) = lines.get(*pos) {
+                if option_line.indent != indent || !option_line.text.starts_with("->") {
+                    break;
+                }
+                let (text, condition, _) = split_line_suffix(&option_line.text[2..]);
+                *pos += 1;
+                let body = parse_block(lines, pos, indent + 1)?;
+                options.push(YarnOption {
+                    text: unescape(&text),
+                    condition: condition.map(|c| convert_expr(&c)),
+                    body,
+                });
+            }
+            stmts.push(Stmt::Options(options));
+            continue;
+        }
+
+        let (text, condition, line_id) = split_line_suffix(&line.text);
+        let (speaker, text) = split_speaker(&text);
+        let stmt = Stmt::Line {
+            speaker,
+            text: unescape(&text),
+            line_id,
+        };
+        stmts.push(match condition {
+            Some(condition) => Stmt::If(vec![(Some(convert_expr(&condition)), vec![stmt])]),
+            None => stmt,
+        });
+        *pos += 1;
+    }
+
+    Ok(stmts)
+}
+
+/// Parse an `<<if>>` chain after its opening line
+fn parse_if(
+    lines: &[SourceLine],
+    pos: &mut usize,
+    min_indent: usize,
+    condition: &str,
+    line: usize,
+) -> Result<Stmt, YarnError> {
+    let mut branches = Vec::new();
+    let mut condition = Some(convert_expr(condition));
+    loop {
+        let body = parse_block(lines, pos, min_indent)?;
+        branches.push((condition.take(), body));
+
+        let Some(terminator) = lines.get(*pos) else {
+            return Err(YarnError::at("<<if>> without <<endif>>", line));
+        };
+        let Some(command) = parse_command(&terminator.text) else {
+            return Err(YarnError::at("<<if>> without <<endif>>", line));
+        };
+        *pos += 1;
+        match split_word(command) {
+            ("elseif", rest) => condition = Some(convert_expr(rest)),
+            ("else", _) => condition = None,
+            ("endif", _) => return Ok(Stmt::If(branches)),
+            _ => return Err(YarnError::at("<<if>> without <<endif>>", line)),
+        }
+    }
+}
+
+/// Get the inside of a line that is exactly one `<<command>>`
+fn parse_command(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix("<<")?.strip_suffix(">>")?;
+    (!inner.contains(">>")).then(|| inner.trim())
+}
+
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (text, ""),
+    }
+}
+
+/// Split `text <<if cond>> #tags` into text, condition and `#line:` id
+fn split_line_suffix(line: &str) -> (String, Option<String>, Option<String>) {
+    let mut text = line.trim().to_string();
+
+    // Tags: everything after the first unescaped '#'
+    let mut line_id = None;
+    let mut prev = '\0';
+    if let Some((index, _)) = text.char_indices().find(|&(_, c)| {
+        let found = c == '#' && prev != '\\';
+        prev = c;
+        found
+    }) {
+        for tag in text[index..].split_whitespace() {
+            if let Some(id) = tag.strip_prefix('#').filter(|t| t.starts_with("line:")) {
+                line_id = Some(id.to_string());
+            }
+        }
+        text.truncate(index);
+    }
+
+    let mut condition = None;
+    let trimmed = text.trim_end();
+    if trimmed.ends_with(">>") {
+        if let Some(start) = trimmed.rfind("<<") {
+            if let ("if", rest) = split_word(&trimmed[start + 2..trimmed.len() - 2]) {
+                condition = Some(rest.to_string());
+                text.truncate(start);
+            }
+        }
+    }
+
+    (text.trim().to_string(), condition, line_id)
+}
+
+/// Split `Speaker: text` into its parts
+pub(crate) fn split_speaker(line: &str) -> (String, String) {
+    if let Some((speaker, text)) = line.split_once(':') {
+        let speaker = speaker.trim();
+        if !speaker.is_empty()
+            && speaker.len() <= 40
+            && !speaker.contains(['{', '<', '\\', '"'])
+            && !speaker.ends_with('\\')
+        {
+            return (speaker.to_string(), text.trim().to_string());
+        }
+    }
+    (String::new(), line.to_string())
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(next @ ('#' | '{' | '}' | '[' | ']' | '<' | '>' | '/' | '\\')) => {
+                    out.push(next)
+                }
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Convert a Yarn expression to `expr` syntax (`$gold gte 10 and not $met` -> `gold >= 10 && ! met`)
+fn convert_expr(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut chars = src.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            out.push(c);
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                out.push(c);
+                if c == '"' && !escaped {
+                    break;
+                }
+                escaped = c == '\\' && !escaped;
+            }
+        } else if c == '$' || c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            if c != '$' {
+                word.push(c);
+            }
+            while let Some(&next) = chars.peek() {
+                if next.is_alphanumeric() || next == '_' || next == '.' {
+                    word.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            out.push_str(match (c, word.as_str()) {
+                ('$', _) => &word,
+                (_, "and") => "&&",
+                (_, "or") => "||",
+                (_, "not") => "!",
+                (_, "xor" | "neq") => "!=",
+                (_, "is" | "eq") => "==",
+                (_, "gt") => ">",
+                (_, "lt") => "<",
+                (_, "gte") => ">=",
+                (_, "lte") => "<=",
+                _ => &word,
+            });
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Convert `$name to expr` (or `$name = expr`) to `name = expr`
+fn convert_set(rest: &str, line: usize) -> Result<String, YarnError> {
+    let rest = rest.trim();
+    let Some(rest) = rest.strip_prefix('$') else {
+        return Err(YarnError::at("Expected a $variable after <<set", line));
+    };
+    let name_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .unwrap_or(rest.len());
+    let (name, value) = rest.split_at(name_len);
+    let value = value.trim_start();
+    let value = value
+        .strip_prefix("to ")
+        .or_else(|| value.strip_prefix('='))
+        .ok_or_else(|| YarnError::at("Expected 'to' or '=' in <<set>>", line))?;
+    Ok(format!("{} = {}", name, convert_expr(value)))
+}
+
+/// Convert `<<name arg "arg 2" {$expr}>>` to `name("arg", "arg 2", expr)`
+fn convert_command(name: &str, rest: &str) -> String {
+    let mut args = Vec::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut arg = String::new();
+        match c {
+            '"' => {
+                arg.push(chars.next().unwrap_or('"'));
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    arg.push(c);
+                    if c == '"' && !escaped {
+                        break;
+                    }
+                    escaped = c == '\\' && !escaped;
+                }
+                args.push(arg);
+            }
+            '{' => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    arg.push(c);
+                }
+                args.push(convert_expr(&arg));
+            }
+            _ => {
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    arg.push(c);
+                    chars.next();
+                }
+                let is_literal = arg.parse::<f64>().is_ok() || arg == "true" || arg == "false";
+                args.push(if let Some(var) = arg.strip_prefix('$') {
+                    var.to_string()
+                } else if is_literal {
+                    arg
+                } else {
+                    quote_str(&arg)
+                });
+            }
+        }
+    }
+    format!("{}({})", name, args.join(", "))
+}
+
+fn sanitize_title(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Builds dialogue nodes back to front, so each statement knows where flow continues
+#[derive(Default)]
+struct Builder {
+    nodes: Vec<DialogueNode>,
+    prefix: String,
+    counter: usize,
+}
+
+impl Builder {
+    fn add(&mut self, mut node: DialogueNode) -> String {
+        self.counter += 1;
+        node.id = format!("{}_{}", self.prefix, self.counter);
+        let id = node.id.clone();
+        self.nodes.push(node);
+        id
+    }
+
+    /// Compile statements that continue to `next`, returning the entry node
+    fn compile(&mut self, stmts: &[Stmt], next: Option<String>) -> Option<String> {
+        let mut next = next;
+        let mut i = stmts.len();
+        while i > 0 {
+            i -= 1;
+            match &stmts[i] {
+                Stmt::Line {
+                    speaker,
+                    text,
+                    line_id,
+                } => {
+                    let mut node = DialogueNode::new_text(speaker, text);
+                    node.loc_key = line_id.clone();
+                    node.next_node = next;
+                    next = Some(self.add(node));
+                }
+                Stmt::Options(options) => {
+                    let mut node = DialogueNode::new_choice("", "");
+                    // The line right before the options becomes the prompt
+                    if let Some(Stmt::Line {
+                        speaker,
+                        text,
+                        line_id,
+                    }) = i.checked_sub(1).map(|prev| &stmts[prev])
+                    {
+                        node.speaker = speaker.clone();
+                        node.text = text.clone();
+                        node.loc_key = line_id.clone();
+                        i -= 1;
+                    }
+                    for option in options {
+                        let target = self.compile(&option.body, next.clone());
+                        node.choices.push(DialogueChoice {
+                            id: Uuid::new_v4().to_string(),
+                            text: option.text.clone(),
+                            next_node: target,
+                            condition: option.condition.clone(),
+                        });
+                    }
+                    next = Some(self.add(node));
+                }
+                Stmt::If(branches) => {
+                    let mut target = next.clone();
+                    for (condition, body) in branches.iter().rev() {
+                        let entry = self.compile(body, next.clone());
+                        match condition {
+                            None => target = entry,
+                            Some(condition) => {
+                                let mut node = DialogueNode::new_condition(condition);
+                                node.next_node = entry;
+                                node.false_node = target;
+                                target = Some(self.add(node));
+                            }
+                        }
+                    }
+                    next = target;
+                }
+                Stmt::Action(_) => {
+                    // Merge consecutive actions into one node
+                    let end = i;
+                    while i > 0 && matches!(stmts[i - 1], Stmt::Action(_)) {
+                        i -= 1;
+                    }
+                    let action = stmts[i..=end]
+                        .iter()
+                        .filter_map(|stmt| match stmt {
+                            Stmt::Action(action) => Some(action.as_str()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>()
+                        .join("; ");
+                    let mut node = DialogueNode::new_action(action);
+                    node.next_node = next;
+                    next = Some(self.add(node));
+                }
+                Stmt::Jump(title) => next = Some(format!("{}{}", JUMP_PREFIX, title)),
+                Stmt::Stop => next = Some(self.add(DialogueNode::new_end())),
+            }
+        }
+        next
+    }
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Export a dialogue tree as a Yarn script
+pub fn export_yarn(tree: &DialogueTree) -> String {
+    let heads = yarn_heads(tree);
+    let titles = yarn_titles(tree, &heads);
+
+    let mut out = String::new();
+    for head in &heads {
+        let node = &tree.nodes[head];
+        let _ = writeln!(out, "title: {}", titles[head]);
+        let _ = writeln!(
+            out,
+            "position: {},{}",
+            node.position.0.round(),
+            node.position.1.round()
+        );
+        out.push_str("---\n");
+        write_chain(&mut out, tree, head, &titles);
+        out.push_str("===\n");
+    }
+    out
+}
+
+/// Pick the nodes that start a Yarn node, in reading order
+///
+/// A node is inlined into its predecessor when its only incoming link is the
+/// `next_node` of a Text or Action node.
+fn yarn_heads(tree: &DialogueTree) -> Vec<String> {
+    let mut incoming: HashMap<&str, (usize, bool)> = HashMap::new();
+    for node in tree.nodes.values() {
+        let linear = matches!(
+            node.node_type,
+            DialogueNodeType::Text | DialogueNodeType::Action
+        );
+        if let Some(next) = &node.next_node {
+            let entry = incoming.entry(next).or_default();
+            entry.0 += 1;
+            entry.1 = linear;
+        }
+        for target in node
+            .false_node
+            .iter()
+            .chain(node.choices.iter().filter_map(|c| c.next_node.as_ref()))
+        {
+            let entry = incoming.entry(target).or_default();
+            entry.0 += 1;
+            entry.1 = false;
+        }
+    }
+
+    let mut ordered: Vec<&DialogueNode> = tree.nodes.values().collect();
+    ordered.sort_by(|a, b| {
+        (b.id == tree.start_node)
+            .cmp(&(a.id == tree.start_node))
+            .then(a.position.1.total_cmp(&b.position.1))
+            .then(a.position.0.total_cmp(&b.position.0))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    let mut heads: Vec<String> = ordered
+        .iter()
+        .filter(|node| {
+            node.id == tree.start_node
+                || !matches!(incoming.get(node.id.as_str()), Some(&(1, true)))
+        })
+        .map(|node| node.id.clone())
+        .collect();
+
+    // Nodes only reachable through a cycle of inlined links still need a head
+    let mut covered: HashSet<String> = HashSet::new();
+    let head_set: HashSet<String> = heads.iter().cloned().collect();
+    for head in &heads {
+        covered.extend(chain(tree, head, &head_set));
+    }
+    for node in ordered {
+        if !covered.contains(&node.id) {
+            heads.push(node.id.clone());
+            let head_set: HashSet<String> = heads.iter().cloned().collect();
+            covered.extend(chain(tree, &node.id, &head_set));
+        }
+    }
+    heads
+}
+
+/// IDs of the nodes written inline starting at `head`
+fn chain(tree: &DialogueTree, head: &str, heads: &HashSet<String>) -> Vec<String> {
+    let mut ids = vec![head.to_string()];
+    let mut current = &tree.nodes[head];
+    while matches!(
+        current.node_type,
+        DialogueNodeType::Text | DialogueNodeType::Action
+    ) {
+        let Some(next) = current
+            .next_node
+            .as_ref()
+            .filter(|next| !heads.contains(*next) && !ids.contains(next))
+            .and_then(|next| tree.nodes.get(next))
+        else {
+            break;
+        };
+        ids.push(next.id.clone());
+        current = next;
+    }
+    ids
+}
+
+/// Give each head a unique Yarn title
+fn yarn_titles(tree: &DialogueTree, heads: &[String]) -> HashMap<String, String> {
+    let mut titles = HashMap::new();
+    let mut used = HashSet::new();
+    let mut counter = 0;
+    for head in heads {
+        let is_identifier = head.starts_with(|c: char| c.is_ascii_alphabetic())
+            && head.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        let mut title = if *head == tree.start_node {
+            "Start".to_string()
+        } else if is_identifier {
+            head.clone()
+        } else {
+            String::new()
+        };
+        while title.is_empty() || used.contains(&title) {
+            counter += 1;
+            title = format!("Node{}", counter);
+        }
+        used.insert(title.clone());
+        titles.insert(head.clone(), title);
+    }
+    titles
+}
+
+fn write_chain(
+    out: &mut String,
+    tree: &DialogueTree,
+    head: &str,
+    titles: &HashMap<String, String>,
+) {
+    let heads: HashSet<String> = titles.keys().cloned().collect();
+    let ids = chain(tree, head, &heads);
+    let jump = |out: &mut String, indent: &str, target: Option<&String>| match target {
+        Some(target) if titles.contains_key(target) => {
+            let _ = writeln!(out, "{}<<jump {}>>", indent, titles[target]);
+        }
+        _ => {
+            let _ = writeln!(out, "{}<<stop>>", indent);
+        }
+    };
+
+    for (index, id) in ids.iter().enumerate() {
+        let node = &tree.nodes[id];
+        if let Some(action) = node.action.as_deref().filter(|a| !a.trim().is_empty()) {
+            write_action(out, action);
+        }
+
+        match node.node_type {
+            DialogueNodeType::Text => write_line(out, node),
+            DialogueNodeType::Choice => {
+                if !node.text.is_empty() {
+                    write_line(out, node);
+                }
+                for choice in &node.choices {
+                    let _ = write!(out, "-> {}", escape(&choice.text));
+                    if let Some(condition) = choice.condition.as_deref().filter(|c| !c.is_empty()) {
+                        let _ = write!(out, " <<if {}>>", yarn_condition(condition));
+                    }
+                    out.push('\n');
+                    jump(out, "    ", choice.next_node.as_ref());
+                }
+                return;
+            }
+            DialogueNodeType::Condition => {
+                let condition = node.condition.as_deref().unwrap_or("false");
+                let _ = writeln!(out, "<<if {}>>", yarn_condition(condition));
+                jump(out, "    ", node.next_node.as_ref());
+                out.push_str("<<else>>\n");
+                jump(out, "    ", node.false_node.as_ref());
+                out.push_str("<<endif>>\n");
+                return;
+            }
+            DialogueNodeType::Action => {}
+            DialogueNodeType::End => {
+                out.push_str("<<stop>>\n");
+                return;
+            }
+        }
+
+        // Continue inline, jump to another Yarn node, or end
+        let inline_next = ids.get(index + 1);
+        match &node.next_node {
+            Some(next) if Some(next) == inline_next => {}
+            Some(next) if titles.contains_key(next) => {
+                let _ = writeln!(out, "<<jump {}>>", titles[next]);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_line(out: &mut String, node: &DialogueNode) {
+    let tag = node
+        .loc_key
+        .as_deref()
+        .filter(|key| key.starts_with("line:"))
+        .map(|key| format!(" #{}", key))
+        .unwrap_or_default();
+    for line in node.text.lines() {
+        if node.speaker.is_empty() {
+            let _ = writeln!(out, "{}{}", escape(line), tag);
+        } else {
+            let _ = writeln!(out, "{}: {}{}", node.speaker, escape(line), tag);
+        }
+    }
+}
+
+fn write_action(out: &mut String, action: &str) {
+    let Ok(statements) = parse_action(action) else {
+        let _ = writeln!(out, "// action: {}", action);
+        return;
+    };
+    for statement in statements {
+        match statement {
+            Statement::Assign { name, op, value } => {
+                let value = match op {
+                    AssignOp::Set => yarn_expr(&value),
+                    AssignOp::Add => format!("${} + {}", name, yarn_operand(&value)),
+                    AssignOp::Sub => format!("${} - {}", name, yarn_operand(&value)),
+                    AssignOp::Mul => format!("${} * {}", name, yarn_operand(&value)),
+                    AssignOp::Div => format!("${} / {}", name, yarn_operand(&value)),
+                };
+                let _ = writeln!(out, "<<set ${} to {}>>", name, value);
+            }
+            Statement::Emit { name, args } => {
+                let _ = write!(out, "<<{}", name);
+                for arg in &args {
+                    match arg {
+                        Expr::Literal(_) => {
+                            let _ = write!(out, " {}", yarn_expr(arg));
+                        }
+                        _ => {
+                            let _ = write!(out, " {{{}}}", yarn_expr(arg));
+                        }
+                    }
+                }
+                out.push_str(">>\n");
+            }
+        }
+    }
+}
+
+fn yarn_condition(condition: &str) -> String {
+    match parse_condition(condition) {
+        Ok(expr) => yarn_expr(&expr),
+        Err(_) => condition.to_string(),
+    }
+}
+
+fn yarn_expr(expr: &Expr) -> String {
+    match expr {
+        Expr::Literal(DialogueValue::String(s)) => quote_str(s),
+        Expr::Literal(DialogueValue::Float(f)) => format!("{:?}", f),
+        Expr::Literal(value) => value.to_string(),
+        Expr::Var(name) => format!("${}", name),
+        Expr::Unary(UnaryOp::Not, operand) => format!("!{}", yarn_operand(operand)),
+        Expr::Unary(UnaryOp::Neg, operand) => format!("-{}", yarn_operand(operand)),
+        Expr::Binary(op, lhs, rhs) => {
+            let op = match op {
+                BinaryOp::Or => "||",
+                BinaryOp::And => "&&",
+                BinaryOp::Eq => "==",
+                BinaryOp::Ne => "!=",
+                BinaryOp::Lt => "<",
+                BinaryOp::Le => "<=",
+                BinaryOp::Gt => ">",
+                BinaryOp::Ge => ">=",
+                BinaryOp::Add => "+",
+                BinaryOp::Sub => "-",
+                BinaryOp::Mul => "*",
+                BinaryOp::Div => "/",
+                BinaryOp::Rem => "%",
+            };
+            format!("{} {} {}", yarn_operand(lhs), op, yarn_operand(rhs))
+        }
+    }
+}
+
+/// Format a sub-expression, parenthesizing binary operations
+fn yarn_operand(expr: &Expr) -> String {
+    match expr {
+        Expr::Binary(..) => format!("({})", yarn_expr(expr)),
+        _ => yarn_expr(expr),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '#' | '{' | '}' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DialogueIssue, DialogueVariables};
+
+    const SCRIPT: &str = r#"
+title: Start
+---
+// Greeting
+<<set $visits to $visits + 1>>
+Merchant: Welcome, traveler! #line:greet
+-> Buy something <<if $gold gte 10>>
+    <<set $gold to $gold - 10>>
+    <<give_item "rusty_key" 1>>
+    Merchant: Pleasure doing business.
+-> Leave
+    <<jump Farewell>>
+<<if $visits > 1>>
+    Merchant: Back again?
+<<else>>
+    Merchant: Come back soon.
+<<endif>>
+===
+title: Farewell
+---
+Merchant: Safe travels.
+<<stop>>
+===
+"#;
+
+    #[test]
+    fn test_import_structure() {
+        let tree = import_yarn(SCRIPT, "Merchant").unwrap();
+        // Falling off the end of a Yarn node ends the dialogue: dead ends are fine here
+        assert!(!tree.validate().iter().any(DialogueIssue::is_error));
+
+        let start = tree.get_start_node().unwrap();
+        assert_eq!(start.node_type, DialogueNodeType::Action);
+        assert_eq!(start.action.as_deref(), Some("visits = visits + 1"));
+
+        let choice = tree.get_node(start.next_node.as_ref().unwrap()).unwrap();
+        assert_eq!(choice.node_type, DialogueNodeType::Choice);
+        assert_eq!(choice.speaker, "Merchant");
+        assert_eq!(choice.text, "Welcome, traveler!");
+        assert_eq!(choice.loc_key.as_deref(), Some("line:greet"));
+        assert_eq!(choice.choices.len(), 2);
+        assert_eq!(choice.choices[0].condition.as_deref(), Some("gold >= 10"));
+
+        let buy = tree
+            .get_node(choice.choices[0].next_node.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(
+            buy.action.as_deref(),
+            Some("gold = gold - 10; give_item(\"rusty_key\", 1)")
+        );
+
+        let leave = tree
+            .get_node(choice.choices[1].next_node.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(leave.text, "Safe travels.");
+        let stop = tree.get_node(leave.next_node.as_ref().unwrap()).unwrap();
+        assert_eq!(stop.node_type, DialogueNodeType::End);
+    }
+
+    #[test]
+    fn test_import_runs() {
+        let tree = import_yarn(SCRIPT, "Merchant").unwrap();
+        let mut vars = DialogueVariables::new();
+        vars.set("gold", 15);
+        let mut signals = Vec::new();
+
+        let choice_id = tree
+            .resolve(Some(tree.start_node.as_str()), &mut vars, &mut signals)
+            .unwrap();
+        let next = tree.continue_from(&choice_id, 0, &vars).unwrap();
+        let thanks = tree.resolve(next, &mut vars, &mut signals).unwrap();
+        assert_eq!(
+            tree.get_node(&thanks).unwrap().text,
+            "Pleasure doing business."
+        );
+        assert_eq!(vars.get_int("gold"), Some(5));
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].name, "give_item");
+
+        // First visit takes the else branch
+        let next = tree.continue_from(&thanks, 0, &vars).unwrap();
+        let goodbye = tree.resolve(next, &mut vars, &mut signals).unwrap();
+        assert_eq!(tree.get_node(&goodbye).unwrap().text, "Come back soon.");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let tree = import_yarn(SCRIPT, "Merchant").unwrap();
+        let exported = export_yarn(&tree);
+        let reimported = import_yarn(&exported, "Merchant").unwrap();
+        assert!(!reimported.validate().iter().any(DialogueIssue::is_error));
+        assert_eq!(reimported.nodes.len(), tree.nodes.len());
+
+        let choice = reimported
+            .nodes
+            .values()
+            .find(|n| n.node_type == DialogueNodeType::Choice)
+            .unwrap();
+        assert_eq!(choice.text, "Welcome, traveler!");
+        assert_eq!(choice.loc_key.as_deref(), Some("line:greet"));
+        assert_eq!(choice.choices[0].condition.as_deref(), Some("gold >= 10"));
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(import_yarn("title: A\n---\nHello\n", "x").is_err());
+        assert!(import_yarn("title: A\n---\n<<jump B>>\n===\n", "x").is_err());
+        assert!(import_yarn("title: A\n---\n<<if $a>>\nHi\n===\n", "x").is_err());
+    }
+}
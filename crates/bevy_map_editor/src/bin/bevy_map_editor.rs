@@ -2,6 +2,8 @@
 //!
 //! Install with: cargo install bevy_map_editor
 //! Run with: bevy_map_editor
+//! Lint a project without opening a window (e.g. in CI):
+//! bevy_map_editor --lint path/to/project.map.json [--assets path/to/assets]
 
 use bevy::asset::{AssetPlugin, UnapprovedPathMode};
 use bevy::ecs::message::MessageReader;
@@ -17,6 +19,11 @@ use bevy_map_editor::EditorPlugin;
 use std::path::PathBuf;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--lint") {
+        std::process::exit(run_lint(&args[1..]));
+    }
+
     // Load preferences early to get saved window size
     let preferences = EditorPreferences::load();
     let window_width = preferences.window_width.unwrap_or(1920.0) as u32;
@@ -75,6 +82,63 @@ fn main() {
         .run();
 }
 
+/// Lint a project headless and print its problems
+///
+/// Returns the process exit code: 0 when there are no errors (warnings are
+/// allowed), 1 when there are errors, 2 on bad usage or an unloadable project.
+fn run_lint(args: &[String]) -> i32 {
+    let mut project_path = None;
+    let mut assets_path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--assets" => assets_path = args.next().map(PathBuf::from),
+            _ if project_path.is_none() => project_path = Some(PathBuf::from(arg)),
+            _ => {
+                eprintln!("Unexpected argument: {}", arg);
+                return 2;
+            }
+        }
+    }
+
+    let Some(project_path) = project_path else {
+        eprintln!("Usage: bevy_map_editor --lint <project.map.json> [--assets <dir>]");
+        return 2;
+    };
+    // Same default as the editor: ./assets
+    let assets_path = assets_path.unwrap_or_else(|| {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join("assets")
+    });
+
+    // Lint the file as saved: load() would drop orphaned data and migrate
+    let project = match Project::read(&project_path) {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("Failed to load '{}': {}", project_path.display(), e);
+            return 2;
+        }
+    };
+
+    let report = project.lint(Some(&assets_path));
+    for problem in &report.problems {
+        println!("{}", problem);
+    }
+    println!(
+        "{}: {} error(s), {} warning(s)",
+        project_path.display(),
+        report.error_count(),
+        report.warning_count()
+    );
+
+    if report.has_errors() {
+        1
+    } else {
+        0
+    }
+}
+
 /// System to auto-open the last project on startup if enabled in preferences
 fn auto_open_last_project(mut project: ResMut<Project>, preferences: Res<EditorPreferences>) {
     if !preferences.auto_open_last_project {
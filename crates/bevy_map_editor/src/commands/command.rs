@@ -2,10 +2,11 @@
 
 use bevy::prelude::*;
 use bevy_map_core::LayerData;
+use bevy_map_schema::Schema;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::project::Project;
+use crate::project::{MigratedProperties, Project};
 use crate::render::RenderState;
 
 /// A command that can be undone/redone
@@ -100,6 +101,86 @@ impl Command for BatchTileCommand {
     }
 }
 
+/// Several commands undone and redone as one step (e.g. a terrain stroke
+/// that paints more than one layer)
+pub struct CompositeCommand {
+    commands: Vec<Box<dyn Command>>,
+    description: String,
+}
+
+impl CompositeCommand {
+    /// Create a composite command; commands run in order and undo in reverse
+    pub fn new(commands: Vec<Box<dyn Command>>, description: impl Into<String>) -> Self {
+        Self {
+            commands,
+            description: description.into(),
+        }
+    }
+}
+
+impl Command for CompositeCommand {
+    fn execute(&self, project: &mut Project, render_state: &mut RenderState) {
+        for command in &self.commands {
+            command.execute(project, render_state);
+        }
+    }
+
+    fn undo(&self, project: &mut Project, render_state: &mut RenderState) {
+        for command in self.commands.iter().rev() {
+            command.undo(project, render_state);
+        }
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Command for pointing a tile layer at a different tileset (e.g. when an
+/// empty layer is first painted with a terrain from another tileset)
+pub struct SetLayerTilesetCommand {
+    pub level_id: Uuid,
+    pub layer_idx: usize,
+    pub old_tileset: Uuid,
+    pub new_tileset: Uuid,
+}
+
+impl SetLayerTilesetCommand {
+    pub fn new(level_id: Uuid, layer_idx: usize, old_tileset: Uuid, new_tileset: Uuid) -> Self {
+        Self {
+            level_id,
+            layer_idx,
+            old_tileset,
+            new_tileset,
+        }
+    }
+
+    fn set(&self, project: &mut Project, render_state: &mut RenderState, tileset: Uuid) {
+        if let Some(level) = project.get_level_mut(self.level_id) {
+            if let Some(layer) = level.layers.get_mut(self.layer_idx) {
+                if let LayerData::Tiles { tileset_id, .. } = &mut layer.data {
+                    *tileset_id = tileset;
+                }
+            }
+        }
+        render_state.needs_rebuild = true;
+    }
+}
+
+impl Command for SetLayerTilesetCommand {
+    fn execute(&self, project: &mut Project, render_state: &mut RenderState) {
+        self.set(project, render_state, self.new_tileset);
+    }
+
+    fn undo(&self, project: &mut Project, render_state: &mut RenderState) {
+        self.set(project, render_state, self.old_tileset);
+    }
+
+    fn description(&self) -> &str {
+        "Set Layer Tileset"
+    }
+}
+
 /// Collect tiles in a rectangular region for undo tracking
 pub fn collect_tiles_in_region(
     project: &Project,
@@ -180,6 +261,53 @@ impl Command for MoveEntityCommand {
     }
 }
 
+/// Command for a schema edit that migrates every level entity and data instance
+pub struct SchemaMigrationCommand {
+    old_schema: Schema,
+    new_schema: Schema,
+    old_schema_revision: Option<u32>,
+    migrated: MigratedProperties,
+    description: String,
+}
+
+impl SchemaMigrationCommand {
+    /// Replace the project's schema with `new_schema`, migrating instances
+    /// through the migrations it recorded after the current schema revision
+    pub fn new(project: &Project, new_schema: Schema, description: impl Into<String>) -> Self {
+        let migrated = project.migrated_properties(&new_schema, project.schema.revision);
+        Self {
+            old_schema: project.schema.clone(),
+            new_schema,
+            old_schema_revision: project.schema_revision,
+            migrated,
+            description: description.into(),
+        }
+    }
+
+    /// Number of instances whose properties change
+    pub fn migrated_count(&self) -> usize {
+        self.migrated.len()
+    }
+}
+
+impl Command for SchemaMigrationCommand {
+    fn execute(&self, project: &mut Project, _render_state: &mut RenderState) {
+        project.schema = self.new_schema.clone();
+        project.schema_revision = Some(self.new_schema.revision);
+        project.apply_migrated_properties(&self.migrated, false);
+    }
+
+    fn undo(&self, project: &mut Project, _render_state: &mut RenderState) {
+        project.schema = self.old_schema.clone();
+        project.schema_revision = self.old_schema_revision;
+        project.apply_migrated_properties(&self.migrated, true);
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
 /// Stores command history for undo/redo
 #[derive(Resource, Default)]
 pub struct CommandHistory {
@@ -6,6 +6,7 @@ mod shortcuts;
 
 pub use clipboard::TileClipboard;
 pub use command::{
-    collect_tiles_in_region, BatchTileCommand, Command, CommandHistory, MoveEntityCommand,
+    collect_tiles_in_region, BatchTileCommand, Command, CommandHistory, CompositeCommand,
+    MoveEntityCommand, SchemaMigrationCommand, SetLayerTilesetCommand,
 };
 pub use shortcuts::handle_keyboard_shortcuts;
@@ -52,9 +52,10 @@ use project::Project;
 use render::MapRenderPlugin;
 use tools::EditorToolsPlugin;
 use ui::{
-    AnimationEditorState, CodePreviewDialogState, DialogueEditorState, EditorTool, EditorUiPlugin,
-    EntityPaintState, GameSettingsDialogState, PendingAction, SchemaEditorState, Selection,
-    SpriteSheetEditorState, TerrainPaintState, TilesetEditorState, ToolMode,
+    AnimationEditorState, AutomapEditorState, CodePreviewDialogState, DialogueEditorState,
+    EditorTool, EditorUiPlugin, EntityPaintState, GameSettingsDialogState, PendingAction,
+    ProblemsState, SchemaEditorState, Selection, SpriteSheetEditorState, TerrainPaintState,
+    TilesetEditorState, ToolMode,
 };
 
 /// Error types for asset path handling
@@ -570,13 +571,8 @@ pub struct EditorState {
     /// Name for new stamp being created
     pub new_stamp_name: String,
 
-    // Autotile / Terrain (Legacy 47-tile blob)
-    pub selected_terrain: Option<uuid::Uuid>,
-    pub show_new_terrain_dialog: bool,
-    pub new_terrain_name: String,
-    pub new_terrain_first_tile: u32,
-
     // Tiled-Style Terrain System
+    pub new_terrain_name: String,
     pub selected_terrain_set: Option<uuid::Uuid>,
     pub selected_terrain_in_set: Option<usize>,
     pub show_new_terrain_set_dialog: bool,
@@ -588,6 +584,10 @@ pub struct EditorState {
     pub show_tileset_editor: bool,
     pub tileset_editor_state: TilesetEditorState,
 
+    // Automap Rule Editor
+    pub show_automap_editor: bool,
+    pub automap_editor_state: AutomapEditorState,
+
     // Physics Layer Editor
     pub show_add_physics_layer_set_dialog: bool,
     pub new_physics_layer_name: String,
@@ -608,6 +608,11 @@ pub struct EditorState {
     pub dialogue_editor_state: DialogueEditorState,
     /// ID of dialogue asset being edited (vs inline property)
     pub dialogue_editor_asset_id: Option<String>,
+    /// Show the project-wide Dialogue Problems window
+    pub show_dialogue_validation: bool,
+    /// Show the project-wide Problems (lint) window
+    pub show_problems: bool,
+    pub problems_state: ProblemsState,
 
     // Terrain painting palette
     pub terrain_paint_state: TerrainPaintState,
@@ -761,11 +766,7 @@ impl Default for EditorState {
             show_stamp_library: false,
             new_stamp_name: String::new(),
 
-            selected_terrain: None,
-            show_new_terrain_dialog: false,
             new_terrain_name: String::new(),
-            new_terrain_first_tile: 0,
-
             selected_terrain_set: None,
             selected_terrain_in_set: None,
             show_new_terrain_set_dialog: false,
@@ -776,6 +777,9 @@ impl Default for EditorState {
             show_tileset_editor: false,
             tileset_editor_state: TilesetEditorState::default(),
 
+            show_automap_editor: false,
+            automap_editor_state: AutomapEditorState::new(),
+
             show_add_physics_layer_set_dialog: false,
             new_physics_layer_name: String::new(),
             new_physics_layer_mask: 0,
@@ -791,6 +795,9 @@ impl Default for EditorState {
             show_dialogue_editor: false,
             dialogue_editor_state: DialogueEditorState::new(),
             dialogue_editor_asset_id: None,
+            show_dialogue_validation: false,
+            show_problems: false,
+            problems_state: ProblemsState::default(),
 
             terrain_paint_state: TerrainPaintState::new(),
 
@@ -28,8 +28,11 @@ impl std::fmt::Display for ProjectError {
 impl std::error::Error for ProjectError {}
 
 impl Project {
-    /// Load project from file
-    pub fn load(path: &Path) -> Result<Self, ProjectError> {
+    /// Read a project file exactly as saved
+    ///
+    /// Unlike [`Project::load`], nothing is cleaned up or migrated, so e.g.
+    /// the lint pass sees the problems `load` would silently fix.
+    pub fn read(path: &Path) -> Result<Self, ProjectError> {
         let content =
             std::fs::read_to_string(path).map_err(|e| ProjectError::IoError(e.to_string()))?;
 
@@ -41,9 +44,49 @@ impl Project {
         // Build lookup indices for O(1) access to levels, tilesets, etc.
         project.rebuild_indices();
 
+        Ok(project)
+    }
+
+    /// Load project from file
+    pub fn load(path: &Path) -> Result<Self, ProjectError> {
+        let mut project = Self::read(path)?;
+
         // Validate and clean up any orphaned references (e.g., terrain sets pointing to deleted tilesets)
         project.validate_and_cleanup();
 
+        // Convert legacy 47-tile terrains to Wang terrain sets
+        let migrated = project
+            .autotile_config
+            .migrate_legacy_terrains(&mut project.levels);
+        if !migrated.is_empty() {
+            bevy::log::info!(
+                "Migrated {} legacy terrain(s) to terrain sets",
+                migrated.len()
+            );
+            project.dirty = true;
+        }
+
+        // Bring instances saved with an older schema revision up to date
+        let migrated = project.migrate_instances();
+        if migrated > 0 {
+            bevy::log::info!(
+                "Migrated {} instance(s) to schema revision {}",
+                migrated,
+                project.schema.revision
+            );
+            project.dirty = true;
+        }
+
+        // Pin translation keys of choices saved before choices had ids
+        let assigned: usize = project
+            .dialogues
+            .iter_mut()
+            .map(|tree| tree.assign_choice_ids())
+            .sum();
+        if assigned > 0 {
+            project.dirty = true;
+        }
+
         // Only mark dirty if we haven't modified anything
         // (validate_and_cleanup sets dirty=true if it removes orphaned data)
         if !project.dirty {
@@ -55,6 +98,7 @@ impl Project {
 
     /// Save project to file
     pub fn save(&mut self, path: &Path) -> Result<(), ProjectError> {
+        self.schema_revision = Some(self.schema.revision);
         let content = serde_json::to_string_pretty(self)
             .map_err(|e| ProjectError::SerializeError(e.to_string()))?;
 
@@ -71,6 +115,7 @@ impl Project {
                     output_dir,
                     generate_entities: self.game_config.generate_entities,
                     generate_enums: self.game_config.generate_enums,
+                    generate_data_types: self.game_config.generate_data_types,
                     generate_stubs: self.game_config.generate_stubs,
                     generate_behaviors: self.game_config.generate_behaviors,
                     generate_health: false,
@@ -0,0 +1,818 @@
+//! Project-wide lint pass
+//!
+//! [`Project::lint`] checks the whole project without modifying it: schema and
+//! property diagnostics, dangling references, unknown entity types, tile
+//! indices beyond their tileset, missing image files, sprite configs pointing
+//! at missing sheets or animations, dialogue problems and unused tilesets.
+//! It needs no running editor, so it can also run headless (e.g. in CI).
+
+use super::Project;
+use bevy_map_core::{tile_index, LayerData, Value, OCCUPIED_CELL};
+use bevy_map_dialogue::DialogueSeverity;
+use bevy_map_schema::{diagnose_instance, diagnose_schema, PropType, Severity, UniqueValues};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use uuid::Uuid;
+
+/// How serious a [`LintProblem`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintSeverity {
+    /// The project is broken (will fail or misbehave at runtime)
+    Error,
+    /// The project works, but probably not as intended
+    Warning,
+}
+
+/// Machine-readable kind of a [`LintProblem`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintCode {
+    /// The schema itself is inconsistent
+    Schema,
+    /// A property value doesn't match its schema definition
+    InvalidProperty,
+    /// A level entity's type isn't in the schema
+    UnknownEntityType,
+    /// A data instance's type isn't in the schema
+    UnknownDataType,
+    /// A reference points at a data instance that doesn't exist
+    DanglingRef,
+    /// A reference points at a data instance of the wrong type
+    WrongRefType,
+    /// A tile layer uses a tileset that doesn't exist
+    MissingTileset,
+    /// A tile index is beyond its tileset's tile count
+    TileOutOfRange,
+    /// An image file referenced by the project doesn't exist
+    MissingImage,
+    /// A sprite config uses a sprite sheet that doesn't exist
+    MissingSpriteSheet,
+    /// A sprite config's default animation isn't in its sprite sheet
+    MissingAnimation,
+    /// A dialogue tree has a validation issue
+    Dialogue,
+    /// A tileset isn't used by any layer, terrain set or stamp
+    UnusedTileset,
+    /// Instances were saved with an older schema revision and still need
+    /// migrating
+    PendingMigration,
+}
+
+impl LintCode {
+    /// The code as a stable snake_case string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintCode::Schema => "schema",
+            LintCode::InvalidProperty => "invalid_property",
+            LintCode::UnknownEntityType => "unknown_entity_type",
+            LintCode::UnknownDataType => "unknown_data_type",
+            LintCode::DanglingRef => "dangling_ref",
+            LintCode::WrongRefType => "wrong_ref_type",
+            LintCode::MissingTileset => "missing_tileset",
+            LintCode::TileOutOfRange => "tile_out_of_range",
+            LintCode::MissingImage => "missing_image",
+            LintCode::MissingSpriteSheet => "missing_sprite_sheet",
+            LintCode::MissingAnimation => "missing_animation",
+            LintCode::Dialogue => "dialogue",
+            LintCode::UnusedTileset => "unused_tileset",
+            LintCode::PendingMigration => "pending_migration",
+        }
+    }
+}
+
+/// What a [`LintProblem`] is about, for navigating to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintTarget {
+    /// The project as a whole
+    Project,
+    Level(Uuid),
+    /// level_id, layer_index
+    Layer(Uuid, usize),
+    /// level_id, entity_id
+    Entity(Uuid, Uuid),
+    DataInstance(Uuid),
+    DataType(String),
+    Tileset(Uuid),
+    TerrainSet(Uuid),
+    Stamp(Uuid),
+    SpriteSheet(Uuid),
+    /// dialogue_id, node_id
+    Dialogue(String, Option<String>),
+}
+
+/// A single problem found by [`Project::lint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintProblem {
+    pub severity: LintSeverity,
+    pub code: LintCode,
+    pub target: LintTarget,
+    /// Human-readable location, e.g. "Level 'Cave' > Entity 'Bat'"
+    pub location: String,
+    pub message: String,
+}
+
+impl LintProblem {
+    /// Whether this problem is an error
+    pub fn is_error(&self) -> bool {
+        self.severity == LintSeverity::Error
+    }
+}
+
+impl fmt::Display for LintProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            severity,
+            self.code.as_str(),
+            self.location,
+            self.message
+        )
+    }
+}
+
+/// All problems found by one lint pass
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintReport {
+    pub problems: Vec<LintProblem>,
+}
+
+impl LintReport {
+    /// Check if any problem is an error
+    pub fn has_errors(&self) -> bool {
+        self.problems.iter().any(|p| p.is_error())
+    }
+
+    /// Number of errors
+    pub fn error_count(&self) -> usize {
+        self.problems.iter().filter(|p| p.is_error()).count()
+    }
+
+    /// Number of warnings
+    pub fn warning_count(&self) -> usize {
+        self.problems.len() - self.error_count()
+    }
+
+    fn push(
+        &mut self,
+        severity: LintSeverity,
+        code: LintCode,
+        target: LintTarget,
+        location: String,
+        message: String,
+    ) {
+        self.problems.push(LintProblem {
+            severity,
+            code,
+            target,
+            location,
+            message,
+        });
+    }
+}
+
+impl Project {
+    /// Check the whole project for problems without changing it
+    ///
+    /// Image paths are resolved against `assets_path` (absolute paths are used
+    /// as-is); pass `None` to skip the file checks. Errors come before
+    /// warnings, otherwise problems keep the project's order.
+    pub fn lint(&self, assets_path: Option<&Path>) -> LintReport {
+        let mut report = LintReport::default();
+
+        self.lint_schema(&mut report);
+        let unique = self.unique_values();
+        self.lint_levels(&mut report, &unique);
+        self.lint_data(&mut report, &unique);
+        self.lint_migrations(&mut report);
+        self.lint_tilesets(&mut report, assets_path);
+        self.lint_sprites(&mut report, assets_path);
+        self.lint_dialogues(&mut report);
+
+        report.problems.sort_by_key(|p| !p.is_error());
+        report
+    }
+
+    fn lint_schema(&self, report: &mut LintReport) {
+        for diagnostic in diagnose_schema(&self.schema).items {
+            // Paths are "/Type/property"
+            let type_name = diagnostic
+                .path
+                .trim_start_matches('/')
+                .split('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            report.push(
+                schema_severity(diagnostic.severity),
+                LintCode::Schema,
+                LintTarget::DataType(type_name.clone()),
+                format!("Type '{}'", type_name),
+                diagnostic.message,
+            );
+        }
+    }
+
+    /// Collect the values of `unique` properties of all entities and data instances
+    pub fn unique_values(&self) -> UniqueValues {
+        let mut unique = UniqueValues::new();
+        for level in &self.levels {
+            let level_key = level.id.to_string();
+            for entity in &level.entities {
+                let json = to_json_map(&entity.properties);
+                unique.add(&self.schema, Some(&level_key), &entity.type_name, &json);
+            }
+        }
+        for instances in self.data.instances.values() {
+            for instance in instances {
+                let json = to_json_map(&instance.properties);
+                unique.add(&self.schema, None, &instance.type_name, &json);
+            }
+        }
+        unique
+    }
+
+    fn lint_levels(&self, report: &mut LintReport, unique: &UniqueValues) {
+        for level in &self.levels {
+            let level_key = level.id.to_string();
+            for (layer_index, layer) in level.layers.iter().enumerate() {
+                let LayerData::Tiles {
+                    tileset_id, tiles, ..
+                } = &layer.data
+                else {
+                    continue;
+                };
+                let location = format!("Level '{}' > Layer '{}'", level.name, layer.name);
+                let target = LintTarget::Layer(level.id, layer_index);
+
+                let Some(tileset) = self.get_tileset(*tileset_id) else {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::MissingTileset,
+                        target,
+                        location,
+                        format!("Uses tileset {} which doesn't exist", tileset_id),
+                    );
+                    continue;
+                };
+
+                let total = tileset.total_tile_count();
+                let out_of_range: Vec<u32> = tiles
+                    .iter()
+                    .flatten()
+                    .filter(|&&tile| tile != OCCUPIED_CELL && tile_index(tile) >= total)
+                    .map(|&tile| tile_index(tile))
+                    .collect();
+                if let Some(max) = out_of_range.iter().max() {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::TileOutOfRange,
+                        target,
+                        location,
+                        format!(
+                            "{} tile(s) beyond tileset '{}' ({} tiles, highest index {})",
+                            out_of_range.len(),
+                            tileset.name,
+                            total,
+                            max
+                        ),
+                    );
+                }
+            }
+
+            for entity in &level.entities {
+                let location = format!(
+                    "Level '{}' > Entity '{}'",
+                    level.name,
+                    display_name(&entity.type_name, &entity.properties)
+                );
+                let target = LintTarget::Entity(level.id, entity.id);
+                if self.schema.get_type(&entity.type_name).is_none() {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::UnknownEntityType,
+                        target,
+                        location,
+                        format!("Unknown entity type '{}'", entity.type_name),
+                    );
+                    continue;
+                }
+                self.lint_properties(
+                    report,
+                    unique,
+                    Some(&level_key),
+                    &entity.type_name,
+                    &entity.properties,
+                    &target,
+                    &location,
+                );
+            }
+        }
+    }
+
+    fn lint_data(&self, report: &mut LintReport, unique: &UniqueValues) {
+        let mut type_names: Vec<_> = self.data.instances.keys().collect();
+        type_names.sort();
+        for type_name in type_names {
+            for instance in self.data.get_by_type(type_name) {
+                let location = format!(
+                    "Data '{}'",
+                    display_name(&instance.type_name, &instance.properties)
+                );
+                let target = LintTarget::DataInstance(instance.id);
+                if self.schema.get_type(&instance.type_name).is_none() {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::UnknownDataType,
+                        target,
+                        location,
+                        format!("Unknown data type '{}'", instance.type_name),
+                    );
+                    continue;
+                }
+                self.lint_properties(
+                    report,
+                    unique,
+                    None,
+                    &instance.type_name,
+                    &instance.properties,
+                    &target,
+                    &location,
+                );
+            }
+        }
+    }
+
+    /// Schema diagnostics plus uniqueness and reference checks for one
+    /// instance's properties
+    fn lint_properties(
+        &self,
+        report: &mut LintReport,
+        unique: &UniqueValues,
+        level_key: Option<&str>,
+        type_name: &str,
+        properties: &HashMap<String, Value>,
+        target: &LintTarget,
+        location: &str,
+    ) {
+        let json = to_json_map(properties);
+        let diagnostics = diagnose_instance(&self.schema, type_name, &json)
+            .items
+            .into_iter()
+            .chain(
+                unique
+                    .diagnose(&self.schema, level_key, type_name, &json)
+                    .items,
+            );
+        for diagnostic in diagnostics {
+            report.push(
+                schema_severity(diagnostic.severity),
+                LintCode::InvalidProperty,
+                target.clone(),
+                location.to_string(),
+                diagnostic.to_string(),
+            );
+        }
+
+        for prop in self.schema.all_properties(type_name) {
+            let Some(value) = properties.get(&prop.name) else {
+                continue;
+            };
+            let (ref_type, ids): (Option<&str>, Vec<&str>) = match prop.prop_type {
+                PropType::Ref => (
+                    prop.ref_type.as_deref(),
+                    value.as_string().into_iter().collect(),
+                ),
+                PropType::Array => match &prop.item_type {
+                    Some(item_type) if self.schema.data_types.contains_key(item_type) => (
+                        Some(item_type.as_str()),
+                        value
+                            .as_array()
+                            .map(|items| items.iter().filter_map(|v| v.as_string()).collect())
+                            .unwrap_or_default(),
+                    ),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            for id in ids.into_iter().filter(|id| !id.is_empty()) {
+                let instance = Uuid::parse_str(id)
+                    .ok()
+                    .and_then(|uuid| self.get_data_instance(uuid));
+                match instance {
+                    None => report.push(
+                        LintSeverity::Error,
+                        LintCode::DanglingRef,
+                        target.clone(),
+                        location.to_string(),
+                        format!(
+                            "Property '{}' references missing instance {}",
+                            prop.name, id
+                        ),
+                    ),
+                    Some(instance) => {
+                        let Some(ref_type) = ref_type else {
+                            continue;
+                        };
+                        let matches = instance.type_name == ref_type
+                            || self
+                                .schema
+                                .base_types(&instance.type_name)
+                                .contains(&ref_type);
+                        if !matches {
+                            report.push(
+                                LintSeverity::Warning,
+                                LintCode::WrongRefType,
+                                target.clone(),
+                                location.to_string(),
+                                format!(
+                                    "Property '{}' should reference a {} but references a {}",
+                                    prop.name, ref_type, instance.type_name
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn lint_migrations(&self, report: &mut LintReport) {
+        let Some(since) = self
+            .schema_revision
+            .filter(|&revision| revision < self.schema.revision)
+        else {
+            return;
+        };
+        let pending = self.migrated_properties(&self.schema, since).len();
+        if pending > 0 {
+            report.push(
+                LintSeverity::Warning,
+                LintCode::PendingMigration,
+                LintTarget::Project,
+                "Project".to_string(),
+                format!(
+                    "{} instance(s) were saved with schema revision {} and will be migrated to \
+                     revision {} when the project is opened",
+                    pending, since, self.schema.revision
+                ),
+            );
+        }
+    }
+
+    fn lint_tilesets(&self, report: &mut LintReport, assets_path: Option<&Path>) {
+        // The editor drops terrain sets without a tileset when it opens a project
+        for set in &self.autotile_config.terrain_sets {
+            if self.get_tileset(set.tileset_id).is_none() {
+                report.push(
+                    LintSeverity::Error,
+                    LintCode::MissingTileset,
+                    LintTarget::TerrainSet(set.id),
+                    format!("Terrain set '{}'", set.name),
+                    format!("Uses tileset {} which doesn't exist", set.tileset_id),
+                );
+            }
+        }
+        for stamp in &self.stamps {
+            if self.get_tileset(stamp.tileset_id).is_none() {
+                report.push(
+                    LintSeverity::Error,
+                    LintCode::MissingTileset,
+                    LintTarget::Stamp(stamp.id),
+                    format!("Stamp '{}'", stamp.name),
+                    format!("Uses tileset {} which doesn't exist", stamp.tileset_id),
+                );
+            }
+        }
+
+        let mut used: HashSet<Uuid> = HashSet::new();
+        for level in &self.levels {
+            for layer in &level.layers {
+                if let LayerData::Tiles { tileset_id, .. } = &layer.data {
+                    used.insert(*tileset_id);
+                }
+            }
+        }
+        used.extend(
+            self.autotile_config
+                .terrain_sets
+                .iter()
+                .map(|set| set.tileset_id),
+        );
+        used.extend(
+            self.autotile_config
+                .terrains
+                .iter()
+                .map(|terrain| terrain.tileset_id),
+        );
+        used.extend(self.stamps.iter().map(|stamp| stamp.tileset_id));
+
+        for tileset in &self.tilesets {
+            let location = format!("Tileset '{}'", tileset.name);
+            let target = LintTarget::Tileset(tileset.id);
+
+            if let Some(assets_path) = assets_path {
+                // The legacy path only matters when there are no images
+                let legacy = tileset
+                    .path
+                    .as_deref()
+                    .filter(|_| tileset.images.is_empty());
+                let paths = tileset
+                    .images
+                    .iter()
+                    .map(|image| image.path.as_str())
+                    .chain(legacy);
+                for path in paths {
+                    if !image_exists(assets_path, path) {
+                        report.push(
+                            LintSeverity::Error,
+                            LintCode::MissingImage,
+                            target.clone(),
+                            location.clone(),
+                            format!("Image file '{}' not found", path),
+                        );
+                    }
+                }
+            }
+
+            if !used.contains(&tileset.id) {
+                report.push(
+                    LintSeverity::Warning,
+                    LintCode::UnusedTileset,
+                    target,
+                    location,
+                    "Not used by any layer, terrain set or stamp".to_string(),
+                );
+            }
+        }
+    }
+
+    fn lint_sprites(&self, report: &mut LintReport, assets_path: Option<&Path>) {
+        if let Some(assets_path) = assets_path {
+            for sheet in &self.sprite_sheets {
+                if !image_exists(assets_path, &sheet.sheet_path) {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::MissingImage,
+                        LintTarget::SpriteSheet(sheet.id),
+                        format!("Sprite sheet '{}'", sheet.name),
+                        format!("Image file '{}' not found", sheet.sheet_path),
+                    );
+                }
+            }
+
+            let mut type_names: Vec<_> = self.schema.data_types.keys().collect();
+            type_names.sort();
+            for type_name in type_names {
+                let Some(icon) = &self.schema.data_types[type_name].icon else {
+                    continue;
+                };
+                if !image_exists(assets_path, icon) {
+                    report.push(
+                        LintSeverity::Warning,
+                        LintCode::MissingImage,
+                        LintTarget::DataType(type_name.clone()),
+                        format!("Type '{}'", type_name),
+                        format!("Icon file '{}' not found", icon),
+                    );
+                }
+            }
+        }
+
+        let mut configs: Vec<_> = self.entity_type_configs.iter().collect();
+        configs.sort_by_key(|(type_name, _)| type_name.as_str());
+        for (type_name, config) in configs {
+            let Some(sprite) = &config.sprite else {
+                continue;
+            };
+            let Some(sheet_id) = sprite.sprite_sheet_id else {
+                continue;
+            };
+            let location = format!("Type '{}' > Sprite", type_name);
+            let target = LintTarget::DataType(type_name.clone());
+            let Some(sheet) = self.get_sprite_sheet(sheet_id) else {
+                report.push(
+                    LintSeverity::Error,
+                    LintCode::MissingSpriteSheet,
+                    target,
+                    location,
+                    format!("Uses sprite sheet {} which doesn't exist", sheet_id),
+                );
+                continue;
+            };
+            if let Some(animation) = &sprite.default_animation {
+                if !sheet.animations.contains_key(animation) {
+                    report.push(
+                        LintSeverity::Error,
+                        LintCode::MissingAnimation,
+                        target,
+                        location,
+                        format!(
+                            "Default animation '{}' not found in sprite sheet '{}'",
+                            animation, sheet.name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn lint_dialogues(&self, report: &mut LintReport) {
+        for dialogue in &self.dialogues {
+            for issue in dialogue.validate() {
+                let severity = match issue.severity {
+                    DialogueSeverity::Error => LintSeverity::Error,
+                    DialogueSeverity::Warning => LintSeverity::Warning,
+                };
+                report.push(
+                    severity,
+                    LintCode::Dialogue,
+                    LintTarget::Dialogue(dialogue.id.clone(), issue.node_id.clone()),
+                    format!("Dialogue '{}'", dialogue.name),
+                    issue.to_string(),
+                );
+            }
+        }
+    }
+}
+
+fn schema_severity(severity: Severity) -> LintSeverity {
+    match severity {
+        Severity::Error => LintSeverity::Error,
+        Severity::Warning => LintSeverity::Warning,
+    }
+}
+
+fn to_json_map(properties: &HashMap<String, Value>) -> HashMap<String, serde_json::Value> {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_json()))
+        .collect()
+}
+
+/// The "name" property if set, otherwise the type name
+fn display_name(type_name: &str, properties: &HashMap<String, Value>) -> String {
+    properties
+        .get("name")
+        .and_then(|v| v.as_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(type_name)
+        .to_string()
+}
+
+/// Check an image path relative to the assets folder (or absolute)
+fn image_exists(assets_path: &Path, path: &str) -> bool {
+    if path.is_empty() {
+        return true;
+    }
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.exists()
+    } else {
+        assets_path.join(path).exists()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{DataInstance, TileStamp};
+    use bevy_map_core::{EntityInstance, Layer, Level, Tileset};
+    use bevy_map_schema::parse_schema;
+
+    #[test]
+    fn test_lint_project() {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Item": { "properties": [] },
+                "Chest": {
+                    "placeable": true,
+                    "properties": [
+                        { "name": "item", "type": "ref", "refType": "Item" }
+                    ]
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+        let mut project = Project::new(schema);
+
+        let tileset = Tileset::new("Ground".to_string(), "ground.png".to_string(), 16, 2, 2);
+        let unused = Tileset::new_empty("Unused".to_string(), 16);
+        let mut level = Level::new("Cave".to_string(), 2, 1);
+        let mut layer = Layer::new_tile_layer("Tiles".to_string(), tileset.id, 2, 1);
+        if let LayerData::Tiles { tiles, .. } = &mut layer.data {
+            tiles[0] = Some(5);
+        }
+        level.layers.push(layer);
+
+        let item = DataInstance::new("Item".to_string());
+        let mut chest = EntityInstance::new("Chest".to_string(), [0.0, 0.0]);
+        chest.set_string("item", Uuid::new_v4().to_string());
+        let mut good_chest = EntityInstance::new("Chest".to_string(), [0.0, 0.0]);
+        good_chest.set_string("item", item.id.to_string());
+        level.add_entity(chest);
+        level.add_entity(good_chest);
+        level.add_entity(EntityInstance::new("Ghost".to_string(), [0.0, 0.0]));
+
+        project.add_data_instance(item);
+        project.add_tileset(tileset);
+        project.add_tileset(unused);
+        project.add_level(level);
+
+        let report = project.lint(None);
+        let codes: Vec<_> = report.problems.iter().map(|p| p.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                LintCode::TileOutOfRange,
+                LintCode::DanglingRef,
+                LintCode::UnknownEntityType,
+                LintCode::UnusedTileset,
+            ]
+        );
+        assert_eq!(report.error_count(), 3);
+        assert_eq!(report.warning_count(), 1);
+    }
+
+    #[test]
+    fn test_lint_unmigrated_file() {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "revision": 2,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Item": {
+                    "properties": [{ "name": "health", "type": "int" }]
+                }
+            },
+            "migrations": [{
+                "revision": 2,
+                "steps": [{ "op": "rename", "type": "Item", "from": "hp", "to": "health" }]
+            }]
+        }"#,
+        )
+        .unwrap();
+        let mut project = Project::new(schema);
+        project.schema_revision = Some(1);
+
+        let mut item = DataInstance::new("Item".to_string());
+        item.properties.insert("hp".to_string(), Value::Int(3));
+        project.add_data_instance(item);
+
+        let missing = Uuid::new_v4();
+        let terrain_set = bevy_map_autotile::TerrainSet::new(
+            "Ground".to_string(),
+            missing,
+            bevy_map_autotile::TerrainSetType::Corner,
+        );
+        let terrain_set_id = terrain_set.id;
+        project.autotile_config.add_terrain_set(terrain_set);
+        let stamp = TileStamp::new("Rock".to_string(), 1, 1, missing);
+        let stamp_id = stamp.id;
+        project.stamps.push(stamp);
+
+        // Written without save(), which would stamp the current schema revision
+        let path = std::env::temp_dir().join(format!("lint_{}.map.json", Uuid::new_v4()));
+        std::fs::write(&path, serde_json::to_string_pretty(&project).unwrap()).unwrap();
+
+        let read = Project::read(&path).unwrap();
+        let targets: Vec<_> = read
+            .lint(None)
+            .problems
+            .into_iter()
+            .filter(|p| p.code != LintCode::InvalidProperty)
+            .map(|p| (p.code, p.target))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (
+                    LintCode::MissingTileset,
+                    LintTarget::TerrainSet(terrain_set_id)
+                ),
+                (LintCode::MissingTileset, LintTarget::Stamp(stamp_id)),
+                (LintCode::PendingMigration, LintTarget::Project),
+            ]
+        );
+
+        // load() silently fixes both
+        let loaded = Project::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(loaded.autotile_config.terrain_sets.is_empty());
+        assert!(!loaded
+            .lint(None)
+            .problems
+            .iter()
+            .any(|p| p.code == LintCode::PendingMigration));
+    }
+}
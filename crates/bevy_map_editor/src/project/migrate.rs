@@ -0,0 +1,244 @@
+//! Applying schema migrations to level entities and data instances
+
+use super::Project;
+use bevy_map_core::Value;
+use bevy_map_schema::{migrate_properties, Schema};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Instance properties before and after a migration
+pub type PropertyChange = (HashMap<String, Value>, HashMap<String, Value>);
+
+/// The instances a migration changes
+#[derive(Debug, Clone, Default)]
+pub struct MigratedProperties {
+    /// (level id, entity id) -> change
+    pub entities: HashMap<(Uuid, Uuid), PropertyChange>,
+    /// data instance id -> change
+    pub data: HashMap<Uuid, PropertyChange>,
+}
+
+impl MigratedProperties {
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.data.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len() + self.data.len()
+    }
+}
+
+impl Project {
+    /// Work out how `schema`'s migrations after revision `since` change the
+    /// project's level entities and data instances, without applying them
+    pub fn migrated_properties(&self, schema: &Schema, since: u32) -> MigratedProperties {
+        let mut migrated = MigratedProperties::default();
+        for level in &self.levels {
+            for entity in &level.entities {
+                if let Some(new) = migrate(schema, since, &entity.type_name, &entity.properties) {
+                    migrated
+                        .entities
+                        .insert((level.id, entity.id), (entity.properties.clone(), new));
+                }
+            }
+        }
+        for instance in self.data.all_instances() {
+            if let Some(new) = migrate(schema, since, &instance.type_name, &instance.properties) {
+                migrated
+                    .data
+                    .insert(instance.id, (instance.properties.clone(), new));
+            }
+        }
+        migrated
+    }
+
+    /// Set instance properties to the old (`undo`) or new side of each change
+    pub fn apply_migrated_properties(&mut self, migrated: &MigratedProperties, undo: bool) {
+        let pick = |(old, new): &PropertyChange| if undo { old.clone() } else { new.clone() };
+        for ((level_id, entity_id), change) in &migrated.entities {
+            if let Some(entity) = self
+                .get_level_mut(*level_id)
+                .and_then(|level| level.get_entity_mut(*entity_id))
+            {
+                entity.properties = pick(change);
+            }
+        }
+        for (instance_id, change) in &migrated.data {
+            if let Some(instance) = self.get_data_instance_mut(*instance_id) {
+                instance.properties = pick(change);
+            }
+        }
+    }
+
+    /// Bring instances saved at an older schema revision up to date
+    ///
+    /// Returns the number of instances that changed.
+    pub fn migrate_instances(&mut self) -> usize {
+        let since = match self.schema_revision {
+            Some(revision) if revision < self.schema.revision => revision,
+            _ => return 0,
+        };
+        let migrated = self.migrated_properties(&self.schema, since);
+        self.apply_migrated_properties(&migrated, false);
+        self.schema_revision = Some(self.schema.revision);
+        migrated.len()
+    }
+}
+
+/// Migrated copy of an instance's properties, if the migration changes them
+fn migrate(
+    schema: &Schema,
+    since: u32,
+    type_name: &str,
+    properties: &HashMap<String, Value>,
+) -> Option<HashMap<String, Value>> {
+    let mut json: HashMap<String, serde_json::Value> = properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_json()))
+        .collect();
+    if !migrate_properties(schema, since, type_name, &mut json) {
+        return None;
+    }
+    Some(
+        json.into_iter()
+            .map(|(key, value)| (key, Value::from_json(value)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, SchemaMigrationCommand};
+    use crate::project::DataInstance;
+    use crate::render::RenderState;
+    use bevy_map_core::{EntityInstance, Level};
+    use bevy_map_schema::{parse_schema, MigrationStep};
+
+    /// A project saved before `hp` was renamed to `health` on both types
+    fn unmigrated_project() -> Project {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "revision": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Enemy": {
+                    "placeable": true,
+                    "properties": [{ "name": "health", "type": "int" }]
+                },
+                "Item": {
+                    "properties": [{ "name": "health", "type": "int" }]
+                }
+            },
+            "migrations": [{
+                "revision": 1,
+                "steps": [
+                    { "op": "rename", "type": "Enemy", "from": "hp", "to": "health" },
+                    { "op": "rename", "type": "Item", "from": "hp", "to": "health" }
+                ]
+            }]
+        }"#,
+        )
+        .unwrap();
+        let mut project = Project::new(schema);
+        project.schema_revision = Some(0);
+
+        let mut level = Level::new("Cave".to_string(), 4, 4);
+        let mut enemy = EntityInstance::new("Enemy".to_string(), [0.0, 0.0]);
+        enemy.set_int("hp", 5);
+        level.add_entity(enemy);
+        project.add_level(level);
+
+        let mut item = DataInstance::new("Item".to_string());
+        item.properties.insert("hp".to_string(), Value::Int(3));
+        project.add_data_instance(item);
+        project
+    }
+
+    /// The enemy's and the item's value of `property`
+    fn values(project: &Project, property: &str) -> (Option<i64>, Option<i64>) {
+        let enemy = &project.levels[0].entities[0];
+        let item = project.data.all_instances().next().unwrap();
+        (
+            enemy.get_int(property),
+            item.properties.get(property).and_then(Value::as_int),
+        )
+    }
+
+    #[test]
+    fn test_migrate_instances() {
+        let mut project = unmigrated_project();
+        let migrated = project.migrated_properties(&project.schema, 0);
+        assert_eq!(migrated.entities.len(), 1);
+        assert_eq!(migrated.data.len(), 1);
+
+        assert_eq!(project.migrate_instances(), 2);
+        assert_eq!(values(&project, "health"), (Some(5), Some(3)));
+        assert!(!project.levels[0].entities[0].properties.contains_key("hp"));
+        assert_eq!(project.schema_revision, Some(1));
+
+        // Nothing left to do, and files without a revision are left alone
+        assert_eq!(project.migrate_instances(), 0);
+        let mut project = unmigrated_project();
+        project.schema_revision = None;
+        assert_eq!(project.migrate_instances(), 0);
+        assert_eq!(values(&project, "health"), (None, None));
+    }
+
+    #[test]
+    fn test_load_migrates_instances() {
+        let project = unmigrated_project();
+        let path = std::env::temp_dir().join(format!("migrate_{}.map.json", Uuid::new_v4()));
+        std::fs::write(&path, serde_json::to_string_pretty(&project).unwrap()).unwrap();
+
+        let loaded = Project::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+        assert_eq!(values(&loaded, "health"), (Some(5), Some(3)));
+        assert_eq!(loaded.schema_revision, Some(1));
+        assert!(loaded.is_dirty());
+    }
+
+    #[test]
+    fn test_schema_migration_command() {
+        let mut project = unmigrated_project();
+        project.migrate_instances();
+        let mut render_state = RenderState::default();
+
+        let mut schema = project.schema.clone();
+        let mut steps = Vec::new();
+        for type_name in ["Enemy", "Item"] {
+            schema.data_types.get_mut(type_name).unwrap().properties[0].name = "life".to_string();
+            steps.push(MigrationStep::Rename {
+                type_name: type_name.to_string(),
+                from: "health".to_string(),
+                to: "life".to_string(),
+            });
+        }
+        schema.record_migration(steps);
+        let command = SchemaMigrationCommand::new(&project, schema, "Rename health to life");
+        assert_eq!(command.migrated_count(), 2);
+
+        command.execute(&mut project, &mut render_state);
+        assert_eq!(values(&project, "life"), (Some(5), Some(3)));
+        assert_eq!(values(&project, "health"), (None, None));
+        assert_eq!(project.schema.revision, 2);
+        assert_eq!(project.schema_revision, Some(2));
+        assert_eq!(project.schema.data_types["Item"].properties[0].name, "life");
+
+        command.undo(&mut project, &mut render_state);
+        assert_eq!(values(&project, "health"), (Some(5), Some(3)));
+        assert_eq!(values(&project, "life"), (None, None));
+        assert_eq!(project.schema.revision, 1);
+        assert_eq!(project.schema_revision, Some(1));
+        assert_eq!(
+            project.schema.data_types["Item"].properties[0].name,
+            "health"
+        );
+
+        // Redo applies the same change again
+        command.execute(&mut project, &mut render_state);
+        assert_eq!(values(&project, "life"), (Some(5), Some(3)));
+    }
+}
@@ -3,8 +3,13 @@
 //! This module handles project file save/load and the Project resource.
 
 mod file;
+mod lint;
+mod migrate;
+mod strings;
 
 pub use file::*;
+pub use lint::*;
+pub use migrate::*;
 
 use bevy::prelude::Resource;
 use bevy_map_animation::SpriteData;
@@ -89,6 +94,9 @@ pub struct GameProjectConfig {
     /// Whether to generate enum definitions
     #[serde(default = "default_true")]
     pub generate_enums: bool,
+    /// Whether to generate serde-deserializable data type structs
+    #[serde(default)]
+    pub generate_data_types: bool,
 
     /// Custom path to VS Code executable (optional - uses auto-detection if not set)
     #[serde(default)]
@@ -112,6 +120,10 @@ pub struct Project {
     #[serde(skip)]
     pub schema_path: Option<PathBuf>,
     pub schema: Schema,
+    /// Schema revision the levels and data instances were saved with
+    /// (missing in older files, which are taken to match the schema)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_revision: Option<u32>,
     pub tilesets: Vec<Tileset>,
     pub data: DataStore,
     pub levels: Vec<Level>,
@@ -157,6 +169,7 @@ impl Default for Project {
             path: None,
             schema_path: None,
             schema: Schema::default(),
+            schema_revision: None,
             tilesets: Vec::new(),
             data: DataStore::default(),
             levels: Vec::new(),
@@ -183,6 +196,7 @@ impl Project {
             path: None,
             schema_path: None,
             schema,
+            schema_revision: None,
             tilesets: Vec::new(),
             data: DataStore::default(),
             levels: Vec::new(),
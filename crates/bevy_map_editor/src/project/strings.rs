@@ -0,0 +1,315 @@
+//! Export/import of translatable strings
+//!
+//! Collects dialogue text, speaker names, choice text and localized schema
+//! properties into a `StringTable` (see `bevy_map_dialogue::locale` for the keys).
+
+use super::Project;
+use bevy_map_core::Value;
+use bevy_map_dialogue::locale::{choice_key, node_text_key, property_key, speaker_key};
+use bevy_map_dialogue::{DialogueNode, StringTable};
+use bevy_map_schema::Schema;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl Project {
+    /// Collect all translatable strings, with their inline text under `source_locale`
+    pub fn export_strings(&self, source_locale: &str) -> StringTable {
+        let mut table = StringTable::new();
+        table.add_locale(source_locale);
+
+        for tree in &self.dialogues {
+            for node in nodes_in_reading_order(tree.nodes.values()) {
+                if !node.speaker.is_empty() {
+                    let key = speaker_key(&node.speaker);
+                    if table.get(source_locale, &key).is_none() {
+                        table.set_context(&key, "Speaker");
+                        table.insert(source_locale, key, &node.speaker);
+                    }
+                }
+                if !node.text.is_empty() {
+                    let key = node_text_key(tree, node);
+                    table.set_context(&key, dialogue_context(&tree.name, node));
+                    table.insert(source_locale, key, &node.text);
+                }
+                for (index, choice) in node.choices.iter().enumerate() {
+                    if choice.text.is_empty() {
+                        continue;
+                    }
+                    let key = choice_key(tree, node, index);
+                    table.set_context(&key, format!("{}: choice {}", tree.name, index + 1));
+                    table.insert(source_locale, key, &choice.text);
+                }
+            }
+        }
+
+        let mut data_types: Vec<_> = self.data.instances.keys().collect();
+        data_types.sort();
+        for type_name in data_types {
+            for instance in self.data.get_by_type(type_name) {
+                export_properties(
+                    &mut table,
+                    source_locale,
+                    &self.schema,
+                    type_name,
+                    instance.id,
+                    &instance.properties,
+                );
+            }
+        }
+
+        for level in &self.levels {
+            for entity in &level.entities {
+                export_properties(
+                    &mut table,
+                    source_locale,
+                    &self.schema,
+                    &entity.type_name,
+                    entity.id,
+                    &entity.properties,
+                );
+            }
+        }
+
+        table
+    }
+
+    /// Replace inline text with the `source_locale` strings of a table
+    ///
+    /// Keys that don't match anything in the project are ignored.
+    /// Returns the number of strings that changed.
+    pub fn import_strings(&mut self, table: &StringTable, source_locale: &str) -> usize {
+        let mut changed = 0;
+        let mut update = |target: &mut String, key: &str| {
+            if let Some(text) = table.get(source_locale, key) {
+                if target != text {
+                    *target = text.to_string();
+                    changed += 1;
+                }
+            }
+        };
+
+        for tree in &mut self.dialogues {
+            // Keys are computed up front since they borrow the tree
+            let keys: Vec<(String, String, Vec<String>)> = tree
+                .nodes
+                .values()
+                .map(|node| {
+                    let choices = (0..node.choices.len())
+                        .map(|i| choice_key(tree, node, i))
+                        .collect();
+                    (node.id.clone(), node_text_key(tree, node), choices)
+                })
+                .collect();
+
+            for (node_id, text_key, choice_keys) in keys {
+                let Some(node) = tree.nodes.get_mut(&node_id) else {
+                    continue;
+                };
+                if !node.speaker.is_empty() {
+                    let key = speaker_key(&node.speaker);
+                    update(&mut node.speaker, &key);
+                }
+                update(&mut node.text, &text_key);
+                for (choice, key) in node.choices.iter_mut().zip(&choice_keys) {
+                    update(&mut choice.text, key);
+                }
+            }
+        }
+
+        let schema = &self.schema;
+        for instance in self.data.instances.values_mut().flatten() {
+            import_properties(
+                schema,
+                &instance.type_name,
+                instance.id,
+                &mut instance.properties,
+                &mut update,
+            );
+        }
+        for level in &mut self.levels {
+            for entity in &mut level.entities {
+                import_properties(
+                    schema,
+                    &entity.type_name,
+                    entity.id,
+                    &mut entity.properties,
+                    &mut update,
+                );
+            }
+        }
+
+        if changed > 0 {
+            self.mark_dirty();
+        }
+        changed
+    }
+}
+
+/// Sort nodes top-to-bottom, left-to-right so exported files follow the graph layout
+fn nodes_in_reading_order<'a>(
+    nodes: impl Iterator<Item = &'a DialogueNode>,
+) -> Vec<&'a DialogueNode> {
+    let mut nodes: Vec<_> = nodes.collect();
+    nodes.sort_by(|a, b| {
+        a.position
+            .1
+            .total_cmp(&b.position.1)
+            .then(a.position.0.total_cmp(&b.position.0))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    nodes
+}
+
+fn dialogue_context(dialogue_name: &str, node: &DialogueNode) -> String {
+    if node.speaker.is_empty() {
+        dialogue_name.to_string()
+    } else {
+        format!("{}: {}", dialogue_name, node.speaker)
+    }
+}
+
+/// Names of a type's localized properties
+fn localized_properties<'a>(schema: &'a Schema, type_name: &str) -> Vec<&'a str> {
+    schema
+        .all_properties(type_name)
+        .into_iter()
+        .filter(|prop| prop.localized)
+        .map(|prop| prop.name.as_str())
+        .collect()
+}
+
+fn export_properties(
+    table: &mut StringTable,
+    source_locale: &str,
+    schema: &Schema,
+    type_name: &str,
+    instance_id: Uuid,
+    properties: &HashMap<String, Value>,
+) {
+    for prop in localized_properties(schema, type_name) {
+        let Some(Value::String(text)) = properties.get(prop) else {
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+        let key = property_key(type_name, instance_id, prop);
+        table.set_context(&key, format!("{}.{}", type_name, prop));
+        table.insert(source_locale, key, text);
+    }
+}
+
+fn import_properties(
+    schema: &Schema,
+    type_name: &str,
+    instance_id: Uuid,
+    properties: &mut HashMap<String, Value>,
+    update: &mut impl FnMut(&mut String, &str),
+) {
+    for prop in localized_properties(schema, type_name) {
+        if let Some(Value::String(text)) = properties.get_mut(prop) {
+            update(text, &property_key(type_name, instance_id, prop));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::DataInstance;
+    use bevy_map_dialogue::{DialogueChoice, DialogueTree};
+    use bevy_map_schema::parse_schema;
+
+    fn project() -> Project {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Item": {
+                    "properties": [
+                        { "name": "name", "type": "string", "localized": true },
+                        { "name": "icon", "type": "string" }
+                    ]
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+        let mut project = Project::new(schema);
+
+        let mut item = DataInstance::new("Item".to_string());
+        item.properties
+            .insert("name".to_string(), Value::String("Sword".to_string()));
+        item.properties
+            .insert("icon".to_string(), Value::String("sword.png".to_string()));
+        project.add_data_instance(item);
+
+        let mut tree = DialogueTree::new("Shop");
+        let start = tree.start_node.clone();
+        let node = tree.get_node_mut(&start).unwrap();
+        node.speaker = "Merchant".to_string();
+        node.text = "Welcome!".to_string();
+        node.choices = vec![
+            DialogueChoice::new("Buy", "end"),
+            DialogueChoice::new("Leave", "end"),
+        ];
+        project.dialogues.push(tree);
+        project
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let mut project = project();
+        let exported = project.export_strings("en");
+        let mut table = StringTable::from_csv(&exported.to_csv()).unwrap();
+        assert_eq!(table.keys().count(), 5);
+
+        // Importing an unchanged table changes nothing
+        assert_eq!(project.import_strings(&table, "en"), 0);
+
+        let tree = &project.dialogues[0];
+        let node = tree.get_start_node().unwrap();
+        table.insert("en", node_text_key(tree, node), "Hello!");
+        table.insert("en", choice_key(tree, node, 1), "Goodbye");
+        table.insert("en", speaker_key("Merchant"), "Trader");
+        let item_id = project.data.get_by_type("Item")[0].id;
+        table.insert("en", property_key("Item", item_id, "name"), "Blade");
+        table.insert("en", property_key("Item", item_id, "icon"), "ignored.png");
+        table.insert("en", "unknown.key", "ignored");
+        assert_eq!(project.import_strings(&table, "en"), 4);
+
+        let node = project.dialogues[0].get_start_node().unwrap();
+        assert_eq!(node.speaker, "Trader");
+        assert_eq!(node.text, "Hello!");
+        assert_eq!(node.choices[0].text, "Buy");
+        assert_eq!(node.choices[1].text, "Goodbye");
+        let item = &project.data.get_by_type("Item")[0];
+        assert_eq!(
+            item.properties.get("name"),
+            Some(&Value::String("Blade".to_string()))
+        );
+        assert_eq!(
+            item.properties.get("icon"),
+            Some(&Value::String("sword.png".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_choice_keys_survive_reordering() {
+        let mut project = project();
+        let table = project.export_strings("en");
+
+        let tree = &mut project.dialogues[0];
+        let start = tree.start_node.clone();
+        tree.get_node_mut(&start).unwrap().choices.reverse();
+        for choice in &mut tree.get_node_mut(&start).unwrap().choices {
+            choice.text.clear();
+        }
+
+        project.import_strings(&table, "en");
+        let node = project.dialogues[0].get_start_node().unwrap();
+        let texts: Vec<_> = node.choices.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["Leave", "Buy"]);
+    }
+}
@@ -290,8 +290,9 @@ fn spawn_level_tilemaps(
         // Group tiles by image (for multi-image tilesets)
         // bevy_ecs_tilemap uses a single texture per tilemap, so we need separate tilemaps per image
         // Also track which tiles are multi-cell (they'll be rendered as Sprites instead)
-        // Tuple: (x, y, local_tile_index, flip_x, flip_y)
-        let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, bool, bool)>> = HashMap::new();
+        // Tuple: (x, y, local_tile_index, flip_x, flip_y, flip_d)
+        let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, bool, bool, bool)>> =
+            HashMap::new();
         // Tuple: (x, y, virtual_idx, grid_w, grid_h, image_index, flip_x, flip_y)
         let mut multi_cell_tiles: Vec<(u32, u32, u32, u32, u32, usize, bool, bool)> = Vec::new();
 
@@ -302,6 +303,7 @@ fn spawn_level_tilemaps(
                     // Extract flip flags and base tile index
                     let flip_x = bevy_map_core::tile_flip_x(*virtual_tile_index);
                     let flip_y = bevy_map_core::tile_flip_y(*virtual_tile_index);
+                    let flip_d = bevy_map_core::tile_flip_diagonal(*virtual_tile_index);
                     let base_tile_index = bevy_map_core::tile_index(*virtual_tile_index);
 
                     // Skip OCCUPIED_CELL sentinel values (used for multi-cell tiles)
@@ -337,6 +339,7 @@ fn spawn_level_tilemaps(
                                 local_tile_index,
                                 flip_x,
                                 flip_y,
+                                flip_d,
                             ));
                         }
                     }
@@ -374,7 +377,7 @@ fn spawn_level_tilemaps(
             let tilemap_entity = commands.spawn_empty().id();
 
             // Spawn tiles for this image
-            for (x, y, local_tile_index, flip_x, flip_y) in &image_tiles {
+            for (x, y, local_tile_index, flip_x, flip_y, flip_d) in &image_tiles {
                 let tile_pos = TilePos { x: *x, y: *y };
                 let tile_entity = commands
                     .spawn(TileBundle {
@@ -384,7 +387,7 @@ fn spawn_level_tilemaps(
                         flip: TileFlip {
                             x: *flip_x,
                             y: *flip_y,
-                            d: false,
+                            d: *flip_d,
                         },
                         ..default()
                     })
@@ -552,14 +555,15 @@ pub fn update_tile(
         new_tile_index.filter(|&idx| bevy_map_core::tile_index(idx) != OCCUPIED_CELL);
 
     // Extract flip flags from tile index
-    let (flip_x, flip_y) = effective_tile_index
+    let (flip_x, flip_y, flip_d) = effective_tile_index
         .map(|idx| {
             (
                 bevy_map_core::tile_flip_x(idx),
                 bevy_map_core::tile_flip_y(idx),
+                bevy_map_core::tile_flip_diagonal(idx),
             )
         })
-        .unwrap_or((false, false));
+        .unwrap_or((false, false, false));
 
     // Strip flip flags to get the actual tile index
     let effective_tile_index = effective_tile_index.map(bevy_map_core::tile_index);
@@ -746,7 +750,7 @@ pub fn update_tile(
                             flip: TileFlip {
                                 x: flip_x,
                                 y: flip_y,
-                                d: false,
+                                d: flip_d,
                             },
                             ..default()
                         })
@@ -1469,6 +1473,24 @@ pub fn mark_viewport_dirty(render_state: &mut RenderState) {
     render_state.mark_dirty();
 }
 
+/// Sprite flips and rotation that draw a tile value with its flip flags
+///
+/// Sprites can't flip diagonally, so a diagonal flip (transpose) is drawn as a
+/// flip followed by a quarter turn counter-clockwise.
+fn sprite_flip(tile: u32) -> (bool, bool, Quat) {
+    let flip_x = bevy_map_core::tile_flip_x(tile);
+    let flip_y = bevy_map_core::tile_flip_y(tile);
+    if bevy_map_core::tile_flip_diagonal(tile) {
+        (
+            !flip_y,
+            flip_x,
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+        )
+    } else {
+        (flip_x, flip_y, Quat::IDENTITY)
+    }
+}
+
 /// Marker component for terrain preview highlight sprites
 #[derive(Component)]
 pub struct TerrainPreviewHighlight;
@@ -1565,7 +1587,10 @@ fn sync_terrain_preview(
         let mut entities = Vec::new();
 
         // Spawn tile sprite
-        if let Some((image_index, local_tile_index)) = tileset.virtual_to_local(tile_id) {
+        let (flip_x, flip_y, rotation) = sprite_flip(tile_id);
+        if let Some((image_index, local_tile_index)) =
+            tileset.virtual_to_local(bevy_map_core::tile_index(tile_id))
+        {
             if let Some(image) = tileset.images.get(image_index) {
                 if let Some((texture_handle, _, img_width, img_height)) =
                     tileset_cache.loaded.get(&image.id)
@@ -1592,9 +1617,12 @@ fn sync_terrain_preview(
                                         layout: atlas_layout_handle,
                                         index: local_tile_index as usize,
                                     }),
+                                    flip_x,
+                                    flip_y,
                                     ..default()
                                 },
-                                Transform::from_xyz(world_x, world_y, 179.0),
+                                Transform::from_xyz(world_x, world_y, 179.0)
+                                    .with_rotation(rotation),
                                 TerrainPreviewHighlight,
                             ))
                             .id();
@@ -0,0 +1,142 @@
+//! Automap rule application
+//!
+//! Runs automapping rule sets on the selected level, either on demand (as an
+//! undoable command) or automatically whenever a rule set's input layers change.
+
+use bevy::prelude::*;
+use bevy_map_core::LayerData;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use uuid::Uuid;
+
+use crate::commands::{collect_tiles_in_region, BatchTileCommand};
+use crate::project::Project;
+use crate::render::RenderState;
+use crate::EditorState;
+
+/// Input-layer hashes from the last automatic run, per (level, rule set)
+#[derive(Resource, Default)]
+pub struct AutomapWatch {
+    input_hashes: HashMap<(Uuid, Uuid), u64>,
+}
+
+/// Run a rule set on a level, returning the undo command for the output layer
+/// (`None` if nothing changed)
+pub(crate) fn apply_rule_set(
+    project: &mut Project,
+    level_id: Uuid,
+    rule_set_id: Uuid,
+) -> Result<Option<BatchTileCommand>, String> {
+    let Some(rule_set) = project.autotile_config.get_rule_set(rule_set_id) else {
+        return Err("Rule set not found".to_string());
+    };
+    let Some(level) = project.levels.iter().find(|l| l.id == level_id) else {
+        return Err("Level not found".to_string());
+    };
+    let Some(layer_idx) = level
+        .layers
+        .iter()
+        .position(|l| l.name == rule_set.output_layer)
+    else {
+        return Err(format!(
+            "Rule set '{}': output layer '{}' not found in level '{}'",
+            rule_set.name, rule_set.output_layer, level.name
+        ));
+    };
+
+    let max_x = level.width as i32 - 1;
+    let max_y = level.height as i32 - 1;
+    let before = collect_tiles_in_region(project, level_id, layer_idx, 0, max_x, 0, max_y);
+
+    let Some(level) = project.levels.iter_mut().find(|l| l.id == level_id) else {
+        return Ok(None);
+    };
+    let Some(rule_set) = project.autotile_config.get_rule_set(rule_set_id) else {
+        return Ok(None);
+    };
+    let changed = rule_set
+        .apply(level)
+        .map_err(|e| format!("Rule set '{}': {}", rule_set.name, e))?;
+    if changed == 0 {
+        return Ok(None);
+    }
+
+    let description = format!("Apply {}", rule_set.name);
+    let after = collect_tiles_in_region(project, level_id, layer_idx, 0, max_x, 0, max_y);
+    Ok(Some(BatchTileCommand::from_diff(
+        level_id,
+        layer_idx,
+        before,
+        after,
+        description,
+    )))
+}
+
+/// Hash the input layers of a rule set in a level
+fn input_hash(project: &Project, level_id: Uuid, rule_set_id: Uuid) -> Option<u64> {
+    let rule_set = project.autotile_config.get_rule_set(rule_set_id)?;
+    let level = project.levels.iter().find(|l| l.id == level_id)?;
+    let mut hasher = DefaultHasher::new();
+    for name in rule_set.input_layers() {
+        name.hash(&mut hasher);
+        if let Some(LayerData::Tiles { tiles, .. }) = level
+            .layers
+            .iter()
+            .find(|l| l.name == name)
+            .map(|l| &l.data)
+        {
+            tiles.hash(&mut hasher);
+        }
+    }
+    Some(hasher.finish())
+}
+
+/// Re-run auto-applied rule sets when their inputs change
+///
+/// Waits for the current paint stroke to finish so a stroke triggers one run.
+/// Generated tiles aren't recorded in the undo history: undoing an input edit
+/// changes the inputs again, which re-runs the rules.
+pub(crate) fn auto_apply_automap_rules(
+    mut editor_state: ResMut<EditorState>,
+    mut project: ResMut<Project>,
+    mut render_state: ResMut<RenderState>,
+    mut watch: ResMut<AutomapWatch>,
+) {
+    if editor_state.is_painting {
+        return;
+    }
+    let Some(level_id) = editor_state.selected_level else {
+        return;
+    };
+
+    if std::mem::take(&mut editor_state.automap_editor_state.rules_changed) {
+        watch.input_hashes.clear();
+    }
+
+    let rule_set_ids: Vec<Uuid> = project
+        .autotile_config
+        .rule_sets
+        .iter()
+        .filter(|rs| rs.auto_apply)
+        .map(|rs| rs.id)
+        .collect();
+
+    for rule_set_id in rule_set_ids {
+        let hash = input_hash(&project, level_id, rule_set_id);
+        if hash.is_none() || watch.input_hashes.get(&(level_id, rule_set_id)) == hash.as_ref() {
+            continue;
+        }
+
+        // Layer errors are reported by "Apply to Level"; auto runs skip them
+        if let Ok(Some(_)) = apply_rule_set(&mut project, level_id, rule_set_id) {
+            project.mark_dirty();
+            render_state.needs_rebuild = true;
+        }
+
+        // Hash after running, in case the output layer is also an input
+        if let Some(hash) = input_hash(&project, level_id, rule_set_id) {
+            watch.input_hashes.insert((level_id, rule_set_id), hash);
+        }
+    }
+}
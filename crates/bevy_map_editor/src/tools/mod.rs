@@ -2,6 +2,10 @@
 //!
 //! Handles viewport input for various editing operations.
 
+mod automap;
+
+pub(crate) use automap::apply_rule_set;
+
 use bevy::ecs::message::MessageReader;
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
@@ -11,7 +15,8 @@ use bevy_map_core::{EntityInstance, LayerData, OCCUPIED_CELL};
 use std::collections::HashMap;
 
 use crate::commands::{
-    collect_tiles_in_region, BatchTileCommand, CommandHistory, MoveEntityCommand,
+    collect_tiles_in_region, BatchTileCommand, Command, CommandHistory, CompositeCommand,
+    MoveEntityCommand, SetLayerTilesetCommand,
 };
 use crate::preferences::EditorPreferences;
 use crate::project::Project;
@@ -27,12 +32,14 @@ impl Plugin for EditorToolsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ViewportInputState>()
             .init_resource::<PaintStrokeTracker>()
+            .init_resource::<automap::AutomapWatch>()
             .add_systems(
                 Update,
                 (
                     handle_viewport_input,
                     handle_zoom_input,
                     finalize_paint_stroke,
+                    automap::auto_apply_automap_rules.after(finalize_paint_stroke),
                 ),
             );
     }
@@ -73,37 +80,29 @@ pub struct PaintStrokeTracker {
     pub layer_idx: Option<usize>,
     /// Changes made during this stroke: (x, y) -> (old_tile, new_tile)
     pub changes: HashMap<(u32, u32), (Option<u32>, Option<u32>)>,
+    /// Changes to other layers of the level, for terrains routed to their own layers
+    pub other_layer_changes: HashMap<usize, HashMap<(u32, u32), (Option<u32>, Option<u32>)>>,
     /// Description of the operation
     pub description: String,
 }
 
-/// Capture a snapshot of tiles in a region around a center point for undo tracking
-fn capture_tile_region(
-    tiles: &[Option<u32>],
-    width: u32,
-    height: u32,
-    center_x: i32,
-    center_y: i32,
-    radius: i32,
-) -> HashMap<(u32, u32), Option<u32>> {
-    let mut snapshot = HashMap::new();
-
-    for dy in -radius..=radius {
-        for dx in -radius..=radius {
-            let x = center_x + dx;
-            let y = center_y + dy;
-
-            if x >= 0 && y >= 0 && x < width as i32 && y < height as i32 {
-                let x = x as u32;
-                let y = y as u32;
-                let idx = (y * width + x) as usize;
-                let tile = tiles.get(idx).copied().flatten();
-                snapshot.insert((x, y), tile);
-            }
-        }
+impl PaintStrokeTracker {
+    /// Record a tile change, keeping the tile from before the stroke as the old value
+    fn record_change(
+        &mut self,
+        layer_idx: usize,
+        x: u32,
+        y: u32,
+        old: Option<u32>,
+        new: Option<u32>,
+    ) {
+        let changes = if self.layer_idx == Some(layer_idx) {
+            &mut self.changes
+        } else {
+            self.other_layer_changes.entry(layer_idx).or_default()
+        };
+        changes.entry((x, y)).or_insert((old, new)).1 = new;
     }
-
-    snapshot
 }
 
 /// Capture a snapshot of tiles in a rectangular bounds for undo tracking
@@ -311,6 +310,15 @@ fn handle_viewport_input(
             EditorTool::Entity => {
                 place_entity(&mut editor_state, &mut project, world_pos);
             }
+            EditorTool::Fill if editor_state.terrain_paint_state.is_terrain_mode => {
+                fill_terrain_area(
+                    &mut editor_state,
+                    &mut project,
+                    &mut render_state,
+                    &mut history,
+                    world_pos,
+                );
+            }
             EditorTool::Fill => {
                 fill_area(
                     &mut editor_state,
@@ -1618,14 +1626,12 @@ fn place_entity(editor_state: &mut EditorState, project: &mut Project, world_pos
     let mut entity = EntityInstance::new(type_name.clone(), position);
 
     // Initialize properties from schema defaults if the type exists
-    if let Some(type_def) = project.schema.get_type(&type_name) {
-        for prop in &type_def.properties {
-            if let Some(default_val) = &prop.default {
-                entity.properties.insert(
-                    prop.name.clone(),
-                    bevy_map_core::Value::from_json(default_val.clone()),
-                );
-            }
+    for prop in project.schema.all_properties(&type_name) {
+        if let Some(default_val) = &prop.default {
+            entity.properties.insert(
+                prop.name.clone(),
+                bevy_map_core::Value::from_json(default_val.clone()),
+            );
         }
     }
 
@@ -1985,8 +1991,13 @@ fn paint_terrain_tile(
         return;
     };
 
-    // Can only paint terrain on Tile layers
-    if !is_tile_layer(project, level_id, layer_idx) {
+    // Can only paint terrain on Tile layers (unless the terrain set routes
+    // terrains to their own layers)
+    let routed = editor_state
+        .selected_terrain_set
+        .and_then(|id| project.autotile_config.get_terrain_set(id))
+        .is_some_and(|ts| ts.routes_layers());
+    if !routed && !is_tile_layer(project, level_id, layer_idx) {
         return;
     }
 
@@ -2007,21 +2018,6 @@ fn paint_terrain_tile(
             full_tile_mode,
         );
     }
-    // Finally check for legacy 47-tile terrains (no full-tile mode for legacy)
-    else if let Some(terrain_id) = editor_state.selected_terrain {
-        paint_legacy_terrain_tile(
-            commands,
-            editor_state,
-            project,
-            render_state,
-            stroke_tracker,
-            tileset_cache,
-            world_pos,
-            level_id,
-            layer_idx,
-            terrain_id,
-        );
-    }
 }
 
 /// Get paint targets along a line between two world positions (for continuous drag painting)
@@ -2163,39 +2159,53 @@ fn paint_terrain_set_tile(
     let Some(level) = project.levels.iter_mut().find(|l| l.id == level_id) else {
         return;
     };
+    let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
+        return;
+    };
     let level_width = level.width;
     let level_height = level.height;
 
-    let (has_tiles, layer_tileset) = level
-        .layers
-        .get(layer_idx)
-        .map(|layer| (layer_has_tiles(layer), get_layer_tileset_id(layer)))
-        .unwrap_or((false, None));
-
-    if has_tiles {
-        if layer_tileset != Some(selected_tileset) {
+    // Find the layers this terrain paints (the selected one unless routed)
+    let route = match terrain_set.terrain_layers(level, layer_idx, terrain_idx) {
+        Ok(route) => route,
+        Err(e) => {
+            // Report once per stroke rather than every frame
+            if !editor_state.is_painting {
+                editor_state.error_message = Some(format!(
+                    "Can't paint terrain '{}': {}",
+                    terrain_set
+                        .terrains
+                        .get(terrain_idx)
+                        .map_or("?", |t| t.name.as_str()),
+                    e
+                ));
+            }
+            editor_state.is_painting = true;
             input_state.last_paint_world_pos = Some(world_pos);
             return;
         }
-    } else {
-        if let Some(layer) = level.layers.get_mut(layer_idx) {
-            if let LayerData::Tiles { tileset_id, .. } = &mut layer.data {
-                *tileset_id = selected_tileset;
-            }
-        }
-    }
+    };
 
-    let tiles = if let Some(layer) = level.layers.get_mut(layer_idx) {
-        if let LayerData::Tiles { tiles, .. } = &mut layer.data {
-            tiles
-        } else {
+    for &idx in route.painted() {
+        let (has_tiles, layer_tileset) = level
+            .layers
+            .get(idx)
+            .map(|layer| (layer_has_tiles(layer), get_layer_tileset_id(layer)))
+            .unwrap_or((false, None));
+        if has_tiles && layer_tileset != Some(selected_tileset) {
             input_state.last_paint_world_pos = Some(world_pos);
             return;
         }
-    } else {
-        input_state.last_paint_world_pos = Some(world_pos);
-        return;
-    };
+    }
+    for &idx in route.painted() {
+        if let Some(layer) = level.layers.get_mut(idx) {
+            if !layer_has_tiles(layer) {
+                if let LayerData::Tiles { tileset_id, .. } = &mut layer.data {
+                    *tileset_id = selected_tileset;
+                }
+            }
+        }
+    }
 
     // Initialize stroke tracker
     if !stroke_tracker.active {
@@ -2203,44 +2213,50 @@ fn paint_terrain_set_tile(
         stroke_tracker.level_id = Some(level_id);
         stroke_tracker.layer_idx = Some(layer_idx);
         stroke_tracker.changes.clear();
+        stroke_tracker.other_layer_changes.clear();
         stroke_tracker.description = "Paint Terrain".to_string();
     }
 
-    // Get terrain set reference for painting (split borrow allows this)
-    let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
-        return;
-    };
-
     // Calculate unified bounding box for all targets (with buffer for corrections)
     let (min_x, min_y, max_x, max_y) = calculate_targets_bounds(&new_targets, 2);
 
-    // Take a single unified snapshot covering all targets (like Tiled)
-    let unified_snapshot =
-        capture_tile_region_bounds(tiles, level_width, level_height, min_x, min_y, max_x, max_y);
+    // Take a single unified snapshot of each painted layer covering all targets (like Tiled)
+    let snapshots: Vec<(usize, HashMap<(u32, u32), Option<u32>>)> = route
+        .painted()
+        .iter()
+        .filter_map(|&idx| match &level.layers.get(idx)?.data {
+            LayerData::Tiles { tiles, .. } => Some((
+                idx,
+                capture_tile_region_bounds(
+                    tiles,
+                    level_width,
+                    level_height,
+                    min_x,
+                    min_y,
+                    max_x,
+                    max_y,
+                ),
+            )),
+            LayerData::Objects { .. } => None,
+        })
+        .collect();
 
-    // Paint all targets in ONE batched operation (like Tiled's approach)
+    // Paint all targets in ONE batched operation per layer (like Tiled's approach)
     // This uses a single WangFiller instead of creating 8 separate ones
-    bevy_map_autotile::paint_terrain_at_targets(
-        tiles,
-        level_width,
-        level_height,
-        &new_targets,
-        terrain_set,
-        terrain_idx,
-    );
+    bevy_map_autotile::paint_terrain_layers(level, &route, &new_targets, terrain_set, terrain_idx);
 
-    // Track all changes at once from unified snapshot
+    // Track all changes at once from the unified snapshots
     // Collect changed tiles for incremental rendering update
     let mut changed_tiles = Vec::new();
-    for ((x, y), old_tile) in unified_snapshot {
-        let idx = (y * level_width + x) as usize;
-        let new_tile = tiles.get(idx).copied().flatten();
-        if old_tile != new_tile {
-            changed_tiles.push((x, y, new_tile));
-            if !stroke_tracker.changes.contains_key(&(x, y)) {
-                stroke_tracker.changes.insert((x, y), (old_tile, new_tile));
-            } else if let Some(change) = stroke_tracker.changes.get_mut(&(x, y)) {
-                change.1 = new_tile;
+    for (idx, snapshot) in snapshots {
+        let Some(LayerData::Tiles { tiles, .. }) = level.layers.get(idx).map(|l| &l.data) else {
+            continue;
+        };
+        for ((x, y), old_tile) in snapshot {
+            let new_tile = tiles.get((y * level_width + x) as usize).copied().flatten();
+            if old_tile != new_tile {
+                changed_tiles.push((idx, x, y, new_tile));
+                stroke_tracker.record_change(idx, x, y, old_tile, new_tile);
             }
         }
     }
@@ -2254,14 +2270,14 @@ fn paint_terrain_set_tile(
 
     // Update tile rendering incrementally (like Tiled's approach)
     // This updates only changed tiles instead of triggering a full rebuild
-    for (x, y, new_tile) in changed_tiles {
+    for (idx, x, y, new_tile) in changed_tiles {
         crate::render::update_tile(
             commands,
             render_state,
             project,
             tileset_cache,
             level_id,
-            layer_idx,
+            idx,
             x,
             y,
             new_tile,
@@ -2275,140 +2291,66 @@ fn paint_terrain_set_tile(
     input_state.last_paint_world_pos = Some(world_pos);
 }
 
-/// Paint using the legacy 47-tile blob terrain system
-fn paint_legacy_terrain_tile(
-    commands: &mut Commands,
+/// Flood fill a contiguous area with the selected terrain (terrain bucket fill)
+fn fill_terrain_area(
     editor_state: &mut EditorState,
     project: &mut Project,
     render_state: &mut RenderState,
-    stroke_tracker: &mut PaintStrokeTracker,
-    tileset_cache: &crate::ui::TilesetTextureCache,
+    history: &mut CommandHistory,
     world_pos: Vec2,
-    level_id: uuid::Uuid,
-    layer_idx: usize,
-    terrain_id: uuid::Uuid,
 ) {
-    let terrain = match project.autotile_config.get_terrain(terrain_id) {
-        Some(t) => t.clone(),
-        None => return,
+    let Some(level_id) = editor_state.selected_level else {
+        return;
     };
-
-    let selected_tileset = terrain.tileset_id;
-
-    let tile_size = project
-        .tilesets
-        .iter()
-        .find(|t| t.id == selected_tileset)
-        .map(|t| t.tile_size as f32)
-        .unwrap_or(32.0);
-
-    let tile_x = (world_pos.x / tile_size).floor() as i32;
-    let tile_y = (world_pos.y / tile_size).floor() as i32;
-
-    if editor_state.last_painted_tile == Some((tile_x as u32, tile_y as u32)) {
+    let Some(layer_idx) = editor_state.selected_layer else {
         return;
-    }
-
-    let Some(level) = project.get_level_mut(level_id) else {
+    };
+    let Some(terrain_set_id) = editor_state.selected_terrain_set else {
         return;
     };
-    if tile_x < 0 || tile_y < 0 || tile_x >= level.width as i32 || tile_y >= level.height as i32 {
+    let Some(terrain_idx) = editor_state.selected_terrain_in_set else {
         return;
-    }
-
-    let tile_x_u32 = tile_x as u32;
-    let tile_y_u32 = tile_y as u32;
-
-    let (has_tiles, layer_tileset) = level
-        .layers
-        .get(layer_idx)
-        .map(|layer| (layer_has_tiles(layer), get_layer_tileset_id(layer)))
-        .unwrap_or((false, None));
+    };
 
-    if has_tiles {
-        if layer_tileset != Some(selected_tileset) {
-            return;
-        }
-    } else {
-        if let Some(layer) = level.layers.get_mut(layer_idx) {
-            if let LayerData::Tiles { tileset_id, .. } = &mut layer.data {
-                *tileset_id = selected_tileset;
-            }
-        }
+    // Can only fill terrain on Tile layers
+    if !is_tile_layer(project, level_id, layer_idx) {
+        return;
     }
 
-    let level_width = level.width;
-    let level_height = level.height;
-
-    if let Some(layer) = level.layers.get_mut(layer_idx) {
-        if let LayerData::Tiles { tiles, .. } = &mut layer.data {
-            let snapshot_region =
-                capture_tile_region(tiles, level_width, level_height, tile_x, tile_y, 1);
-
-            let first_tile = terrain.base_tile.saturating_sub(46);
-            let last_tile = terrain.base_tile;
-            let is_terrain_tile = |tile: Option<u32>| -> bool {
-                match tile {
-                    Some(t) => t >= first_tile && t <= last_tile,
-                    None => false,
-                }
-            };
-
-            bevy_map_autotile::paint_autotile(
-                tiles,
-                level_width,
-                level_height,
-                tile_x_u32,
-                tile_y_u32,
-                &terrain,
-                is_terrain_tile,
-            );
-
-            if !stroke_tracker.active {
-                stroke_tracker.active = true;
-                stroke_tracker.level_id = Some(level_id);
-                stroke_tracker.layer_idx = Some(layer_idx);
-                stroke_tracker.changes.clear();
-                stroke_tracker.description = "Paint Terrain".to_string();
-            }
-
-            // Collect changed tiles for incremental update
-            let mut changed_tiles = Vec::new();
-            for ((x, y), old_tile) in snapshot_region {
-                let idx = (y * level_width + x) as usize;
-                let new_tile = tiles.get(idx).copied().flatten();
-                if old_tile != new_tile {
-                    changed_tiles.push((x, y, new_tile));
-                    if !stroke_tracker.changes.contains_key(&(x, y)) {
-                        stroke_tracker.changes.insert((x, y), (old_tile, new_tile));
-                    } else {
-                        if let Some(change) = stroke_tracker.changes.get_mut(&(x, y)) {
-                            change.1 = new_tile;
-                        }
-                    }
-                }
-            }
+    let tile_size = get_tile_size(editor_state, project);
+    let start_x = (world_pos.x / tile_size).floor() as i32;
+    let start_y = (world_pos.y / tile_size).floor() as i32;
 
-            // Update tile rendering incrementally for each changed tile
-            for (x, y, new_tile) in changed_tiles {
-                crate::render::update_tile(
-                    commands,
-                    render_state,
-                    project,
-                    tileset_cache,
-                    level_id,
-                    layer_idx,
-                    x,
-                    y,
-                    new_tile,
-                );
-            }
-        }
+    let Some(level) = project.get_level(level_id) else {
+        return;
+    };
+    if start_x < 0 || start_y < 0 || start_x >= level.width as i32 || start_y >= level.height as i32
+    {
+        return;
     }
 
-    project.mark_dirty();
-    editor_state.is_painting = true;
-    editor_state.last_painted_tile = Some((tile_x_u32, tile_y_u32));
+    let Some(route) = terrain_route(editor_state, project, level_id, layer_idx, terrain_idx) else {
+        return;
+    };
+    let result = edit_routed_terrain(
+        project,
+        render_state,
+        level_id,
+        std::slice::from_ref(&route),
+        terrain_set_id,
+        "Terrain fill",
+        |level, terrain_set| {
+            bevy_map_autotile::fill_terrain_region_layers(
+                level,
+                &route,
+                start_x,
+                start_y,
+                terrain_set,
+                terrain_idx,
+            );
+        },
+    );
+    push_terrain_edit(editor_state, project, render_state, history, result);
 }
 
 /// Fill a rectangular area with terrain tiles using the autotile system
@@ -2440,185 +2382,200 @@ fn fill_terrain_rectangle(
         return;
     }
 
-    // Extract tileset_id before mutable access to levels
-    let selected_tileset = {
-        let Some(ts) = project.autotile_config.get_terrain_set(terrain_set_id) else {
-            return;
-        };
-        ts.tileset_id
-    };
-
-    // Use split borrowing - access levels directly
-    let Some(level) = project.levels.iter().find(|l| l.id == level_id) else {
+    let Some(level) = project.get_level(level_id) else {
         return;
     };
-    let level_width = level.width as i32;
-    let level_height = level.height as i32;
-
     let min_x = start_x.min(end_x).max(0);
-    let max_x = start_x.max(end_x).min(level_width - 1);
+    let max_x = start_x.max(end_x).min(level.width as i32 - 1);
     let min_y = start_y.min(end_y).max(0);
-    let max_y = start_y.max(end_y).min(level_height - 1);
-
-    let update_min_x = (min_x - 1).max(0);
-    let update_max_x = (max_x + 1).min(level_width - 1);
-    let update_min_y = (min_y - 1).max(0);
-    let update_max_y = (max_y + 1).min(level_height - 1);
-
-    let before_tiles = collect_tiles_in_region(
-        project,
-        level_id,
-        layer_idx,
-        update_min_x,
-        update_max_x,
-        update_min_y,
-        update_max_y,
-    );
-
-    let Some(level) = project.levels.iter_mut().find(|l| l.id == level_id) else {
+    let max_y = start_y.max(end_y).min(level.height as i32 - 1);
+    if min_x > max_x || min_y > max_y {
         return;
-    };
-
-    let (has_tiles, layer_tileset) = level
-        .layers
-        .get(layer_idx)
-        .map(|layer| (layer_has_tiles(layer), get_layer_tileset_id(layer)))
-        .unwrap_or((false, None));
+    }
 
-    if has_tiles {
-        if layer_tileset != Some(selected_tileset) {
-            return;
-        }
-    } else {
-        if let Some(layer) = level.layers.get_mut(layer_idx) {
-            if let LayerData::Tiles { tileset_id, .. } = &mut layer.data {
-                *tileset_id = selected_tileset;
+    // Every corner and edge of every tile in the rectangle, like a full-tile
+    // brush stroke over each of them
+    let mut targets = Vec::new();
+    for y in min_y as u32..=max_y as u32 + 1 {
+        for x in min_x as u32..=max_x as u32 + 1 {
+            targets.push(bevy_map_autotile::PaintTarget::Corner {
+                corner_x: x,
+                corner_y: y,
+            });
+            if x <= max_x as u32 {
+                targets.push(bevy_map_autotile::PaintTarget::HorizontalEdge {
+                    tile_x: x,
+                    edge_y: y,
+                });
+            }
+            if y <= max_y as u32 {
+                targets.push(bevy_map_autotile::PaintTarget::VerticalEdge {
+                    edge_x: x,
+                    tile_y: y,
+                });
             }
         }
     }
 
-    let level_width = level.width;
-    let level_height = level.height;
-
-    let tiles = if let Some(layer) = level.layers.get_mut(layer_idx) {
-        if let LayerData::Tiles { tiles, .. } = &mut layer.data {
-            tiles
-        } else {
-            return;
-        }
-    } else {
+    let Some(route) = terrain_route(editor_state, project, level_id, layer_idx, terrain_idx) else {
         return;
     };
+    let result = edit_routed_terrain(
+        project,
+        render_state,
+        level_id,
+        std::slice::from_ref(&route),
+        terrain_set_id,
+        "Fill Terrain Rectangle",
+        |level, terrain_set| {
+            bevy_map_autotile::paint_terrain_layers(
+                level,
+                &route,
+                &targets,
+                terrain_set,
+                terrain_idx,
+            );
+        },
+    );
+    push_terrain_edit(editor_state, project, render_state, history, result);
+}
+
+/// Find the layers the selected terrain is painted on, reporting routing errors
+fn terrain_route(
+    editor_state: &mut EditorState,
+    project: &Project,
+    level_id: uuid::Uuid,
+    layer_idx: usize,
+    terrain_idx: usize,
+) -> Option<bevy_map_autotile::TerrainLayers> {
+    let terrain_set = project
+        .autotile_config
+        .get_terrain_set(editor_state.selected_terrain_set?)?;
+    let level = project.get_level(level_id)?;
+    match terrain_set.terrain_layers(level, layer_idx, terrain_idx) {
+        Ok(route) => Some(route),
+        Err(e) => {
+            editor_state.error_message = Some(format!(
+                "Can't paint terrain '{}': {}",
+                terrain_set
+                    .terrains
+                    .get(terrain_idx)
+                    .map_or("?", |t| t.name.as_str()),
+                e
+            ));
+            None
+        }
+    }
+}
 
-    // Get terrain set reference (split borrow allows this after getting tiles from levels)
+/// Run a terrain edit on the layers of one or more routes as one undo step
+///
+/// Every layer the routes write to must use the terrain set's tileset or be
+/// empty; empty layers the edit paints take on the set's tileset, undone
+/// with it. Returns `None` if nothing changed.
+pub(crate) fn edit_routed_terrain(
+    project: &mut Project,
+    render_state: &mut RenderState,
+    level_id: uuid::Uuid,
+    routes: &[bevy_map_autotile::TerrainLayers],
+    terrain_set_id: uuid::Uuid,
+    description: &str,
+    edit: impl FnOnce(&mut bevy_map_core::Level, &bevy_map_autotile::TerrainSet),
+) -> Result<Option<Box<dyn Command>>, String> {
     let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
-        return;
+        return Ok(None);
+    };
+    let selected_tileset = terrain_set.tileset_id;
+    let Some(level) = project.get_level(level_id) else {
+        return Ok(None);
     };
 
-    // Fill with uniform terrain tiles
-    let uniform_tiles = terrain_set.find_uniform_tiles(terrain_idx);
-    let uniform_tile = uniform_tiles.first().copied();
+    let mut painted: Vec<usize> = routes
+        .iter()
+        .flat_map(|route| route.painted().iter().copied())
+        .collect();
+    painted.sort_unstable();
+    painted.dedup();
 
-    if let Some(tile_index) = uniform_tile {
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                let idx = (y as u32 * level_width + x as u32) as usize;
-                if idx < tiles.len() {
-                    tiles[idx] = Some(tile_index);
+    let mut retargets = Vec::new();
+    for &idx in &painted {
+        let Some(layer) = level.layers.get(idx) else {
+            continue;
+        };
+        match get_layer_tileset_id(layer) {
+            Some(tileset) if tileset != selected_tileset => {
+                if layer_has_tiles(layer) {
+                    return Err(format!(
+                        "Layer '{}' uses a different tileset than terrain set '{}'.",
+                        layer.name, terrain_set.name
+                    ));
                 }
+                retargets.push(SetLayerTilesetCommand::new(
+                    level_id,
+                    idx,
+                    tileset,
+                    selected_tileset,
+                ));
             }
-        }
-    } else {
-        return;
-    }
-
-    // Update edge tiles
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            let is_at_edge = x == min_x || x == max_x || y == min_y || y == max_y;
-            if is_at_edge {
-                bevy_map_autotile::update_tile_with_neighbors(
-                    tiles,
-                    level_width,
-                    level_height,
-                    x,
-                    y,
-                    terrain_set,
-                    terrain_idx,
-                );
-            }
+            _ => {}
         }
     }
 
-    // Update outside neighbor tiles
-    let update_min_x = (min_x - 1).max(0);
-    let update_max_x = (max_x + 1).min(level_width as i32 - 1);
-    let update_min_y = (min_y - 1).max(0);
-    let update_max_y = (max_y + 1).min(level_height as i32 - 1);
+    let max_x = level.width as i32 - 1;
+    let max_y = level.height as i32 - 1;
+    let before: Vec<_> = painted
+        .iter()
+        .map(|&idx| collect_tiles_in_region(project, level_id, idx, 0, max_x, 0, max_y))
+        .collect();
 
-    for y in update_min_y..=update_max_y {
-        for x in update_min_x..=update_max_x {
-            let is_inside = x >= min_x && x <= max_x && y >= min_y && y <= max_y;
-            if is_inside {
-                continue;
-            }
+    // Split borrow: the level is edited while the terrain set is read
+    let Some(level) = project.levels.iter_mut().find(|l| l.id == level_id) else {
+        return Ok(None);
+    };
+    let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
+        return Ok(None);
+    };
+    edit(level, terrain_set);
 
-            let idx = (y as u32 * level_width + x as u32) as usize;
-            let current_tile = tiles.get(idx).copied().flatten();
-
-            if let Some(tile) = current_tile {
-                if let Some(tile_data) = terrain_set.get_tile_terrain(tile) {
-                    if let Some(primary_terrain) = tile_data.terrains.iter().find_map(|t| *t) {
-                        bevy_map_autotile::update_tile_with_neighbors(
-                            tiles,
-                            level_width,
-                            level_height,
-                            x,
-                            y,
-                            terrain_set,
-                            primary_terrain,
-                        );
-                    }
-                }
-            }
+    let mut commands: Vec<Box<dyn Command>> = Vec::new();
+    for (&idx, before) in painted.iter().zip(before) {
+        let after = collect_tiles_in_region(project, level_id, idx, 0, max_x, 0, max_y);
+        let command = BatchTileCommand::from_diff(level_id, idx, before, after, description);
+        if command.changes.is_empty() {
+            continue;
         }
+        if let Some(pos) = retargets.iter().position(|r| r.layer_idx == idx) {
+            let set_tileset = retargets.swap_remove(pos);
+            set_tileset.execute(project, render_state);
+            commands.push(Box::new(set_tileset));
+        }
+        commands.push(Box::new(command));
     }
 
-    let after_tiles = collect_tiles_in_region(
-        project,
-        level_id,
-        layer_idx,
-        update_min_x,
-        update_max_x,
-        update_min_y,
-        update_max_y,
-    );
-
-    let command = BatchTileCommand::from_diff(
-        level_id,
-        layer_idx,
-        before_tiles,
-        after_tiles,
-        "Fill Terrain Rectangle",
-    );
+    Ok(match commands.len() {
+        0 => None,
+        1 => commands.pop(),
+        _ => Some(Box::new(CompositeCommand::new(commands, description))),
+    })
+}
 
-    if !command.changes.is_empty() {
-        let mut inverse_changes = std::collections::HashMap::new();
-        for ((x, y), (old_tile, new_tile)) in &command.changes {
-            inverse_changes.insert((*x, *y), (*new_tile, *old_tile));
+/// Record the outcome of [`edit_routed_terrain`]
+pub(crate) fn push_terrain_edit(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    render_state: &mut RenderState,
+    history: &mut CommandHistory,
+    result: Result<Option<Box<dyn Command>>, String>,
+) {
+    match result {
+        Ok(Some(command)) => {
+            project.mark_dirty();
+            render_state.needs_rebuild = true;
+            history.push_undo(command);
         }
-
-        let inverse_command = BatchTileCommand::new(
-            level_id,
-            layer_idx,
-            inverse_changes,
-            "Undo Fill Terrain Rectangle",
-        );
-        history.push_undo(Box::new(inverse_command));
+        Ok(None) => {}
+        Err(e) => editor_state.error_message = Some(e),
     }
-
-    project.mark_dirty();
-    render_state.needs_rebuild = true;
 }
 
 /// System to finalize paint strokes and create undo commands
@@ -2633,23 +2590,31 @@ fn finalize_paint_stroke(
     }
 
     if !editor_state.is_painting && !mouse_buttons.pressed(MouseButton::Left) {
-        if !stroke_tracker.changes.is_empty() {
-            if let (Some(level_id), Some(layer_idx)) =
-                (stroke_tracker.level_id, stroke_tracker.layer_idx)
-            {
-                let mut inverse_changes = HashMap::new();
-                for ((x, y), (old_tile, new_tile)) in &stroke_tracker.changes {
-                    inverse_changes.insert((*x, *y), (*old_tile, *new_tile));
-                }
-
-                let inverse_command = BatchTileCommand::new(
-                    level_id,
-                    layer_idx,
-                    inverse_changes,
-                    stroke_tracker.description.clone(),
-                );
-
-                history.push_undo(Box::new(inverse_command));
+        if let (Some(level_id), Some(layer_idx)) =
+            (stroke_tracker.level_id, stroke_tracker.layer_idx)
+        {
+            let description = stroke_tracker.description.clone();
+            let mut layer_changes: Vec<_> = stroke_tracker.other_layer_changes.drain().collect();
+            layer_changes.push((layer_idx, std::mem::take(&mut stroke_tracker.changes)));
+            layer_changes.sort_by_key(|(idx, _)| *idx);
+
+            // One undo step, even when a routed terrain stroke painted several layers
+            let mut layer_commands: Vec<Box<dyn Command>> = layer_changes
+                .into_iter()
+                .filter(|(_, changes)| !changes.is_empty())
+                .map(|(idx, changes)| -> Box<dyn Command> {
+                    Box::new(BatchTileCommand::new(
+                        level_id,
+                        idx,
+                        changes,
+                        description.clone(),
+                    ))
+                })
+                .collect();
+            if layer_commands.len() > 1 {
+                history.push_undo(Box::new(CompositeCommand::new(layer_commands, description)));
+            } else if let Some(command) = layer_commands.pop() {
+                history.push_undo(command);
             }
         }
 
@@ -2657,6 +2622,7 @@ fn finalize_paint_stroke(
         stroke_tracker.level_id = None;
         stroke_tracker.layer_idx = None;
         stroke_tracker.changes.clear();
+        stroke_tracker.other_layer_changes.clear();
         stroke_tracker.description.clear();
     }
 }
@@ -2700,14 +2666,8 @@ fn calculate_terrain_preview(
         return;
     };
 
-    let Some(layer) = level.layers.get(layer_idx) else {
-        editor_state.terrain_preview.active = false;
-        return;
-    };
-
-    let tiles = if let LayerData::Tiles { tiles, .. } = &layer.data {
-        tiles
-    } else {
+    // Preview every layer the terrain paints (the selected one unless routed)
+    let Ok(route) = terrain_set.terrain_layers(level, layer_idx, terrain_idx) else {
         editor_state.terrain_preview.active = false;
         return;
     };
@@ -2766,10 +2726,9 @@ fn calculate_terrain_preview(
 
     // Calculate preview using the autotile algorithm
     // For full-tile mode, we need to preview all targets together
-    let preview_tiles = bevy_map_autotile::preview_terrain_at_targets(
-        tiles,
-        level.width,
-        level.height,
+    let preview_tiles = bevy_map_autotile::preview_terrain_layers(
+        level,
+        &route,
         &paint_targets,
         terrain_set,
         terrain_idx,
@@ -3,14 +3,15 @@
 //! This module provides an animation editor with a timeline-focused dopesheet view:
 //! - Compact toolbar with animation selection and playback controls
 //! - Main dopesheet view with Frames, Windows, and Triggers tracks
-//! - Floating preview window (toggleable)
+//! - Floating preview window (toggleable) with hitbox/hurtbox shape editing
 //! - Collapsible frame picker for building animations
 //!
 //! For spritesheet setup (image loading, grid config), use the SpriteSheet Editor.
 
 use bevy_egui::egui;
 use bevy_map_animation::{
-    AnimationDef, AnimationTrigger, AnimationWindow, LoopMode, SpriteData, TriggerPayload,
+    AnimationDef, AnimationTrigger, AnimationWindow, CollisionShape, HitboxKind, LoopMode,
+    SpriteData, TriggerPayload, WindowShape,
 };
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -26,6 +27,17 @@ pub enum DragHandle {
     Body,
 }
 
+/// Drag handle type for window shapes in the preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeDragHandle {
+    /// Moving the whole shape
+    Body,
+    /// Resizing (rectangle corner or circle radius)
+    Resize,
+    /// Moving a single polygon vertex
+    Vertex(usize),
+}
+
 /// State for the Animation Editor
 #[derive(Default, Clone)]
 pub struct AnimationEditorState {
@@ -87,6 +99,11 @@ pub struct AnimationEditorState {
     pub drag_start_time: u32,
     /// Original window end time at drag start (for body drag to preserve duration)
     pub drag_original_end: u32,
+    // === Window Shape State ===
+    /// Selected shape within the selected window
+    pub selected_shape: Option<Uuid>,
+    /// Shape handle being dragged in the preview
+    pub dragging_shape: Option<ShapeDragHandle>,
     // === Context Menu State ===
     /// Time position where right-click occurred (for adding events)
     pub context_menu_time: Option<u32>,
@@ -137,6 +154,9 @@ impl AnimationEditorState {
             dragging_window: None,
             drag_start_time: 0,
             drag_original_end: 0,
+            // Window shapes
+            selected_shape: None,
+            dragging_shape: None,
             // Context menu
             context_menu_time: None,
         }
@@ -170,6 +190,8 @@ impl AnimationEditorState {
         self.dragging_window = None;
         self.drag_start_time = 0;
         self.drag_original_end = 0;
+        self.selected_shape = None;
+        self.dragging_shape = None;
         self.context_menu_time = None;
     }
 
@@ -241,7 +263,7 @@ pub fn render_animation_editor(
     let mut is_open = true;
 
     // Render floating preview window (separate from main window)
-    render_floating_preview(ctx, state); // state is already &mut
+    render_floating_preview(ctx, state, &mut result); // state is already &mut
 
     egui::Window::new("Animation Editor")
         .open(&mut is_open)
@@ -1307,9 +1329,19 @@ fn render_details_panel(
                     }
                 });
 
+                let frame_pos = state.preview_frame % anim.frames.len().max(1);
+                render_window_shapes(
+                    ui,
+                    &mut anim.windows[idx],
+                    frame_pos,
+                    &mut state.selected_shape,
+                    result,
+                );
+
                 if delete_window {
                     anim.windows.remove(idx);
                     state.selected_window = None;
+                    state.selected_shape = None;
                     result.changed = true;
                 }
             } else {
@@ -1444,6 +1476,137 @@ fn render_payload_details(
     }
 }
 
+/// Render the hitbox/hurtbox shape list for a window
+fn render_window_shapes(
+    ui: &mut egui::Ui,
+    window: &mut AnimationWindow,
+    frame_pos: usize,
+    selected_shape: &mut Option<Uuid>,
+    result: &mut AnimationEditorResult,
+) {
+    // Drop a stale selection (e.g. after switching windows)
+    if selected_shape.is_some_and(|id| window.get_shape(id).is_none()) {
+        *selected_shape = None;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label("Shapes:");
+        for shape in &window.shapes {
+            let color = shape_color(shape.kind);
+            let label = egui::RichText::new(if shape.name.is_empty() {
+                shape.kind.display_name()
+            } else {
+                &shape.name
+            })
+            .color(color);
+            if ui
+                .selectable_label(*selected_shape == Some(shape.id), label)
+                .clicked()
+            {
+                *selected_shape = Some(shape.id);
+            }
+        }
+        for kind in HitboxKind::all() {
+            if ui
+                .small_button(format!("+ {}", kind.display_name()))
+                .clicked()
+            {
+                let shape = WindowShape::new(
+                    kind.display_name().to_lowercase(),
+                    *kind,
+                    CollisionShape::rectangle([0.25, 0.25], [0.5, 0.5]),
+                );
+                *selected_shape = Some(shape.id);
+                window.add_shape(shape);
+                result.changed = true;
+            }
+        }
+    });
+
+    let Some(shape_id) = *selected_shape else {
+        return;
+    };
+    let Some(shape) = window.get_shape_mut(shape_id) else {
+        return;
+    };
+
+    let mut delete_shape = false;
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut shape.name).desired_width(80.0));
+
+        egui::ComboBox::from_id_salt("window_shape_kind")
+            .width(70.0)
+            .selected_text(shape.kind.display_name())
+            .show_ui(ui, |ui| {
+                for kind in HitboxKind::all() {
+                    if ui
+                        .selectable_label(shape.kind == *kind, kind.display_name())
+                        .clicked()
+                    {
+                        shape.kind = *kind;
+                        result.changed = true;
+                    }
+                }
+            });
+
+        let current = shape.shape_at_frame_mut(frame_pos);
+        egui::ComboBox::from_id_salt("window_shape_type")
+            .width(80.0)
+            .selected_text(current.name())
+            .show_ui(ui, |ui| {
+                let options = [
+                    CollisionShape::Full,
+                    CollisionShape::rectangle([0.25, 0.25], [0.5, 0.5]),
+                    CollisionShape::circle([0.5, 0.5], 0.25),
+                    CollisionShape::polygon(vec![[0.5, 0.2], [0.8, 0.8], [0.2, 0.8]]),
+                ];
+                for option in options {
+                    if ui
+                        .selectable_label(current.name() == option.name(), option.name())
+                        .clicked()
+                        && current.name() != option.name()
+                    {
+                        *current = option;
+                        result.changed = true;
+                    }
+                }
+            });
+
+        ui.separator();
+        let mut keyed = shape.has_frame_override(frame_pos);
+        if ui
+            .checkbox(&mut keyed, format!("Key frame {}", frame_pos + 1))
+            .on_hover_text("Give this frame its own shape instead of the window's base shape")
+            .changed()
+        {
+            if keyed {
+                shape.add_frame_override(frame_pos);
+            } else {
+                shape.remove_frame_override(frame_pos);
+            }
+            result.changed = true;
+        }
+
+        if ui.button("🗑 Delete Shape").clicked() {
+            delete_shape = true;
+        }
+    });
+
+    if delete_shape {
+        window.remove_shape(shape_id);
+        *selected_shape = None;
+        result.changed = true;
+    }
+}
+
+/// Editor color for a window shape kind
+fn shape_color(kind: HitboxKind) -> egui::Color32 {
+    match kind {
+        HitboxKind::Hitbox => egui::Color32::from_rgb(230, 70, 70),
+        HitboxKind::Hurtbox => egui::Color32::from_rgb(70, 140, 230),
+    }
+}
+
 // ============================================================================
 // Frame Picker (Collapsible)
 // ============================================================================
@@ -1614,7 +1777,11 @@ fn render_frame_picker(
 // ============================================================================
 
 /// Render the floating preview window
-fn render_floating_preview(ctx: &egui::Context, state: &mut AnimationEditorState) {
+fn render_floating_preview(
+    ctx: &egui::Context,
+    state: &mut AnimationEditorState,
+    result: &mut AnimationEditorResult,
+) {
     if !state.show_preview {
         return;
     }
@@ -1675,21 +1842,241 @@ fn render_floating_preview(ctx: &egui::Context, state: &mut AnimationEditorState
             let scale = scale_x.min(scale_y).max(1.0);
             let display_size = egui::vec2(frame_w * scale, frame_h * scale);
 
-            ui.vertical_centered(|ui| {
-                ui.add(
-                    egui::Image::new(egui::load::SizedTexture::new(texture_id, display_size)).uv(
-                        egui::Rect::from_min_max(egui::pos2(u0, v0), egui::pos2(u1, v1)),
-                    ),
-                );
-            });
+            let image_rect = ui
+                .vertical_centered(|ui| {
+                    ui.add(
+                        egui::Image::new(egui::load::SizedTexture::new(texture_id, display_size))
+                            .uv(egui::Rect::from_min_max(
+                                egui::pos2(u0, v0),
+                                egui::pos2(u1, v1),
+                            )),
+                    )
+                    .rect
+                })
+                .inner;
 
             ui.label(format!("Frame {} / {}", frame_idx + 1, anim.frames.len()));
+
+            render_preview_shapes(ui, image_rect, state, result);
         });
 
     // Update state if closed via X button
     state.show_preview = show;
 }
 
+/// Draw the shapes of windows active on the preview frame, and edit the selected one
+fn render_preview_shapes(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    state: &mut AnimationEditorState,
+    result: &mut AnimationEditorResult,
+) {
+    let Some(anim_name) = state.selected_animation.clone() else {
+        return;
+    };
+    let Some(anim) = state.sprite_data.animations.get_mut(&anim_name) else {
+        return;
+    };
+    if anim.frames.is_empty() {
+        return;
+    }
+
+    let frame_pos = state.preview_frame % anim.frames.len();
+    let time_ms = anim.frame_to_time_ms(frame_pos);
+    let painter = ui.painter_at(rect);
+
+    for window in anim.windows.iter().filter(|w| w.is_active_at(time_ms)) {
+        for shape in &window.shapes {
+            let selected =
+                state.selected_window == Some(window.id) && state.selected_shape == Some(shape.id);
+            draw_shape(
+                &painter,
+                rect,
+                shape.shape_at_frame(frame_pos),
+                shape_color(shape.kind),
+                selected,
+            );
+        }
+    }
+
+    // Only the selected shape of an active window is editable
+    let Some(shape) = state
+        .selected_window
+        .and_then(|id| anim.get_window_mut(id))
+        .filter(|w| w.is_active_at(time_ms))
+        .and_then(|w| state.selected_shape.and_then(|id| w.get_shape_mut(id)))
+    else {
+        return;
+    };
+    let shape = shape.shape_at_frame_mut(frame_pos);
+
+    let response = ui.interact(
+        rect,
+        ui.id().with("preview_shape_edit"),
+        egui::Sense::drag(),
+    );
+    if response.drag_started() {
+        state.dragging_shape = response
+            .interact_pointer_pos()
+            .and_then(|pos| hit_test_shape(rect, shape, pos));
+    }
+    if response.dragged() {
+        if let Some(handle) = state.dragging_shape {
+            let delta = response.drag_delta();
+            let d = [delta.x / rect.width(), delta.y / rect.height()];
+            drag_shape(
+                shape,
+                handle,
+                d,
+                rect.width() / rect.width().min(rect.height()),
+            );
+            result.changed = true;
+        }
+    }
+    if response.drag_stopped() {
+        state.dragging_shape = None;
+    }
+}
+
+/// Convert a normalized frame coordinate to screen space
+fn norm_to_screen(rect: egui::Rect, p: [f32; 2]) -> egui::Pos2 {
+    egui::pos2(
+        rect.min.x + p[0] * rect.width(),
+        rect.min.y + p[1] * rect.height(),
+    )
+}
+
+/// Draw a frame-normalized shape over the preview
+fn draw_shape(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    shape: &CollisionShape,
+    color: egui::Color32,
+    selected: bool,
+) {
+    let fill = color.gamma_multiply(if selected { 0.35 } else { 0.2 });
+    let stroke = egui::Stroke::new(if selected { 2.0 } else { 1.0 }, color);
+    let handle = |pos: egui::Pos2| {
+        if selected {
+            painter.circle_filled(pos, 4.0, egui::Color32::WHITE);
+        }
+    };
+
+    match shape {
+        CollisionShape::None => {}
+        CollisionShape::Full => {
+            painter.rect(rect, 0.0, fill, stroke, egui::StrokeKind::Inside);
+        }
+        CollisionShape::Rectangle { offset, size } => {
+            let min = norm_to_screen(rect, *offset);
+            let max = norm_to_screen(rect, [offset[0] + size[0], offset[1] + size[1]]);
+            painter.rect(
+                egui::Rect::from_min_max(min, max),
+                0.0,
+                fill,
+                stroke,
+                egui::StrokeKind::Inside,
+            );
+            handle(max);
+        }
+        CollisionShape::Circle { offset, radius } => {
+            let center = norm_to_screen(rect, *offset);
+            let r = radius * rect.width().min(rect.height());
+            painter.circle(center, r, fill, stroke);
+            handle(center + egui::vec2(r, 0.0));
+        }
+        CollisionShape::Polygon { points } => {
+            let screen: Vec<egui::Pos2> = points.iter().map(|p| norm_to_screen(rect, *p)).collect();
+            painter.add(egui::Shape::convex_polygon(screen.clone(), fill, stroke));
+            for pos in screen {
+                handle(pos);
+            }
+        }
+    }
+}
+
+/// Find which handle of a shape is under the pointer
+fn hit_test_shape(
+    rect: egui::Rect,
+    shape: &CollisionShape,
+    pos: egui::Pos2,
+) -> Option<ShapeDragHandle> {
+    const HANDLE_RADIUS: f32 = 6.0;
+    match shape {
+        CollisionShape::None | CollisionShape::Full => None,
+        CollisionShape::Rectangle { offset, size } => {
+            let min = norm_to_screen(rect, *offset);
+            let max = norm_to_screen(rect, [offset[0] + size[0], offset[1] + size[1]]);
+            if max.distance(pos) <= HANDLE_RADIUS {
+                Some(ShapeDragHandle::Resize)
+            } else if egui::Rect::from_min_max(min, max).contains(pos) {
+                Some(ShapeDragHandle::Body)
+            } else {
+                None
+            }
+        }
+        CollisionShape::Circle { offset, radius } => {
+            let center = norm_to_screen(rect, *offset);
+            let r = radius * rect.width().min(rect.height());
+            if (center + egui::vec2(r, 0.0)).distance(pos) <= HANDLE_RADIUS {
+                Some(ShapeDragHandle::Resize)
+            } else if center.distance(pos) <= r {
+                Some(ShapeDragHandle::Body)
+            } else {
+                None
+            }
+        }
+        CollisionShape::Polygon { points } => {
+            if let Some(i) = points
+                .iter()
+                .position(|p| norm_to_screen(rect, *p).distance(pos) <= HANDLE_RADIUS)
+            {
+                return Some(ShapeDragHandle::Vertex(i));
+            }
+            // Bounding box is close enough for grabbing the body
+            let screen: Vec<egui::Pos2> = points.iter().map(|p| norm_to_screen(rect, *p)).collect();
+            egui::Rect::from_points(&screen)
+                .contains(pos)
+                .then_some(ShapeDragHandle::Body)
+        }
+    }
+}
+
+/// Apply a normalized drag delta to a shape
+///
+/// `radius_scale` converts a normalized X delta into a radius delta, since
+/// circle radii are relative to the frame's smaller side.
+fn drag_shape(shape: &mut CollisionShape, handle: ShapeDragHandle, d: [f32; 2], radius_scale: f32) {
+    const MIN_SIZE: f32 = 0.02;
+    match (shape, handle) {
+        (CollisionShape::Rectangle { offset, .. }, ShapeDragHandle::Body)
+        | (CollisionShape::Circle { offset, .. }, ShapeDragHandle::Body) => {
+            offset[0] += d[0];
+            offset[1] += d[1];
+        }
+        (CollisionShape::Rectangle { size, .. }, ShapeDragHandle::Resize) => {
+            size[0] = (size[0] + d[0]).max(MIN_SIZE);
+            size[1] = (size[1] + d[1]).max(MIN_SIZE);
+        }
+        (CollisionShape::Circle { radius, .. }, ShapeDragHandle::Resize) => {
+            *radius = (*radius + d[0] * radius_scale).max(MIN_SIZE);
+        }
+        (CollisionShape::Polygon { points }, ShapeDragHandle::Body) => {
+            for p in points.iter_mut() {
+                p[0] += d[0];
+                p[1] += d[1];
+            }
+        }
+        (CollisionShape::Polygon { points }, ShapeDragHandle::Vertex(i)) => {
+            if let Some(p) = points.get_mut(i) {
+                p[0] += d[0];
+                p[1] += d[1];
+            }
+        }
+        _ => {}
+    }
+}
+
 // ============================================================================
 // Preview Timer Update
 // ============================================================================
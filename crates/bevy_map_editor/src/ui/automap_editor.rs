@@ -0,0 +1,488 @@
+//! Automap rule editor window
+//!
+//! Edits the project's automapping rule sets: the output layer, rule order,
+//! input patterns and weighted output tiles. Pattern cells are clicked to
+//! cycle through Any, Not Empty, Empty, the selected tile and "not" the
+//! selected tile.
+
+use bevy_egui::egui;
+use bevy_map_autotile::{AutomapRule, AutomapRuleSet, CellMatch, RuleInput, RuleOutput};
+use bevy_map_core::LayerData;
+use uuid::Uuid;
+
+use super::PendingAction;
+use crate::project::Project;
+use crate::EditorState;
+
+/// Pattern sizes offered in the editor
+const PATTERN_SIZES: [u32; 4] = [1, 3, 5, 7];
+
+/// State for the automap rule editor
+#[derive(Default)]
+pub struct AutomapEditorState {
+    /// Rule set being edited
+    pub selected_rule_set: Option<Uuid>,
+    /// Set when rules were edited, so auto-applied rule sets re-run
+    pub rules_changed: bool,
+}
+
+impl AutomapEditorState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Actions on a rule that change the rule list
+enum RuleAction {
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+}
+
+/// Render the automap rule editor window
+pub fn render_automap_editor(
+    ctx: &egui::Context,
+    editor_state: &mut EditorState,
+    project: &mut Project,
+) {
+    if !editor_state.show_automap_editor {
+        return;
+    }
+
+    // Tile layer names of the selected level, for the layer pickers
+    let layer_names: Vec<String> = editor_state
+        .selected_level
+        .and_then(|id| project.get_level(id))
+        .map(|level| {
+            level
+                .layers
+                .iter()
+                .filter(|l| matches!(l.data, LayerData::Tiles { .. }))
+                .map(|l| l.name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    let selected_tile = editor_state.selected_tile;
+
+    let mut is_open = true;
+    let mut changed = false;
+
+    egui::Window::new("Automap Rules")
+        .open(&mut is_open)
+        .resizable(true)
+        .default_size([620.0, 520.0])
+        .show(ctx, |ui| {
+            let rule_sets = &mut project.autotile_config.rule_sets;
+            let state = &mut editor_state.automap_editor_state;
+            if state
+                .selected_rule_set
+                .map_or(true, |id| !rule_sets.iter().any(|rs| rs.id == id))
+            {
+                state.selected_rule_set = rule_sets.first().map(|rs| rs.id);
+            }
+
+            ui.horizontal(|ui| {
+                let current = state
+                    .selected_rule_set
+                    .and_then(|id| rule_sets.iter().find(|rs| rs.id == id))
+                    .map(|rs| rs.name.clone())
+                    .unwrap_or_else(|| "(none)".to_string());
+                egui::ComboBox::from_label("Rule Set")
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        for rule_set in rule_sets.iter() {
+                            ui.selectable_value(
+                                &mut state.selected_rule_set,
+                                Some(rule_set.id),
+                                &rule_set.name,
+                            );
+                        }
+                    });
+
+                if ui.button("New").clicked() {
+                    let output = layer_names.first().cloned().unwrap_or_default();
+                    let rule_set =
+                        AutomapRuleSet::new(format!("Rules {}", rule_sets.len() + 1), output);
+                    state.selected_rule_set = Some(rule_set.id);
+                    rule_sets.push(rule_set);
+                    changed = true;
+                }
+                if let Some(id) = state.selected_rule_set {
+                    if ui.button("Delete").clicked() {
+                        rule_sets.retain(|rs| rs.id != id);
+                        state.selected_rule_set = None;
+                        changed = true;
+                    }
+                }
+            });
+            ui.separator();
+
+            let Some(rule_set) = state
+                .selected_rule_set
+                .and_then(|id| rule_sets.iter_mut().find(|rs| rs.id == id))
+            else {
+                ui.label("Create a rule set to start adding rules.");
+                return;
+            };
+
+            changed |= render_rule_set_settings(ui, rule_set, &layer_names);
+            if ui
+                .button("Apply to Level")
+                .on_hover_text("Run the rules on the selected level (undoable)")
+                .clicked()
+            {
+                editor_state.pending_action = Some(PendingAction::ApplyAutomapRules {
+                    rule_set_id: rule_set.id,
+                });
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .id_salt("automap_rules_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    let mut action = None;
+                    let count = rule_set.rules.len();
+                    for (i, rule) in rule_set.rules.iter_mut().enumerate() {
+                        ui.push_id(i, |ui| {
+                            changed |= render_rule(
+                                ui,
+                                i,
+                                count,
+                                rule,
+                                &layer_names,
+                                selected_tile,
+                                &mut action,
+                            );
+                        });
+                    }
+
+                    match action {
+                        Some(RuleAction::MoveUp(i)) if i > 0 => rule_set.rules.swap(i, i - 1),
+                        Some(RuleAction::MoveDown(i)) if i + 1 < count => {
+                            rule_set.rules.swap(i, i + 1)
+                        }
+                        Some(RuleAction::Remove(i)) => {
+                            rule_set.rules.remove(i);
+                        }
+                        _ => {}
+                    }
+                    changed |= action.is_some();
+
+                    if ui.button("+ Add Rule").clicked() {
+                        let mut rule = AutomapRule::new(format!("Rule {}", count + 1));
+                        let layer = layer_names.first().cloned().unwrap_or_default();
+                        rule.inputs.push(RuleInput::new(layer, 3));
+                        rule_set.rules.push(rule);
+                        changed = true;
+                    }
+                });
+        });
+
+    if !is_open {
+        editor_state.show_automap_editor = false;
+    }
+    if changed {
+        editor_state.automap_editor_state.rules_changed = true;
+        project.mark_dirty();
+    }
+}
+
+/// Name, output layer and options of a rule set. Returns true if changed.
+fn render_rule_set_settings(
+    ui: &mut egui::Ui,
+    rule_set: &mut AutomapRuleSet,
+    layer_names: &[String],
+) -> bool {
+    let mut changed = false;
+    egui::Grid::new("automap_rule_set_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name:");
+            changed |= ui.text_edit_singleline(&mut rule_set.name).changed();
+            ui.end_row();
+
+            ui.label("Output Layer:");
+            changed |= layer_combo(
+                ui,
+                "automap_output_layer",
+                &mut rule_set.output_layer,
+                layer_names,
+            );
+            ui.end_row();
+
+            ui.label("Seed:");
+            changed |= ui.add(egui::DragValue::new(&mut rule_set.seed)).changed();
+            ui.end_row();
+        });
+    ui.horizontal(|ui| {
+        changed |= ui
+            .checkbox(&mut rule_set.clear_output, "Clear unmatched cells")
+            .on_hover_text(
+                "Output cells no rule writes are erased, so the layer is fully generated",
+            )
+            .changed();
+        changed |= ui
+            .checkbox(&mut rule_set.auto_apply, "Apply on edit")
+            .on_hover_text("Re-run whenever an input layer of the selected level changes")
+            .changed();
+    });
+    changed
+}
+
+/// Pick a layer by name. Returns true if changed.
+fn layer_combo(ui: &mut egui::Ui, id: &str, layer: &mut String, layer_names: &[String]) -> bool {
+    let mut changed = false;
+    let text = if layer.is_empty() {
+        "(none)".to_string()
+    } else if layer_names.contains(layer) {
+        layer.clone()
+    } else {
+        format!("{} (missing)", layer)
+    };
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for name in layer_names {
+                changed |= ui.selectable_value(layer, name.clone(), name).changed();
+            }
+        });
+    changed
+}
+
+/// Render one rule. Returns true if changed.
+fn render_rule(
+    ui: &mut egui::Ui,
+    index: usize,
+    count: usize,
+    rule: &mut AutomapRule,
+    layer_names: &[String],
+    selected_tile: Option<u32>,
+    action: &mut Option<RuleAction>,
+) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        changed |= ui.checkbox(&mut rule.enabled, "").changed();
+        changed |= ui
+            .add(egui::TextEdit::singleline(&mut rule.name).desired_width(160.0))
+            .changed();
+        if ui
+            .add_enabled(index > 0, egui::Button::new("^"))
+            .on_hover_text("Move up")
+            .clicked()
+        {
+            *action = Some(RuleAction::MoveUp(index));
+        }
+        if ui
+            .add_enabled(index + 1 < count, egui::Button::new("v"))
+            .on_hover_text("Move down")
+            .clicked()
+        {
+            *action = Some(RuleAction::MoveDown(index));
+        }
+        if ui.button("Delete").clicked() {
+            *action = Some(RuleAction::Remove(index));
+        }
+    });
+
+    egui::CollapsingHeader::new("Details")
+        .id_salt("automap_rule_details")
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Chance:");
+                changed |= ui
+                    .add(egui::Slider::new(&mut rule.chance, 0.0..=1.0))
+                    .changed();
+            });
+            ui.horizontal(|ui| {
+                changed |= ui.checkbox(&mut rule.flip_x, "Flip X").changed();
+                changed |= ui.checkbox(&mut rule.flip_y, "Flip Y").changed();
+                changed |= ui.checkbox(&mut rule.rotate, "Rotate").changed();
+                changed |= ui
+                    .checkbox(&mut rule.break_on_match, "Break on match")
+                    .on_hover_text("Later rules skip cells this rule writes")
+                    .changed();
+            });
+
+            ui.label("Inputs (all must match):");
+            let mut remove_input = None;
+            for (i, input) in rule.inputs.iter_mut().enumerate() {
+                ui.push_id(i, |ui| {
+                    ui.horizontal(|ui| {
+                        changed |= layer_combo(ui, "input_layer", &mut input.layer, layer_names);
+                        let mut size = input.size;
+                        egui::ComboBox::from_id_salt("input_size")
+                            .selected_text(format!("{}x{}", size, size))
+                            .width(50.0)
+                            .show_ui(ui, |ui| {
+                                for s in PATTERN_SIZES {
+                                    ui.selectable_value(&mut size, s, format!("{}x{}", s, s));
+                                }
+                            });
+                        if size != input.size {
+                            input.resize(size);
+                            changed = true;
+                        }
+                        if ui.small_button("x").on_hover_text("Remove input").clicked() {
+                            remove_input = Some(i);
+                        }
+                    });
+                    changed |= render_pattern(ui, input, selected_tile);
+                });
+            }
+            if let Some(i) = remove_input {
+                rule.inputs.remove(i);
+                changed = true;
+            }
+            if ui.button("+ Add Input").clicked() {
+                let layer = layer_names.first().cloned().unwrap_or_default();
+                rule.inputs.push(RuleInput::new(layer, 3));
+                changed = true;
+            }
+
+            ui.label("Outputs (weighted):");
+            changed |= render_outputs(ui, rule, selected_tile);
+        });
+    ui.separator();
+
+    changed
+}
+
+/// Render an input pattern as a grid of clickable cells. Returns true if changed.
+fn render_pattern(ui: &mut egui::Ui, input: &mut RuleInput, selected_tile: Option<u32>) -> bool {
+    let mut changed = false;
+    let half = (input.size / 2) as i32;
+    egui::Grid::new("pattern")
+        .spacing([2.0, 2.0])
+        .show(ui, |ui| {
+            for dy in -half..=half {
+                for dx in -half..=half {
+                    let cell = input.cell(dx, dy);
+                    let mut text = egui::RichText::new(cell_label(cell)).monospace();
+                    if dx == 0 && dy == 0 {
+                        text = text.strong().underline();
+                    }
+                    let response = ui
+                        .add(egui::Button::new(text).min_size(egui::vec2(32.0, 24.0)))
+                        .on_hover_text(format!(
+                            "{}\nClick: cycle, right-click: Any",
+                            cell_description(cell)
+                        ));
+                    if response.clicked() {
+                        let next = next_cell(cell, selected_tile);
+                        input.set_cell(dx, dy, next);
+                        changed = true;
+                    } else if response.secondary_clicked() {
+                        input.set_cell(dx, dy, CellMatch::Any);
+                        changed = true;
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    changed
+}
+
+fn cell_label(cell: &CellMatch) -> String {
+    let tiles = |tiles: &[u32]| match tiles {
+        [tile] => tile.to_string(),
+        [tile, ..] => format!("{}+", tile),
+        [] => String::new(),
+    };
+    match cell {
+        CellMatch::Any => "·".to_string(),
+        CellMatch::Empty => "□".to_string(),
+        CellMatch::NotEmpty => "■".to_string(),
+        CellMatch::Tiles(t) => tiles(t),
+        CellMatch::NotTiles(t) => format!("!{}", tiles(t)),
+    }
+}
+
+fn cell_description(cell: &CellMatch) -> String {
+    let list = |tiles: &[u32]| {
+        tiles
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match cell {
+        CellMatch::Any => "Any".to_string(),
+        CellMatch::Empty => "Empty".to_string(),
+        CellMatch::NotEmpty => "Not empty".to_string(),
+        CellMatch::Tiles(t) => format!("Tile {}", list(t)),
+        CellMatch::NotTiles(t) => format!("Not tile {}", list(t)),
+    }
+}
+
+/// Next state when a pattern cell is clicked (tile states need a selected tile)
+fn next_cell(cell: &CellMatch, selected_tile: Option<u32>) -> CellMatch {
+    match (cell, selected_tile) {
+        (CellMatch::Any, _) => CellMatch::NotEmpty,
+        (CellMatch::NotEmpty, _) => CellMatch::Empty,
+        (CellMatch::Empty, Some(tile)) => CellMatch::Tiles(vec![tile]),
+        (CellMatch::Tiles(_), Some(tile)) => CellMatch::NotTiles(vec![tile]),
+        _ => CellMatch::Any,
+    }
+}
+
+/// Render the output list with add buttons. Returns true if changed.
+fn render_outputs(ui: &mut egui::Ui, rule: &mut AutomapRule, selected_tile: Option<u32>) -> bool {
+    let mut changed = false;
+    let mut remove = None;
+    for (i, output) in rule.outputs.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            match output.tile {
+                Some(tile) => ui.label(format!("Tile {}", tile)),
+                None => ui.label("Erase"),
+            };
+            ui.label("Weight:");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut output.weight)
+                        .speed(0.1)
+                        .range(0.0..=100.0),
+                )
+                .changed();
+            if ui
+                .small_button("x")
+                .on_hover_text("Remove output")
+                .clicked()
+            {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        rule.outputs.remove(i);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        let label = match selected_tile {
+            Some(tile) => format!("+ Tile {}", tile),
+            None => "+ Selected Tile".to_string(),
+        };
+        if ui
+            .add_enabled(selected_tile.is_some(), egui::Button::new(label))
+            .on_disabled_hover_text("Select a tile in the palette first")
+            .clicked()
+        {
+            rule.outputs.push(RuleOutput {
+                tile: selected_tile,
+                weight: 1.0,
+            });
+            changed = true;
+        }
+        if ui.button("+ Erase").clicked() {
+            rule.outputs.push(RuleOutput {
+                tile: None,
+                weight: 1.0,
+            });
+            changed = true;
+        }
+    });
+    changed
+}
@@ -14,6 +14,7 @@ pub enum CodePreviewTab {
     #[default]
     Entities,
     Enums,
+    Data,
     Stubs,
     Behaviors,
 }
@@ -23,6 +24,7 @@ impl CodePreviewTab {
         &[
             CodePreviewTab::Entities,
             CodePreviewTab::Enums,
+            CodePreviewTab::Data,
             CodePreviewTab::Stubs,
             CodePreviewTab::Behaviors,
         ]
@@ -32,6 +34,7 @@ impl CodePreviewTab {
         match self {
             CodePreviewTab::Entities => "Entities",
             CodePreviewTab::Enums => "Enums",
+            CodePreviewTab::Data => "Data",
             CodePreviewTab::Stubs => "Stubs",
             CodePreviewTab::Behaviors => "Behaviors",
         }
@@ -49,6 +52,8 @@ pub struct CodePreviewDialogState {
     pub entities_code: String,
     /// Generated enums code
     pub enums_code: String,
+    /// Generated data types code
+    pub data_code: String,
     /// Generated stubs code
     pub stubs_code: String,
     /// Generated behaviors code
@@ -56,7 +61,7 @@ pub struct CodePreviewDialogState {
     /// Error message if generation failed
     pub error: Option<String>,
     /// Scroll position for each tab
-    pub scroll_positions: [f32; 4],
+    pub scroll_positions: [f32; 5],
     /// Output path for generated code files (for opening in VS Code)
     pub output_path: Option<PathBuf>,
     /// Custom VS Code path (from project config)
@@ -71,11 +76,13 @@ impl CodePreviewDialogState {
         &mut self,
         entities: String,
         enums: String,
+        data: String,
         stubs: String,
         behaviors: String,
     ) {
         self.entities_code = entities;
         self.enums_code = enums;
+        self.data_code = data;
         self.stubs_code = stubs;
         self.behaviors_code = behaviors;
         self.error = None;
@@ -91,6 +98,7 @@ impl CodePreviewDialogState {
         match self.selected_tab {
             CodePreviewTab::Entities => &self.entities_code,
             CodePreviewTab::Enums => &self.enums_code,
+            CodePreviewTab::Data => &self.data_code,
             CodePreviewTab::Stubs => &self.stubs_code,
             CodePreviewTab::Behaviors => &self.behaviors_code,
         }
@@ -102,6 +110,7 @@ impl CodePreviewDialogState {
             let filename = match self.selected_tab {
                 CodePreviewTab::Entities => "entities.rs",
                 CodePreviewTab::Enums => "enums.rs",
+                CodePreviewTab::Data => "data.rs",
                 CodePreviewTab::Stubs => "stubs.rs",
                 CodePreviewTab::Behaviors => "behaviors.rs",
             };
@@ -232,7 +241,7 @@ mod tests {
 
     #[test]
     fn test_code_preview_tab() {
-        assert_eq!(CodePreviewTab::all().len(), 4);
+        assert_eq!(CodePreviewTab::all().len(), 5);
         assert_eq!(CodePreviewTab::Entities.label(), "Entities");
     }
 
@@ -243,6 +252,7 @@ mod tests {
         state.set_content(
             "entities".to_string(),
             "enums".to_string(),
+            "data".to_string(),
             "stubs".to_string(),
             "behaviors".to_string(),
         );
@@ -251,5 +261,8 @@ mod tests {
 
         state.selected_tab = CodePreviewTab::Enums;
         assert_eq!(state.current_code(), "enums");
+
+        state.selected_tab = CodePreviewTab::Data;
+        assert_eq!(state.current_code(), "data");
     }
 }
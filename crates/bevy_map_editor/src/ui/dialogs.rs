@@ -3,9 +3,26 @@
 use bevy_egui::egui;
 
 use crate::project::Project;
+use crate::ui::Selection;
 use crate::EditorState;
 use crate::{AssetsBasePath, CopyFileCallback};
 
+/// Locale column that holds the project's inline (source-language) text in string tables
+#[cfg(feature = "native")]
+const SOURCE_LOCALE: &str = "en";
+
+/// The dialogue that dialogue actions apply to: the one open in the dialogue editor,
+/// otherwise the one selected in the tree view
+pub(crate) fn active_dialogue_id(editor_state: &EditorState) -> Option<String> {
+    editor_state
+        .dialogue_editor_asset_id
+        .clone()
+        .or_else(|| match &editor_state.selection {
+            Selection::Dialogue(id) => Some(id.clone()),
+            _ => None,
+        })
+}
+
 /// Actions that can be triggered from menus
 #[derive(Debug, Clone, PartialEq)]
 pub enum PendingAction {
@@ -13,6 +30,18 @@ pub enum PendingAction {
     Open,
     Save,
     SaveAs,
+    /// Export translatable strings to a CSV string table
+    ExportStrings,
+    /// Import source-language strings from a CSV string table
+    ImportStrings,
+    /// Import a Yarn Spinner script or compiled Ink story as a new dialogue
+    ImportDialogue,
+    /// Export the open or selected dialogue as a Yarn Spinner script
+    ExportDialogue,
+    /// Save a template image of a terrain set's missing combinations
+    ExportTerrainTemplate {
+        terrain_set_id: uuid::Uuid,
+    },
     Exit,
     Undo,
     Redo,
@@ -22,6 +51,20 @@ pub enum PendingAction {
     SelectAll,
     /// Create a stamp from the current tile selection
     CreateStampFromSelection,
+    /// Repaint one terrain of a terrain set as another across the selected layer
+    ReplaceTerrain {
+        terrain_set_id: uuid::Uuid,
+        from: usize,
+        to: usize,
+    },
+    /// Re-resolve the selected layer against a terrain set's current tile assignments
+    ResolveTerrainLayer {
+        terrain_set_id: uuid::Uuid,
+    },
+    /// Run an automap rule set on the selected level
+    ApplyAutomapRules {
+        rule_set_id: uuid::Uuid,
+    },
     /// Open the game settings dialog
     OpenGameSettings,
     /// Run the game (save first, then launch)
@@ -129,6 +172,156 @@ pub fn render_dialogs(
                     }
                 }
             }
+            PendingAction::ExportStrings => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("String Table", &["strings.csv", "csv"])
+                        .set_file_name(format!("{}.strings.csv", project.name()))
+                        .save_file()
+                    {
+                        let table = project.export_strings(SOURCE_LOCALE);
+                        if let Err(e) = std::fs::write(&path, table.to_csv()) {
+                            editor_state.error_message =
+                                Some(format!("Failed to export strings: {}", e));
+                        }
+                    }
+                }
+            }
+            PendingAction::ImportStrings => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("String Table", &["strings.csv", "csv"])
+                        .pick_file()
+                    {
+                        let result = std::fs::read_to_string(&path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|source| {
+                                bevy_map_dialogue::StringTable::from_csv(&source)
+                                    .map_err(|e| e.to_string())
+                            });
+                        match result {
+                            Ok(table) => {
+                                let changed = project.import_strings(&table, SOURCE_LOCALE);
+                                bevy::log::info!("Imported {} changed string(s)", changed);
+                            }
+                            Err(e) => {
+                                editor_state.error_message =
+                                    Some(format!("Failed to import strings: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            PendingAction::ImportDialogue => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("Dialogue", &["yarn", "json"])
+                        .add_filter("Yarn Spinner", &["yarn"])
+                        .add_filter("Ink (compiled)", &["json"])
+                        .pick_file()
+                    {
+                        let file_name = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_default();
+                        // "story.ink.json" -> "story"
+                        let name = file_name
+                            .split('.')
+                            .next()
+                            .unwrap_or("Dialogue")
+                            .to_string();
+                        let is_yarn = path.extension().is_some_and(|ext| ext == "yarn");
+
+                        let result = std::fs::read_to_string(&path)
+                            .map_err(|e| e.to_string())
+                            .and_then(|source| {
+                                if is_yarn {
+                                    bevy_map_dialogue::yarn::import_yarn(&source, name)
+                                        .map_err(|e| e.to_string())
+                                } else {
+                                    bevy_map_dialogue::ink::import_ink_json(&source, name)
+                                        .map_err(|e| e.to_string())
+                                }
+                            });
+                        match result {
+                            Ok(dialogue) => {
+                                let id = dialogue.id.clone();
+                                project.add_dialogue(dialogue);
+                                editor_state.selection = Selection::Dialogue(id);
+                            }
+                            Err(e) => {
+                                editor_state.error_message =
+                                    Some(format!("Failed to import dialogue: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            PendingAction::ExportDialogue => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(dialogue) =
+                        active_dialogue_id(editor_state).and_then(|id| project.get_dialogue(&id))
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Yarn Spinner", &["yarn"])
+                            .set_file_name(format!("{}.yarn", dialogue.name))
+                            .save_file()
+                        {
+                            let source = bevy_map_dialogue::yarn::export_yarn(dialogue);
+                            if let Err(e) = std::fs::write(&path, source) {
+                                editor_state.error_message =
+                                    Some(format!("Failed to export dialogue: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+            PendingAction::ExportTerrainTemplate { terrain_set_id } => {
+                #[cfg(feature = "native")]
+                {
+                    if let Some(terrain_set) =
+                        project.autotile_config.get_terrain_set(terrain_set_id)
+                    {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("PNG Image", &["png"])
+                            .set_file_name(format!("{} missing.png", terrain_set.name))
+                            .save_file()
+                        {
+                            let tile_size = project
+                                .get_tileset(terrain_set.tileset_id)
+                                .map_or(32, |t| t.tile_size);
+                            let coverage = terrain_set.coverage();
+                            let saved = terrain_set
+                                .coverage_template(&coverage.missing, tile_size, 8)
+                                .ok_or_else(|| "image would be too large".to_string())
+                                .and_then(|template| {
+                                    image::save_buffer(
+                                        &path,
+                                        &template.pixels,
+                                        template.width,
+                                        template.height,
+                                        image::ExtendedColorType::Rgba8,
+                                    )
+                                    .map_err(|e| e.to_string())
+                                });
+                            if let Err(e) = saved {
+                                editor_state.error_message =
+                                    Some(format!("Failed to export template: {}", e));
+                            } else if coverage.is_truncated() {
+                                editor_state.error_message = Some(format!(
+                                    "Only the first {} of {} combinations were checked, \
+                                     so the exported template may not list every missing tile.",
+                                    coverage.checked, coverage.total
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
             _ => {
                 // Put other actions back
                 editor_state.pending_action = Some(action);
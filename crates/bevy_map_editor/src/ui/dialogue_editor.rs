@@ -4,12 +4,31 @@
 //! - Visual node canvas with pan/zoom
 //! - Multiple node types (Text, Choice, Condition, Action, End)
 //! - Drag-and-drop connections between nodes
-//! - Node properties panel
+//! - Node properties panel with condition/action syntax checking
+//! - Playtest panel that steps through the tree (see [`super::dialogue_playtest`])
+//! - Validation badges on nodes with errors or warnings
 
 use bevy_egui::egui::{self, Color32, CornerRadius, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2};
-use bevy_map_dialogue::{DialogueChoice, DialogueNode, DialogueNodeType, DialogueTree};
+use bevy_map_dialogue::{
+    parse_action, parse_condition, DialogueChoice, DialogueIssue, DialogueNode, DialogueNodeType,
+    DialogueTree, ExprError,
+};
 use uuid::Uuid;
 
+use super::dialogue_playtest::{render_playtest_panel, DialoguePlaytest};
+use super::dialogue_validation::{render_issue_list, severity_color, worst_severity};
+
+/// Output connector of a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputPort {
+    /// `next_node` (the true branch on condition nodes)
+    Next,
+    /// `false_node` on condition nodes
+    False,
+    /// A choice's `next_node`
+    Choice(usize),
+}
+
 /// State for the dialogue editor
 #[derive(Default)]
 pub struct DialogueEditorState {
@@ -25,14 +44,16 @@ pub struct DialogueEditorState {
     pub selected_node: Option<String>,
     /// Node being dragged
     pub dragging_node: Option<String>,
-    /// Connection being created: (source_node_id, is_choice, choice_index)
-    pub creating_connection: Option<(String, bool, usize)>,
+    /// Connection being created: (source_node_id, output port)
+    pub creating_connection: Option<(String, OutputPort)>,
     /// Zoom level
     pub zoom: f32,
     /// Show node creation menu at position
     pub show_create_menu: Option<Pos2>,
     /// Name input for new dialogue
     pub name_input: String,
+    /// Running playtest, if any
+    pub playtest: Option<DialoguePlaytest>,
 }
 
 impl DialogueEditorState {
@@ -56,6 +77,7 @@ impl DialogueEditorState {
             zoom: 1.0,
             show_create_menu: None,
             name_input: dialogue.name,
+            playtest: None,
         }
     }
 
@@ -71,6 +93,7 @@ impl DialogueEditorState {
         self.pan_offset = Vec2::ZERO;
         self.zoom = 1.0;
         self.show_create_menu = None;
+        self.playtest = None;
     }
 
     /// Get the current dialogue tree
@@ -92,6 +115,8 @@ const NODE_WIDTH: f32 = 200.0;
 const NODE_HEADER_HEIGHT: f32 = 28.0;
 const NODE_PADDING: f32 = 8.0;
 const CONNECTION_RADIUS: f32 = 6.0;
+const FALSE_BRANCH_COLOR: Color32 = Color32::from_rgb(220, 90, 90);
+const PLAYTEST_COLOR: Color32 = Color32::from_rgb(0, 200, 255);
 
 /// Render the dialogue editor window
 pub fn render_dialogue_editor(
@@ -100,6 +125,7 @@ pub fn render_dialogue_editor(
 ) -> DialogueEditorResult {
     let mut result = DialogueEditorResult::default();
     let mut is_open = true;
+    let issues = state.dialogue_tree.validate();
 
     egui::Window::new("Dialogue Editor")
         .open(&mut is_open)
@@ -135,6 +161,22 @@ pub fn render_dialogue_editor(
 
                 ui.separator();
 
+                if state.playtest.is_some() {
+                    if ui.button("Stop Playtest").clicked() {
+                        state.playtest = None;
+                    }
+                } else if ui
+                    .button("Play")
+                    .on_hover_text("Step through the dialogue from the start node")
+                    .clicked()
+                {
+                    let mut playtest = DialoguePlaytest::default();
+                    playtest.start(&state.dialogue_tree);
+                    state.playtest = Some(playtest);
+                }
+
+                ui.separator();
+
                 // Handle selected node actions
                 let selected_id = state.selected_node.clone();
                 if let Some(ref selected) = selected_id {
@@ -174,6 +216,15 @@ pub fn render_dialogue_editor(
                     ui.separator();
 
                     if let Some(selected_id) = &state.selected_node.clone() {
+                        let node_issues: Vec<DialogueIssue> = issues
+                            .iter()
+                            .filter(|i| i.node_id.as_ref() == Some(selected_id))
+                            .cloned()
+                            .collect();
+                        if !node_issues.is_empty() {
+                            render_issue_list(ui, &node_issues);
+                            ui.separator();
+                        }
                         if let Some(node) = state.dialogue_tree.get_node_mut(selected_id) {
                             if render_node_properties(ui, node) {
                                 result.changed = true;
@@ -181,9 +232,35 @@ pub fn render_dialogue_editor(
                         }
                     } else {
                         ui.label("Select a node to edit its properties");
+                        if !issues.is_empty() {
+                            ui.separator();
+                            ui.label("Problems:");
+                            egui::ScrollArea::vertical()
+                                .id_salt("dialogue_issue_list")
+                                .show(ui, |ui| {
+                                    if let Some(node_id) = render_issue_list(ui, &issues) {
+                                        state.selected_node = Some(node_id);
+                                    }
+                                });
+                        }
                     }
                 });
 
+            // Left panel - Playtest
+            if let Some(playtest) = &mut state.playtest {
+                let mut stop = false;
+                egui::SidePanel::left("dialogue_playtest_panel")
+                    .resizable(true)
+                    .default_width(260.0)
+                    .min_width(200.0)
+                    .show_inside(ui, |ui| {
+                        stop = render_playtest_panel(ui, &state.dialogue_tree, playtest);
+                    });
+                if stop {
+                    state.playtest = None;
+                }
+            }
+
             // Central panel - Canvas
             egui::CentralPanel::default().show_inside(ui, |ui| {
                 let available = ui.available_size();
@@ -204,7 +281,7 @@ pub fn render_dialogue_editor(
                 draw_connections(&painter, canvas_rect, state);
 
                 // Draw nodes
-                let node_changes = draw_nodes(ui, &painter, canvas_rect, state);
+                let node_changes = draw_nodes(ui, &painter, canvas_rect, state, &issues);
                 if node_changes.changed {
                     result.changed = true;
                 }
@@ -220,33 +297,27 @@ pub fn render_dialogue_editor(
                 if let Some(conn) = node_changes.start_connection {
                     state.creating_connection = Some(conn);
                 }
-                if let Some((source_id, is_choice, choice_idx, target_id)) =
-                    node_changes.complete_connection
-                {
-                    if is_choice {
-                        if let Some(source) = state.dialogue_tree.get_node_mut(&source_id) {
-                            if let Some(choice) = source.choices.get_mut(choice_idx) {
-                                choice.next_node = Some(target_id);
-                                result.changed = true;
+                if let Some((source_id, port, target_id)) = node_changes.complete_connection {
+                    if let Some(source) = state.dialogue_tree.get_node_mut(&source_id) {
+                        let link = match port {
+                            OutputPort::Next => Some(&mut source.next_node),
+                            OutputPort::False => Some(&mut source.false_node),
+                            OutputPort::Choice(idx) => {
+                                source.choices.get_mut(idx).map(|c| &mut c.next_node)
                             }
+                        };
+                        if let Some(link) = link {
+                            *link = Some(target_id);
+                            result.changed = true;
                         }
-                    } else if let Some(source) = state.dialogue_tree.get_node_mut(&source_id) {
-                        source.next_node = Some(target_id);
-                        result.changed = true;
                     }
                     state.creating_connection = None;
                 }
 
                 // Draw connection being created
-                if let Some((source_id, is_choice, choice_idx)) = &state.creating_connection {
+                if let Some((source_id, port)) = &state.creating_connection {
                     if let Some(source_node) = state.dialogue_tree.get_node(source_id) {
-                        let source_pos = node_output_pos(
-                            source_node,
-                            canvas_rect,
-                            state,
-                            *is_choice,
-                            *choice_idx,
-                        );
+                        let source_pos = node_output_pos(source_node, canvas_rect, state, *port);
                         if let Some(pointer_pos) = ui.ctx().pointer_latest_pos() {
                             painter.line_segment(
                                 [source_pos, pointer_pos],
@@ -342,17 +413,26 @@ fn draw_connections(painter: &egui::Painter, canvas_rect: Rect, state: &Dialogue
         // Draw next_node connection
         if let Some(next_id) = &node.next_node {
             if let Some(target) = state.dialogue_tree.get_node(next_id) {
-                let start = node_output_pos(node, canvas_rect, state, false, 0);
+                let start = node_output_pos(node, canvas_rect, state, OutputPort::Next);
                 let end = node_input_pos(target, canvas_rect, state);
                 draw_bezier_connection(painter, start, end, Color32::WHITE);
             }
         }
 
+        // Draw false branch connection
+        if let Some(false_id) = &node.false_node {
+            if let Some(target) = state.dialogue_tree.get_node(false_id) {
+                let start = node_output_pos(node, canvas_rect, state, OutputPort::False);
+                let end = node_input_pos(target, canvas_rect, state);
+                draw_bezier_connection(painter, start, end, FALSE_BRANCH_COLOR);
+            }
+        }
+
         // Draw choice connections
         for (i, choice) in node.choices.iter().enumerate() {
             if let Some(next_id) = &choice.next_node {
                 if let Some(target) = state.dialogue_tree.get_node(next_id) {
-                    let start = node_output_pos(node, canvas_rect, state, true, i);
+                    let start = node_output_pos(node, canvas_rect, state, OutputPort::Choice(i));
                     let end = node_input_pos(target, canvas_rect, state);
                     draw_bezier_connection(painter, start, end, Color32::from_rgb(255, 180, 100));
                 }
@@ -415,18 +495,19 @@ fn node_output_pos(
     node: &DialogueNode,
     canvas_rect: Rect,
     state: &DialogueEditorState,
-    is_choice: bool,
-    choice_index: usize,
+    port: OutputPort,
 ) -> Pos2 {
     let node_rect = node_rect(node, canvas_rect, state);
-    if is_choice {
-        let y_offset = NODE_HEADER_HEIGHT + NODE_PADDING + (choice_index as f32 + 0.5) * 24.0;
-        Pos2::new(node_rect.right(), node_rect.top() + y_offset)
-    } else {
-        Pos2::new(
+    match port {
+        OutputPort::Next => Pos2::new(
             node_rect.right(),
             node_rect.top() + NODE_HEADER_HEIGHT / 2.0,
-        )
+        ),
+        OutputPort::False => Pos2::new(node_rect.right(), node_rect.bottom() - NODE_PADDING),
+        OutputPort::Choice(choice_index) => {
+            let y_offset = NODE_HEADER_HEIGHT + NODE_PADDING + (choice_index as f32 + 0.5) * 24.0;
+            Pos2::new(node_rect.right(), node_rect.top() + y_offset)
+        }
     }
 }
 
@@ -451,8 +532,8 @@ struct NodeDrawResult {
     selected: Option<String>,
     dragging: Option<String>,
     stop_dragging: bool,
-    start_connection: Option<(String, bool, usize)>,
-    complete_connection: Option<(String, bool, usize, String)>,
+    start_connection: Option<(String, OutputPort)>,
+    complete_connection: Option<(String, OutputPort, String)>,
     connection_dropped_on_target: bool,
 }
 
@@ -462,6 +543,7 @@ fn draw_nodes(
     painter: &egui::Painter,
     canvas_rect: Rect,
     state: &mut DialogueEditorState,
+    issues: &[DialogueIssue],
 ) -> NodeDrawResult {
     let mut result = NodeDrawResult::default();
     let node_ids: Vec<String> = state.dialogue_tree.nodes.keys().cloned().collect();
@@ -481,12 +563,19 @@ fn draw_nodes(
 
         let is_selected = state.selected_node.as_ref() == Some(&node_id);
         let is_start = state.dialogue_tree.start_node == node_id;
+        let is_playing = state
+            .playtest
+            .as_ref()
+            .and_then(|p| p.current_node.as_ref())
+            == Some(&node_id);
 
         // Node colors
         let (r, g, b) = node.node_type.color();
         let header_color = Color32::from_rgb(r, g, b);
         let body_color = Color32::from_gray(50);
-        let border_color = if is_selected {
+        let border_color = if is_playing {
+            PLAYTEST_COLOR
+        } else if is_selected {
             Color32::YELLOW
         } else if is_start {
             Color32::GREEN
@@ -514,7 +603,7 @@ fn draw_nodes(
         painter.rect_stroke(
             rect,
             CornerRadius::same(4),
-            Stroke::new(2.0, border_color),
+            Stroke::new(if is_playing { 3.0 } else { 2.0 }, border_color),
             StrokeKind::Outside,
         );
 
@@ -554,13 +643,29 @@ fn draw_nodes(
         );
 
         // Draw output connector
-        let output_pos = node_output_pos(&node, canvas_rect, state, false, 0);
+        let output_pos = node_output_pos(&node, canvas_rect, state, OutputPort::Next);
         painter.circle_filled(
             output_pos,
             CONNECTION_RADIUS,
             Color32::from_rgb(200, 100, 100),
         );
 
+        // Condition nodes branch: Next is the true output, plus a false output
+        let is_condition = node.node_type == DialogueNodeType::Condition;
+        let false_pos = node_output_pos(&node, canvas_rect, state, OutputPort::False);
+        if is_condition {
+            painter.circle_filled(false_pos, CONNECTION_RADIUS, FALSE_BRANCH_COLOR);
+            for (pos, label) in [(output_pos, "true"), (false_pos, "false")] {
+                painter.text(
+                    pos - Vec2::new(CONNECTION_RADIUS + 2.0, 0.0),
+                    egui::Align2::RIGHT_CENTER,
+                    label,
+                    egui::FontId::proportional(10.0),
+                    Color32::LIGHT_GRAY,
+                );
+            }
+        }
+
         // Draw choice connectors
         for (i, choice) in node.choices.iter().enumerate() {
             let choice_y =
@@ -590,6 +695,30 @@ fn draw_nodes(
             result.selected = Some(node_id.clone());
         }
 
+        // Validation badge, with the node's issues on hover
+        let node_issues: Vec<&DialogueIssue> = issues
+            .iter()
+            .filter(|i| i.node_id.as_ref() == Some(&node_id))
+            .collect();
+        if let Some(severity) = worst_severity(node_issues.iter().copied()) {
+            let badge_pos = Pos2::new(header_rect.right() - 12.0, header_rect.center().y);
+            painter.circle_filled(badge_pos, 7.0, severity_color(severity));
+            painter.text(
+                badge_pos,
+                egui::Align2::CENTER_CENTER,
+                "!",
+                egui::FontId::proportional(11.0),
+                Color32::BLACK,
+            );
+            let messages: Vec<&str> = node_issues.iter().map(|i| i.message.as_str()).collect();
+            ui.interact(
+                Rect::from_center_size(badge_pos, Vec2::splat(14.0)),
+                egui::Id::new(format!("{}_issues", node_id)),
+                Sense::hover(),
+            )
+            .on_hover_text(messages.join("\n"));
+        }
+
         // Handle dragging
         if node_response.drag_started() {
             result.dragging = Some(node_id.clone());
@@ -619,7 +748,21 @@ fn draw_nodes(
         );
 
         if output_response.drag_started() {
-            result.start_connection = Some((node_id.clone(), false, 0));
+            result.start_connection = Some((node_id.clone(), OutputPort::Next));
+        }
+
+        if is_condition {
+            let false_rect =
+                Rect::from_center_size(false_pos, Vec2::splat(CONNECTION_RADIUS * 2.5));
+            let false_response = ui.interact(
+                false_rect,
+                egui::Id::new(format!("{}_false", node_id)),
+                Sense::click_and_drag(),
+            );
+
+            if false_response.drag_started() {
+                result.start_connection = Some((node_id.clone(), OutputPort::False));
+            }
         }
 
         // Handle connection drop on input
@@ -631,10 +774,9 @@ fn draw_nodes(
         );
 
         if input_response.hovered() && ui.input(|i| i.pointer.any_released()) {
-            if let Some((source_id, is_choice, choice_idx)) = &state.creating_connection {
+            if let Some((source_id, port)) = &state.creating_connection {
                 if source_id != &node_id {
-                    result.complete_connection =
-                        Some((source_id.clone(), *is_choice, *choice_idx, node_id.clone()));
+                    result.complete_connection = Some((source_id.clone(), *port, node_id.clone()));
                     result.connection_dropped_on_target = true;
                 }
             }
@@ -642,7 +784,7 @@ fn draw_nodes(
 
         // Handle choice output connections
         for (i, _) in node.choices.iter().enumerate() {
-            let choice_out_pos = node_output_pos(&node, canvas_rect, state, true, i);
+            let choice_out_pos = node_output_pos(&node, canvas_rect, state, OutputPort::Choice(i));
             let choice_out_rect =
                 Rect::from_center_size(choice_out_pos, Vec2::splat(CONNECTION_RADIUS * 2.0));
             let choice_out_response = ui.interact(
@@ -652,7 +794,7 @@ fn draw_nodes(
             );
 
             if choice_out_response.drag_started() {
-                result.start_connection = Some((node_id.clone(), true, i));
+                result.start_connection = Some((node_id.clone(), OutputPort::Choice(i)));
             }
         }
     }
@@ -705,35 +847,60 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
         changed = true;
     }
 
-    ui.separator();
-
-    // Condition (optional)
-    ui.collapsing("Condition", |ui| {
-        let mut condition = node.condition.clone().unwrap_or_default();
-        ui.label("Show this node if:");
+    // String key used by translation tables
+    ui.horizontal(|ui| {
+        ui.label("String Key:");
+        let mut loc_key = node.loc_key.clone().unwrap_or_default();
         if ui
-            .add(
-                egui::TextEdit::singleline(&mut condition)
-                    .hint_text("e.g. has_quest(\"quest_id\")"),
-            )
+            .add(egui::TextEdit::singleline(&mut loc_key).hint_text("<dialogue id>.<node id>"))
+            .on_hover_text("Base key for translations (text is <key>.text, choices <key>.choice.N)")
             .changed()
         {
-            node.condition = if condition.is_empty() {
+            node.loc_key = if loc_key.trim().is_empty() {
                 None
             } else {
-                Some(condition)
+                Some(loc_key)
             };
             changed = true;
         }
-        ui.small("(Scripting not yet implemented)");
     });
 
+    ui.separator();
+
+    // Condition (evaluated by condition nodes)
+    if node.node_type == DialogueNodeType::Condition || node.condition.is_some() {
+        ui.collapsing("Condition", |ui| {
+            let mut condition = node.condition.clone().unwrap_or_default();
+            ui.label("Branch on (true → Next, false → False):");
+            if ui
+                .add(
+                    egui::TextEdit::singleline(&mut condition)
+                        .hint_text("e.g. gold >= 10 && !met_king"),
+                )
+                .changed()
+            {
+                node.condition = if condition.is_empty() {
+                    None
+                } else {
+                    Some(condition)
+                };
+                changed = true;
+            }
+            if let Some(condition) = &node.condition {
+                show_expr_error(ui, parse_condition(condition).err());
+            }
+        });
+    }
+
     // Action (optional)
     ui.collapsing("Action", |ui| {
         let mut action = node.action.clone().unwrap_or_default();
         ui.label("Execute when entering:");
         if ui
-            .add(egui::TextEdit::singleline(&mut action).hint_text("e.g. give_item(\"item_id\")"))
+            .add(
+                egui::TextEdit::singleline(&mut action)
+                    .hint_text("e.g. gold -= 10; give_item(\"key\")"),
+            )
             .changed()
         {
             node.action = if action.is_empty() {
@@ -743,7 +910,9 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
             };
             changed = true;
         }
-        ui.small("(Scripting not yet implemented)");
+        if let Some(action) = &node.action {
+            show_expr_error(ui, parse_action(action).err());
+        }
     });
 
     ui.separator();
@@ -776,6 +945,9 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
                     changed = true;
                 }
             });
+            if let Some(cond) = &choice.condition {
+                show_expr_error(ui, parse_condition(cond).err());
+            }
         }
 
         if let Some(idx) = to_remove {
@@ -785,6 +957,7 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
 
         if ui.button("+ Add Choice").clicked() {
             node.choices.push(DialogueChoice {
+                id: Uuid::new_v4().to_string(),
                 text: "New choice".to_string(),
                 next_node: None,
                 condition: None,
@@ -812,9 +985,32 @@ fn render_node_properties(ui: &mut egui::Ui, node: &mut DialogueNode) -> bool {
         }
     });
 
+    if node.node_type == DialogueNodeType::Condition || node.false_node.is_some() {
+        let false_display = node
+            .false_node
+            .as_ref()
+            .map(|n| truncate_str(n, 12))
+            .unwrap_or_else(|| "(none)".to_string());
+
+        ui.horizontal(|ui| {
+            ui.label(format!("False: {}", false_display));
+            if node.false_node.is_some() && ui.small_button("x").clicked() {
+                node.false_node = None;
+                changed = true;
+            }
+        });
+    }
+
     changed
 }
 
+/// Show a syntax error below a condition/action field
+fn show_expr_error(ui: &mut egui::Ui, error: Option<ExprError>) {
+    if let Some(err) = error {
+        ui.colored_label(Color32::from_rgb(255, 100, 100), format!("⚠ {}", err));
+    }
+}
+
 /// Truncate a string to a maximum length
 fn truncate_str(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -0,0 +1,328 @@
+//! Playtest panel for the dialogue editor
+//!
+//! Steps through the tree being edited with the same logic as the runtime
+//! `DialogueRunner` ([`DialogueTree::resolve`] and [`DialogueTree::continue_from`]),
+//! so branches can be tried without launching the game.
+
+use bevy_egui::egui::{self, Color32};
+use bevy_map_dialogue::{
+    DialogueNodeType, DialogueSignal, DialogueTree, DialogueValue, DialogueVariables,
+};
+
+/// An entry in the playtest transcript
+#[derive(Debug, Clone)]
+pub enum TranscriptEntry {
+    /// A Text or Choice node was shown
+    Line { speaker: String, text: String },
+    /// The player picked a choice
+    Choice(String),
+    /// An action emitted a signal
+    Signal(DialogueSignal),
+    /// The dialogue ended
+    End,
+}
+
+/// Type of a variable added from the playtest panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum VariableType {
+    #[default]
+    Bool,
+    Int,
+    Float,
+    String,
+}
+
+impl VariableType {
+    const ALL: [VariableType; 4] = [
+        VariableType::Bool,
+        VariableType::Int,
+        VariableType::Float,
+        VariableType::String,
+    ];
+
+    fn display_name(self) -> &'static str {
+        match self {
+            VariableType::Bool => "Bool",
+            VariableType::Int => "Int",
+            VariableType::Float => "Float",
+            VariableType::String => "String",
+        }
+    }
+
+    fn default_value(self) -> DialogueValue {
+        match self {
+            VariableType::Bool => DialogueValue::Bool(false),
+            VariableType::Int => DialogueValue::Int(0),
+            VariableType::Float => DialogueValue::Float(0.0),
+            VariableType::String => DialogueValue::String(String::new()),
+        }
+    }
+}
+
+/// State of a playtest run
+#[derive(Debug, Clone, Default)]
+pub struct DialoguePlaytest {
+    /// Node waiting for the player (`None` once the dialogue has ended)
+    pub current_node: Option<String>,
+    /// Variables read by conditions and written by actions
+    pub variables: DialogueVariables,
+    /// Lines shown, choices picked and signals emitted so far
+    pub transcript: Vec<TranscriptEntry>,
+    /// Name input for adding a variable
+    new_variable: String,
+    /// Type of the variable being added
+    new_variable_type: VariableType,
+}
+
+impl DialoguePlaytest {
+    /// Start a run from the tree's start node, keeping the current variables
+    pub fn start(&mut self, tree: &DialogueTree) {
+        self.transcript.clear();
+        let start = tree.start_node.clone();
+        self.enter(tree, Some(&start));
+    }
+
+    /// Enter a node, running Condition/Action nodes until one waits for the player
+    fn enter(&mut self, tree: &DialogueTree, node_id: Option<&str>) {
+        let mut signals = Vec::new();
+        self.current_node = tree.resolve(node_id, &mut self.variables, &mut signals);
+        self.transcript
+            .extend(signals.into_iter().map(TranscriptEntry::Signal));
+
+        match self
+            .current_node
+            .as_deref()
+            .and_then(|id| tree.get_node(id))
+        {
+            Some(node) if !node.text.is_empty() => self.transcript.push(TranscriptEntry::Line {
+                speaker: node.speaker.clone(),
+                text: node.text.clone(),
+            }),
+            Some(_) => {}
+            None => self.transcript.push(TranscriptEntry::End),
+        }
+    }
+
+    /// Continue from the current node (`choice_index` is ignored for Text nodes)
+    pub fn advance(&mut self, tree: &DialogueTree, choice_index: usize) {
+        let Some(current) = self.current_node.clone() else {
+            return;
+        };
+        let Some(next) = tree.continue_from(&current, choice_index, &self.variables) else {
+            return;
+        };
+
+        if let Some(node) = tree.get_node(&current) {
+            if node.node_type == DialogueNodeType::Choice {
+                // With every choice hidden this falls through to next_node: nothing was picked
+                let picked = node.choices.get(choice_index);
+                if let Some(choice) = picked.filter(|c| c.is_visible(&self.variables)) {
+                    self.transcript
+                        .push(TranscriptEntry::Choice(choice.text.clone()));
+                }
+            }
+        }
+        let next = next.map(str::to_string);
+        self.enter(tree, next.as_deref());
+    }
+}
+
+/// Render the playtest panel. Returns `true` if the playtest should stop.
+pub fn render_playtest_panel(
+    ui: &mut egui::Ui,
+    tree: &DialogueTree,
+    playtest: &mut DialoguePlaytest,
+) -> bool {
+    let mut stop = false;
+
+    ui.horizontal(|ui| {
+        ui.heading("Playtest");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("Stop").clicked() {
+                stop = true;
+            }
+            if ui
+                .button("Restart")
+                .on_hover_text("Run again from the start node with the current variables")
+                .clicked()
+            {
+                playtest.start(tree);
+            }
+        });
+    });
+    ui.separator();
+
+    render_current_node(ui, tree, playtest);
+    ui.separator();
+
+    egui::CollapsingHeader::new("Variables")
+        .default_open(true)
+        .show(ui, |ui| render_variables(ui, playtest));
+    ui.separator();
+
+    ui.label("Transcript:");
+    egui::ScrollArea::vertical()
+        .id_salt("dialogue_playtest_transcript")
+        .auto_shrink([false, false])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for entry in &playtest.transcript {
+                render_transcript_entry(ui, entry);
+            }
+        });
+
+    stop
+}
+
+/// Render the node waiting for the player, with Continue or choice buttons
+fn render_current_node(ui: &mut egui::Ui, tree: &DialogueTree, playtest: &mut DialoguePlaytest) {
+    let Some(current) = playtest.current_node.clone() else {
+        ui.label("(Dialogue ended)");
+        return;
+    };
+    let Some(node) = tree.get_node(&current) else {
+        ui.colored_label(
+            Color32::from_rgb(255, 100, 100),
+            "The current node was deleted. Restart to continue.",
+        );
+        return;
+    };
+
+    if !node.speaker.is_empty() {
+        ui.strong(&node.speaker);
+    }
+    if !node.text.is_empty() {
+        ui.label(&node.text);
+    }
+    ui.add_space(4.0);
+
+    let mut advance = None;
+    if node.node_type == DialogueNodeType::Choice {
+        if node.choices.is_empty() {
+            ui.label("(No choices)");
+        }
+        for (i, choice) in node.choices.iter().enumerate() {
+            let label = format!("{}. {}", i + 1, choice.text);
+            if choice.is_visible(&playtest.variables) {
+                if ui.button(label).clicked() {
+                    advance = Some(i);
+                }
+            } else {
+                ui.add_enabled(false, egui::Button::new(label))
+                    .on_disabled_hover_text(format!(
+                        "Hidden: `{}` is false",
+                        choice.condition.as_deref().unwrap_or_default()
+                    ));
+            }
+        }
+        // Like the runtime, fall through to the node's next node
+        if node.visible_choices(&playtest.variables).next().is_none()
+            && ui
+                .button("Continue")
+                .on_hover_text("No choice is visible, so the dialogue continues to the next node")
+                .clicked()
+        {
+            advance = Some(0);
+        }
+    } else if ui.button("Continue").clicked() {
+        advance = Some(0);
+    }
+
+    if let Some(index) = advance {
+        playtest.advance(tree, index);
+    }
+}
+
+/// Render editors for the playtest variables, plus a row to add one
+fn render_variables(ui: &mut egui::Ui, playtest: &mut DialoguePlaytest) {
+    let mut variables: Vec<(String, DialogueValue)> = playtest
+        .variables
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect();
+    variables.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if variables.is_empty() {
+        ui.label("(No variables set)");
+    }
+
+    egui::Grid::new("dialogue_playtest_variables")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for (name, mut value) in variables {
+                ui.label(&name);
+                let changed = match &mut value {
+                    DialogueValue::Bool(b) => ui.checkbox(b, "").changed(),
+                    DialogueValue::Int(i) => ui.add(egui::DragValue::new(i)).changed(),
+                    DialogueValue::Float(f) => ui.add(egui::DragValue::new(f).speed(0.1)).changed(),
+                    DialogueValue::String(s) => ui
+                        .add(egui::TextEdit::singleline(s).desired_width(100.0))
+                        .changed(),
+                };
+                if ui.small_button("x").on_hover_text("Remove").clicked() {
+                    playtest.variables.remove(&name);
+                } else if changed {
+                    playtest.variables.set(name, value);
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut playtest.new_variable)
+                .hint_text("name")
+                .desired_width(80.0),
+        );
+        egui::ComboBox::from_id_salt("dialogue_playtest_new_variable_type")
+            .selected_text(playtest.new_variable_type.display_name())
+            .width(60.0)
+            .show_ui(ui, |ui| {
+                for ty in VariableType::ALL {
+                    ui.selectable_value(&mut playtest.new_variable_type, ty, ty.display_name());
+                }
+            });
+        let name = playtest.new_variable.trim().to_string();
+        if ui
+            .add_enabled(!name.is_empty(), egui::Button::new("Add"))
+            .clicked()
+        {
+            playtest
+                .variables
+                .set(name, playtest.new_variable_type.default_value());
+            playtest.new_variable.clear();
+        }
+    });
+}
+
+fn render_transcript_entry(ui: &mut egui::Ui, entry: &TranscriptEntry) {
+    match entry {
+        TranscriptEntry::Line { speaker, text } if speaker.is_empty() => {
+            ui.label(text);
+        }
+        TranscriptEntry::Line { speaker, text } => {
+            ui.label(format!("{}: {}", speaker, text));
+        }
+        TranscriptEntry::Choice(text) => {
+            ui.colored_label(Color32::from_rgb(255, 200, 150), format!("> {}", text));
+        }
+        TranscriptEntry::Signal(signal) => {
+            let args: Vec<String> = signal
+                .args
+                .iter()
+                .map(|arg| match arg {
+                    DialogueValue::String(s) => format!("{:?}", s),
+                    other => other.to_string(),
+                })
+                .collect();
+            ui.colored_label(
+                Color32::from_rgb(50, 205, 50),
+                format!("{}({})", signal.name, args.join(", ")),
+            );
+        }
+        TranscriptEntry::End => {
+            ui.colored_label(Color32::GRAY, "(End)");
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Dialogue validation display
+//!
+//! Shared helpers for showing [`DialogueIssue`]s, and the project-wide
+//! Dialogue Problems window listing the issues of every dialogue.
+
+use bevy_egui::egui::{self, Color32};
+use bevy_map_dialogue::{DialogueIssue, DialogueSeverity};
+
+use crate::project::Project;
+use crate::EditorState;
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(230, 70, 70);
+const WARNING_COLOR: Color32 = Color32::from_rgb(255, 180, 0);
+
+/// Color used for an issue's badge and text
+pub fn severity_color(severity: DialogueSeverity) -> Color32 {
+    match severity {
+        DialogueSeverity::Error => ERROR_COLOR,
+        DialogueSeverity::Warning => WARNING_COLOR,
+    }
+}
+
+/// The most severe severity among issues (`None` if there are none)
+pub fn worst_severity<'a>(
+    issues: impl IntoIterator<Item = &'a DialogueIssue>,
+) -> Option<DialogueSeverity> {
+    let mut worst = None;
+    for issue in issues {
+        if issue.is_error() {
+            return Some(DialogueSeverity::Error);
+        }
+        worst = Some(DialogueSeverity::Warning);
+    }
+    worst
+}
+
+/// Short summary such as "2 errors, 1 warning"
+pub fn issue_summary(issues: &[DialogueIssue]) -> String {
+    let errors = issues.iter().filter(|i| i.is_error()).count();
+    let warnings = issues.len() - errors;
+    let plural = |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+    match (errors, warnings) {
+        (0, 0) => "No problems".to_string(),
+        (0, w) => plural(w, "warning"),
+        (e, 0) => plural(e, "error"),
+        (e, w) => format!("{}, {}", plural(e, "error"), plural(w, "warning")),
+    }
+}
+
+/// Render issues as a list. Returns the node ID of a clicked issue.
+pub fn render_issue_list(ui: &mut egui::Ui, issues: &[DialogueIssue]) -> Option<String> {
+    let mut clicked = None;
+    for issue in issues {
+        let color = severity_color(issue.severity);
+        let text = egui::RichText::new(issue.to_string()).color(color);
+        match &issue.node_id {
+            Some(node_id) => {
+                if ui
+                    .add(egui::Label::new(text).sense(egui::Sense::click()))
+                    .on_hover_text("Click to select the node")
+                    .clicked()
+                {
+                    clicked = Some(node_id.clone());
+                }
+            }
+            None => {
+                ui.label(text);
+            }
+        }
+    }
+    clicked
+}
+
+/// Render the project-wide Dialogue Problems window
+///
+/// Returns `(dialogue_id, node_id)` when an issue or Open button is clicked.
+pub fn render_dialogue_validation_window(
+    ctx: &egui::Context,
+    editor_state: &mut EditorState,
+    project: &Project,
+) -> Option<(String, Option<String>)> {
+    if !editor_state.show_dialogue_validation {
+        return None;
+    }
+
+    let mut open_request = None;
+    let mut is_open = true;
+
+    egui::Window::new("Dialogue Problems")
+        .open(&mut is_open)
+        .default_size([500.0, 400.0])
+        .resizable(true)
+        .show(ctx, |ui| {
+            let results: Vec<_> = project
+                .dialogues
+                .iter()
+                .map(|dialogue| (dialogue, dialogue.validate()))
+                .filter(|(_, issues)| !issues.is_empty())
+                .collect();
+
+            if results.is_empty() {
+                ui.label(format!(
+                    "No problems found in {} dialogue(s)",
+                    project.dialogues.len()
+                ));
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (dialogue, issues) in results {
+                    let header = egui::RichText::new(format!(
+                        "{} ({})",
+                        dialogue.name,
+                        issue_summary(&issues)
+                    ))
+                    .color(severity_color(
+                        worst_severity(&issues).unwrap_or(DialogueSeverity::Warning),
+                    ));
+
+                    egui::CollapsingHeader::new(header)
+                        .id_salt(&dialogue.id)
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            if ui.button("Open in Editor").clicked() {
+                                open_request = Some((dialogue.id.clone(), None));
+                            }
+                            if let Some(node_id) = render_issue_list(ui, &issues) {
+                                open_request = Some((dialogue.id.clone(), Some(node_id)));
+                            }
+                        });
+                }
+            });
+        });
+
+    if !is_open {
+        editor_state.show_dialogue_validation = false;
+    }
+
+    open_request
+}
@@ -1,6 +1,7 @@
 //! Entity palette for placing schema-defined placeable types
 
 use bevy_egui::egui;
+use std::collections::BTreeMap;
 
 use crate::project::Project;
 use crate::EditorState;
@@ -12,6 +13,8 @@ pub struct EntityPaintState {
     pub selected_entity_type: Option<String>,
     /// Whether entity placement mode is active
     pub is_entity_mode: bool,
+    /// Group placeable types under their root base type
+    pub group_by_base: bool,
 }
 
 impl EntityPaintState {
@@ -19,6 +22,7 @@ impl EntityPaintState {
         Self {
             selected_entity_type: None,
             is_entity_mode: false,
+            group_by_base: false,
         }
     }
 }
@@ -40,31 +44,40 @@ pub fn render_entity_palette(ui: &mut egui::Ui, editor_state: &mut EditorState,
     }
 
     ui.label("Click to select an entity type, then place on canvas with the Entity tool.");
+    ui.checkbox(
+        &mut editor_state.entity_paint_state.group_by_base,
+        "Group by base type",
+    );
     ui.separator();
 
-    // List all placeable types
-    for type_name in placeable_types {
-        if let Some(type_def) = project.schema.get_type(type_name) {
-            let selected = editor_state.selected_entity_type.as_deref() == Some(type_name);
-
-            ui.horizontal(|ui| {
-                // Color swatch from type's color field
-                let color = parse_hex_color(&type_def.color);
-                let (rect, _) =
-                    ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
-                ui.painter().rect_filled(rect, 2.0, color);
-
-                // Icon indicator if type has one
-                if type_def.icon.is_some() {
-                    ui.label("img");
-                }
-
-                // Selectable label with type name
-                if ui.selectable_label(selected, type_name).clicked() {
-                    editor_state.selected_entity_type = Some(type_name.to_string());
-                    // Don't automatically switch tools - let users manually select Entity tool
-                }
-            });
+    if editor_state.entity_paint_state.group_by_base {
+        // Types sharing a root base go together; types without a base come last
+        let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut ungrouped = Vec::new();
+        for type_name in placeable_types {
+            match project.schema.base_types(type_name).last() {
+                Some(root) => groups.entry(root).or_default().push(type_name),
+                None => ungrouped.push(type_name),
+            }
+        }
+
+        for (base, type_names) in groups {
+            egui::CollapsingHeader::new(base)
+                .id_salt(format!("entity_palette_base_{}", base))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for type_name in type_names {
+                        render_type_row(ui, editor_state, project, type_name);
+                    }
+                });
+        }
+        for type_name in ungrouped {
+            render_type_row(ui, editor_state, project, type_name);
+        }
+    } else {
+        // List all placeable types
+        for type_name in placeable_types {
+            render_type_row(ui, editor_state, project, type_name);
         }
     }
 
@@ -80,12 +93,17 @@ pub fn render_entity_palette(ui: &mut egui::Ui, editor_state: &mut EditorState,
                 ui.label(format!("Icon: {}", icon));
             }
 
-            ui.label(format!("Properties: {}", type_def.properties.len()));
+            if let Some(base) = &type_def.extends {
+                ui.label(format!("Extends: {}", base));
+            }
+
+            let properties = project.schema.all_properties(type_name);
+            ui.label(format!("Properties: {}", properties.len()));
 
             // List property names
-            if !type_def.properties.is_empty() {
+            if !properties.is_empty() {
                 ui.collapsing("Properties", |ui| {
-                    for prop in &type_def.properties {
+                    for prop in properties {
                         let required = if prop.required { "*" } else { "" };
                         ui.label(format!("• {}{}: {:?}", prop.name, required, prop.prop_type));
                     }
@@ -95,6 +113,37 @@ pub fn render_entity_palette(ui: &mut egui::Ui, editor_state: &mut EditorState,
     }
 }
 
+/// Render one selectable placeable type
+fn render_type_row(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    project: &Project,
+    type_name: &str,
+) {
+    let Some(type_def) = project.schema.get_type(type_name) else {
+        return;
+    };
+    let selected = editor_state.selected_entity_type.as_deref() == Some(type_name);
+
+    ui.horizontal(|ui| {
+        // Color swatch from type's color field
+        let color = parse_hex_color(&type_def.color);
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 20.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, color);
+
+        // Icon indicator if type has one
+        if type_def.icon.is_some() {
+            ui.label("img");
+        }
+
+        // Selectable label with type name
+        if ui.selectable_label(selected, type_name).clicked() {
+            editor_state.selected_entity_type = Some(type_name.to_string());
+            // Don't automatically switch tools - let users manually select Entity tool
+        }
+    });
+}
+
 /// Parse a hex color string like "#FF0000" or "FF0000" into egui::Color32
 fn parse_hex_color(color_str: &str) -> egui::Color32 {
     let hex = color_str.trim_start_matches('#');
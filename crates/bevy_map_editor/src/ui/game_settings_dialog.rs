@@ -42,6 +42,8 @@ pub struct GameSettingsDialogState {
     pub generate_behaviors: bool,
     /// Whether to generate enums
     pub generate_enums: bool,
+    /// Whether to generate data type structs
+    pub generate_data_types: bool,
     /// Custom VS Code path (empty = auto-detect)
     pub vscode_path: String,
     /// Cached VS Code availability status (None = not checked yet)
@@ -78,6 +80,7 @@ impl GameSettingsDialogState {
         self.generate_stubs = project.game_config.generate_stubs;
         self.generate_behaviors = project.game_config.generate_behaviors;
         self.generate_enums = project.game_config.generate_enums;
+        self.generate_data_types = project.game_config.generate_data_types;
 
         // Load VS Code path
         self.vscode_path = project.game_config.vscode_path.clone().unwrap_or_default();
@@ -379,6 +382,7 @@ pub fn render_game_settings_dialog(
                         ui.label("Generate:");
                         ui.checkbox(&mut state.generate_entities, "Entity structs");
                         ui.checkbox(&mut state.generate_enums, "Enum definitions");
+                        ui.checkbox(&mut state.generate_data_types, "Data type structs (serde)");
                         ui.checkbox(&mut state.generate_stubs, "Behavior stubs");
                         ui.checkbox(
                             &mut state.generate_behaviors,
@@ -519,6 +523,7 @@ pub fn render_game_settings_dialog(
                             project.game_config.generate_stubs = state.generate_stubs;
                             project.game_config.generate_behaviors = state.generate_behaviors;
                             project.game_config.generate_enums = state.generate_enums;
+                            project.game_config.generate_data_types = state.generate_data_types;
 
                             // Save VS Code path
                             project.game_config.vscode_path = if state.vscode_path.is_empty() {
@@ -6,6 +6,8 @@ use bevy_map_core::{
     ComponentOverrides, EntityTypeConfig, InputConfig, InputOverrides, PhysicsConfig,
     PhysicsOverrides, SpriteConfig, SpriteOverrides,
 };
+use bevy_map_schema::{diagnose_instance, path_join, Diagnostics, Severity};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::project::Project;
@@ -178,6 +180,7 @@ fn render_entity_inspector(
         dialogue_options,
         ref_options,
         animation_names,
+        diagnostics,
     ) = {
         let Some(level) = project.get_level(level_id) else {
             ui.label("Level not found");
@@ -189,7 +192,9 @@ fn render_entity_inspector(
         };
 
         let type_name = entity.type_name.clone();
-        let type_def = project.schema.get_type(&type_name).cloned();
+        let type_def = project.schema.resolved_type(&type_name);
+        let diagnostics =
+            diagnose_properties(project, Some(level_id), &type_name, &entity.properties);
         let entity_type_config = project.get_entity_type_config(&type_name).cloned();
         let enums = project.schema.enums.clone();
 
@@ -243,6 +248,7 @@ fn render_entity_inspector(
             dialogue_options,
             ref_options,
             animation_names,
+            diagnostics,
         )
     };
 
@@ -315,6 +321,8 @@ fn render_entity_inspector(
                 &dialogue_options,
                 &ref_options,
             );
+
+            render_property_diagnostics(ui, &diagnostics, &type_name, &prop_def.name);
         }
     }
 
@@ -338,6 +346,63 @@ fn render_entity_inspector(
     should_delete
 }
 
+/// Validate properties against the schema so the inspector can mark bad fields
+///
+/// `level_id` is the level of an entity (`None` for data instances), used to
+/// find duplicates of `unique` properties.
+fn diagnose_properties(
+    project: &Project,
+    level_id: Option<Uuid>,
+    type_name: &str,
+    properties: &HashMap<String, bevy_map_core::Value>,
+) -> Diagnostics {
+    let json: HashMap<String, serde_json::Value> = properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_json()))
+        .collect();
+    let mut diagnostics = diagnose_instance(&project.schema, type_name, &json);
+
+    // Uniqueness needs every instance, so only collect them when it matters
+    let has_unique = project
+        .schema
+        .all_properties(type_name)
+        .iter()
+        .any(|prop| prop.unique.is_some());
+    if has_unique {
+        let level = level_id.map(|id| id.to_string());
+        diagnostics.items.extend(
+            project
+                .unique_values()
+                .diagnose(&project.schema, level.as_deref(), type_name, &json)
+                .items,
+        );
+    }
+    diagnostics
+}
+
+/// Show the diagnostics for a property (and anything nested in it) below its editor
+fn render_property_diagnostics(
+    ui: &mut egui::Ui,
+    diagnostics: &Diagnostics,
+    type_name: &str,
+    prop_name: &str,
+) {
+    let path = path_join(&path_join("", type_name), prop_name);
+    for diagnostic in diagnostics.at(&path) {
+        let color = match diagnostic.severity {
+            Severity::Error => egui::Color32::from_rgb(230, 80, 80),
+            Severity::Warning => egui::Color32::from_rgb(230, 180, 60),
+        };
+        // Nested diagnostics show where below the property they are
+        let text = match diagnostic.path.strip_prefix(path.as_str()) {
+            Some(rest) if !rest.is_empty() => format!("{}: {}", rest, diagnostic.message),
+            _ => diagnostic.message.clone(),
+        };
+        ui.colored_label(color, text)
+            .on_hover_text(diagnostic.code.as_str());
+    }
+}
+
 fn render_tileset_inspector(ui: &mut egui::Ui, tileset_id: Uuid, project: &mut Project) {
     let Some(tileset) = project.tilesets.iter_mut().find(|t| t.id == tileset_id) else {
         ui.label("Tileset not found");
@@ -391,9 +456,19 @@ fn render_data_type_inspector(ui: &mut egui::Ui, type_name: &str, project: &mut
 
     ui.horizontal(|ui| {
         ui.label("Placeable:");
-        ui.label(if type_def.placeable { "Yes" } else { "No" });
+        ui.label(if type_def.is_placeable() { "Yes" } else { "No" });
     });
 
+    if let Some(base) = &type_def.extends {
+        ui.horizontal(|ui| {
+            ui.label("Extends:");
+            ui.label(base);
+        });
+    }
+    if type_def.is_abstract {
+        ui.label("Abstract");
+    }
+
     if let Some(icon) = &type_def.icon {
         ui.horizontal(|ui| {
             ui.label("Icon:");
@@ -403,15 +478,19 @@ fn render_data_type_inspector(ui: &mut egui::Ui, type_name: &str, project: &mut
 
     // Show properties
     ui.separator();
-    ui.label(format!("Properties ({}):", type_def.properties.len()));
+    let properties = project.schema.resolved_properties(type_name);
+    ui.label(format!("Properties ({}):", properties.len()));
 
-    for prop in &type_def.properties {
+    for (owner, prop) in properties {
         ui.horizontal(|ui| {
             ui.label(&prop.name);
             ui.label(format!("({:?})", prop.prop_type));
             if prop.required {
                 ui.label("*required");
             }
+            if owner != type_name {
+                ui.weak(format!("from {}", owner));
+            }
         });
     }
 
@@ -435,14 +514,15 @@ fn render_data_instance_inspector(
     let mut should_delete = false;
 
     // Phase 1: Extract read-only schema data before mutable borrow
-    let (type_name, type_def, enums, sprite_sheets, dialogue_options, ref_options) = {
+    let (type_name, type_def, enums, sprite_sheets, dialogue_options, ref_options, diagnostics) = {
         let Some(instance) = project.get_data_instance(instance_id) else {
             ui.label("Instance not found");
             return false;
         };
 
         let type_name = instance.type_name.clone();
-        let type_def = project.schema.get_type(&type_name).cloned();
+        let type_def = project.schema.resolved_type(&type_name);
+        let diagnostics = diagnose_properties(project, None, &type_name, &instance.properties);
         let enums = project.schema.enums.clone();
 
         // Collect sprite sheet data (full SpriteData for embedding)
@@ -484,6 +564,7 @@ fn render_data_instance_inspector(
             sprite_sheets,
             dialogue_options,
             ref_options,
+            diagnostics,
         )
     };
 
@@ -538,6 +619,8 @@ fn render_data_instance_inspector(
                 result.create_instance_for_array =
                     Some((create_type, instance_id, prop_def.name.clone()));
             }
+
+            render_property_diagnostics(ui, &diagnostics, &type_name, &prop_def.name);
         }
     } else {
         // Fallback if no schema - display raw properties read-only
@@ -666,6 +749,19 @@ fn render_dialogue_inspector(
         });
     }
 
+    let issues = dialogue.validate();
+    ui.horizontal(|ui| {
+        ui.label("Problems:");
+        let summary = super::dialogue_validation::issue_summary(&issues);
+        match super::dialogue_validation::worst_severity(&issues) {
+            Some(severity) => ui.colored_label(
+                super::dialogue_validation::severity_color(severity),
+                summary,
+            ),
+            None => ui.label(summary),
+        };
+    });
+
     ui.separator();
 
     if ui.button("Open Editor").clicked() {
@@ -692,6 +788,7 @@ pub fn get_default_value(prop_def: &bevy_map_schema::PropertyDef) -> bevy_map_co
         PropType::Int => Value::Int(0),
         PropType::Float => Value::Float(0.0),
         PropType::Bool => Value::Bool(false),
+        PropType::Enum if prop_def.is_flags() => Value::Array(Vec::new()),
         PropType::Enum => Value::String(String::new()),
         PropType::Ref => Value::Null,
         PropType::Array => Value::Array(Vec::new()),
@@ -715,23 +812,7 @@ fn should_show_property(
     prop_def: &bevy_map_schema::PropertyDef,
     properties: &std::collections::HashMap<String, bevy_map_core::Value>,
 ) -> bool {
-    let Some(show_if) = &prop_def.show_if else {
-        return true;
-    };
-
-    if let Some((prop_name, expected)) = show_if.split_once('=') {
-        if let Some(actual) = properties.get(prop_name.trim()) {
-            let actual_str = match actual {
-                bevy_map_core::Value::String(s) => s.clone(),
-                bevy_map_core::Value::Bool(b) => b.to_string(),
-                bevy_map_core::Value::Int(i) => i.to_string(),
-                _ => return false,
-            };
-            return actual_str == expected.trim();
-        }
-        return false;
-    }
-    true
+    prop_def.is_shown(|name| properties.get(name).map(|value| value.to_json()))
 }
 
 /// Parse a hex color string to RGB floats
@@ -767,16 +848,20 @@ fn render_property_value_editor(
     use bevy_map_schema::PropType;
 
     match prop_def.prop_type {
-        PropType::String => {
+        PropType::String | PropType::Multiline => {
             let mut s = value.as_string().unwrap_or(&String::new()).to_string();
-            if ui.text_edit_singleline(&mut s).changed() {
-                *value = Value::String(s);
+            let mut edit = if prop_def.prop_type == PropType::Multiline {
+                egui::TextEdit::multiline(&mut s)
+            } else {
+                egui::TextEdit::singleline(&mut s)
+            };
+            if let Some(max_length) = prop_def.max_length {
+                edit = edit.char_limit(max_length);
             }
-        }
-
-        PropType::Multiline => {
-            let mut s = value.as_string().unwrap_or(&String::new()).to_string();
-            if ui.text_edit_multiline(&mut s).changed() {
+            if let Some(pattern) = &prop_def.pattern {
+                edit = edit.hint_text(pattern);
+            }
+            if ui.add(edit).changed() {
                 *value = Value::String(s);
             }
         }
@@ -810,6 +895,12 @@ fn render_property_value_editor(
             }
         }
 
+        PropType::Enum if prop_def.is_flags() => {
+            if let Some(enum_values) = prop_def.enum_type.as_ref().and_then(|e| enums.get(e)) {
+                render_flags_editor(ui, prop_def, value, enum_values);
+            }
+        }
+
         PropType::Enum => {
             if let Some(enum_type) = &prop_def.enum_type {
                 if let Some(enum_values) = enums.get(enum_type) {
@@ -989,6 +1080,54 @@ fn render_property_value_editor(
     None
 }
 
+/// Render a multi-select enum as one checkbox per value
+///
+/// Values are stored as an array of names in enum order. Unchecked values are
+/// disabled once `max_items` are selected.
+fn render_flags_editor(
+    ui: &mut egui::Ui,
+    prop_def: &bevy_map_schema::PropertyDef,
+    value: &mut bevy_map_core::Value,
+    enum_values: &[String],
+) {
+    use bevy_map_core::Value;
+
+    let selected: Vec<&str> = value
+        .as_array()
+        .map(|items| items.iter().filter_map(|v| v.as_string()).collect())
+        .unwrap_or_default();
+    let at_max = prop_def.max_items.is_some_and(|max| selected.len() >= max);
+
+    let mut toggled = None;
+    ui.horizontal_wrapped(|ui| {
+        for enum_val in enum_values {
+            let mut checked = selected.contains(&enum_val.as_str());
+            let enabled = checked || !at_max;
+            if ui
+                .add_enabled(enabled, egui::Checkbox::new(&mut checked, enum_val))
+                .changed()
+            {
+                toggled = Some((enum_val.as_str(), checked));
+            }
+        }
+    });
+
+    if let Some((toggled_val, checked)) = toggled {
+        let items = enum_values
+            .iter()
+            .filter(|v| {
+                if v.as_str() == toggled_val {
+                    checked
+                } else {
+                    selected.contains(&v.as_str())
+                }
+            })
+            .map(|v| Value::String(v.clone()))
+            .collect();
+        *value = Value::Array(items);
+    }
+}
+
 /// Render an array editor with add/remove support
 /// Returns Some(type_name) if user clicks "Create New" for a custom type
 fn render_array_editor(
@@ -1020,6 +1159,8 @@ fn render_array_editor(
     };
 
     let item_count = items.len();
+    let can_add = prop_def.max_items.map_or(true, |max| item_count < max);
+    let can_remove = prop_def.min_items.map_or(true, |min| item_count > min);
     let mut create_new_type: Option<String> = None;
 
     // Show item type in header for clarity
@@ -1105,8 +1246,11 @@ fn render_array_editor(
                         }
                     }
 
-                    // Remove button
-                    if ui.small_button("X").clicked() {
+                    // Remove button (disabled at the minimum item count)
+                    if ui
+                        .add_enabled(can_remove, egui::Button::new("X").small())
+                        .clicked()
+                    {
                         to_remove = Some(idx);
                     }
                 });
@@ -1121,17 +1265,29 @@ fn render_array_editor(
             if is_custom_type {
                 ui.horizontal(|ui| {
                     // Add existing instance
-                    if ui.button("+ Add Existing").clicked() {
+                    if ui
+                        .add_enabled(can_add, egui::Button::new("+ Add Existing"))
+                        .clicked()
+                    {
                         items.push(Value::Null);
                     }
                     // Create new instance button
-                    if ui.button(format!("+ Create New {}", item_type)).clicked() {
+                    if ui
+                        .add_enabled(
+                            can_add,
+                            egui::Button::new(format!("+ Create New {}", item_type)),
+                        )
+                        .clicked()
+                    {
                         create_new_type = Some(item_type.to_string());
                     }
                 });
             } else {
                 // Primitive type - just add button
-                if ui.button("+ Add").clicked() {
+                if ui
+                    .add_enabled(can_add, egui::Button::new("+ Add"))
+                    .clicked()
+                {
                     let new_item = match item_type {
                         "String" => Value::String(String::new()),
                         "Int" => Value::Int(0),
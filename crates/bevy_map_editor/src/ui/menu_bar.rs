@@ -9,6 +9,7 @@ use crate::preferences::EditorPreferences;
 use crate::project::Project;
 use crate::EditorState;
 
+use super::dialogs::active_dialogue_id;
 use super::PendingAction;
 
 /// Render the menu bar
@@ -64,6 +65,29 @@ pub fn render_menu_bar(
                     ui.close();
                 }
                 ui.separator();
+                if ui.button("Export Strings...").clicked() {
+                    editor_state.pending_action = Some(PendingAction::ExportStrings);
+                    ui.close();
+                }
+                if ui.button("Import Strings...").clicked() {
+                    editor_state.pending_action = Some(PendingAction::ImportStrings);
+                    ui.close();
+                }
+                if ui.button("Import Dialogue...").clicked() {
+                    editor_state.pending_action = Some(PendingAction::ImportDialogue);
+                    ui.close();
+                }
+                if ui
+                    .add_enabled(
+                        active_dialogue_id(editor_state).is_some(),
+                        egui::Button::new("Export Dialogue as Yarn..."),
+                    )
+                    .clicked()
+                {
+                    editor_state.pending_action = Some(PendingAction::ExportDialogue);
+                    ui.close();
+                }
+                ui.separator();
                 if ui.button("Settings...").clicked() {
                     editor_state.show_settings_dialog = true;
                     ui.close();
@@ -224,6 +248,10 @@ pub fn render_menu_bar(
                     editor_state.show_tileset_editor = true;
                     ui.close();
                 }
+                if ui.button("Automap Rules...").clicked() {
+                    editor_state.show_automap_editor = true;
+                    ui.close();
+                }
                 if ui.button("Sprite Sheet Editor...").clicked() {
                     editor_state.show_spritesheet_editor = true;
                     ui.close();
@@ -238,6 +266,15 @@ pub fn render_menu_bar(
                     editor_state.show_dialogue_editor = true;
                     ui.close();
                 }
+                if ui.button("Dialogue Problems...").clicked() {
+                    editor_state.show_dialogue_validation = true;
+                    ui.close();
+                }
+                if ui.button("Project Problems...").clicked() {
+                    editor_state.show_problems = true;
+                    editor_state.problems_state.report = None;
+                    ui.close();
+                }
                 ui.separator();
                 // Data editor
                 if ui.button("Schema Editor...").clicked() {
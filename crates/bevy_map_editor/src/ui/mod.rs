@@ -4,14 +4,18 @@
 
 mod animation_editor;
 mod asset_browser;
+mod automap_editor;
 mod code_preview_dialog;
 mod dialogs;
 mod dialogue_editor;
+mod dialogue_playtest;
+mod dialogue_validation;
 mod entity_palette;
 mod game_settings_dialog;
 mod inspector;
 mod menu_bar;
 mod new_project_dialog;
+mod problems;
 mod schema_editor;
 mod settings_dialog;
 mod spritesheet_editor;
@@ -26,13 +30,16 @@ mod world_view;
 
 pub use animation_editor::{render_animation_editor, AnimationEditorResult, AnimationEditorState};
 pub use asset_browser::{render_asset_browser, AssetBrowserResult, AssetBrowserState};
+pub use automap_editor::{render_automap_editor, AutomapEditorState};
 pub use code_preview_dialog::{render_code_preview_dialog, CodePreviewDialogState, CodePreviewTab};
 pub use dialogs::*;
 pub use dialogue_editor::{render_dialogue_editor, DialogueEditorResult, DialogueEditorState};
+pub use dialogue_validation::render_dialogue_validation_window;
 pub use entity_palette::{render_entity_palette, EntityPaintState};
 pub use game_settings_dialog::GameSettingsDialogState;
 pub use inspector::{get_default_value, render_inspector, InspectorResult, Selection};
 pub use menu_bar::*;
+pub use problems::{render_problems_window, ProblemsState};
 pub use schema_editor::{render_schema_editor, SchemaEditorState};
 pub use spritesheet_editor::{
     render_spritesheet_editor, SpriteSheetEditorResult, SpriteSheetEditorState,
@@ -55,8 +62,8 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
 
-use crate::commands::{CommandHistory, TileClipboard};
-use crate::project::{DataInstance, Project};
+use crate::commands::{CommandHistory, SchemaMigrationCommand, TileClipboard};
+use crate::project::{DataInstance, LintTarget, Project};
 use crate::render::RenderState;
 use crate::EditorState;
 
@@ -747,7 +754,11 @@ fn render_ui(
 
     // Handle create new data instance from tree view
     if let Some(type_name) = tree_view_result.create_data_instance {
-        if let Some(type_def) = project.schema.get_type(&type_name).cloned() {
+        if let Some(type_def) = project
+            .schema
+            .resolved_type(&type_name)
+            .filter(|def| !def.is_abstract)
+        {
             let mut instance = DataInstance::new(type_name.clone());
             // Populate with default values from type definition
             for prop_def in &type_def.properties {
@@ -1417,7 +1428,6 @@ fn render_ui(
     }
 
     // Terrain dialogs
-    terrain::render_new_terrain_dialog(ctx, &mut editor_state, &mut project);
     terrain::render_new_terrain_set_dialog(ctx, &mut editor_state, &mut project);
     terrain::render_add_terrain_to_set_dialog(ctx, &mut editor_state, &mut project);
 
@@ -1441,6 +1451,9 @@ fn render_ui(
     // Tileset & Terrain Editor (modal window)
     render_tileset_editor(ctx, &mut editor_state, &mut project, Some(&tileset_cache));
 
+    // Automap rule editor
+    render_automap_editor(ctx, &mut editor_state, &mut project);
+
     // SpriteSheet Editor (modal window) - for spritesheet setup
     if editor_state.show_spritesheet_editor {
         let result = render_spritesheet_editor(ctx, &mut editor_state.spritesheet_editor_state);
@@ -1524,6 +1537,48 @@ fn render_ui(
         }
     }
 
+    // Dialogue Problems window: clicking an issue opens its dialogue at the node
+    let mut open_dialogue = render_dialogue_validation_window(ctx, &mut editor_state, &project);
+
+    // Project Problems window: clicking a problem selects what it's about
+    if let Some(target) =
+        render_problems_window(ctx, &mut editor_state, &project, assets_base_path.path())
+    {
+        match target {
+            LintTarget::Project => {}
+            LintTarget::Level(level_id) => {
+                editor_state.selection = Selection::Level(level_id);
+                editor_state.selected_level = Some(level_id);
+            }
+            LintTarget::Layer(level_id, layer_idx) => {
+                editor_state.selection = Selection::Layer(level_id, layer_idx);
+                editor_state.selected_level = Some(level_id);
+                editor_state.selected_layer = Some(layer_idx);
+            }
+            LintTarget::Entity(level_id, entity_id) => {
+                editor_state.selection = Selection::Entity(level_id, entity_id);
+                editor_state.selected_level = Some(level_id);
+            }
+            LintTarget::DataInstance(id) => editor_state.selection = Selection::DataInstance(id),
+            LintTarget::DataType(name) => editor_state.selection = Selection::DataType(name),
+            LintTarget::Tileset(id) => editor_state.selection = Selection::Tileset(id),
+            LintTarget::TerrainSet(id) => editor_state.selected_terrain_set = Some(id),
+            LintTarget::Stamp(id) => editor_state.selected_stamp = Some(id),
+            LintTarget::SpriteSheet(id) => editor_state.selection = Selection::SpriteSheet(id),
+            LintTarget::Dialogue(id, node_id) => open_dialogue = Some((id, node_id)),
+        }
+    }
+
+    if let Some((id, node_id)) = open_dialogue {
+        if let Some(dialogue) = project.get_dialogue(&id) {
+            editor_state.dialogue_editor_state =
+                DialogueEditorState::from_dialogue(dialogue.clone());
+            editor_state.dialogue_editor_state.selected_node = node_id;
+            editor_state.dialogue_editor_asset_id = Some(id);
+            editor_state.show_dialogue_editor = true;
+        }
+    }
+
     // Dialogue Editor (modal window)
     if editor_state.show_dialogue_editor {
         let result = render_dialogue_editor(ctx, &mut editor_state.dialogue_editor_state);
@@ -1581,6 +1636,19 @@ fn process_edit_actions(
     mut clipboard: ResMut<TileClipboard>,
     mut render_state: ResMut<RenderState>,
 ) {
+    // Schema edits that migrate existing instances
+    if let Some(migration) = editor_state.schema_editor_state.pending_migration.take() {
+        let description = migration.description.clone();
+        let command =
+            SchemaMigrationCommand::new(&project, migration.schema, migration.description);
+        bevy::log::info!(
+            "{} (migrated {} instance(s))",
+            description,
+            command.migrated_count()
+        );
+        history.execute(Box::new(command), &mut project, &mut render_state);
+    }
+
     // Check for pending edit actions
     let action = editor_state.pending_action.take();
 
@@ -1612,6 +1680,39 @@ fn process_edit_actions(
             PendingAction::CreateStampFromSelection => {
                 create_stamp_from_selection(&mut editor_state, &mut project);
             }
+            PendingAction::ReplaceTerrain {
+                terrain_set_id,
+                from,
+                to,
+            } => {
+                replace_terrain_in_layer(
+                    &mut editor_state,
+                    &mut project,
+                    &mut render_state,
+                    &mut history,
+                    terrain_set_id,
+                    from,
+                    to,
+                );
+            }
+            PendingAction::ResolveTerrainLayer { terrain_set_id } => {
+                resolve_terrain_in_layer(
+                    &mut editor_state,
+                    &mut project,
+                    &mut render_state,
+                    &mut history,
+                    terrain_set_id,
+                );
+            }
+            PendingAction::ApplyAutomapRules { rule_set_id } => {
+                apply_automap_rules(
+                    &mut editor_state,
+                    &mut project,
+                    &mut render_state,
+                    &mut history,
+                    rule_set_id,
+                );
+            }
             PendingAction::OpenGameSettings => {
                 // Initialize dialog state from project config
                 editor_state
@@ -2188,6 +2289,7 @@ fn handle_generate_code(editor_state: &mut EditorState, project: &mut Project) {
         output_dir: output_dir.clone(),
         generate_entities: project.game_config.generate_entities,
         generate_enums: project.game_config.generate_enums,
+        generate_data_types: project.game_config.generate_data_types,
         generate_stubs: project.game_config.generate_stubs,
         generate_behaviors: project.game_config.generate_behaviors,
         generate_health: false,
@@ -2215,7 +2317,7 @@ fn handle_generate_code(editor_state: &mut EditorState, project: &mut Project) {
 /// Handle the "Preview Code" action
 fn handle_preview_code(editor_state: &mut EditorState, project: &Project) {
     use bevy_map_codegen::generator::{
-        preview_behaviors, preview_entities, preview_enums, preview_stubs,
+        preview_behaviors, preview_data_types, preview_entities, preview_enums, preview_stubs,
     };
 
     // Generate preview for each tab
@@ -2229,6 +2331,11 @@ fn handle_preview_code(editor_state: &mut EditorState, project: &Project) {
         Err(e) => format!("// Error generating enums: {}", e),
     };
 
+    let data = match preview_data_types(&project.schema) {
+        Ok(code) => code,
+        Err(e) => format!("// Error generating data types: {}", e),
+    };
+
     let stubs = match preview_stubs(&project.schema) {
         Ok(code) => code,
         Err(e) => format!("// Error generating stubs: {}", e),
@@ -2256,7 +2363,7 @@ fn handle_preview_code(editor_state: &mut EditorState, project: &Project) {
 
     editor_state
         .code_preview_dialog
-        .set_content(entities, enums, stubs, behaviors);
+        .set_content(entities, enums, data, stubs, behaviors);
     editor_state.code_preview_dialog.open = true;
 }
 
@@ -2401,3 +2508,144 @@ fn create_stamp_from_selection(editor_state: &mut EditorState, project: &mut Pro
     // Select the new stamp
     editor_state.selected_stamp = Some(stamp_id);
 }
+
+/// Repaint terrain `from` as terrain `to` across the selected layer (or the
+/// layers the terrains are routed to)
+fn replace_terrain_in_layer(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    render_state: &mut RenderState,
+    history: &mut CommandHistory,
+    terrain_set_id: Uuid,
+    from: usize,
+    to: usize,
+) {
+    // The lower terrain's route also covers the stack layers above it
+    edit_terrain_layer(
+        editor_state,
+        project,
+        render_state,
+        history,
+        terrain_set_id,
+        "Replace terrain",
+        |terrain_set, level, layer_idx| {
+            let lower = if terrain_set.stack_level(from) <= terrain_set.stack_level(to) {
+                from
+            } else {
+                to
+            };
+            Ok(vec![terrain_set.terrain_layers(level, layer_idx, lower)?])
+        },
+        |level, routes, terrain_set| {
+            for route in routes {
+                bevy_map_autotile::replace_terrain_layers(level, route, terrain_set, from, to);
+            }
+        },
+    );
+}
+
+/// Re-resolve the selected layer (or every layer the terrain set is routed
+/// to) against the set's current tile assignments
+fn resolve_terrain_in_layer(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    render_state: &mut RenderState,
+    history: &mut CommandHistory,
+    terrain_set_id: Uuid,
+) {
+    edit_terrain_layer(
+        editor_state,
+        project,
+        render_state,
+        history,
+        terrain_set_id,
+        "Re-resolve terrain",
+        |terrain_set, level, layer_idx| terrain_set.all_terrain_layers(level, layer_idx),
+        |level, routes, terrain_set| {
+            for route in routes {
+                // Fixed seed: resolving the same layer twice gives the same tiles
+                bevy_map_autotile::resolve_terrain_layers(level, route, terrain_set, 0);
+            }
+        },
+    );
+}
+
+/// Run a whole-layer terrain edit on the layers `routes` picks as one undoable command
+fn edit_terrain_layer(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    render_state: &mut RenderState,
+    history: &mut CommandHistory,
+    terrain_set_id: Uuid,
+    description: &str,
+    routes: impl FnOnce(
+        &bevy_map_autotile::TerrainSet,
+        &bevy_map_core::Level,
+        usize,
+    ) -> Result<
+        Vec<bevy_map_autotile::TerrainLayers>,
+        bevy_map_autotile::TerrainLayerError,
+    >,
+    edit: impl FnOnce(
+        &mut bevy_map_core::Level,
+        &[bevy_map_autotile::TerrainLayers],
+        &bevy_map_autotile::TerrainSet,
+    ),
+) {
+    let (Some(level_id), Some(layer_idx)) =
+        (editor_state.selected_level, editor_state.selected_layer)
+    else {
+        editor_state.error_message = Some("Select a tile layer first.".to_string());
+        return;
+    };
+    let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
+        return;
+    };
+    let Some(level) = project.get_level(level_id) else {
+        return;
+    };
+    let routes = match routes(terrain_set, level, layer_idx) {
+        Ok(routes) => routes,
+        Err(e) => {
+            editor_state.error_message = Some(format!(
+                "Can't edit terrain set '{}': {}",
+                terrain_set.name, e
+            ));
+            return;
+        }
+    };
+
+    let result = crate::tools::edit_routed_terrain(
+        project,
+        render_state,
+        level_id,
+        &routes,
+        terrain_set_id,
+        description,
+        |level, terrain_set| edit(level, &routes, terrain_set),
+    );
+    crate::tools::push_terrain_edit(editor_state, project, render_state, history, result);
+}
+
+/// Run an automap rule set on the selected level as one undoable edit
+fn apply_automap_rules(
+    editor_state: &mut EditorState,
+    project: &mut Project,
+    render_state: &mut RenderState,
+    history: &mut CommandHistory,
+    rule_set_id: Uuid,
+) {
+    let Some(level_id) = editor_state.selected_level else {
+        editor_state.error_message = Some("Select a level to apply automap rules to.".to_string());
+        return;
+    };
+    match crate::tools::apply_rule_set(project, level_id, rule_set_id) {
+        Ok(Some(command)) => {
+            project.mark_dirty();
+            render_state.needs_rebuild = true;
+            history.push_undo(Box::new(command));
+        }
+        Ok(None) => {}
+        Err(e) => editor_state.error_message = Some(e),
+    }
+}
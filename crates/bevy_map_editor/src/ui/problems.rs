@@ -0,0 +1,103 @@
+//! Project Problems window
+//!
+//! Shows the result of [`Project::lint`] for the whole project. The report is
+//! computed when the window opens and on Refresh, not every frame, since it
+//! touches every tile and checks files on disk.
+
+use bevy_egui::egui::{self, Color32};
+use std::path::Path;
+
+use crate::project::{LintReport, LintSeverity, LintTarget, Project};
+use crate::EditorState;
+
+const ERROR_COLOR: Color32 = Color32::from_rgb(230, 70, 70);
+const WARNING_COLOR: Color32 = Color32::from_rgb(255, 180, 0);
+
+/// State for the Problems window
+#[derive(Default)]
+pub struct ProblemsState {
+    /// Last lint result (`None` = needs to be recomputed)
+    pub report: Option<LintReport>,
+    /// Hide warnings and only list errors
+    pub errors_only: bool,
+}
+
+/// Render the project-wide Problems window
+///
+/// Returns the target of a clicked problem so the caller can navigate to it.
+pub fn render_problems_window(
+    ctx: &egui::Context,
+    editor_state: &mut EditorState,
+    project: &Project,
+    assets_path: &Path,
+) -> Option<LintTarget> {
+    if !editor_state.show_problems {
+        return None;
+    }
+
+    let mut clicked = None;
+    let mut is_open = true;
+    let state = &mut editor_state.problems_state;
+    let report = state
+        .report
+        .get_or_insert_with(|| project.lint(Some(assets_path)));
+
+    egui::Window::new("Problems")
+        .open(&mut is_open)
+        .default_size([600.0, 400.0])
+        .resizable(true)
+        .show(ctx, |ui| {
+            let mut refresh = false;
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "{} error(s), {} warning(s)",
+                    report.error_count(),
+                    report.warning_count()
+                ));
+                ui.separator();
+                ui.checkbox(&mut state.errors_only, "Errors only");
+                if ui.button("Refresh").clicked() {
+                    refresh = true;
+                }
+            });
+            ui.separator();
+
+            if report.problems.is_empty() {
+                ui.label("No problems found");
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for problem in &report.problems {
+                    if state.errors_only && !problem.is_error() {
+                        continue;
+                    }
+                    let color = match problem.severity {
+                        LintSeverity::Error => ERROR_COLOR,
+                        LintSeverity::Warning => WARNING_COLOR,
+                    };
+                    ui.horizontal(|ui| {
+                        ui.colored_label(color, "●");
+                        let text = format!("{}: {}", problem.location, problem.message);
+                        if ui
+                            .add(egui::Label::new(text).sense(egui::Sense::click()))
+                            .on_hover_text(format!("{}\nClick to select", problem.code.as_str()))
+                            .clicked()
+                        {
+                            clicked = Some(problem.target.clone());
+                        }
+                    });
+                }
+            });
+
+            if refresh {
+                *report = project.lint(Some(assets_path));
+            }
+        });
+
+    if !is_open {
+        editor_state.show_problems = false;
+        editor_state.problems_state.report = None;
+    }
+
+    clicked
+}
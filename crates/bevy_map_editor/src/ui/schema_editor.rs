@@ -10,7 +10,10 @@ use bevy_egui::egui;
 use bevy_map_core::{
     ColliderConfig, InputConfig, InputProfile, PhysicsBodyType, PhysicsConfig, SpriteConfig,
 };
-use bevy_map_schema::{PropType, PropertyDef, TypeDef, ViewportDisplayMode};
+use bevy_map_schema::{
+    diagnose_condition, MigrationStep, PropType, PropertyDef, Schema, Severity, TypeDef,
+    UniqueScope, ViewportDisplayMode,
+};
 
 /// State for the schema editor
 #[derive(Default)]
@@ -41,6 +44,16 @@ pub struct SchemaEditorState {
 
     // Color picker state
     pub color_picker_buffer: [f32; 3],
+
+    /// Schema edit to apply as an undoable migration of all instances
+    pub pending_migration: Option<PendingSchemaMigration>,
+}
+
+/// A schema edit that existing property values have to follow
+pub struct PendingSchemaMigration {
+    /// The edited schema, with the migration recorded
+    pub schema: Schema,
+    pub description: String,
 }
 
 /// Subtab within the type editor
@@ -72,6 +85,14 @@ pub struct PropertyEditState {
     pub item_type: Option<String>,
     pub embedded_type: Option<String>,
     pub show_if: String,
+    pub localized: bool,
+    pub pattern: String,
+    pub min_length: String,
+    pub max_length: String,
+    pub min_items: String,
+    pub max_items: String,
+    pub unique: Option<UniqueScope>,
+    pub flags: bool,
 }
 
 impl Default for PropertyEditState {
@@ -87,6 +108,14 @@ impl Default for PropertyEditState {
             item_type: None,
             embedded_type: None,
             show_if: String::new(),
+            localized: false,
+            pattern: String::new(),
+            min_length: String::new(),
+            max_length: String::new(),
+            min_items: String::new(),
+            max_items: String::new(),
+            unique: None,
+            flags: false,
         }
     }
 }
@@ -104,6 +133,14 @@ impl PropertyEditState {
             item_type: None,
             embedded_type: None,
             show_if: String::new(),
+            localized: false,
+            pattern: String::new(),
+            min_length: String::new(),
+            max_length: String::new(),
+            min_items: String::new(),
+            max_items: String::new(),
+            unique: None,
+            flags: false,
         }
     }
 
@@ -119,10 +156,22 @@ impl PropertyEditState {
             item_type: prop.item_type.clone(),
             embedded_type: prop.embedded_type.clone(),
             show_if: prop.show_if.clone().unwrap_or_default(),
+            localized: prop.localized,
+            pattern: prop.pattern.clone().unwrap_or_default(),
+            min_length: prop.min_length.map(|v| v.to_string()).unwrap_or_default(),
+            max_length: prop.max_length.map(|v| v.to_string()).unwrap_or_default(),
+            min_items: prop.min_items.map(|v| v.to_string()).unwrap_or_default(),
+            max_items: prop.max_items.map(|v| v.to_string()).unwrap_or_default(),
+            unique: prop.unique,
+            flags: prop.flags,
         }
     }
 
     pub fn to_property(&self) -> PropertyDef {
+        // Constraints that don't apply to the type are dropped
+        let is_string = matches!(self.prop_type, PropType::String | PropType::Multiline);
+        let is_flags = self.flags && self.prop_type == PropType::Enum;
+        let has_items = self.prop_type == PropType::Array || is_flags;
         PropertyDef {
             name: self.name.clone(),
             prop_type: self.prop_type,
@@ -139,6 +188,23 @@ impl PropertyEditState {
             ref_type: self.ref_type.clone(),
             item_type: self.item_type.clone(),
             embedded_type: self.embedded_type.clone(),
+            localized: self.localized && is_string,
+            pattern: if self.pattern.is_empty() || !is_string {
+                None
+            } else {
+                Some(self.pattern.clone())
+            },
+            min_length: self.min_length.parse().ok().filter(|_| is_string),
+            max_length: self.max_length.parse().ok().filter(|_| is_string),
+            min_items: self.min_items.parse().ok().filter(|_| has_items),
+            max_items: self.max_items.parse().ok().filter(|_| has_items),
+            unique: self.unique.filter(|_| {
+                matches!(
+                    self.prop_type,
+                    PropType::String | PropType::Int | PropType::Enum | PropType::Ref
+                ) && !is_flags
+            }),
+            flags: is_flags,
         }
     }
 }
@@ -399,6 +465,14 @@ fn render_data_types_tab(
                                 state.selected_type = Some(type_name.clone());
                                 state.selected_property_idx = None;
                             }
+                            if project
+                                .schema
+                                .data_types
+                                .get(type_name)
+                                .is_some_and(|t| t.is_abstract)
+                            {
+                                ui.weak("abstract");
+                            }
                             if ui.small_button("X").clicked() {
                                 to_delete = Some(type_name.clone());
                             }
@@ -407,6 +481,12 @@ fn render_data_types_tab(
 
                     if let Some(name) = to_delete {
                         project.schema.data_types.remove(&name);
+                        // Types extending the deleted one lose their base
+                        for type_def in project.schema.data_types.values_mut() {
+                            if type_def.extends.as_ref() == Some(&name) {
+                                type_def.extends = None;
+                            }
+                        }
                         if state.selected_type.as_ref() == Some(&name) {
                             state.selected_type = None;
                         }
@@ -447,6 +527,8 @@ fn render_type_editor(
         current_icon,
         current_marker_size,
         current_viewport_display,
+        current_extends,
+        current_abstract,
     ) = {
         let type_def = project.schema.data_types.get(type_name).unwrap();
         (
@@ -455,15 +537,31 @@ fn render_type_editor(
             type_def.icon.clone(),
             type_def.marker_size,
             type_def.viewport_display,
+            type_def.extends.clone(),
+            type_def.is_abstract,
         )
     };
 
+    // Possible base types: anything that doesn't already derive from this type
+    let mut base_candidates: Vec<String> = project
+        .schema
+        .data_types
+        .keys()
+        .filter(|name| {
+            name.as_str() != type_name && !project.schema.base_types(name).contains(&type_name)
+        })
+        .cloned()
+        .collect();
+    base_candidates.sort();
+
     // Type settings
     let mut new_placeable = current_placeable;
     let mut new_color = parse_color_rgb(&current_color);
     let mut new_icon = current_icon.clone().unwrap_or_default();
     let mut new_marker_size = current_marker_size.unwrap_or(16) as i32;
     let mut new_viewport_display = current_viewport_display;
+    let mut new_extends = current_extends;
+    let mut new_abstract = current_abstract;
     let mut settings_changed = false;
 
     egui::CollapsingHeader::new("Settings")
@@ -474,6 +572,38 @@ fn render_type_editor(
                 .num_columns(2)
                 .spacing([10.0, 4.0])
                 .show(ui, |ui| {
+                    // Base type
+                    ui.label("Extends:");
+                    egui::ComboBox::from_id_salt(format!("extends_{}", type_name))
+                        .selected_text(new_extends.as_deref().unwrap_or("(none)"))
+                        .show_ui(ui, |ui| {
+                            if ui
+                                .selectable_value(&mut new_extends, None, "(none)")
+                                .changed()
+                            {
+                                settings_changed = true;
+                            }
+                            for base in &base_candidates {
+                                if ui
+                                    .selectable_value(&mut new_extends, Some(base.clone()), base)
+                                    .changed()
+                                {
+                                    settings_changed = true;
+                                }
+                            }
+                        });
+                    ui.end_row();
+
+                    // Abstract checkbox
+                    ui.label("Abstract:");
+                    if ui
+                        .checkbox(&mut new_abstract, "Only used as a base type")
+                        .changed()
+                    {
+                        settings_changed = true;
+                    }
+                    ui.end_row();
+
                     // Placeable checkbox
                     ui.label("Placeable:");
                     if ui
@@ -584,6 +714,8 @@ fn render_type_editor(
     // Apply changes after UI rendering
     if settings_changed {
         if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
+            type_def.extends = new_extends;
+            type_def.is_abstract = new_abstract;
             type_def.placeable = new_placeable;
             type_def.marker_size = if new_placeable {
                 Some(new_marker_size as u32)
@@ -667,10 +799,67 @@ fn render_properties_section(
     egui::ScrollArea::vertical()
         .id_salt(format!("property_list_scroll_{}", type_name))
         .show(ui, |ui| {
+            // Inherited properties not overridden here, greyed out
+            let inherited: Vec<(String, PropertyDef)> = project
+                .schema
+                .resolved_properties(type_name)
+                .into_iter()
+                .filter(|(owner, _)| *owner != type_name)
+                .map(|(owner, prop)| (owner.to_string(), prop.clone()))
+                .collect();
+            let overridden: Vec<(String, String)> = match project.schema.data_types.get(type_name) {
+                Some(type_def) => type_def
+                    .extends
+                    .as_deref()
+                    .map(|base| project.schema.resolved_properties(base))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(_, prop)| type_def.properties.iter().any(|p| p.name == prop.name))
+                    .map(|(owner, prop)| (prop.name.clone(), owner.to_string()))
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            let mut to_override = None;
+            for (owner, prop) in &inherited {
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(false, |ui| {
+                        ui.label(&prop.name);
+                        ui.label(format!("({})", prop.prop_type.display_name()));
+                    });
+                    ui.weak(format!("from {}", owner));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .small_button("Override")
+                            .on_hover_text("Redeclare this property on this type")
+                            .clicked()
+                        {
+                            to_override = Some(prop.clone());
+                        }
+                    });
+                });
+            }
+            if let Some(prop) = to_override {
+                if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
+                    type_def.properties.push(prop);
+                    project.mark_dirty();
+                }
+            }
+            if !inherited.is_empty() {
+                ui.separator();
+            }
+
             let type_def = project.schema.data_types.get(type_name).cloned();
             if let Some(type_def) = type_def {
                 let mut to_delete = None;
+                let mut to_embed = None;
                 let mut to_edit = None;
+                let embedded_props: Vec<String> = type_def
+                    .properties
+                    .iter()
+                    .filter(|p| p.prop_type == PropType::Embedded && p.embedded_type.is_some())
+                    .map(|p| p.name.clone())
+                    .collect();
                 let mut to_move_up = None;
                 let mut to_move_down = None;
 
@@ -710,12 +899,21 @@ fn render_properties_section(
                                     if let Some(ref embedded_type) = prop.embedded_type {
                                         details.push(format!("embedded: {}", embedded_type));
                                     }
+                                    if prop.localized {
+                                        details.push("localized".to_string());
+                                    }
                                     if let Some(min) = prop.min {
                                         details.push(format!("min: {}", min));
                                     }
                                     if let Some(max) = prop.max {
                                         details.push(format!("max: {}", max));
                                     }
+                                    details.extend(prop.constraint_descriptions());
+                                    if let Some((_, owner)) =
+                                        overridden.iter().find(|(name, _)| *name == prop.name)
+                                    {
+                                        details.push(format!("overrides {}", owner));
+                                    }
                                     if !details.is_empty() {
                                         ui.label(details.join(", "));
                                     }
@@ -733,12 +931,31 @@ fn render_properties_section(
                                 ui.with_layout(
                                     egui::Layout::right_to_left(egui::Align::Center),
                                     |ui| {
-                                        if ui.small_button("X").clicked() {
+                                        if ui
+                                            .small_button("X")
+                                            .on_hover_text("Delete, removing its values")
+                                            .clicked()
+                                        {
                                             to_delete = Some(idx);
                                         }
                                         if ui.small_button("Edit").clicked() {
                                             to_edit = Some(idx);
                                         }
+                                        let targets: Vec<&String> = embedded_props
+                                            .iter()
+                                            .filter(|name| **name != prop.name)
+                                            .collect();
+                                        if !targets.is_empty() {
+                                            ui.menu_button("Move", |ui| {
+                                                ui.label("Move into embedded property:");
+                                                for name in targets {
+                                                    if ui.button(name).clicked() {
+                                                        to_embed = Some((idx, name.clone()));
+                                                        ui.close();
+                                                    }
+                                                }
+                                            });
+                                        }
                                         if idx + 1 < type_def.properties.len()
                                             && ui.small_button("v").clicked()
                                         {
@@ -758,10 +975,50 @@ fn render_properties_section(
 
                 // Apply changes
                 if let Some(idx) = to_delete {
-                    if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
-                        type_def.properties.remove(idx);
+                    let name = type_def.properties[idx].name.clone();
+                    state.selected_property_idx = None;
+                    if overridden.iter().any(|(prop_name, _)| *prop_name == name) {
+                        // Values stay valid under the inherited property
+                        if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
+                            type_def.properties.remove(idx);
+                            project.mark_dirty();
+                        }
+                    } else {
+                        let step = MigrationStep::Delete {
+                            type_name: type_name.to_string(),
+                            property: name,
+                        };
+                        queue_migration(state, project, vec![step], |schema| {
+                            if let Some(type_def) = schema.data_types.get_mut(type_name) {
+                                type_def.properties.remove(idx);
+                            }
+                        });
+                    }
+                }
+                if let Some((idx, into)) = to_embed {
+                    let prop = type_def.properties[idx].clone();
+                    let embedded_type = type_def
+                        .properties
+                        .iter()
+                        .find(|p| p.name == into)
+                        .and_then(|p| p.embedded_type.clone());
+                    if let Some(embedded_type) = embedded_type {
                         state.selected_property_idx = None;
-                        project.mark_dirty();
+                        let step = MigrationStep::Embed {
+                            type_name: type_name.to_string(),
+                            property: prop.name.clone(),
+                            into,
+                        };
+                        queue_migration(state, project, vec![step], |schema| {
+                            if let Some(type_def) = schema.data_types.get_mut(type_name) {
+                                type_def.properties.remove(idx);
+                            }
+                            if let Some(embedded) = schema.embedded_types.get_mut(&embedded_type) {
+                                if !embedded.properties.iter().any(|p| p.name == prop.name) {
+                                    embedded.properties.push(prop);
+                                }
+                            }
+                        });
                     }
                 }
                 if let Some(idx) = to_edit {
@@ -1285,6 +1542,7 @@ fn render_add_property_dialog(
                 ui,
                 &mut editor_state.schema_editor_state.property_edit_state,
                 project,
+                editor_state.schema_editor_state.selected_type.as_deref(),
                 "add",
             );
 
@@ -1336,6 +1594,25 @@ fn render_edit_property_dialog(
     let mut close = false;
     let mut save = false;
 
+    // Renaming onto another property of the type would merge their values
+    let name_taken = {
+        let state = &editor_state.schema_editor_state;
+        let new_name = &state.property_edit_state.name;
+        let old_name = state.selected_type.as_deref().and_then(|type_name| {
+            let type_def = project.schema.data_types.get(type_name)?;
+            let prop = type_def.properties.get(state.selected_property_idx?)?;
+            Some((type_name, prop.name.as_str()))
+        });
+        old_name.is_some_and(|(type_name, old_name)| {
+            new_name != old_name
+                && project
+                    .schema
+                    .all_properties(type_name)
+                    .iter()
+                    .any(|prop| &prop.name == new_name)
+        })
+    };
+
     egui::Window::new("Edit Property")
         .id(egui::Id::new("edit_property_dialog"))
         .collapsible(false)
@@ -1346,19 +1623,31 @@ fn render_edit_property_dialog(
                 ui,
                 &mut editor_state.schema_editor_state.property_edit_state,
                 project,
+                editor_state.schema_editor_state.selected_type.as_deref(),
                 "edit",
             );
 
+            if name_taken {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!(
+                        "A property named '{}' already exists",
+                        editor_state.schema_editor_state.property_edit_state.name
+                    ),
+                );
+            }
+
             ui.separator();
             ui.horizontal(|ui| {
                 if ui.button("Cancel").clicked() {
                     close = true;
                 }
-                let can_save = !editor_state
-                    .schema_editor_state
-                    .property_edit_state
-                    .name
-                    .is_empty();
+                let can_save = !name_taken
+                    && !editor_state
+                        .schema_editor_state
+                        .property_edit_state
+                        .name
+                        .is_empty();
                 if ui
                     .add_enabled(can_save, egui::Button::new("Save"))
                     .clicked()
@@ -1372,14 +1661,45 @@ fn render_edit_property_dialog(
     if save {
         if let Some(type_name) = &editor_state.schema_editor_state.selected_type.clone() {
             if let Some(prop_idx) = editor_state.schema_editor_state.selected_property_idx {
-                if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
-                    if prop_idx < type_def.properties.len() {
-                        let prop = editor_state
-                            .schema_editor_state
-                            .property_edit_state
-                            .to_property();
-                        type_def.properties[prop_idx] = prop;
-                        project.mark_dirty();
+                let state = &mut editor_state.schema_editor_state;
+                let old = project
+                    .schema
+                    .data_types
+                    .get(type_name)
+                    .and_then(|type_def| type_def.properties.get(prop_idx))
+                    .cloned();
+                if let Some(old) = old {
+                    let prop = state.property_edit_state.to_property();
+
+                    // Renames and type changes migrate the existing values
+                    let mut steps = Vec::new();
+                    if prop.name != old.name {
+                        steps.push(MigrationStep::Rename {
+                            type_name: type_name.clone(),
+                            from: old.name.clone(),
+                            to: prop.name.clone(),
+                        });
+                    }
+                    if prop.prop_type != old.prop_type {
+                        steps.push(MigrationStep::Retype {
+                            type_name: type_name.clone(),
+                            property: prop.name.clone(),
+                            from: old.prop_type,
+                            to: prop.prop_type,
+                        });
+                    }
+
+                    if steps.is_empty() {
+                        if let Some(type_def) = project.schema.data_types.get_mut(type_name) {
+                            type_def.properties[prop_idx] = prop;
+                            project.mark_dirty();
+                        }
+                    } else {
+                        queue_migration(state, project, steps, |schema| {
+                            if let Some(type_def) = schema.data_types.get_mut(type_name) {
+                                type_def.properties[prop_idx] = prop;
+                            }
+                        });
                     }
                 }
             }
@@ -1391,11 +1711,37 @@ fn render_edit_property_dialog(
     }
 }
 
+/// Queue a schema edit as a migration that existing values follow
+///
+/// `edit` changes a copy of the schema; the steps are recorded on it as a new
+/// schema revision. The edit and the migrated instances are applied together
+/// as one undoable command.
+fn queue_migration(
+    state: &mut SchemaEditorState,
+    project: &crate::project::Project,
+    steps: Vec<MigrationStep>,
+    edit: impl FnOnce(&mut Schema),
+) {
+    let mut schema = project.schema.clone();
+    edit(&mut schema);
+    let description = steps
+        .iter()
+        .map(MigrationStep::describe)
+        .collect::<Vec<_>>()
+        .join(", ");
+    schema.record_migration(steps);
+    state.pending_migration = Some(PendingSchemaMigration {
+        schema,
+        description,
+    });
+}
+
 /// Render the property editing form
 fn render_property_form(
     ui: &mut egui::Ui,
     state: &mut PropertyEditState,
     project: &crate::project::Project,
+    type_name: Option<&str>,
     id_context: &str,
 ) {
     egui::Grid::new(format!("property_form_{}", id_context))
@@ -1444,6 +1790,25 @@ fn render_property_form(
 
             // Type-specific options
             match state.prop_type {
+                PropType::String | PropType::Multiline => {
+                    ui.label("Localized:");
+                    ui.checkbox(&mut state.localized, "")
+                        .on_hover_text("Include this property in exported string tables");
+                    ui.end_row();
+
+                    ui.label("Pattern:");
+                    ui.text_edit_singleline(&mut state.pattern)
+                        .on_hover_text("Regular expression, e.g. ^[A-Z][0-9]+$");
+                    ui.end_row();
+
+                    ui.label("Min Length:");
+                    ui.text_edit_singleline(&mut state.min_length);
+                    ui.end_row();
+
+                    ui.label("Max Length:");
+                    ui.text_edit_singleline(&mut state.max_length);
+                    ui.end_row();
+                }
                 PropType::Int | PropType::Float => {
                     ui.label("Min:");
                     ui.text_edit_singleline(&mut state.min);
@@ -1470,6 +1835,15 @@ fn render_property_form(
                             }
                         });
                     ui.end_row();
+
+                    ui.label("Flags:");
+                    ui.checkbox(&mut state.flags, "")
+                        .on_hover_text("Allow selecting several values");
+                    ui.end_row();
+
+                    if state.flags {
+                        render_item_count_fields(ui, state);
+                    }
                 }
                 PropType::Ref => {
                     ui.label("Reference Type:");
@@ -1529,25 +1903,80 @@ fn render_property_form(
                             }
                         });
                     ui.end_row();
+
+                    render_item_count_fields(ui, state);
                 }
                 _ => {}
             }
 
+            if matches!(
+                state.prop_type,
+                PropType::String | PropType::Int | PropType::Ref
+            ) || (state.prop_type == PropType::Enum && !state.flags)
+            {
+                ui.label("Unique:");
+                let text = state.unique.map_or("No", |scope| match scope {
+                    UniqueScope::Project => "In project",
+                    UniqueScope::Level => "In level",
+                });
+                egui::ComboBox::from_id_salt(format!("unique_{}", id_context))
+                    .selected_text(text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut state.unique, None, "No");
+                        ui.selectable_value(
+                            &mut state.unique,
+                            Some(UniqueScope::Project),
+                            "In project",
+                        );
+                        ui.selectable_value(
+                            &mut state.unique,
+                            Some(UniqueScope::Level),
+                            "In level",
+                        );
+                    });
+                ui.end_row();
+            }
+
             // Show If (conditional visibility)
             ui.label("Show If:");
             ui.text_edit_singleline(&mut state.show_if);
             ui.end_row();
         });
 
+    // Check show_if against the type's properties as it's typed
+    if let Some(type_name) = type_name.filter(|_| !state.show_if.trim().is_empty()) {
+        let diagnostics = diagnose_condition(&project.schema, type_name, &state.to_property());
+        for diagnostic in diagnostics.iter() {
+            let color = match diagnostic.severity {
+                Severity::Error => egui::Color32::from_rgb(230, 80, 80),
+                Severity::Warning => egui::Color32::from_rgb(230, 180, 60),
+            };
+            ui.colored_label(color, &diagnostic.message);
+        }
+    }
+
     // Help text for show_if
     ui.add_space(4.0);
     ui.label(
-        egui::RichText::new("'Show If' format: property_name=value (e.g., type=weapon)")
-            .small()
-            .weak(),
+        egui::RichText::new(
+            "'Show If' examples: kind == Weapon && damage > 0, element in [Fire, Ice] || !magical",
+        )
+        .small()
+        .weak(),
     );
 }
 
+/// Min/max item count rows of the property form (Array and flags Enum)
+fn render_item_count_fields(ui: &mut egui::Ui, state: &mut PropertyEditState) {
+    ui.label("Min Items:");
+    ui.text_edit_singleline(&mut state.min_items);
+    ui.end_row();
+
+    ui.label("Max Items:");
+    ui.text_edit_singleline(&mut state.max_items);
+    ui.end_row();
+}
+
 /// Parse a hex color string to egui Color32
 fn parse_color(color_str: &str) -> egui::Color32 {
     let color_str = color_str.trim_start_matches('#');
@@ -6,46 +6,6 @@ use bevy_map_autotile::{Color, TerrainSet, TerrainSetType};
 use crate::project::Project;
 use crate::EditorState;
 
-/// Render the new terrain dialog (legacy 47-tile blob)
-pub fn render_new_terrain_dialog(
-    ctx: &egui::Context,
-    editor_state: &mut EditorState,
-    _project: &mut Project,
-) {
-    if !editor_state.show_new_terrain_dialog {
-        return;
-    }
-
-    egui::Window::new("New Terrain")
-        .collapsible(false)
-        .resizable(false)
-        .show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Name:");
-                ui.text_edit_singleline(&mut editor_state.new_terrain_name);
-            });
-
-            ui.horizontal(|ui| {
-                ui.label("First Tile:");
-                ui.add(egui::DragValue::new(
-                    &mut editor_state.new_terrain_first_tile,
-                ));
-            });
-
-            ui.separator();
-
-            ui.horizontal(|ui| {
-                if ui.button("Create").clicked() {
-                    // TODO: Create legacy terrain
-                    editor_state.show_new_terrain_dialog = false;
-                }
-                if ui.button("Cancel").clicked() {
-                    editor_state.show_new_terrain_dialog = false;
-                }
-            });
-        });
-}
-
 /// Render the new terrain set dialog (Tiled-compatible)
 pub fn render_new_terrain_set_dialog(
     ctx: &egui::Context,
@@ -3,7 +3,7 @@
 use bevy_egui::egui;
 use uuid::Uuid;
 
-use super::TilesetTextureCache;
+use super::{PendingAction, TilesetTextureCache};
 use crate::project::Project;
 use crate::EditorState;
 
@@ -15,6 +15,10 @@ pub struct TerrainPaintState {
     pub selected_terrain_idx: Option<usize>,
     /// Whether painting with terrain brush
     pub is_terrain_mode: bool,
+    /// Terrain to replace in the "Replace Terrain" section
+    pub replace_from: usize,
+    /// Terrain to replace it with
+    pub replace_to: usize,
 }
 
 impl TerrainPaintState {
@@ -23,6 +27,8 @@ impl TerrainPaintState {
             selected_terrain_set: None,
             selected_terrain_idx: None,
             is_terrain_mode: false,
+            replace_from: 0,
+            replace_to: 0,
         }
     }
 }
@@ -167,6 +173,65 @@ fn render_terrain_brushes(ui: &mut egui::Ui, editor_state: &mut EditorState, pro
                     }
                 });
             }
+
+            ui.separator();
+            render_replace_terrain(ui, editor_state, terrain_set);
+
+            if ui
+                .button("Re-resolve Layer")
+                .on_hover_text(
+                    "Repick every terrain tile on the selected layer from the current tile assignments",
+                )
+                .clicked()
+            {
+                editor_state.pending_action = Some(PendingAction::ResolveTerrainLayer {
+                    terrain_set_id: terrain_set.id,
+                });
+            }
         }
     }
 }
+
+/// Render the "Replace Terrain" section for repainting one terrain as another
+fn render_replace_terrain(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    terrain_set: &bevy_map_autotile::TerrainSet,
+) {
+    if terrain_set.terrains.len() < 2 {
+        return;
+    }
+
+    egui::CollapsingHeader::new("Replace Terrain").show(ui, |ui| {
+        let state = &mut editor_state.terrain_paint_state;
+        let last = terrain_set.terrains.len() - 1;
+        state.replace_from = state.replace_from.min(last);
+        state.replace_to = state.replace_to.min(last);
+
+        for (label, value) in [
+            ("Replace", &mut state.replace_from),
+            ("With", &mut state.replace_to),
+        ] {
+            egui::ComboBox::from_label(label)
+                .selected_text(&terrain_set.terrains[*value].name)
+                .show_ui(ui, |ui| {
+                    for (idx, terrain) in terrain_set.terrains.iter().enumerate() {
+                        ui.selectable_value(value, idx, &terrain.name);
+                    }
+                });
+        }
+
+        let (from, to) = (state.replace_from, state.replace_to);
+        if ui
+            .add_enabled(from != to, egui::Button::new("Replace in Layer"))
+            .on_hover_text("Repaint every tile corner/edge of this terrain on the selected layer")
+            .clicked()
+        {
+            editor_state.pending_action = Some(PendingAction::ReplaceTerrain {
+                terrain_set_id: terrain_set.id,
+                from,
+                to,
+            });
+        }
+    });
+}
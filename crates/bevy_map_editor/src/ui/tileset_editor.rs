@@ -11,11 +11,11 @@
 use bevy::color::Color;
 use bevy_egui::egui::{self, Color32, Pos2, Shape};
 use bevy_map_autotile::terrain::Color as TerrainColor;
-use bevy_map_autotile::TerrainSetType;
+use bevy_map_autotile::{TerrainCoverage, TerrainSetType};
 use bevy_map_core::PhysicsLayerSet;
 use std::f32::consts::PI;
 
-use super::{find_base_tile_for_position, EditorTheme, TilesetTextureCache};
+use super::{find_base_tile_for_position, EditorTheme, PendingAction, TilesetTextureCache};
 use crate::project::Project;
 use crate::EditorState;
 
@@ -35,6 +35,8 @@ pub struct TilesetEditorState {
     pub merge_drag_start: Option<(u32, u32, usize)>,
     /// Active shift+drag selection current position for tile merging (col, row)
     pub merge_drag_current: Option<(u32, u32)>,
+    /// Last coverage check, for the terrain set with this ID
+    pub terrain_coverage: Option<(uuid::Uuid, TerrainCoverage)>,
 }
 
 impl Default for TilesetEditorState {
@@ -48,6 +50,7 @@ impl Default for TilesetEditorState {
             collision_editor: CollisionEditorState::default(),
             merge_drag_start: None,
             merge_drag_current: None,
+            terrain_coverage: None,
         }
     }
 }
@@ -854,6 +857,26 @@ fn render_terrain_sets_tab(
                                 }
                             }
                         }
+
+                        if let Some(terrain_set) = project.autotile_config.get_terrain_set_mut(ts_id)
+                        {
+                            ui.separator();
+                            let changed = ui
+                                .checkbox(
+                                    &mut terrain_set.allow_transforms,
+                                    "Allow flipping/rotation",
+                                )
+                                .on_hover_text(
+                                    "Also paint flipped and rotated tiles when no authored tile fits",
+                                )
+                                .changed();
+                            if changed {
+                                project.mark_dirty();
+                            }
+                        }
+
+                        render_terrain_layers(ui, project, ts_id);
+                        render_terrain_coverage(ui, editor_state, project, ts_id);
                     }
                 });
         });
@@ -2906,29 +2929,29 @@ fn handle_collision_canvas_input(
                         return;
                     };
 
-                    if let Some(shape) = collision_shape {
-                        if let bevy_map_core::CollisionShape::Polygon { points } = &shape {
-                            // Create a temporary copy with the moved vertex
-                            let mut preview_points = points.clone();
-                            if *index < preview_points.len() {
-                                // Clamp during preview too
-                                preview_points[*index] = [
-                                    drag_state.current_pos[0].clamp(0.0, 1.0),
-                                    drag_state.current_pos[1].clamp(0.0, 1.0),
-                                ];
-                            }
-                            // Draw the preview polygon and handles
-                            let preview_shape = bevy_map_core::CollisionShape::Polygon {
-                                points: preview_points,
-                            };
-                            draw_collision_shape_on_canvas(
-                                ui.painter(),
-                                canvas_rect,
-                                &preview_shape,
-                                physics_color,
-                            );
-                            draw_collision_handles(ui.painter(), canvas_rect, &preview_shape);
+                    if let Some(bevy_map_core::CollisionShape::Polygon { points }) =
+                        &collision_shape
+                    {
+                        // Create a temporary copy with the moved vertex
+                        let mut preview_points = points.clone();
+                        if *index < preview_points.len() {
+                            // Clamp during preview too
+                            preview_points[*index] = [
+                                drag_state.current_pos[0].clamp(0.0, 1.0),
+                                drag_state.current_pos[1].clamp(0.0, 1.0),
+                            ];
                         }
+                        // Draw the preview polygon and handles
+                        let preview_shape = bevy_map_core::CollisionShape::Polygon {
+                            points: preview_points,
+                        };
+                        draw_collision_shape_on_canvas(
+                            ui.painter(),
+                            canvas_rect,
+                            &preview_shape,
+                            physics_color,
+                        );
+                        draw_collision_handles(ui.painter(), canvas_rect, &preview_shape);
                     }
                 }
             }
@@ -2950,14 +2973,11 @@ fn handle_collision_canvas_input(
                         .and_then(|layer| layer.get_tile_physics(tile_idx))
                         .map(|data| data.shape.clone());
 
-                    if let Some(shape) = collision_shape {
-                        if let bevy_map_core::CollisionShape::Polygon { points } = &shape {
+                    match &collision_shape {
+                        Some(bevy_map_core::CollisionShape::Polygon { points }) => {
                             hit_test_polygon_vertex(canvas_rect, points, pointer_pos, 8.0)
-                        } else {
-                            None
                         }
-                    } else {
-                        None
+                        _ => None,
                     }
                 } else {
                     None
@@ -3413,3 +3433,192 @@ pub fn render_new_physics_layer_set_dialog(
             });
         });
 }
+
+/// Maximum missing combinations listed in the coverage section
+const MAX_LISTED_MISSING: usize = 50;
+
+/// Per-terrain target layers and the layer stack for overlapping transitions
+fn render_terrain_layers(ui: &mut egui::Ui, project: &mut Project, terrain_set_id: uuid::Uuid) {
+    // Layers are matched by name, so offer every tile layer name in the project
+    let mut layer_names: Vec<String> = Vec::new();
+    for level in &project.levels {
+        for layer in &level.layers {
+            if matches!(layer.data, bevy_map_core::LayerData::Tiles { .. })
+                && !layer_names.contains(&layer.name)
+            {
+                layer_names.push(layer.name.clone());
+            }
+        }
+    }
+
+    let Some(terrain_set) = project.autotile_config.get_terrain_set_mut(terrain_set_id) else {
+        return;
+    };
+
+    let mut changed = false;
+    egui::CollapsingHeader::new("Layers")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.small("Paint terrains onto tile layers (none = selected layer).");
+            egui::Grid::new("terrain_layers_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (idx, terrain) in terrain_set.terrains.iter_mut().enumerate() {
+                        ui.label(&terrain.name);
+                        egui::ComboBox::from_id_salt(("terrain_layer", idx))
+                            .selected_text(layer_label(terrain.layer.as_deref(), &layer_names))
+                            .show_ui(ui, |ui| {
+                                changed |= ui
+                                    .selectable_value(&mut terrain.layer, None, "Selected layer")
+                                    .changed();
+                                for name in &layer_names {
+                                    changed |= ui
+                                        .selectable_value(
+                                            &mut terrain.layer,
+                                            Some(name.clone()),
+                                            name,
+                                        )
+                                        .changed();
+                                }
+                            });
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(4.0);
+            ui.label("Layer Stack").on_hover_text(
+                "Bottom layer first. Each stack layer only keeps tiles showing its own \
+                     terrains, drawn over the layers below it.",
+            );
+            let mut remove = None;
+            for idx in 0..terrain_set.layer_stack.len() {
+                ui.horizontal(|ui| {
+                    ui.small(format!("{}.", idx + 1));
+                    let current = terrain_set.layer_stack[idx].clone();
+                    egui::ComboBox::from_id_salt(("terrain_layer_stack", idx))
+                        .selected_text(layer_label(Some(&current), &layer_names))
+                        .show_ui(ui, |ui| {
+                            for name in &layer_names {
+                                let in_stack =
+                                    *name != current && terrain_set.layer_stack.contains(name);
+                                if ui
+                                    .add_enabled(
+                                        !in_stack,
+                                        egui::Button::selectable(*name == current, name.as_str()),
+                                    )
+                                    .clicked()
+                                {
+                                    terrain_set.layer_stack[idx] = name.clone();
+                                    changed = true;
+                                }
+                            }
+                        });
+                    if ui.small_button("x").clicked() {
+                        remove = Some(idx);
+                    }
+                });
+            }
+            if let Some(idx) = remove {
+                terrain_set.layer_stack.remove(idx);
+                changed = true;
+            }
+            let next = layer_names
+                .iter()
+                .find(|name| !terrain_set.layer_stack.contains(name));
+            if ui
+                .add_enabled(next.is_some(), egui::Button::new("+ Add Layer"))
+                .clicked()
+            {
+                if let Some(name) = next {
+                    terrain_set.layer_stack.push(name.clone());
+                    changed = true;
+                }
+            }
+        });
+
+    if changed {
+        project.mark_dirty();
+    }
+}
+
+/// Picker text for a terrain layer name; names no level has are flagged
+fn layer_label(layer: Option<&str>, layer_names: &[String]) -> String {
+    match layer {
+        None => "Selected layer".to_string(),
+        Some(name) if layer_names.iter().any(|n| n == name) => name.to_string(),
+        Some(name) => format!("{} (missing)", name),
+    }
+}
+
+/// Coverage section of the terrain list: which terrain combinations have no tile
+fn render_terrain_coverage(
+    ui: &mut egui::Ui,
+    editor_state: &mut EditorState,
+    project: &Project,
+    terrain_set_id: uuid::Uuid,
+) {
+    let Some(terrain_set) = project.autotile_config.get_terrain_set(terrain_set_id) else {
+        return;
+    };
+
+    egui::CollapsingHeader::new("Coverage")
+        .default_open(false)
+        .show(ui, |ui| {
+            if ui
+                .button("Check Coverage")
+                .on_hover_text("List terrain combinations no tile matches")
+                .clicked()
+            {
+                editor_state.tileset_editor_state.terrain_coverage =
+                    Some((terrain_set_id, terrain_set.coverage()));
+            }
+
+            let Some((_, coverage)) = editor_state
+                .tileset_editor_state
+                .terrain_coverage
+                .as_ref()
+                .filter(|(id, _)| *id == terrain_set_id)
+            else {
+                return;
+            };
+
+            ui.label(format!(
+                "{} of {} combinations have a tile",
+                coverage.covered(),
+                coverage.checked
+            ));
+            if coverage.is_truncated() {
+                ui.small(format!(
+                    "Only the first {} of {} combinations were checked.",
+                    coverage.checked, coverage.total
+                ));
+            }
+            if coverage.missing.is_empty() {
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .id_salt("terrain_coverage_missing")
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for wang in coverage.missing.iter().take(MAX_LISTED_MISSING) {
+                        ui.small(terrain_set.describe_wang_id(wang));
+                    }
+                    if coverage.missing.len() > MAX_LISTED_MISSING {
+                        ui.small(format!(
+                            "...and {} more",
+                            coverage.missing.len() - MAX_LISTED_MISSING
+                        ));
+                    }
+                });
+
+            if ui
+                .button("Export Template PNG...")
+                .on_hover_text("Save an image with one tile per missing combination")
+                .clicked()
+            {
+                editor_state.pending_action =
+                    Some(PendingAction::ExportTerrainTemplate { terrain_set_id });
+            }
+        });
+}
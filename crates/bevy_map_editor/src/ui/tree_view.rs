@@ -248,7 +248,7 @@ pub fn render_tree_view(
                                         .default_open(false)
                                         .show(ui, |ui| {
                                             // Check if type has "name" property for rename functionality
-                                            let has_name_property = type_def.properties.iter()
+                                            let has_name_property = project.schema.all_properties(type_name).iter()
                                                 .any(|p| p.name == "name");
 
                                             // List DataStore instances first
@@ -404,13 +404,15 @@ pub fn render_tree_view(
                                             }
                                         });
 
-                                    // "+" button to create new instance
-                                    if ui.small_button("+").clicked() {
+                                    // "+" button to create new instance (abstract types have none)
+                                    if type_def.is_abstract {
+                                        ui.weak("abstract");
+                                    } else if ui.small_button("+").clicked() {
                                         result.create_data_instance = Some(type_name.to_string());
                                     }
 
                                     // Placeable indicator
-                                    if type_def.placeable {
+                                    if type_def.is_placeable() {
                                         ui.label("📍");
                                     }
                                 });
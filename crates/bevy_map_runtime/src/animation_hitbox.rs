@@ -0,0 +1,398 @@
+//! Animation hitbox plugin for spawning sensors from animation windows
+//!
+//! This module provides the `AnimationHitboxPlugin` which turns the shapes attached
+//! to an `AnimationWindow` into Avian2D sensor colliders while the window is open.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::{AnimationHitboxPlugin, AnimationHitbox};
+//!
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(MapRuntimePlugin::default())
+//!     .add_plugins(AnimationHitboxPlugin)
+//!     .add_observer(|trigger: On<CollisionStart>, hitboxes: Query<&AnimationHitbox>| {
+//!         if let Ok(hitbox) = hitboxes.get(trigger.collider1) {
+//!             info!("{:?} from window {} hit {:?}", hitbox.kind, hitbox.window_id, trigger.collider2);
+//!         }
+//!     })
+//!     .run();
+//! ```
+//!
+//! # How It Works
+//!
+//! - On a window's `Begin` phase, one sensor child is spawned per `WindowShape`
+//! - Each frame, sensors are re-shaped when the animation frame or sprite flip changes
+//! - On the window's `End` phase (or when the animation changes), the sensors are despawned
+
+use bevy::prelude::*;
+use bevy_map_animation::HitboxKind;
+use uuid::Uuid;
+
+#[cfg(feature = "physics")]
+use avian2d::prelude::*;
+
+#[cfg(feature = "physics")]
+use bevy_map_animation::{
+    update_animated_sprites, AnimatedSprite, AnimationWindowEvent, CollisionShape, SpriteData,
+    WindowPhase,
+};
+
+/// Plugin that spawns sensor colliders for animation window shapes
+///
+/// Requires the `physics` feature. Entities need an `AnimatedSprite` whose
+/// animation windows carry shapes (authored in the editor's animation editor).
+pub struct AnimationHitboxPlugin;
+
+#[cfg(feature = "physics")]
+impl Plugin for AnimationHitboxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_window_hitboxes, update_window_hitboxes)
+                .chain()
+                .after(update_animated_sprites),
+        );
+    }
+}
+
+#[cfg(not(feature = "physics"))]
+impl Plugin for AnimationHitboxPlugin {
+    fn build(&self, _app: &mut App) {
+        // No-op when physics feature is disabled
+        bevy::log::warn!(
+            "AnimationHitboxPlugin requires the 'physics' feature. Enable it with: \
+            bevy_map_runtime = {{ features = [\"physics\"] }}"
+        );
+    }
+}
+
+/// Sensor spawned for a shape of an active animation window
+///
+/// Spawned as a child of the animated entity, so it follows its transform.
+#[derive(Component, Debug, Clone)]
+pub struct AnimationHitbox {
+    /// The animated entity this sensor belongs to
+    pub owner: Entity,
+    /// Animation the window belongs to
+    pub animation: String,
+    /// The window that spawned this sensor
+    pub window_id: Uuid,
+    /// The shape within the window
+    pub shape_id: Uuid,
+    /// Whether this is a hitbox or a hurtbox
+    pub kind: HitboxKind,
+    /// Frame position and flip state the collider was last built for
+    applied: Option<(usize, bool, bool)>,
+}
+
+/// System that spawns and despawns sensors on window Begin/End
+///
+/// A window can begin and end within one batch of events. Its sensors are only
+/// queued at that point and invisible to `hitboxes`, so they are tracked in
+/// `pending` to be despawned as well.
+#[cfg(feature = "physics")]
+fn spawn_window_hitboxes(
+    mut commands: Commands,
+    mut window_events: MessageReader<AnimationWindowEvent>,
+    sprite_assets: Res<Assets<SpriteData>>,
+    owners: Query<(&AnimatedSprite, Option<&Sprite>)>,
+    hitboxes: Query<(Entity, &AnimationHitbox)>,
+    mut pending: Local<Vec<(Entity, Entity, Uuid)>>,
+) {
+    pending.clear();
+    for event in window_events.read() {
+        match event.phase {
+            WindowPhase::Begin => {
+                let Ok((animated, sprite)) = owners.get(event.entity) else {
+                    continue;
+                };
+                let Some(sprite_data) = sprite_assets.get(&animated.sprite_data) else {
+                    continue;
+                };
+                let Some(animation) = sprite_data.get_animation(&event.animation) else {
+                    continue;
+                };
+                let Some(window) = animation.get_window(event.window_id) else {
+                    continue;
+                };
+                let frame_pos = animation
+                    .frame_position_at_time(animated.elapsed_ms)
+                    .unwrap_or(0);
+                let (flip_x, flip_y) = sprite.map_or((false, false), |s| (s.flip_x, s.flip_y));
+
+                for shape in &window.shapes {
+                    let mut entity = commands.spawn((
+                        AnimationHitbox {
+                            owner: event.entity,
+                            animation: event.animation.clone(),
+                            window_id: window.id,
+                            shape_id: shape.id,
+                            kind: shape.kind,
+                            applied: Some((frame_pos, flip_x, flip_y)),
+                        },
+                        Sensor,
+                        CollisionEventsEnabled,
+                        ChildOf(event.entity),
+                    ));
+                    pending.push((entity.id(), event.entity, window.id));
+                    match shape_to_sensor(
+                        &shape.shape_at_frame(frame_pos).flipped(flip_x, flip_y),
+                        sprite_data,
+                    ) {
+                        Some((collider, transform)) => {
+                            entity.insert((collider, transform));
+                        }
+                        None => {
+                            entity.insert(Transform::default());
+                        }
+                    }
+                }
+            }
+            WindowPhase::End => {
+                for (entity, hitbox) in hitboxes.iter() {
+                    if hitbox.owner == event.entity && hitbox.window_id == event.window_id {
+                        commands.entity(entity).despawn();
+                    }
+                }
+                pending.retain(|&(entity, owner, window_id)| {
+                    let ended = owner == event.entity && window_id == event.window_id;
+                    if ended {
+                        commands.entity(entity).despawn();
+                    }
+                    !ended
+                });
+            }
+            WindowPhase::Tick => {}
+        }
+    }
+}
+
+/// System that re-shapes sensors per frame and cleans up stale ones
+#[cfg(feature = "physics")]
+fn update_window_hitboxes(
+    mut commands: Commands,
+    sprite_assets: Res<Assets<SpriteData>>,
+    owners: Query<(&AnimatedSprite, Option<&Sprite>)>,
+    mut hitboxes: Query<(Entity, &mut AnimationHitbox)>,
+) {
+    for (entity, mut hitbox) in hitboxes.iter_mut() {
+        // Owner gone or switched animation mid-window: the End phase will never fire
+        let Ok((animated, sprite)) = owners.get(hitbox.owner) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if animated.current_animation.as_deref() != Some(hitbox.animation.as_str()) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let Some(sprite_data) = sprite_assets.get(&animated.sprite_data) else {
+            continue;
+        };
+        let Some(animation) = sprite_data.get_animation(&hitbox.animation) else {
+            continue;
+        };
+        let Some(shape) = animation
+            .get_window(hitbox.window_id)
+            .and_then(|w| w.get_shape(hitbox.shape_id))
+        else {
+            continue;
+        };
+
+        let frame_pos = animation
+            .frame_position_at_time(animated.elapsed_ms)
+            .unwrap_or(0);
+        let (flip_x, flip_y) = sprite.map_or((false, false), |s| (s.flip_x, s.flip_y));
+        let state = Some((frame_pos, flip_x, flip_y));
+        if hitbox.applied == state {
+            continue;
+        }
+        hitbox.applied = state;
+
+        match shape_to_sensor(
+            &shape.shape_at_frame(frame_pos).flipped(flip_x, flip_y),
+            sprite_data,
+        ) {
+            Some((collider, transform)) => {
+                commands.entity(entity).insert((collider, transform));
+            }
+            None => {
+                commands.entity(entity).remove::<Collider>();
+            }
+        }
+    }
+}
+
+/// Convert a frame-normalized shape to a collider and its offset from the sprite center
+///
+/// Note: Editor uses Y-down (top=0), Bevy uses Y-up, so Y is flipped.
+#[cfg(feature = "physics")]
+fn shape_to_sensor(
+    shape: &CollisionShape,
+    sprite_data: &SpriteData,
+) -> Option<(Collider, Transform)> {
+    let w = sprite_data.frame_width as f32;
+    let h = sprite_data.frame_height as f32;
+    let (collider, x, y) = match shape {
+        CollisionShape::None => return None,
+        CollisionShape::Full => (Collider::rectangle(w, h), 0.0, 0.0),
+        CollisionShape::Rectangle { offset, size } => (
+            Collider::rectangle(size[0] * w, size[1] * h),
+            // Offset is the top-left corner; convert to center relative to frame center
+            (offset[0] + size[0] / 2.0 - 0.5) * w,
+            (0.5 - offset[1] - size[1] / 2.0) * h,
+        ),
+        CollisionShape::Circle { offset, radius } => (
+            Collider::circle(*radius * w.min(h)),
+            (offset[0] - 0.5) * w,
+            (0.5 - offset[1]) * h,
+        ),
+        CollisionShape::Polygon { points } => {
+            if points.len() < 3 {
+                return None;
+            }
+            let scaled: Vec<Vec2> = points
+                .iter()
+                .map(|p| Vec2::new((p[0] - 0.5) * w, (0.5 - p[1]) * h))
+                .collect();
+            (Collider::convex_hull(scaled)?, 0.0, 0.0)
+        }
+    };
+    Some((collider, Transform::from_xyz(x, y, 0.0)))
+}
+
+#[cfg(all(test, feature = "physics"))]
+mod tests {
+    use super::*;
+    use bevy_map_animation::{
+        AnimationDef, AnimationWindow, LoopMode, TriggerPayload, WindowShape,
+    };
+
+    fn sprite_data() -> SpriteData {
+        SpriteData::new("hero.png", 32, 16)
+    }
+
+    fn sensor(shape: CollisionShape) -> Option<(Collider, Transform)> {
+        shape_to_sensor(&shape, &sprite_data())
+    }
+
+    #[test]
+    fn test_rectangle_sensor() {
+        // Top-left quarter of the frame: centered up and to the left (Y up)
+        let (collider, transform) =
+            sensor(CollisionShape::rectangle([0.0, 0.0], [0.5, 0.5])).unwrap();
+        let cuboid = collider.shape().as_cuboid().unwrap();
+        assert_eq!((cuboid.half_extents.x, cuboid.half_extents.y), (8.0, 4.0));
+        assert_eq!(transform.translation, Vec3::new(-8.0, 4.0, 0.0));
+
+        let (collider, transform) = sensor(CollisionShape::Full).unwrap();
+        let cuboid = collider.shape().as_cuboid().unwrap();
+        assert_eq!((cuboid.half_extents.x, cuboid.half_extents.y), (16.0, 8.0));
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_circle_sensor() {
+        // Radius scales with the smaller frame side
+        let (collider, transform) = sensor(CollisionShape::Circle {
+            offset: [0.5, 0.75],
+            radius: 0.25,
+        })
+        .unwrap();
+        assert_eq!(collider.shape().as_ball().unwrap().radius, 4.0);
+        assert_eq!(transform.translation, Vec3::new(0.0, -4.0, 0.0));
+    }
+
+    #[test]
+    fn test_polygon_sensor() {
+        assert!(sensor(CollisionShape::Polygon {
+            points: vec![[0.0, 0.0], [1.0, 1.0]],
+        })
+        .is_none());
+        assert!(sensor(CollisionShape::None).is_none());
+
+        let (collider, transform) = sensor(CollisionShape::Polygon {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [0.5, 1.0]],
+        })
+        .unwrap();
+        let aabb = collider.aabb(Vec2::ZERO, Rotation::IDENTITY);
+        assert_eq!(
+            (aabb.min, aabb.max),
+            (Vec2::new(-16.0, -8.0), Vec2::new(16.0, 8.0))
+        );
+        assert_eq!(transform.translation, Vec3::ZERO);
+    }
+
+    /// App with an owner playing "attack", whose window has two shapes
+    fn setup() -> (App, Entity, Uuid) {
+        let mut window = AnimationWindow::new("swing", 0, 200);
+        window.shapes = vec![
+            WindowShape::new("blade", HitboxKind::Hitbox, CollisionShape::Full),
+            WindowShape::new("body", HitboxKind::Hurtbox, CollisionShape::None),
+        ];
+        let window_id = window.id;
+        let mut animation = AnimationDef::new(vec![0, 1], 100, LoopMode::Loop);
+        animation.add_window(window);
+        let mut data = sprite_data();
+        data.add_animation("attack", animation);
+
+        let mut app = App::new();
+        app.init_resource::<Assets<SpriteData>>()
+            .add_message::<AnimationWindowEvent>()
+            .add_systems(Update, spawn_window_hitboxes);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<SpriteData>>()
+            .add(data);
+        let mut animated = AnimatedSprite::new(handle);
+        animated.play("attack");
+        let owner = app.world_mut().spawn(animated).id();
+        (app, owner, window_id)
+    }
+
+    fn window_event(owner: Entity, window_id: Uuid, phase: WindowPhase) -> AnimationWindowEvent {
+        AnimationWindowEvent {
+            entity: owner,
+            animation: "attack".to_string(),
+            window_id,
+            window_name: "swing".to_string(),
+            phase,
+            payload: TriggerPayload::None,
+            progress: 0.0,
+        }
+    }
+
+    fn hitbox_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&AnimationHitbox>()
+            .iter(app.world())
+            .count()
+    }
+
+    #[test]
+    fn test_window_begin_and_end() {
+        let (mut app, owner, window_id) = setup();
+        app.world_mut()
+            .write_message(window_event(owner, window_id, WindowPhase::Begin));
+        app.update();
+        assert_eq!(hitbox_count(&mut app), 2);
+
+        app.world_mut()
+            .write_message(window_event(owner, window_id, WindowPhase::End));
+        app.update();
+        assert_eq!(hitbox_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_window_begin_and_end_in_one_batch() {
+        let (mut app, owner, window_id) = setup();
+        app.world_mut()
+            .write_message(window_event(owner, window_id, WindowPhase::Begin));
+        app.world_mut()
+            .write_message(window_event(owner, window_id, WindowPhase::End));
+        app.update();
+        assert_eq!(hitbox_count(&mut app), 0);
+    }
+}
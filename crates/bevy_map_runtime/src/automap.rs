@@ -0,0 +1,263 @@
+//! Re-running automapping rules at runtime
+//!
+//! The editor saves rule output into the output layers, so maps load with it
+//! already applied. Games that change tiles (digging, building) can re-run the
+//! project's rule sets on a spawned map by writing [`ApplyAutomapRulesEvent`];
+//! only the output layers that change are respawned.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use bevy_map_runtime::{ApplyAutomapRulesEvent, MapHandle, MapProject};
+//!
+//! fn dig(
+//!     mut maps: ResMut<Assets<MapProject>>,
+//!     map: Single<(Entity, &MapHandle)>,
+//!     mut automap: MessageWriter<ApplyAutomapRulesEvent>,
+//! ) {
+//!     let (entity, handle) = *map;
+//!     if let Some(project) = maps.get_mut_untracked(&handle.0) {
+//!         project.level.set_tile(0, 4, 2, None);
+//!         automap.write(ApplyAutomapRulesEvent { map: entity });
+//!     }
+//! }
+//! ```
+//!
+//! `get_mut_untracked` keeps the change from hot-reloading (and respawning)
+//! the whole map. Tile layers that aren't automapping outputs still need their
+//! tiles updated, e.g. with [`set_tile`](crate::set_tile).
+
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::*;
+use bevy_map_autotile::{AutomapError, AutomapRuleSet, AutotileConfig};
+use bevy_map_core::{Level, MapProject};
+use serde::Deserialize;
+
+use crate::{spawn_tile_layer, MapHandle, MapLayerIndex, MapRoot, MapRuntimeSettings};
+
+/// Event to re-run the automapping rules of a spawned map
+#[derive(Message, Debug, Clone)]
+pub struct ApplyAutomapRulesEvent {
+    /// Entity with the map's [`MapHandle`], or the spawned [`MapRoot`]
+    pub map: Entity,
+}
+
+/// Automapping rule sets saved with a map project
+///
+/// Empty if the project has no autotile configuration or it doesn't parse.
+pub fn automap_rule_sets(project: &MapProject) -> Vec<AutomapRuleSet> {
+    project
+        .autotile_config
+        .as_ref()
+        .and_then(|config| AutotileConfig::deserialize(config).ok())
+        .map(|config| config.rule_sets)
+        .unwrap_or_default()
+}
+
+/// Apply rule sets to a level in order, returning the indices of the layers
+/// they changed
+///
+/// Rule sets whose output layer isn't in the level are skipped, like rule
+/// sets written for another level of the project.
+pub fn apply_automap_rules(
+    level: &mut Level,
+    rule_sets: &[AutomapRuleSet],
+) -> Result<Vec<usize>, AutomapError> {
+    let mut changed_layers = Vec::new();
+    for rule_set in rule_sets {
+        let changed = match rule_set.apply(level) {
+            Ok(changed) => changed,
+            Err(AutomapError::MissingLayer(name)) if name == rule_set.output_layer => continue,
+            Err(err) => return Err(err),
+        };
+        let Some(layer_index) = level
+            .layers
+            .iter()
+            .position(|layer| layer.name == rule_set.output_layer)
+        else {
+            continue;
+        };
+        if changed > 0 && !changed_layers.contains(&layer_index) {
+            changed_layers.push(layer_index);
+        }
+    }
+    Ok(changed_layers)
+}
+
+/// System that re-runs the rules for each [`ApplyAutomapRulesEvent`] and
+/// respawns the changed layers
+pub(crate) fn handle_apply_automap_rules(
+    mut commands: Commands,
+    mut events: MessageReader<ApplyAutomapRulesEvent>,
+    mut maps: ResMut<Assets<MapProject>>,
+    settings: Res<MapRuntimeSettings>,
+    map_handles: Query<&Children, With<MapHandle>>,
+    roots: Query<(&MapRoot, &Children)>,
+    layers: Query<&MapLayerIndex>,
+) {
+    for event in events.read() {
+        // Accept the MapHandle entity as well as the MapRoot child it spawns
+        let root = if roots.contains(event.map) {
+            Some(event.map)
+        } else {
+            map_handles
+                .get(event.map)
+                .ok()
+                .and_then(|children| children.iter().find(|child| roots.contains(*child)))
+        };
+        let Some((root, Ok((map_root, children)))) = root.map(|root| (root, roots.get(root)))
+        else {
+            warn!(
+                "ApplyAutomapRulesEvent: {:?} is not a spawned map",
+                event.map
+            );
+            continue;
+        };
+        // Untracked, so the change doesn't trigger a full hot-reload respawn
+        let Some(project) = maps.get_mut_untracked(&map_root.handle) else {
+            continue;
+        };
+
+        let rule_sets = automap_rule_sets(project);
+        let changed_layers = match apply_automap_rules(&mut project.level, &rule_sets) {
+            Ok(changed_layers) => changed_layers,
+            Err(err) => {
+                warn!("Automapping failed: {}", err);
+                continue;
+            }
+        };
+        if changed_layers.is_empty() || !settings.enable_render {
+            continue;
+        }
+
+        for child in children.iter() {
+            if layers
+                .get(child)
+                .is_ok_and(|layer| changed_layers.contains(&layer.0))
+            {
+                commands.entity(child).despawn();
+            }
+        }
+        for &layer_index in &changed_layers {
+            spawn_tile_layer(
+                &mut commands,
+                project,
+                &map_root.textures,
+                root,
+                layer_index,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_map_autotile::{AutomapRule, CellMatch, RuleInput, RuleOutput};
+    use bevy_map_core::Layer;
+    use uuid::Uuid;
+
+    /// Level with a "Ground" layer and an empty "Decor" layer
+    fn level() -> Level {
+        let mut level = Level::new("Test".to_string(), 3, 1);
+        for name in ["Ground", "Decor"] {
+            level.add_layer(Layer::new_tile_layer(name.to_string(), Uuid::nil(), 3, 1));
+        }
+        level
+    }
+
+    /// Rule set marking ground tiles on "Decor"
+    fn rule_set(output_layer: &str) -> AutomapRuleSet {
+        let mut input = RuleInput::new("Ground", 1);
+        input.set_cell(0, 0, CellMatch::NotEmpty);
+        let mut rule = AutomapRule::new("Mark");
+        rule.inputs.push(input);
+        rule.outputs.push(RuleOutput {
+            tile: Some(7),
+            weight: 1.0,
+        });
+        let mut rule_set = AutomapRuleSet::new("Marks", output_layer);
+        rule_set.rules.push(rule);
+        rule_set
+    }
+
+    #[test]
+    fn test_rule_sets_from_project() {
+        let mut project = MapProject::new(level(), Vec::new());
+        assert!(automap_rule_sets(&project).is_empty());
+
+        let mut config = AutotileConfig::new();
+        config.rule_sets.push(rule_set("Decor"));
+        project.autotile_config = Some(serde_json::to_value(&config).unwrap());
+        let rule_sets = automap_rule_sets(&project);
+        assert_eq!(rule_sets, config.rule_sets);
+    }
+
+    #[test]
+    fn test_apply_reports_changed_layers() {
+        let mut level = level();
+        let rule_sets = [rule_set("Decor"), rule_set("Other level")];
+        assert_eq!(apply_automap_rules(&mut level, &rule_sets), Ok(Vec::new()));
+
+        level.set_tile(0, 1, 0, Some(1));
+        assert_eq!(apply_automap_rules(&mut level, &rule_sets), Ok(vec![1]));
+        assert_eq!(level.get_tile(1, 1, 0), Some(7));
+        assert_eq!(apply_automap_rules(&mut level, &rule_sets), Ok(Vec::new()));
+
+        // Missing input layers are still errors
+        let mut broken = rule_set("Decor");
+        broken.rules[0].inputs[0].layer = "Missing".to_string();
+        assert_eq!(
+            apply_automap_rules(&mut level, &[broken]),
+            Err(AutomapError::MissingLayer("Missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_event_respawns_changed_layers() {
+        let mut project = MapProject::new(level(), Vec::new());
+        let mut config = AutotileConfig::new();
+        config.rule_sets.push(rule_set("Decor"));
+        project.autotile_config = Some(serde_json::to_value(&config).unwrap());
+        project.level.set_tile(0, 0, 0, Some(1));
+
+        let mut app = App::new();
+        app.init_resource::<Assets<MapProject>>()
+            .insert_resource(MapRuntimeSettings {
+                enable_render: true,
+            })
+            .add_message::<ApplyAutomapRulesEvent>()
+            .add_systems(Update, handle_apply_automap_rules);
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<MapProject>>()
+            .add(project);
+        let ground = app.world_mut().spawn(MapLayerIndex(0)).id();
+        let decor = app.world_mut().spawn(MapLayerIndex(1)).id();
+        let root = app
+            .world_mut()
+            .spawn(MapRoot {
+                handle: handle.clone(),
+                textures: Default::default(),
+            })
+            .add_children(&[ground, decor])
+            .id();
+        let map = app
+            .world_mut()
+            .spawn(MapHandle(handle.clone()))
+            .add_child(root)
+            .id();
+
+        app.world_mut()
+            .write_message(ApplyAutomapRulesEvent { map });
+        app.update();
+        let project = app
+            .world()
+            .resource::<Assets<MapProject>>()
+            .get(&handle)
+            .unwrap();
+        assert_eq!(project.level.get_tile(1, 0, 0), Some(7));
+        assert!(app.world().get_entity(ground).is_ok());
+        assert!(app.world().get_entity(decor).is_err());
+    }
+}
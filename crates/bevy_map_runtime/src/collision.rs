@@ -122,8 +122,9 @@ pub fn spawn_tile_colliders(
                 for y in 0..level.height {
                     for x in 0..level.width {
                         let idx = (y * level.width + x) as usize;
-                        if let Some(&Some(tile_index)) = tiles.get(idx) {
-                            // Check if this tile has collision
+                        if let Some(&Some(tile)) = tiles.get(idx) {
+                            // Check if this tile has collision (flip bits aren't part of the index)
+                            let tile_index = bevy_map_core::tile_index(tile);
                             for physics_layer in tileset.physics_layers.layers.iter() {
                                 if let Some(collision) = physics_layer.get_tile_physics(tile_index)
                                 {
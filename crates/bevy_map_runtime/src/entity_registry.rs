@@ -1,10 +1,11 @@
 //! Entity registry for automatic entity spawning from map data
 //!
 //! This module provides a registry-based system for spawning game entities
-//! from EntityInstance data in map files.
+//! from EntityInstance data in map files, and for capturing their current
+//! state back into a `Level` (save games, runtime level authoring).
 
 use bevy::prelude::*;
-use bevy_map_core::{ComponentOverrides, EntityInstance, Value};
+use bevy_map_core::{ComponentOverrides, EntityInstance, Level, Value};
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use uuid::Uuid;
@@ -36,6 +37,22 @@ pub trait MapEntityType: Component + Sized + Send + Sync + 'static {
     /// Creates an instance of this component from map entity data
     fn from_instance(instance: &EntityInstance) -> Self;
 
+    /// Writes this component's properties into map entity data
+    ///
+    /// The reverse of `from_instance`. Properties not owned by this component
+    /// are left untouched.
+    fn write_properties(&self, _instance: &mut EntityInstance) {
+        // Default: no-op
+    }
+
+    /// Creates a new EntityInstance of this type at the origin holding this
+    /// component's properties
+    fn to_instance(&self) -> EntityInstance {
+        let mut instance = EntityInstance::new(Self::type_name().to_string(), [0.0, 0.0]);
+        self.write_properties(&mut instance);
+        instance
+    }
+
     /// Returns the property names for sprite fields (for manual sprite handle injection)
     /// Override this if your entity has fields that should receive sprite handles.
     fn sprite_properties() -> &'static [&'static str] {
@@ -190,6 +207,7 @@ pub struct Dialogue {
 /// Trait object for spawning entities
 trait EntitySpawner: Send + Sync {
     fn spawn(&self, commands: &mut Commands, instance: &EntityInstance, transform: Transform);
+    fn write_properties(&self, world: &World, entity: Entity, instance: &mut EntityInstance);
 }
 
 /// Generic spawner implementation for any MapEntityType
@@ -231,6 +249,12 @@ impl<T: MapEntityType> EntitySpawner for TypedSpawner<T> {
             },
         ));
     }
+
+    fn write_properties(&self, world: &World, entity: Entity, instance: &mut EntityInstance) {
+        if let Some(component) = world.get::<T>(entity) {
+            component.write_properties(instance);
+        }
+    }
 }
 
 /// Parse a hex color string like "#ff0000" or "#ff000080" (with alpha)
@@ -365,6 +389,74 @@ impl EntityRegistry {
     }
 }
 
+/// Capture the current state of all map entities back into a level
+///
+/// Every entity with a `MapEntityMarker` becomes an EntityInstance:
+/// - The position is its `Transform` translation with `base_transform` (the
+///   transform the level was spawned with) undone
+/// - Properties start from its `EntityProperties`, then the registered
+///   `MapEntityType` component (if any) writes its current field values over them
+///
+/// Instances already in the level are updated in place, keeping their order and
+/// any data the runtime doesn't track (template links, editor metadata).
+/// Instances whose entity no longer exists are removed, and marker entities
+/// spawned at runtime are appended.
+///
+/// Map entities are not tied to the map they were spawned from, so with more
+/// than one map spawned every map's entities end up in `level`.
+///
+/// Returns the number of captured entities.
+pub fn capture_map_entities(
+    world: &mut World,
+    level: &mut Level,
+    base_transform: Transform,
+) -> usize {
+    let inverse = base_transform.compute_affine().inverse();
+
+    let mut query = world.query::<(
+        Entity,
+        &MapEntityMarker,
+        &Transform,
+        Option<&EntityProperties>,
+    )>();
+    let registry = world.get_resource::<EntityRegistry>();
+    let mut captured: Vec<EntityInstance> = Vec::new();
+    for (entity, marker, transform, properties) in query.iter(world) {
+        let mut instance = level
+            .get_entity(marker.instance_id)
+            .cloned()
+            .unwrap_or_else(|| EntityInstance::new(marker.type_name.clone(), [0.0, 0.0]));
+        instance.id = marker.instance_id;
+        instance.type_name = marker.type_name.clone();
+
+        let position = inverse.transform_point3(transform.translation);
+        instance.position = [position.x, position.y];
+
+        if let Some(properties) = properties {
+            instance.properties = properties.properties.clone();
+            instance.component_overrides = properties.component_overrides.clone();
+        }
+        if let Some(spawner) = registry.and_then(|r| r.spawners.get(&marker.type_name)) {
+            spawner.write_properties(world, entity, &mut instance);
+        }
+
+        captured.push(instance);
+    }
+
+    // Keep the level's order; new entities go last
+    let order: HashMap<Uuid, usize> = level
+        .entities
+        .iter()
+        .enumerate()
+        .map(|(index, instance)| (instance.id, index))
+        .collect();
+    captured.sort_by_key(|instance| order.get(&instance.id).copied().unwrap_or(usize::MAX));
+
+    let count = captured.len();
+    level.entities = captured;
+    count
+}
+
 /// Extension trait for registering map entities with the Bevy App
 pub trait MapEntityExt {
     /// Register a map entity type for automatic spawning
@@ -470,6 +562,11 @@ mod tests {
                 health: instance.get_int("health").unwrap_or(100) as i32,
             }
         }
+
+        fn write_properties(&self, instance: &mut EntityInstance) {
+            instance.set_string("name", self.name.clone());
+            instance.set_int("health", self.health as i64);
+        }
     }
 
     #[test]
@@ -484,4 +581,76 @@ mod tests {
         assert!(registry.is_registered("TestEntity"));
         assert!(!registry.is_registered("OtherEntity"));
     }
+
+    #[test]
+    fn test_to_instance() {
+        let entity = TestEntity {
+            name: "Guard".to_string(),
+            health: 42,
+        };
+        let instance = entity.to_instance();
+        assert_eq!(instance.type_name, "TestEntity");
+        assert_eq!(instance.get_string("name"), Some("Guard"));
+        assert_eq!(instance.get_int("health"), Some(42));
+    }
+
+    #[test]
+    fn test_capture_map_entities() {
+        let mut level = Level::new("Test".to_string(), 10, 10);
+        let mut kept = EntityInstance::new("TestEntity".to_string(), [10.0, 20.0]);
+        kept.set_string("name", "Guard".to_string());
+        kept.set_string("note", "editor only".to_string());
+        kept.template_id = Some(Uuid::new_v4());
+        let despawned = EntityInstance::new("TestEntity".to_string(), [0.0, 0.0]);
+        level.add_entity(kept.clone());
+        level.add_entity(despawned);
+
+        let mut registry = EntityRegistry::new();
+        registry.register::<TestEntity>();
+        let mut world = World::new();
+        world.insert_resource(registry);
+
+        let base_transform = Transform::from_xyz(100.0, 50.0, 0.0);
+        world.spawn((
+            TestEntity {
+                name: "Guard".to_string(),
+                health: 5,
+            },
+            base_transform * Transform::from_xyz(30.0, 40.0, 0.0),
+            MapEntityMarker {
+                instance_id: kept.id,
+                type_name: kept.type_name.clone(),
+            },
+            EntityProperties {
+                properties: kept.properties.clone(),
+                component_overrides: kept.component_overrides.clone(),
+            },
+        ));
+        let new_id = Uuid::new_v4();
+        world.spawn((
+            Transform::from_xyz(100.0, 50.0, 0.0),
+            MapEntityMarker {
+                instance_id: new_id,
+                type_name: "Unregistered".to_string(),
+            },
+        ));
+
+        assert_eq!(
+            capture_map_entities(&mut world, &mut level, base_transform),
+            2
+        );
+        assert_eq!(level.entities.len(), 2);
+
+        let captured = &level.entities[0];
+        assert_eq!(captured.id, kept.id);
+        assert_eq!(captured.position, [30.0, 40.0]);
+        assert_eq!(captured.get_int("health"), Some(5));
+        assert_eq!(captured.get_string("note"), Some("editor only"));
+        assert_eq!(captured.template_id, kept.template_id);
+
+        let added = &level.entities[1];
+        assert_eq!(added.id, new_id);
+        assert_eq!(added.type_name, "Unregistered");
+        assert_eq!(added.position, [0.0, 0.0]);
+    }
 }
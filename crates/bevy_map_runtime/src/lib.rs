@@ -68,7 +68,7 @@ use bevy::asset::AssetEvent;
 use bevy::ecs::message::{Message, MessageReader, MessageWriter};
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
-use bevy_map_core::MapProject;
+use bevy_map_core::{tile_flip_diagonal, tile_flip_x, tile_flip_y, tile_index, MapProject};
 use std::collections::HashMap;
 use std::path::Path;
 use uuid::Uuid;
@@ -126,6 +126,8 @@ pub use bevy_map_autotile;
 pub use bevy_map_core;
 pub use bevy_map_dialogue;
 
+pub mod animation_hitbox;
+pub mod automap;
 pub mod camera;
 pub mod collision;
 pub mod entity_input;
@@ -136,6 +138,8 @@ pub mod loader;
 pub mod render;
 
 // Re-export commonly used types
+pub use animation_hitbox::{AnimationHitbox, AnimationHitboxPlugin};
+pub use automap::{apply_automap_rules, automap_rule_sets, ApplyAutomapRulesEvent};
 pub use camera::{clamp_camera_to_bounds, setup_camera_bounds_from_map, CameraBounds};
 pub use collision::{MapCollider, MapCollisionPlugin};
 pub use entity_input::{
@@ -144,8 +148,8 @@ pub use entity_input::{
 };
 pub use entity_physics::{EntityPhysicsSpawned, MapEntityPhysicsPlugin};
 pub use entity_registry::{
-    attach_dialogues, Dialogue, EntityProperties, EntityRegistry, MapEntityExt, MapEntityMarker,
-    MapEntityType,
+    attach_dialogues, capture_map_entities, Dialogue, EntityProperties, EntityRegistry,
+    MapEntityExt, MapEntityMarker, MapEntityType,
 };
 pub use entity_sprite::{EntitySpriteSetup, EntitySpriteSpawned, MapEntitySpritePlugin};
 pub use loader::{MapLoadError, MapProjectLoader};
@@ -153,8 +157,10 @@ pub use render::{complete_sprite_loads, spawn_sprite_components, SpriteSlot};
 
 // Re-export key dialogue types for convenience
 pub use bevy_map_dialogue::{
-    DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle, DialogueNode,
-    DialogueNodeType, DialogueRunner, DialogueTree, StartDialogueEvent,
+    CurrentLocale, DialogueChoice, DialogueChoiceEvent, DialogueEndEvent, DialogueHandle,
+    DialogueNode, DialogueNodeType, DialogueRunner, DialogueSignalEvent, DialogueSignalExt,
+    DialogueSignalType, DialogueTree, DialogueUiPlugin, DialogueUiStyle, DialogueValue,
+    DialogueVariables, Localizer, StartDialogueEvent, StringTable,
 };
 
 // Re-export key animation types for convenience
@@ -162,8 +168,8 @@ pub use bevy_map_animation::{
     AnimatedSprite, AnimationCustomEvent, AnimationDef, AnimationEventExt, AnimationParticleEvent,
     AnimationSoundEvent, AnimationTrigger, AnimationTriggerEvent, AnimationTriggerRegistry,
     AnimationTriggerType, AnimationTriggered, AnimationWindow, AnimationWindowChanged,
-    AnimationWindowEvent, AnimationWindowRegistry, AnimationWindowType, LoopMode,
-    SpriteAnimationPlugin, SpriteData, TriggerPayload, WindowPhase, WindowTracker,
+    AnimationWindowEvent, AnimationWindowRegistry, AnimationWindowType, HitboxKind, LoopMode,
+    SpriteAnimationPlugin, SpriteData, TriggerPayload, WindowPhase, WindowShape, WindowTracker,
 };
 
 /// Plugin for runtime map loading and optional rendering
@@ -173,6 +179,7 @@ pub use bevy_map_animation::{
 /// - Automatic map spawning when `MapHandle` components are added
 /// - Hot-reload support when using Bevy's `file_watcher` feature
 /// - Manual spawning via `SpawnMapEvent` and `SpawnMapProjectEvent`
+/// - Re-running automapping rules on spawned maps via `ApplyAutomapRulesEvent`
 ///
 /// By default, rendering-related systems (tilemaps, sprites, camera bounds)
 /// are enabled. To disable render-dependent features while keeping map loading
@@ -248,6 +255,7 @@ impl Plugin for MapRuntimePlugin {
             .add_message::<SpawnMapEvent>()
             .add_message::<SpawnMapProjectEvent>()
             .add_message::<MapSpawnedEvent>()
+            .add_message::<ApplyAutomapRulesEvent>()
             // Systems
             .add_systems(Update, handle_spawn_map_events)
             .add_systems(Update, handle_spawn_map_project_events)
@@ -260,6 +268,7 @@ impl Plugin for MapRuntimePlugin {
                 )
                     .chain(),
             )
+            .add_systems(Update, automap::handle_apply_automap_rules)
             // Dialogue attachment system
             .add_systems(Update, attach_dialogues)
             // Dialogue tree auto-loading systems (opt-in)
@@ -1136,13 +1145,14 @@ pub fn spawn_map_with_options(
                 for y in 0..level.height {
                     for x in 0..level.width {
                         let idx = (y * level.width + x) as usize;
-                        if let Some(&Some(tile_index)) = tiles.get(idx) {
+                        if let Some(&Some(tile)) = tiles.get(idx) {
                             let tile_pos = TilePos { x, y };
                             let tile_entity = commands
                                 .spawn(TileBundle {
                                     position: tile_pos,
                                     tilemap_id: TilemapId(tilemap_entity),
-                                    texture_index: TileTextureIndex(tile_index),
+                                    texture_index: TileTextureIndex(tile_index(tile)),
+                                    flip: tile_flip(tile),
                                     ..default()
                                 })
                                 .id();
@@ -1183,14 +1193,25 @@ pub fn spawn_map_with_options(
     map_entity
 }
 
+/// Get the [`TileFlip`] encoded in a layer tile value's `TILE_FLIP_*` bits
+pub fn tile_flip(tile: u32) -> TileFlip {
+    TileFlip {
+        x: tile_flip_x(tile),
+        y: tile_flip_y(tile),
+        d: tile_flip_diagonal(tile),
+    }
+}
+
 /// Update a tile at runtime
+///
+/// `tile` is a layer tile value: any `TILE_FLIP_*` bits become the tile's [`TileFlip`].
 pub fn set_tile(
     commands: &mut Commands,
     tile_storage: &mut TileStorage,
     tilemap_entity: Entity,
     x: u32,
     y: u32,
-    tile_index: Option<u32>,
+    tile: Option<u32>,
 ) {
     let tile_pos = TilePos { x, y };
 
@@ -1201,12 +1222,13 @@ pub fn set_tile(
     }
 
     // Spawn new tile if index provided
-    if let Some(index) = tile_index {
+    if let Some(tile) = tile {
         let tile_entity = commands
             .spawn(TileBundle {
                 position: tile_pos,
                 tilemap_id: TilemapId(tilemap_entity),
-                texture_index: TileTextureIndex(index),
+                texture_index: TileTextureIndex(tile_index(tile)),
+                flip: tile_flip(tile),
                 ..default()
             })
             .id();
@@ -1310,7 +1332,6 @@ pub fn spawn_map_project_with_options(
     entity_registry: Option<&EntityRegistry>,
 ) -> Entity {
     let level = &project.level;
-    let tile_size = textures.tile_size;
 
     let map_entity = commands
         .spawn((
@@ -1324,140 +1345,219 @@ pub fn spawn_map_project_with_options(
 
     if options.enable_render {
         // Spawn each tile layer
-        for (layer_index, layer) in level.layers.iter().enumerate() {
-            info!("Processing layer {}: '{}'", layer_index, layer.name);
-
-            if let bevy_map_core::LayerData::Tiles {
-                tileset_id, tiles, ..
-            } = &layer.data
-            {
-                info!(
-                    "  Layer {} is a tile layer with {} tiles, tileset {}",
-                    layer_index,
-                    tiles.len(),
-                    tileset_id
-                );
+        for layer_index in 0..level.layers.len() {
+            spawn_tile_layer(commands, project, textures, map_entity, layer_index);
+        }
+    }
 
-                if tiles.is_empty() {
-                    info!("  Layer {} has empty tiles array, skipping", layer_index);
-                    continue;
-                }
+    // Spawn entities if registry is provided
+    if let Some(registry) = entity_registry {
+        registry.spawn_all(commands, &level.entities, transform);
+    }
 
-                // Get tileset from project
-                let Some(tileset) = project.get_tileset(*tileset_id) else {
-                    warn!(
-                        "Layer {} references missing tileset {}",
-                        layer_index, tileset_id
-                    );
-                    continue;
-                };
+    map_entity
+}
 
-                info!(
-                    "  Found tileset '{}' with {} images",
-                    tileset.name,
-                    tileset.images.len()
-                );
+/// Spawn the tilemaps of a tile layer as children of `map_entity`
+///
+/// Layers using several tileset images get one tilemap per image, each tagged
+/// with the layer's [`MapLayerIndex`]. Non-tile layers spawn nothing.
+pub fn spawn_tile_layer(
+    commands: &mut Commands,
+    project: &bevy_map_core::MapProject,
+    textures: &TilesetTextures,
+    map_entity: Entity,
+    layer_index: usize,
+) {
+    let level = &project.level;
+    let tile_size = textures.tile_size;
+    let Some(layer) = level.layers.get(layer_index) else {
+        return;
+    };
 
-                // For multi-image tilesets, we need to create separate tilemaps per image
-                // because bevy_ecs_tilemap uses a single texture per tilemap.
-                // Group tiles by which image they belong to.
-                let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32)>> = HashMap::new();
+    info!("Processing layer {}: '{}'", layer_index, layer.name);
 
-                for y in 0..level.height {
-                    for x in 0..level.width {
-                        let idx = (y * level.width + x) as usize;
-                        if let Some(&Some(virtual_tile_index)) = tiles.get(idx) {
-                            if let Some((image_index, local_tile_index)) =
-                                tileset.virtual_to_local(virtual_tile_index)
-                            {
-                                tiles_by_image.entry(image_index).or_default().push((
-                                    x,
-                                    y,
-                                    local_tile_index,
-                                ));
-                            }
-                        }
-                    }
-                }
+    if let bevy_map_core::LayerData::Tiles {
+        tileset_id, tiles, ..
+    } = &layer.data
+    {
+        info!(
+            "  Layer {} is a tile layer with {} tiles, tileset {}",
+            layer_index,
+            tiles.len(),
+            tileset_id
+        );
 
-                // Spawn a tilemap for each image used in this layer
-                for (image_index, image_tiles) in tiles_by_image {
-                    info!(
-                        "Layer {}: Spawning {} tiles from tileset {} image {}",
-                        layer_index,
-                        image_tiles.len(),
-                        tileset_id,
-                        image_index
-                    );
-                    let Some(texture_handle) = textures.get(*tileset_id, image_index) else {
-                        warn!(
-                            "Missing texture for tileset {} image {}",
-                            tileset_id, image_index
-                        );
-                        continue;
-                    };
-
-                    let map_size = TilemapSize {
-                        x: level.width,
-                        y: level.height,
-                    };
-
-                    let tilemap_tile_size = TilemapTileSize {
-                        x: tile_size,
-                        y: tile_size,
-                    };
-
-                    let grid_size: TilemapGridSize = tilemap_tile_size.into();
-                    let mut tile_storage = TileStorage::empty(map_size);
-                    let tilemap_entity = commands.spawn_empty().id();
-
-                    // Spawn tiles for this image
-                    for (x, y, local_tile_index) in image_tiles {
-                        let tile_pos = TilePos { x, y };
-                        let tile_entity = commands
-                            .spawn(TileBundle {
-                                position: tile_pos,
-                                tilemap_id: TilemapId(tilemap_entity),
-                                texture_index: TileTextureIndex(local_tile_index),
-                                ..default()
-                            })
-                            .id();
-                        tile_storage.set(&tile_pos, tile_entity);
-                    }
+        if tiles.is_empty() {
+            info!("  Layer {} has empty tiles array, skipping", layer_index);
+            return;
+        }
+
+        // Get tileset from project
+        let Some(tileset) = project.get_tileset(*tileset_id) else {
+            warn!(
+                "Layer {} references missing tileset {}",
+                layer_index, tileset_id
+            );
+            return;
+        };
 
-                    // Z-offset: layer_index * 0.1 + image_index * 0.01
-                    // This ensures proper ordering: all images in layer 0 render before layer 1
-                    let layer_z = layer_index as f32 * level.z_height + image_index as f32 * 0.01;
-
-                    commands.entity(tilemap_entity).insert((
-                        TilemapBundle {
-                            grid_size,
-                            map_type: TilemapType::Square,
-                            size: map_size,
-                            storage: tile_storage,
-                            texture: TilemapTexture::Single(texture_handle.clone()),
-                            tile_size: tilemap_tile_size,
-                            transform: Transform::from_xyz(0.0, 0.0, layer_z),
-                            ..default()
-                        },
-                        MapLayerIndex(layer_index),
-                    ));
-
-                    commands.entity(map_entity).add_child(tilemap_entity);
+        info!(
+            "  Found tileset '{}' with {} images",
+            tileset.name,
+            tileset.images.len()
+        );
+
+        // For multi-image tilesets, we need to create separate tilemaps per image
+        // because bevy_ecs_tilemap uses a single texture per tilemap.
+        // Group tiles by which image they belong to.
+        let mut tiles_by_image: HashMap<usize, Vec<(u32, u32, u32, TileFlip)>> = HashMap::new();
+
+        for y in 0..level.height {
+            for x in 0..level.width {
+                let idx = (y * level.width + x) as usize;
+                if let Some(&Some(tile)) = tiles.get(idx) {
+                    if let Some((image_index, local_tile_index)) =
+                        tileset.virtual_to_local(tile_index(tile))
+                    {
+                        tiles_by_image.entry(image_index).or_default().push((
+                            x,
+                            y,
+                            local_tile_index,
+                            tile_flip(tile),
+                        ));
+                    }
                 }
-            } else {
-                info!(
-                    "  Layer {} is not a tile layer (entity layer or other)",
-                    layer_index
+            }
+        }
+
+        // Spawn a tilemap for each image used in this layer
+        for (image_index, image_tiles) in tiles_by_image {
+            info!(
+                "Layer {}: Spawning {} tiles from tileset {} image {}",
+                layer_index,
+                image_tiles.len(),
+                tileset_id,
+                image_index
+            );
+            let Some(texture_handle) = textures.get(*tileset_id, image_index) else {
+                warn!(
+                    "Missing texture for tileset {} image {}",
+                    tileset_id, image_index
                 );
+                continue;
+            };
+
+            let map_size = TilemapSize {
+                x: level.width,
+                y: level.height,
+            };
+
+            let tilemap_tile_size = TilemapTileSize {
+                x: tile_size,
+                y: tile_size,
+            };
+
+            let grid_size: TilemapGridSize = tilemap_tile_size.into();
+            let mut tile_storage = TileStorage::empty(map_size);
+            let tilemap_entity = commands.spawn_empty().id();
+
+            // Spawn tiles for this image
+            for (x, y, local_tile_index, flip) in image_tiles {
+                let tile_pos = TilePos { x, y };
+                let tile_entity = commands
+                    .spawn(TileBundle {
+                        position: tile_pos,
+                        tilemap_id: TilemapId(tilemap_entity),
+                        texture_index: TileTextureIndex(local_tile_index),
+                        flip,
+                        ..default()
+                    })
+                    .id();
+                tile_storage.set(&tile_pos, tile_entity);
             }
+
+            // Z-offset: layer_index * 0.1 + image_index * 0.01
+            // This ensures proper ordering: all images in layer 0 render before layer 1
+            let layer_z = layer_index as f32 * level.z_height + image_index as f32 * 0.01;
+
+            commands.entity(tilemap_entity).insert((
+                TilemapBundle {
+                    grid_size,
+                    map_type: TilemapType::Square,
+                    size: map_size,
+                    storage: tile_storage,
+                    texture: TilemapTexture::Single(texture_handle.clone()),
+                    tile_size: tilemap_tile_size,
+                    transform: Transform::from_xyz(0.0, 0.0, layer_z),
+                    ..default()
+                },
+                MapLayerIndex(layer_index),
+            ));
+
+            commands.entity(map_entity).add_child(tilemap_entity);
         }
+    } else {
+        info!(
+            "  Layer {} is not a tile layer (entity layer or other)",
+            layer_index
+        );
     }
+}
 
-    // Spawn entities if registry is provided
-    if let Some(registry) = entity_registry {
-        registry.spawn_all(commands, &level.entities, transform);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::world::CommandQueue;
+    use bevy_map_core::{Layer, LayerData, Level, Tileset, TILE_FLIP_DIAGONAL, TILE_FLIP_X};
+
+    #[test]
+    fn test_spawn_flipped_tiles() {
+        let tileset = Tileset::new("Ground".to_string(), "ground.png".to_string(), 16, 4, 4);
+        let mut level = Level::new("Test".to_string(), 2, 1);
+        let mut layer = Layer::new_tile_layer("Tiles".to_string(), tileset.id, 2, 1);
+        if let LayerData::Tiles { tiles, .. } = &mut layer.data {
+            tiles[0] = Some(5);
+            tiles[1] = Some(5 | TILE_FLIP_X | TILE_FLIP_DIAGONAL);
+        }
+        level.layers.push(layer);
 
-    map_entity
+        let mut textures = TilesetTextures::new();
+        textures.insert(tileset.id, 0, Handle::default());
+        let project = MapProject::new(level, vec![tileset]);
+
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        spawn_map_project(
+            &mut commands,
+            &project,
+            &textures,
+            Transform::default(),
+            None,
+        );
+        queue.apply(&mut world);
+
+        let mut tiles: Vec<_> = world
+            .query::<(&TilePos, &TileTextureIndex, &TileFlip)>()
+            .iter(&world)
+            .map(|(pos, index, flip)| (pos.x, index.0, *flip))
+            .collect();
+        tiles.sort_by_key(|(x, _, _)| *x);
+        assert_eq!(
+            tiles,
+            [
+                (0, 5, TileFlip::default()),
+                (
+                    1,
+                    5,
+                    TileFlip {
+                        x: true,
+                        y: false,
+                        d: true
+                    }
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,551 @@
+//! `showIf` conditions
+//!
+//! A property's `showIf` is a small boolean expression over the other
+//! properties of the same instance:
+//!
+//! ```text
+//! expr       = and ( "||" and )*
+//! and        = not ( "&&" not )*
+//! not        = "!" not | "(" expr ")" | comparison
+//! comparison = NAME [ op literal | "in" "[" literal ( "," literal )* "]" ]
+//! op         = "==" | "=" | "!=" | "<" | "<=" | ">" | ">="
+//! literal    = NUMBER | "string" | 'string' | true | false | null | NAME
+//! ```
+//!
+//! The left side of a comparison is always a property name. A bare name on the
+//! right is a string, which is how enum values are written
+//! (`kind == Weapon`). `=` means `==`, so older conditions like `type=weapon`
+//! keep working. A name on its own tests whether the value is truthy (true,
+//! non-zero, non-empty). `<`, `<=`, `>` and `>=` only compare numbers.
+//!
+//! A condition that doesn't parse but has the old `name=value` shape compares
+//! the property to the rest of the text, so values with spaces or dashes
+//! (`kind=Big Sword`, `tag=fire-type`) and empty values (`name=`) still work.
+//!
+//! ```text
+//! kind == Weapon && damage > 0
+//! element in [Fire, Ice] || !magical
+//! ```
+
+use std::fmt;
+
+use thiserror::Error;
+
+/// A parsed `showIf` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    expr: Expr,
+}
+
+/// A literal value on the right side of a comparison
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Null => f.write_str("null"),
+            Literal::Bool(b) => write!(f, "{}", b),
+            Literal::Number(n) => write!(f, "{}", n),
+            Literal::String(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Comparison operator of a condition term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// `name in [...]`
+    In,
+}
+
+impl CompareOp {
+    /// Whether this operator only applies to numbers
+    pub fn is_ordering(&self) -> bool {
+        matches!(
+            self,
+            CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge
+        )
+    }
+}
+
+/// A single property test in a condition, for checking it against a schema
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConditionTerm<'a> {
+    pub property: &'a str,
+    /// `None` for a truthiness test
+    pub op: Option<CompareOp>,
+    pub values: &'a [Literal],
+}
+
+/// A `showIf` expression that doesn't parse
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at character {position}")]
+pub struct ConditionError {
+    pub message: String,
+    /// Character offset of the problem
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Truthy(String),
+    Compare(String, CompareOp, Vec<Literal>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Condition {
+    /// Parse a condition
+    pub fn parse(source: &str) -> Result<Self, ConditionError> {
+        parse_expr(source)
+            .or_else(|err| parse_legacy(source).ok_or(err))
+            .map(|expr| Self { expr })
+    }
+
+    /// Evaluate the condition, looking property values up with `get`
+    ///
+    /// Missing properties read as `null`.
+    pub fn evaluate(&self, get: impl Fn(&str) -> Option<serde_json::Value>) -> bool {
+        eval(&self.expr, &get)
+    }
+
+    /// The property tests in the condition, in source order
+    pub fn terms(&self) -> Vec<ConditionTerm<'_>> {
+        let mut terms = Vec::new();
+        collect_terms(&self.expr, &mut terms);
+        terms
+    }
+}
+
+fn parse_expr(source: &str) -> Result<Expr, ConditionError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end: source.chars().count(),
+    };
+    let expr = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some((token, position)) => Err(ConditionError {
+            message: format!("Unexpected {}", token.describe()),
+            position,
+        }),
+    }
+}
+
+/// Read a `name=rest of line` condition, the only form before expressions
+fn parse_legacy(source: &str) -> Option<Expr> {
+    let (name, value) = source.split_once('=')?;
+    let name = name.trim();
+    let mut chars = name.chars();
+    let is_name = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if !is_name || value.starts_with('=') {
+        return None;
+    }
+    let value = Literal::String(value.trim().to_string());
+    Some(Expr::Compare(name.to_string(), CompareOp::Eq, vec![value]))
+}
+
+fn collect_terms<'a>(expr: &'a Expr, terms: &mut Vec<ConditionTerm<'a>>) {
+    match expr {
+        Expr::Truthy(property) => terms.push(ConditionTerm {
+            property,
+            op: None,
+            values: &[],
+        }),
+        Expr::Compare(property, op, values) => terms.push(ConditionTerm {
+            property,
+            op: Some(*op),
+            values,
+        }),
+        Expr::Not(inner) => collect_terms(inner, terms),
+        Expr::And(a, b) | Expr::Or(a, b) => {
+            collect_terms(a, terms);
+            collect_terms(b, terms);
+        }
+    }
+}
+
+fn eval(expr: &Expr, get: &impl Fn(&str) -> Option<serde_json::Value>) -> bool {
+    match expr {
+        Expr::Truthy(name) => is_truthy(&get(name).unwrap_or_default()),
+        Expr::Compare(name, op, values) => {
+            let value = get(name).unwrap_or_default();
+            match op {
+                CompareOp::Eq => values.iter().any(|v| literal_eq(&value, v)),
+                CompareOp::Ne => !values.iter().any(|v| literal_eq(&value, v)),
+                CompareOp::In => values.iter().any(|v| literal_eq(&value, v)),
+                ordering => match (value.as_f64(), values.first()) {
+                    (Some(a), Some(Literal::Number(b))) => match ordering {
+                        CompareOp::Lt => a < *b,
+                        CompareOp::Le => a <= *b,
+                        CompareOp::Gt => a > *b,
+                        _ => a >= *b,
+                    },
+                    _ => false,
+                },
+            }
+        }
+        Expr::Not(inner) => !eval(inner, get),
+        Expr::And(a, b) => eval(a, get) && eval(b, get),
+        Expr::Or(a, b) => eval(a, get) || eval(b, get),
+    }
+}
+
+fn is_truthy(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => false,
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        serde_json::Value::String(s) => !s.is_empty(),
+        serde_json::Value::Array(items) => !items.is_empty(),
+        serde_json::Value::Object(_) => true,
+    }
+}
+
+fn literal_eq(value: &serde_json::Value, literal: &Literal) -> bool {
+    match (value, literal) {
+        (serde_json::Value::Null, Literal::Null) => true,
+        (serde_json::Value::Bool(a), Literal::Bool(b)) => a == b,
+        (serde_json::Value::Number(a), Literal::Number(b)) => a.as_f64() == Some(*b),
+        // Strings compare by text, so "3" == 3 and "true" == true like before
+        (serde_json::Value::String(a), literal) => *a == literal.to_string(),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Number(f64),
+    String(String),
+    Op(CompareOp),
+    Not,
+    And,
+    Or,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Name(name) => format!("'{}'", name),
+            Token::Number(n) => format!("number {}", n),
+            Token::String(s) => format!("string \"{}\"", s),
+            Token::Op(_) => "operator".to_string(),
+            Token::Not => "'!'".to_string(),
+            Token::And => "'&&'".to_string(),
+            Token::Or => "'||'".to_string(),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::LBracket => "'['".to_string(),
+            Token::RBracket => "']'".to_string(),
+            Token::Comma => "','".to_string(),
+        }
+    }
+}
+
+/// Split a condition into tokens with their character offsets
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ConditionError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let error = |message: &str, position: usize| ConditionError {
+        message: message.to_string(),
+        position,
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '&' if next == Some('&') => Token::And,
+            '|' if next == Some('|') => Token::Or,
+            '=' if next == Some('=') => Token::Op(CompareOp::Eq),
+            '=' => Token::Op(CompareOp::Eq),
+            '!' if next == Some('=') => Token::Op(CompareOp::Ne),
+            '!' => Token::Not,
+            '<' if next == Some('=') => Token::Op(CompareOp::Le),
+            '<' => Token::Op(CompareOp::Lt),
+            '>' if next == Some('=') => Token::Op(CompareOp::Ge),
+            '>' => Token::Op(CompareOp::Gt),
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| error("Unterminated string", start))?;
+                let text: String = chars[i + 1..i + 1 + end].iter().collect();
+                i += end + 2;
+                tokens.push((Token::String(text), start));
+                continue;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse()
+                    .map_err(|_| error(&format!("Invalid number '{}'", text), start))?;
+                tokens.push((Token::Number(number), start));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push((Token::Name(chars[start..i].iter().collect()), start));
+                continue;
+            }
+            c => return Err(error(&format!("Unexpected character '{}'", c), start)),
+        };
+        // Two-character tokens
+        i += match token {
+            Token::And | Token::Or => 2,
+            Token::Op(CompareOp::Ne | CompareOp::Le | CompareOp::Ge) => 2,
+            Token::Op(CompareOp::Eq) if next == Some('=') => 2,
+            _ => 1,
+        };
+        tokens.push((token, start));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Offset reported for errors at the end of the input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.pos).map(|(token, at)| (token, *at))
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        if self.peek().is_some_and(|(token, _)| token == expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ConditionError {
+        ConditionError {
+            message: message.into(),
+            position: self.peek().map_or(self.end, |(_, at)| at),
+        }
+    }
+
+    fn expect(&mut self, expected: &Token) -> Result<(), ConditionError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {}", expected.describe())))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ConditionError> {
+        let mut expr = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ConditionError> {
+        let mut expr = self.parse_not()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ConditionError> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        if self.eat(&Token::LParen) {
+            let expr = self.parse_or()?;
+            self.expect(&Token::RParen)?;
+            return Ok(expr);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ConditionError> {
+        let name = match self.peek() {
+            Some((Token::Name(name), _)) => name.clone(),
+            Some((token, _)) => {
+                let message = format!("Expected a property name, found {}", token.describe());
+                return Err(self.error(message));
+            }
+            None => return Err(self.error("Expected a property name")),
+        };
+        self.pos += 1;
+
+        match self.peek() {
+            Some((Token::Op(op), _)) => {
+                let op = *op;
+                self.pos += 1;
+                let literal = self.parse_literal()?;
+                if op.is_ordering() && !matches!(literal, Literal::Number(_)) {
+                    return Err(ConditionError {
+                        message: "Only numbers can be compared with <, <=, > and >=".to_string(),
+                        position: self.tokens[self.pos - 1].1,
+                    });
+                }
+                Ok(Expr::Compare(name, op, vec![literal]))
+            }
+            Some((Token::Name(keyword), _)) if keyword == "in" => {
+                self.pos += 1;
+                self.expect(&Token::LBracket)?;
+                let mut values = vec![self.parse_literal()?];
+                while self.eat(&Token::Comma) {
+                    values.push(self.parse_literal()?);
+                }
+                self.expect(&Token::RBracket)?;
+                Ok(Expr::Compare(name, CompareOp::In, values))
+            }
+            _ => Ok(Expr::Truthy(name)),
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, ConditionError> {
+        let error = self.error("Expected a value");
+        match self.next() {
+            Some((Token::Number(n), _)) => Ok(Literal::Number(n)),
+            Some((Token::String(s), _)) => Ok(Literal::String(s)),
+            Some((Token::Name(name), _)) => Ok(match name.as_str() {
+                "true" => Literal::Bool(true),
+                "false" => Literal::Bool(false),
+                "null" => Literal::Null,
+                _ => Literal::String(name),
+            }),
+            _ => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval_with(source: &str, values: serde_json::Value) -> bool {
+        let condition = Condition::parse(source).unwrap();
+        condition.evaluate(|name| values.get(name).cloned())
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let values = json!({ "kind": "Weapon", "damage": 5, "magical": false, "count": "3" });
+
+        assert!(eval_with("kind=Weapon", values.clone()));
+        assert!(eval_with("kind == 'Weapon' && damage > 4", values.clone()));
+        assert!(!eval_with("kind != Weapon || damage <= 4", values.clone()));
+        assert!(eval_with("kind in [Armor, Weapon]", values.clone()));
+        assert!(eval_with("!magical && !(damage < 0)", values.clone()));
+        assert!(eval_with("count == 3", values.clone()));
+        assert!(eval_with("damage >= -1.5", values.clone()));
+        assert!(!eval_with("missing", values.clone()));
+        assert!(eval_with("missing == null", values.clone()));
+        // && binds tighter than ||
+        assert!(eval_with("magical && damage > 9 || kind", values));
+    }
+
+    #[test]
+    fn test_legacy_conditions() {
+        let values = json!({ "kind": "Big Sword", "tag": "fire-type", "name": "" });
+
+        assert!(eval_with("kind=Big Sword", values.clone()));
+        assert!(!eval_with("kind=Big Shield", values.clone()));
+        assert!(eval_with("tag = fire-type", values.clone()));
+        assert!(eval_with("name=", values.clone()));
+        assert!(!eval_with("missing=", values));
+
+        let condition = Condition::parse("kind=Big Sword").unwrap();
+        assert_eq!(
+            condition.terms()[0].values,
+            &[Literal::String("Big Sword".to_string())]
+        );
+
+        // Only a plain name on the left falls back
+        for source in ["a b=c d", "kind==Big Sword", "kind!=Big Sword"] {
+            assert!(Condition::parse(source).is_err(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_terms() {
+        let condition = Condition::parse("a && (b != 1 || c in [x, 'y z'])").unwrap();
+        let terms: Vec<_> = condition
+            .terms()
+            .iter()
+            .map(|t| (t.property, t.op, t.values.len()))
+            .collect();
+        assert_eq!(
+            terms,
+            vec![
+                ("a", None, 0),
+                ("b", Some(CompareOp::Ne), 1),
+                ("c", Some(CompareOp::In), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (source, position) in [
+            ("kind ==", 7),
+            ("kind == Weapon &&", 17),
+            ("(a", 2),
+            ("a in [x", 7),
+            ("a > 'b'", 4),
+            ("a == 'b", 5),
+            ("a # b", 2),
+            ("== b", 0),
+        ] {
+            let err = Condition::parse(source).unwrap_err();
+            assert_eq!(err.position, position, "{}: {}", source, err);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Validation diagnostics
+//!
+//! Validation collects every problem instead of stopping at the first one.
+//! Each diagnostic carries a JSON-pointer-like path (`/Type/property/0/field`),
+//! a severity and a machine-readable code, so tools can point at every bad
+//! field at once.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::SchemaError;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The data is invalid
+    Error,
+    /// The data is valid but probably not what was intended
+    Warning,
+}
+
+/// Machine-readable kind of a diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCode {
+    /// A property references an enum that doesn't exist
+    UnknownEnum,
+    /// A property references a data type that doesn't exist
+    UnknownRefType,
+    /// A property references an embedded type that doesn't exist
+    UnknownEmbeddedType,
+    /// An array property's item type is neither built-in nor defined
+    UnknownItemType,
+    /// A type extends a type that doesn't exist
+    UnknownBaseType,
+    /// A type's `extends` chain loops back on itself
+    CyclicExtends,
+    /// An overriding property changes the inherited property's type
+    OverrideTypeChanged,
+    /// A constraint is invalid or doesn't apply to the property's type
+    InvalidConstraint,
+    /// A `showIf` condition doesn't parse or doesn't fit the type's properties
+    InvalidCondition,
+    /// An instance has a type that isn't in the schema
+    UnknownType,
+    /// An instance has an abstract type
+    AbstractType,
+    /// A required property is missing
+    MissingRequired,
+    /// A value has the wrong JSON type for its property
+    WrongType,
+    /// A number is below the property's minimum
+    BelowMin,
+    /// A number is above the property's maximum
+    AboveMax,
+    /// A string isn't one of the enum's values
+    InvalidEnumValue,
+    /// A string doesn't match the property's pattern
+    PatternMismatch,
+    /// A string is shorter than the property's minimum length
+    TooShort,
+    /// A string is longer than the property's maximum length
+    TooLong,
+    /// An array has fewer items than the property's minimum
+    TooFewItems,
+    /// An array has more items than the property's maximum
+    TooManyItems,
+    /// A unique property's value is used by another instance in its scope
+    Duplicate,
+}
+
+impl DiagnosticCode {
+    /// The code as a stable snake_case string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::UnknownEnum => "unknown_enum",
+            DiagnosticCode::UnknownRefType => "unknown_ref_type",
+            DiagnosticCode::UnknownEmbeddedType => "unknown_embedded_type",
+            DiagnosticCode::UnknownItemType => "unknown_item_type",
+            DiagnosticCode::UnknownBaseType => "unknown_base_type",
+            DiagnosticCode::CyclicExtends => "cyclic_extends",
+            DiagnosticCode::OverrideTypeChanged => "override_type_changed",
+            DiagnosticCode::InvalidConstraint => "invalid_constraint",
+            DiagnosticCode::InvalidCondition => "invalid_condition",
+            DiagnosticCode::UnknownType => "unknown_type",
+            DiagnosticCode::AbstractType => "abstract_type",
+            DiagnosticCode::MissingRequired => "missing_required",
+            DiagnosticCode::WrongType => "wrong_type",
+            DiagnosticCode::BelowMin => "below_min",
+            DiagnosticCode::AboveMax => "above_max",
+            DiagnosticCode::InvalidEnumValue => "invalid_enum_value",
+            DiagnosticCode::PatternMismatch => "pattern_mismatch",
+            DiagnosticCode::TooShort => "too_short",
+            DiagnosticCode::TooLong => "too_long",
+            DiagnosticCode::TooFewItems => "too_few_items",
+            DiagnosticCode::TooManyItems => "too_many_items",
+            DiagnosticCode::Duplicate => "duplicate",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single validation problem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    /// Where the problem is, e.g. `/Enemy/loot/2/count`
+    pub path: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// All diagnostics from one validation run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an error
+    pub fn error(&mut self, code: DiagnosticCode, path: String, message: String) {
+        self.items.push(Diagnostic {
+            severity: Severity::Error,
+            code,
+            path,
+            message,
+        });
+    }
+
+    /// Record a warning
+    pub fn warning(&mut self, code: DiagnosticCode, path: String, message: String) {
+        self.items.push(Diagnostic {
+            severity: Severity::Warning,
+            code,
+            path,
+            message,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items.iter()
+    }
+
+    /// Check if any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Iterate over errors only
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.items
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Iterate over diagnostics at `path` or anywhere below it
+    pub fn at<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a Diagnostic> {
+        self.items.iter().filter(move |diagnostic| {
+            diagnostic
+                .path
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Convert to a result that fails with the first error, if there is one
+    pub fn into_result(self) -> Result<(), SchemaError> {
+        match self.errors().next() {
+            Some(error) => Err(SchemaError::ValidationError(error.message.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Append a segment to a diagnostic path, escaping `~` and `/` like JSON pointers
+pub fn path_join(path: &str, segment: &str) -> String {
+    format!("{}/{}", path, segment.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let path = path_join(&path_join("", "Enemy"), "loot/drops");
+        assert_eq!(path, "/Enemy/loot~1drops");
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.warning(
+            DiagnosticCode::UnknownItemType,
+            "/Enemy/loot".to_string(),
+            "w".to_string(),
+        );
+        diagnostics.error(
+            DiagnosticCode::WrongType,
+            "/Enemy/loot/0".to_string(),
+            "e".to_string(),
+        );
+        diagnostics.error(
+            DiagnosticCode::WrongType,
+            "/Enemy/lootTable".to_string(),
+            "other".to_string(),
+        );
+
+        assert_eq!(diagnostics.at("/Enemy/loot").count(), 2);
+        assert!(diagnostics.has_errors());
+        assert_eq!(diagnostics.errors().count(), 2);
+        assert_eq!(DiagnosticCode::MissingRequired.as_str(), "missing_required");
+        assert_eq!(
+            serde_json::to_value(DiagnosticCode::MissingRequired).unwrap(),
+            serde_json::json!("missing_required")
+        );
+    }
+}
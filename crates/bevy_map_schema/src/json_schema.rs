@@ -0,0 +1,528 @@
+//! JSON Schema export
+//!
+//! Describes `schema.json` files and the instance data of a [`Schema`] as
+//! standard JSON Schema (draft 2020-12), so external tools and editors can
+//! validate them without this crate.
+//!
+//! Instance schemas follow [`diagnose_instance`](crate::diagnose_instance):
+//! every value may also be `null`, `showIf` properties are never required, and
+//! Point, Color, Sprite and Dialogue values are left unconstrained. `unique`
+//! can't be expressed in JSON Schema and is skipped.
+
+use serde_json::{json, Map, Value};
+
+use crate::{PropType, PropertyDef, Schema};
+
+/// The `$schema` URI of the generated schemas
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema of a `schema.json` file
+pub fn schema_file_json_schema() -> Value {
+    let mut root = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "bevy_map schema",
+        "$ref": "#/$defs/schemaFile",
+    });
+    root["$defs"] = Value::Object(schema_file_defs());
+    root
+}
+
+/// JSON Schema of the properties object of an instance of `type_name`
+///
+/// Returns `None` if the type isn't in the schema.
+pub fn instance_json_schema(schema: &Schema, type_name: &str) -> Option<Value> {
+    schema.get_type(type_name)?;
+    let mut root = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": type_name,
+        "$ref": type_ref(type_name),
+    });
+    root["$defs"] = Value::Object(type_defs(schema));
+    Some(root)
+}
+
+/// JSON Schema of a `.map.json` project file whose data follows `schema`
+///
+/// Checks the embedded schema, the data instances of each data type and the
+/// properties of level entities by their `type_name`.
+pub fn project_json_schema(schema: &Schema) -> Value {
+    let concrete: Vec<&str> = schema
+        .data_type_names()
+        .into_iter()
+        .filter(|name| !schema.data_types[*name].is_abstract)
+        .collect();
+
+    let instances: Map<String, Value> = concrete
+        .iter()
+        .map(|name| {
+            let instance = json!({
+                "type": "object",
+                "required": ["id", "type_name", "properties"],
+                "properties": {
+                    "id": { "type": "string" },
+                    "type_name": { "const": name },
+                    "properties": { "$ref": type_ref(name) },
+                },
+            });
+            (
+                name.to_string(),
+                json!({ "type": "array", "items": instance }),
+            )
+        })
+        .collect();
+
+    // Entities pick their properties schema by type name
+    let entity_types: Vec<Value> = schema
+        .placeable_type_names()
+        .into_iter()
+        .map(|name| {
+            json!({
+                "if": {
+                    "required": ["type_name"],
+                    "properties": { "type_name": { "const": name } },
+                },
+                "then": {
+                    "properties": { "properties": { "$ref": type_ref(name) } },
+                },
+            })
+        })
+        .collect();
+
+    let mut defs = schema_file_defs();
+    defs.extend(type_defs(schema));
+    defs.insert(
+        "entity".to_string(),
+        json!({
+            "type": "object",
+            "required": ["id", "type_name", "position"],
+            "properties": {
+                "id": { "type": "string" },
+                "type_name": { "type": "string" },
+                "position": {
+                    "type": "array",
+                    "items": { "type": "number" },
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+                "template_id": { "type": "string" },
+                "properties": { "type": "object" },
+            },
+            "allOf": entity_types,
+        }),
+    );
+
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": format!("{} map project", schema.project.name),
+        "type": "object",
+        "required": ["version"],
+        "properties": {
+            "version": { "type": "integer", "minimum": 0 },
+            "schema": { "$ref": "#/$defs/schemaFile" },
+            "schema_revision": { "type": "integer", "minimum": 0 },
+            "data": {
+                "type": "object",
+                "properties": {
+                    "instances": { "type": "object", "properties": instances },
+                },
+            },
+            "levels": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "entities": {
+                            "type": "array",
+                            "items": { "$ref": "#/$defs/entity" },
+                        },
+                    },
+                },
+            },
+        },
+        "$defs": defs,
+    })
+}
+
+/// `$ref` to the `$defs` entry of a type
+fn type_ref(type_name: &str) -> String {
+    format!("#/$defs/type.{}", type_name)
+}
+
+/// `$ref` to the `$defs` entry of an enum
+fn enum_ref(enum_name: &str) -> String {
+    format!("#/$defs/enum.{}", enum_name)
+}
+
+/// `$defs` entries for every type (`type.Name`) and enum (`enum.Name`)
+fn type_defs(schema: &Schema) -> Map<String, Value> {
+    let mut defs = Map::new();
+    for (name, values) in &schema.enums {
+        defs.insert(format!("enum.{}", name), json!({ "enum": values }));
+    }
+    for name in schema.data_types.keys().chain(schema.embedded_types.keys()) {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for prop in schema.all_properties(name) {
+            properties.insert(prop.name.clone(), property_json_schema(schema, prop));
+            if prop.required && prop.show_if.is_none() {
+                required.push(prop.name.clone());
+            }
+        }
+        defs.insert(
+            format!("type.{}", name),
+            json!({
+                "title": name,
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }),
+        );
+    }
+    defs
+}
+
+/// JSON Schema of one property's value
+fn property_json_schema(schema: &Schema, prop: &PropertyDef) -> Value {
+    let mut value = match prop.prop_type {
+        PropType::String | PropType::Multiline => json!({ "type": "string" }),
+        PropType::Int => json!({ "type": "integer" }),
+        PropType::Float => json!({ "type": "number" }),
+        PropType::Bool => json!({ "type": "boolean" }),
+        PropType::Enum if prop.is_flags() => json!({
+            "type": "array",
+            "items": enum_json_schema(schema, prop.enum_type.as_deref()),
+            "uniqueItems": true,
+        }),
+        PropType::Enum => enum_json_schema(schema, prop.enum_type.as_deref()),
+        PropType::Ref => json!({ "type": "string" }),
+        PropType::Array => json!({
+            "type": "array",
+            "items": match prop.item_type.as_deref() {
+                Some(item_type) => nullable(item_json_schema(schema, item_type)),
+                None => json!({}),
+            },
+        }),
+        PropType::Embedded => match prop.embedded_type.as_deref() {
+            Some(embedded) if schema.embedded_types.contains_key(embedded) => {
+                json!({ "$ref": type_ref(embedded) })
+            }
+            _ => json!({ "type": "object" }),
+        },
+        // Point, Color, Sprite and Dialogue are checked at runtime
+        _ => return json!({}),
+    };
+
+    let object = value.as_object_mut().expect("property schemas are objects");
+    let mut set = |key: &str, v: Option<Value>| {
+        if let Some(v) = v {
+            object.insert(key.to_string(), v);
+        }
+    };
+    set("minimum", prop.min.map(Value::from));
+    set("maximum", prop.max.map(Value::from));
+    set("pattern", prop.pattern.clone().map(Value::from));
+    set("minLength", prop.min_length.map(Value::from));
+    set("maxLength", prop.max_length.map(Value::from));
+    set("minItems", prop.min_items.map(Value::from));
+    set("maxItems", prop.max_items.map(Value::from));
+
+    let mut value = nullable(value);
+    if let Some(default) = &prop.default {
+        value["default"] = default.clone();
+    }
+    value
+}
+
+/// JSON Schema of a single enum value
+fn enum_json_schema(schema: &Schema, enum_type: Option<&str>) -> Value {
+    match enum_type {
+        Some(name) if schema.enums.contains_key(name) => json!({ "$ref": enum_ref(name) }),
+        _ => json!({ "type": "string" }),
+    }
+}
+
+/// JSON Schema of an array item, mirroring the validator's item types
+fn item_json_schema(schema: &Schema, item_type: &str) -> Value {
+    match item_type {
+        "String" => json!({ "type": "string" }),
+        "Int" => json!({ "type": "integer" }),
+        "Float" => json!({ "type": "number" }),
+        "Bool" => json!({ "type": "boolean" }),
+        _ if schema.embedded_types.contains_key(item_type) => {
+            json!({ "$ref": type_ref(item_type) })
+        }
+        // Items of data types are references to instances
+        _ if schema.data_types.contains_key(item_type) => json!({ "type": "string" }),
+        _ if schema.enums.contains_key(item_type) => json!({ "$ref": enum_ref(item_type) }),
+        _ => json!({}),
+    }
+}
+
+/// Also accept `null`, like the validator does
+fn nullable(mut value: Value) -> Value {
+    match value.get("type").and_then(Value::as_str) {
+        Some(kind) => {
+            value["type"] = json!([kind, "null"]);
+            value
+        }
+        None if value.as_object().is_some_and(Map::is_empty) => value,
+        None => json!({ "anyOf": [value, { "type": "null" }] }),
+    }
+}
+
+/// `$defs` entries describing the schema file format itself
+fn schema_file_defs() -> Map<String, Value> {
+    let defs = json!({
+        "schemaFile": {
+            "type": "object",
+            "required": ["version", "project"],
+            "properties": {
+                "version": { "type": "integer", "minimum": 0 },
+                "revision": { "type": "integer", "minimum": 0 },
+                "project": { "$ref": "#/$defs/projectConfig" },
+                "enums": {
+                    "type": "object",
+                    "additionalProperties": { "type": "array", "items": { "type": "string" } },
+                },
+                "data_types": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/$defs/typeDef" },
+                },
+                "embedded_types": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/$defs/typeDef" },
+                },
+                "migrations": { "type": "array", "items": { "$ref": "#/$defs/migration" } },
+            },
+        },
+        "projectConfig": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string" },
+                "tile_size": { "type": "integer", "minimum": 0 },
+                "default_layer_types": { "type": "array", "items": { "type": "string" } },
+            },
+        },
+        "typeDef": {
+            "type": "object",
+            "properties": {
+                "color": { "type": "string" },
+                "icon": { "type": ["string", "null"] },
+                "placeable": { "type": "boolean" },
+                "marker_size": { "type": ["integer", "null"], "minimum": 0 },
+                "viewport_display": { "enum": ["colored_square", "icon", "sprite"] },
+                "properties": { "type": "array", "items": { "$ref": "#/$defs/propertyDef" } },
+                "extends": { "type": ["string", "null"] },
+                "abstract": { "type": "boolean" },
+            },
+        },
+        "propertyDef": {
+            "type": "object",
+            "required": ["name", "type"],
+            "properties": {
+                "name": { "type": "string" },
+                "type": { "$ref": "#/$defs/propType" },
+                "required": { "type": "boolean" },
+                "default": {},
+                "min": { "type": ["number", "null"] },
+                "max": { "type": ["number", "null"] },
+                "showIf": { "type": ["string", "null"] },
+                "enumType": { "type": ["string", "null"] },
+                "refType": { "type": ["string", "null"] },
+                "itemType": { "type": ["string", "null"] },
+                "embeddedType": { "type": ["string", "null"] },
+                "localized": { "type": "boolean" },
+                "pattern": { "type": ["string", "null"] },
+                "minLength": { "type": ["integer", "null"], "minimum": 0 },
+                "maxLength": { "type": ["integer", "null"], "minimum": 0 },
+                "minItems": { "type": ["integer", "null"], "minimum": 0 },
+                "maxItems": { "type": ["integer", "null"], "minimum": 0 },
+                "unique": { "enum": ["project", "level", null] },
+                "flags": { "type": "boolean" },
+            },
+        },
+        "propType": {
+            "enum": [
+                "string", "multiline", "int", "float", "bool", "enum", "ref",
+                "array", "embedded", "point", "color", "sprite", "dialogue",
+            ],
+        },
+        "migration": {
+            "type": "object",
+            "required": ["revision", "steps"],
+            "properties": {
+                "revision": { "type": "integer", "minimum": 0 },
+                "steps": { "type": "array", "items": { "$ref": "#/$defs/migrationStep" } },
+            },
+        },
+        "migrationStep": {
+            "type": "object",
+            "required": ["op", "type"],
+            "properties": {
+                "op": { "enum": ["rename", "retype", "embed", "delete"] },
+                "type": { "type": "string" },
+            },
+            "oneOf": [
+                {
+                    "properties": { "op": { "const": "rename" } },
+                    "required": ["from", "to"],
+                },
+                {
+                    "properties": {
+                        "op": { "const": "retype" },
+                        "from": { "$ref": "#/$defs/propType" },
+                        "to": { "$ref": "#/$defs/propType" },
+                    },
+                    "required": ["property", "from", "to"],
+                },
+                {
+                    "properties": { "op": { "const": "embed" } },
+                    "required": ["property", "into"],
+                },
+                {
+                    "properties": { "op": { "const": "delete" } },
+                    "required": ["property"],
+                },
+            ],
+        },
+    });
+    match defs {
+        Value::Object(defs) => defs,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_schema;
+
+    const SCHEMA: &str = r##"{
+        "version": 1,
+        "project": { "name": "Test" },
+        "enums": { "Element": ["Fire", "Ice"] },
+        "data_types": {
+            "Actor": {
+                "abstract": true,
+                "properties": [
+                    { "name": "name", "type": "string", "required": true, "maxLength": 16 }
+                ]
+            },
+            "Enemy": {
+                "extends": "Actor",
+                "placeable": true,
+                "properties": [
+                    { "name": "health", "type": "int", "min": 1, "default": 10 },
+                    { "name": "element", "type": "enum", "enumType": "Element" },
+                    { "name": "weak", "type": "enum", "enumType": "Element", "flags": true },
+                    { "name": "stats", "type": "embedded", "embeddedType": "Stats" },
+                    { "name": "loot", "type": "array", "itemType": "Item", "maxItems": 3 },
+                    { "name": "boss", "type": "bool" },
+                    { "name": "title", "type": "string", "required": true, "showIf": "boss" }
+                ]
+            },
+            "Item": {
+                "properties": [{ "name": "value", "type": "float" }]
+            }
+        },
+        "embedded_types": {
+            "Stats": { "properties": [{ "name": "speed", "type": "float" }] }
+        }
+    }"##;
+
+    #[test]
+    fn test_instance_json_schema() {
+        let schema = parse_schema(SCHEMA).unwrap();
+        let json = instance_json_schema(&schema, "Enemy").unwrap();
+        assert_eq!(json["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(json["$ref"], "#/$defs/type.Enemy");
+        assert!(instance_json_schema(&schema, "Missing").is_none());
+
+        let defs = &json["$defs"];
+        let enemy = &defs["type.Enemy"];
+        // Inherited properties are included, showIf properties aren't required
+        assert_eq!(enemy["required"], json!(["name"]));
+        let props = &enemy["properties"];
+        assert_eq!(props["name"]["type"], json!(["string", "null"]));
+        assert_eq!(props["name"]["maxLength"], 16);
+        assert_eq!(props["health"]["minimum"], 1.0);
+        assert_eq!(props["health"]["default"], 10);
+        assert_eq!(props["element"]["anyOf"][0]["$ref"], "#/$defs/enum.Element");
+        assert_eq!(props["weak"]["uniqueItems"], true);
+        assert_eq!(props["stats"]["anyOf"][0]["$ref"], "#/$defs/type.Stats");
+        assert_eq!(props["loot"]["items"]["type"], json!(["string", "null"]));
+        assert_eq!(props["loot"]["maxItems"], 3);
+        assert_eq!(defs["enum.Element"]["enum"], json!(["Fire", "Ice"]));
+        assert!(defs["type.Stats"]["properties"]["speed"].is_object());
+    }
+
+    #[test]
+    fn test_project_json_schema() {
+        let schema = parse_schema(SCHEMA).unwrap();
+        let json = project_json_schema(&schema);
+        let instances = &json["properties"]["data"]["properties"]["instances"]["properties"];
+        assert!(instances.get("Item").is_some());
+        assert!(instances.get("Enemy").is_some());
+        // Abstract types have no instances
+        assert!(instances.get("Actor").is_none());
+        assert_eq!(
+            instances["Item"]["items"]["properties"]["type_name"]["const"],
+            "Item"
+        );
+
+        let entity = &json["$defs"]["entity"];
+        let branches = entity["allOf"].as_array().unwrap();
+        assert_eq!(branches.len(), 1);
+        assert_eq!(
+            branches[0]["then"]["properties"]["properties"]["$ref"],
+            "#/$defs/type.Enemy"
+        );
+        assert!(json["$defs"]["schemaFile"].is_object());
+        assert!(json["$defs"]["type.Enemy"].is_object());
+    }
+
+    #[test]
+    fn test_schema_file_json_schema() {
+        let json = schema_file_json_schema();
+        let defs = json["$defs"].as_object().unwrap();
+        // Every $ref points at a definition
+        let text = json.to_string();
+        for reference in text.split("\"$ref\":\"#/$defs/").skip(1) {
+            let name = &reference[..reference.find('"').unwrap()];
+            assert!(defs.contains_key(name), "missing $defs entry {}", name);
+        }
+        let prop_types = defs["propType"]["enum"].as_array().unwrap();
+        for prop_type in PropType::all_active() {
+            assert!(prop_types.contains(&serde_json::to_value(prop_type).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_validate_example_project() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../examples/assets/maps/example_project.map.json"
+        );
+        let project: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let schema: Schema = serde_json::from_value(project["schema"].clone()).unwrap();
+
+        // Compiling checks the generated schemas against the draft 2020-12 meta-schema
+        let validator = jsonschema::draft202012::new(&project_json_schema(&schema)).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&project)
+            .map(|error| format!("{} at {}", error, error.instance_path))
+            .collect();
+        assert!(errors.is_empty(), "{:#?}", errors);
+
+        let schema_file = jsonschema::draft202012::new(&schema_file_json_schema()).unwrap();
+        assert!(schema_file.is_valid(&project["schema"]));
+
+        let mut broken = project.clone();
+        broken["data"]["instances"]["Item"][0]["properties"]["min_level"] = json!("high");
+        assert!(!validator.is_valid(&broken));
+        let mut broken = project;
+        broken["schema"]["data_types"]["Item"]["properties"][0]["type"] = json!("number");
+        assert!(!schema_file.is_valid(&broken["schema"]));
+    }
+}
@@ -18,9 +18,17 @@
 //! schema.validate_entity(&entity)?;
 //! ```
 
+mod condition;
+mod diagnostics;
+mod json_schema;
+mod migration;
 mod types;
 mod validate;
 
+pub use condition::*;
+pub use diagnostics::*;
+pub use json_schema::*;
+pub use migration::*;
 pub use types::*;
 pub use validate::*;
 
@@ -0,0 +1,402 @@
+//! Schema migrations
+//!
+//! Renaming, retyping, moving or deleting a property would orphan every value
+//! saved under the old definition. Each such change is recorded as a
+//! [`MigrationStep`]; the steps of one edit form a [`Migration`] stamped with
+//! the schema revision it produced. Data saved at an older revision is brought
+//! up to date by applying every later migration in order. The revision is
+//! separate from the schema's `version`, which is the file format version.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{PropType, Schema};
+
+/// The steps of one schema edit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Migration {
+    /// Schema revision after this migration
+    pub revision: u32,
+    pub steps: Vec<MigrationStep>,
+}
+
+/// A change to a property that existing values have to follow
+///
+/// Steps apply to instances of the type and of every type extending it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum MigrationStep {
+    /// Rename a property, keeping its values
+    Rename {
+        #[serde(rename = "type")]
+        type_name: String,
+        from: String,
+        to: String,
+    },
+    /// Change a property's type, converting its values (see [`convert_value`])
+    Retype {
+        #[serde(rename = "type")]
+        type_name: String,
+        property: String,
+        from: PropType,
+        to: PropType,
+    },
+    /// Move a property into the object of an embedded property of the same type
+    Embed {
+        #[serde(rename = "type")]
+        type_name: String,
+        property: String,
+        into: String,
+    },
+    /// Delete a property and its values
+    Delete {
+        #[serde(rename = "type")]
+        type_name: String,
+        property: String,
+    },
+}
+
+impl MigrationStep {
+    /// The type whose property changes
+    pub fn type_name(&self) -> &str {
+        match self {
+            MigrationStep::Rename { type_name, .. }
+            | MigrationStep::Retype { type_name, .. }
+            | MigrationStep::Embed { type_name, .. }
+            | MigrationStep::Delete { type_name, .. } => type_name,
+        }
+    }
+
+    /// Short description, e.g. "Rename Enemy.hp to health"
+    pub fn describe(&self) -> String {
+        match self {
+            MigrationStep::Rename {
+                type_name,
+                from,
+                to,
+            } => format!("Rename {}.{} to {}", type_name, from, to),
+            MigrationStep::Retype {
+                type_name,
+                property,
+                from,
+                to,
+            } => format!(
+                "Change {}.{} from {} to {}",
+                type_name,
+                property,
+                from.display_name(),
+                to.display_name()
+            ),
+            MigrationStep::Embed {
+                type_name,
+                property,
+                into,
+            } => format!("Move {}.{} into {}", type_name, property, into),
+            MigrationStep::Delete {
+                type_name,
+                property,
+            } => format!("Delete {}.{}", type_name, property),
+        }
+    }
+
+    /// Apply the step to the properties of one object, returning whether they changed
+    fn apply(&self, properties: &mut HashMap<String, serde_json::Value>) -> bool {
+        match self {
+            MigrationStep::Rename { from, to, .. } => match properties.remove(from) {
+                Some(value) => {
+                    properties.insert(to.clone(), value);
+                    true
+                }
+                None => false,
+            },
+            MigrationStep::Retype { property, to, .. } => {
+                let Some(value) = properties.get(property) else {
+                    return false;
+                };
+                match convert_value(value, *to) {
+                    Some(converted) if converted == *value => false,
+                    Some(converted) => {
+                        properties.insert(property.clone(), converted);
+                        true
+                    }
+                    // Values that can't be converted are dropped so the default applies
+                    None => {
+                        properties.remove(property);
+                        true
+                    }
+                }
+            }
+            MigrationStep::Embed { property, into, .. } => {
+                // Leave the value alone rather than overwrite a non-object
+                let mut target = match properties.get(into) {
+                    None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+                    Some(serde_json::Value::Object(object)) => object.clone(),
+                    Some(_) => return false,
+                };
+                let Some(value) = properties.remove(property) else {
+                    return false;
+                };
+                target.insert(property.clone(), value);
+                properties.insert(into.clone(), serde_json::Value::Object(target));
+                true
+            }
+            MigrationStep::Delete { property, .. } => properties.remove(property).is_some(),
+        }
+    }
+}
+
+impl Schema {
+    /// Record migration steps as a new schema revision, returning that revision
+    ///
+    /// The schema's own definitions should already reflect the steps.
+    pub fn record_migration(&mut self, steps: Vec<MigrationStep>) -> u32 {
+        self.revision += 1;
+        self.migrations.push(Migration {
+            revision: self.revision,
+            steps,
+        });
+        self.revision
+    }
+
+    /// Migration steps recorded after schema revision `revision`, oldest first
+    pub fn migrations_since(&self, revision: u32) -> impl Iterator<Item = &MigrationStep> {
+        self.migrations
+            .iter()
+            .filter(move |migration| migration.revision > revision)
+            .flat_map(|migration| migration.steps.iter())
+    }
+}
+
+/// Apply the migrations after schema revision `since` to an instance of `type_name`
+///
+/// Embedded objects (directly or in arrays) are migrated too. They are found
+/// through the current schema, so an embedded property that was itself
+/// renamed by a later step is skipped. Returns whether anything changed.
+pub fn migrate_properties(
+    schema: &Schema,
+    since: u32,
+    type_name: &str,
+    properties: &mut HashMap<String, serde_json::Value>,
+) -> bool {
+    let mut changed = false;
+    for step in schema.migrations_since(since) {
+        changed |= apply_step(schema, step, type_name, properties);
+    }
+    changed
+}
+
+/// Apply one step to an object of `type_name` and the embedded objects inside it
+fn apply_step(
+    schema: &Schema,
+    step: &MigrationStep,
+    type_name: &str,
+    properties: &mut HashMap<String, serde_json::Value>,
+) -> bool {
+    let mut changed = false;
+    if type_name == step.type_name() || schema.base_types(type_name).contains(&step.type_name()) {
+        changed |= step.apply(properties);
+    }
+
+    for prop in schema.all_properties(type_name) {
+        let embedded_type = match prop.prop_type {
+            PropType::Embedded => prop.embedded_type.as_deref(),
+            PropType::Array => prop
+                .item_type
+                .as_deref()
+                .filter(|item_type| schema.embedded_types.contains_key(*item_type)),
+            _ => None,
+        };
+        let (Some(embedded_type), Some(value)) = (embedded_type, properties.get_mut(&prop.name))
+        else {
+            continue;
+        };
+        let objects: Vec<&mut serde_json::Map<String, serde_json::Value>> = match value {
+            serde_json::Value::Object(object) => vec![object],
+            serde_json::Value::Array(items) => items
+                .iter_mut()
+                .filter_map(|item| item.as_object_mut())
+                .collect(),
+            _ => Vec::new(),
+        };
+        for object in objects {
+            let mut nested: HashMap<String, serde_json::Value> =
+                std::mem::take(object).into_iter().collect();
+            changed |= apply_step(schema, step, embedded_type, &mut nested);
+            *object = nested.into_iter().collect();
+        }
+    }
+    changed
+}
+
+/// Convert a value to a property type
+///
+/// Numbers, strings and booleans convert between each other where the text
+/// allows it ("3" to 3, 1 to true, 2.6 to 3), a single value becomes a
+/// one-item array and an array becomes its first item. Returns `None` when
+/// there's no sensible conversion. Null stays null.
+pub fn convert_value(value: &serde_json::Value, to: PropType) -> Option<serde_json::Value> {
+    use serde_json::Value as Json;
+
+    let textual = matches!(
+        to,
+        PropType::String
+            | PropType::Multiline
+            | PropType::Enum
+            | PropType::Ref
+            | PropType::Dialogue
+            | PropType::Color
+    );
+    let scalar = textual || matches!(to, PropType::Int | PropType::Float | PropType::Bool);
+    if let (Json::Array(items), true) = (value, scalar) {
+        return match items.first() {
+            Some(item) => convert_value(item, to),
+            None => Some(Json::Null),
+        };
+    }
+
+    match (to, value) {
+        (_, Json::Null) => Some(Json::Null),
+        (_, Json::String(_)) if textual => Some(value.clone()),
+        (_, Json::Number(n)) if textual && to != PropType::Color => {
+            Some(Json::String(n.to_string()))
+        }
+        (_, Json::Bool(b)) if textual && to != PropType::Color => Some(Json::String(b.to_string())),
+        (PropType::Int, Json::Number(n)) => n
+            .as_i64()
+            .or_else(|| n.as_f64().map(|f| f.round() as i64))
+            .map(Json::from),
+        (PropType::Int, Json::String(s)) => {
+            let s = s.trim();
+            s.parse::<i64>()
+                .ok()
+                .or_else(|| s.parse::<f64>().ok().map(|f| f.round() as i64))
+                .map(Json::from)
+        }
+        (PropType::Int, Json::Bool(b)) => Some(Json::from(i64::from(*b))),
+        (PropType::Float, Json::Number(n)) => n.as_f64().map(Json::from),
+        (PropType::Float, Json::String(s)) => s.trim().parse::<f64>().ok().map(Json::from),
+        (PropType::Float, Json::Bool(b)) => Some(Json::from(if *b { 1.0 } else { 0.0 })),
+        (PropType::Bool, Json::Bool(_)) => Some(value.clone()),
+        (PropType::Bool, Json::Number(n)) => n.as_f64().map(|n| Json::Bool(n != 0.0)),
+        (PropType::Bool, Json::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Some(Json::Bool(true)),
+            "false" | "0" | "" => Some(Json::Bool(false)),
+            _ => None,
+        },
+        (PropType::Array, Json::Array(_)) => Some(value.clone()),
+        (PropType::Array, _) => Some(Json::Array(vec![value.clone()])),
+        (PropType::Embedded | PropType::Point, Json::Object(_)) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_schema;
+    use serde_json::json;
+
+    #[test]
+    fn test_convert_value() {
+        for (value, to, expected) in [
+            (json!(3), PropType::Float, Some(json!(3.0))),
+            (json!(2.6), PropType::Int, Some(json!(3))),
+            (json!(" 42 "), PropType::Int, Some(json!(42))),
+            (json!("abc"), PropType::Int, None),
+            (json!(0), PropType::Bool, Some(json!(false))),
+            (json!("True"), PropType::Bool, Some(json!(true))),
+            (json!(true), PropType::String, Some(json!("true"))),
+            (json!(7), PropType::Multiline, Some(json!("7"))),
+            (json!("Fire"), PropType::Array, Some(json!(["Fire"]))),
+            (json!(["Fire", "Ice"]), PropType::Enum, Some(json!("Fire"))),
+            (json!({ "x": 1 }), PropType::Int, None),
+            (json!(null), PropType::Int, Some(json!(null))),
+        ] {
+            assert_eq!(convert_value(&value, to), expected, "{} to {:?}", value, to);
+        }
+    }
+
+    #[test]
+    fn test_migrate_properties() {
+        let mut schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Enemy": {
+                    "properties": [
+                        { "name": "health", "type": "float" },
+                        { "name": "stats", "type": "embedded", "embeddedType": "Stats" },
+                        { "name": "drops", "type": "array", "itemType": "Drop" }
+                    ]
+                },
+                "Boss": { "extends": "Enemy" }
+            },
+            "embedded_types": {
+                "Stats": { "properties": [{ "name": "speed", "type": "int" }] },
+                "Drop": { "properties": [{ "name": "item", "type": "string" }] }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let revision = schema.record_migration(vec![
+            MigrationStep::Rename {
+                type_name: "Enemy".to_string(),
+                from: "hp".to_string(),
+                to: "health".to_string(),
+            },
+            MigrationStep::Retype {
+                type_name: "Enemy".to_string(),
+                property: "health".to_string(),
+                from: PropType::String,
+                to: PropType::Float,
+            },
+        ]);
+        assert_eq!(revision, 1);
+        schema.record_migration(vec![
+            MigrationStep::Embed {
+                type_name: "Enemy".to_string(),
+                property: "speed".to_string(),
+                into: "stats".to_string(),
+            },
+            MigrationStep::Rename {
+                type_name: "Drop".to_string(),
+                from: "name".to_string(),
+                to: "item".to_string(),
+            },
+            MigrationStep::Delete {
+                type_name: "Enemy".to_string(),
+                property: "legacy".to_string(),
+            },
+        ]);
+
+        let mut props: HashMap<String, serde_json::Value> = serde_json::from_value(json!({
+            "hp": "12.5",
+            "speed": 4,
+            "legacy": true,
+            "drops": [{ "name": "Sword" }, 3]
+        }))
+        .unwrap();
+        assert!(migrate_properties(&schema, 0, "Boss", &mut props));
+        assert_eq!(
+            serde_json::to_value(&props).unwrap(),
+            json!({
+                "health": 12.5,
+                "stats": { "speed": 4 },
+                "drops": [{ "item": "Sword" }, 3]
+            })
+        );
+
+        // Already at the latest revision
+        assert!(!migrate_properties(&schema, 2, "Boss", &mut props));
+
+        // Migrations round-trip through the schema file
+        let json = serde_json::to_string(&schema).unwrap();
+        let reloaded = parse_schema(&json).unwrap();
+        assert_eq!(reloaded.version, 1);
+        assert_eq!(reloaded.revision, 2);
+        assert_eq!(reloaded.migrations, schema.migrations);
+        assert_eq!(reloaded.migrations_since(1).count(), 3);
+    }
+}
@@ -6,7 +6,12 @@ use std::collections::HashMap;
 /// The schema loaded from schema.json - defines all types and enums
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Schema {
+    /// Schema file format version
     pub version: u32,
+    /// Bumped by every recorded migration; instances saved at an older
+    /// revision are migrated when the project is opened
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub revision: u32,
     pub project: ProjectConfig,
     #[serde(default)]
     pub enums: HashMap<String, Vec<String>>,
@@ -14,6 +19,13 @@ pub struct Schema {
     pub data_types: HashMap<String, TypeDef>,
     #[serde(default)]
     pub embedded_types: HashMap<String, TypeDef>,
+    /// Recorded property migrations, oldest first (see [`Migration`](crate::Migration))
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub migrations: Vec<crate::Migration>,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 impl Schema {
@@ -24,6 +36,92 @@ impl Schema {
             .or_else(|| self.embedded_types.get(name))
     }
 
+    /// Get a type definition together with its name as stored in the schema
+    fn type_entry(&self, name: &str) -> Option<(&str, &TypeDef)> {
+        self.data_types
+            .get_key_value(name)
+            .or_else(|| self.embedded_types.get_key_value(name))
+            .map(|(key, def)| (key.as_str(), def))
+    }
+
+    /// Get the base types of a type, nearest base first
+    ///
+    /// Stops at a missing base or when the chain loops back on itself.
+    pub fn base_types(&self, name: &str) -> Vec<&str> {
+        let mut bases: Vec<&str> = Vec::new();
+        let mut current = self.get_type(name).and_then(|def| def.extends.as_deref());
+        while let Some(base) = current {
+            if base == name || bases.contains(&base) {
+                break;
+            }
+            let Some(def) = self.get_type(base) else {
+                break;
+            };
+            bases.push(base);
+            current = def.extends.as_deref();
+        }
+        bases
+    }
+
+    /// Get all properties of a type, including inherited ones
+    ///
+    /// Inherited properties come first (root base first). A property redeclared
+    /// by a derived type overrides the inherited one in place.
+    pub fn all_properties(&self, name: &str) -> Vec<&PropertyDef> {
+        self.resolved_properties(name)
+            .into_iter()
+            .map(|(_, prop)| prop)
+            .collect()
+    }
+
+    /// Get a copy of a type definition with its inherited properties merged in
+    pub fn resolved_type(&self, name: &str) -> Option<TypeDef> {
+        let mut def = self.get_type(name)?.clone();
+        def.properties = self.all_properties(name).into_iter().cloned().collect();
+        Some(def)
+    }
+
+    /// Like [`Schema::all_properties`], paired with the name of the type
+    /// declaring each property
+    pub fn resolved_properties(&self, name: &str) -> Vec<(&str, &PropertyDef)> {
+        let Some((name, _)) = self.type_entry(name) else {
+            return Vec::new();
+        };
+        let mut owners = self.base_types(name);
+        owners.reverse();
+        owners.push(name);
+
+        let mut resolved: Vec<(&str, &PropertyDef)> = Vec::new();
+        for owner in owners {
+            let Some(def) = self.get_type(owner) else {
+                continue;
+            };
+            for prop in &def.properties {
+                match resolved.iter_mut().find(|(_, p)| p.name == prop.name) {
+                    Some(slot) => *slot = (owner, prop),
+                    None => resolved.push((owner, prop)),
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Get the names of enums used by flags properties, sorted
+    pub fn flag_enum_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .data_types
+            .values()
+            .chain(self.embedded_types.values())
+            .flat_map(|def| &def.properties)
+            .filter(|prop| prop.is_flags())
+            .filter_map(|prop| prop.enum_type.as_deref())
+            .filter(|name| self.enums.contains_key(*name))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
     /// Get enum values by name
     pub fn get_enum(&self, name: &str) -> Option<&Vec<String>> {
         self.enums.get(name)
@@ -48,7 +146,7 @@ impl Schema {
         let mut names: Vec<&str> = self
             .data_types
             .iter()
-            .filter(|(_, def)| def.placeable)
+            .filter(|(_, def)| def.is_placeable())
             .map(|(name, _)| name.as_str())
             .collect();
         names.sort();
@@ -99,6 +197,25 @@ pub struct TypeDef {
     pub viewport_display: ViewportDisplayMode,
     #[serde(default)]
     pub properties: Vec<PropertyDef>,
+    /// Base type to inherit properties from (a type of the same kind)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Abstract types only exist to be extended and can't be placed or instantiated
+    #[serde(
+        default,
+        rename = "abstract",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub is_abstract: bool,
+}
+
+impl TypeDef {
+    /// Whether instances of this type can be placed in levels
+    ///
+    /// Abstract types are never placeable, whatever their `placeable` flag says.
+    pub fn is_placeable(&self) -> bool {
+        self.placeable && !self.is_abstract
+    }
 }
 
 fn default_color() -> String {
@@ -114,6 +231,8 @@ impl Default for TypeDef {
             marker_size: None,
             viewport_display: ViewportDisplayMode::default(),
             properties: Vec::new(),
+            extends: None,
+            is_abstract: false,
         }
     }
 }
@@ -129,6 +248,8 @@ pub struct PropertyDef {
     pub default: Option<serde_json::Value>,
     pub min: Option<f64>,
     pub max: Option<f64>,
+    /// Condition over the instance's other properties that decides whether
+    /// this property is shown, see [`Condition`](crate::Condition)
     #[serde(rename = "showIf")]
     pub show_if: Option<String>,
     #[serde(rename = "enumType")]
@@ -139,6 +260,99 @@ pub struct PropertyDef {
     pub item_type: Option<String>,
     #[serde(rename = "embeddedType")]
     pub embedded_type: Option<String>,
+    /// Whether this String/Multiline property is translatable (exported to string tables)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub localized: bool,
+    /// Regular expression a String/Multiline value must match (unanchored,
+    /// use `^...$` to match the whole value)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Minimum String/Multiline length in characters
+    #[serde(rename = "minLength", default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Maximum String/Multiline length in characters
+    #[serde(rename = "maxLength", default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Minimum number of items of an Array or flags Enum
+    #[serde(rename = "minItems", default, skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+    /// Maximum number of items of an Array or flags Enum
+    #[serde(rename = "maxItems", default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// Values must not repeat between instances in this scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique: Option<UniqueScope>,
+    /// Enum property holding a set of values (an array of value names)
+    /// instead of a single value
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flags: bool,
+}
+
+impl PropertyDef {
+    /// Whether this is a multi-select (bit-flag) Enum property
+    pub fn is_flags(&self) -> bool {
+        self.flags && self.prop_type == PropType::Enum
+    }
+
+    /// Whether the property is shown, looking the other properties up with `get`
+    ///
+    /// Properties without a `showIf`, or with one that doesn't parse, are
+    /// always shown.
+    pub fn is_shown(&self, get: impl Fn(&str) -> Option<serde_json::Value>) -> bool {
+        match self.show_if.as_deref().map(str::trim) {
+            None | Some("") => true,
+            Some(source) => crate::Condition::parse(source).map_or(true, |c| c.evaluate(get)),
+        }
+    }
+
+    /// Short descriptions of the value constraints, e.g. "1-16 chars"
+    pub fn constraint_descriptions(&self) -> Vec<String> {
+        let mut descriptions = Vec::new();
+        if let Some(pattern) = &self.pattern {
+            descriptions.push(format!("pattern: {}", pattern));
+        }
+        if let Some(range) = describe_range(self.min_length, self.max_length, "chars") {
+            descriptions.push(range);
+        }
+        if let Some(range) = describe_range(self.min_items, self.max_items, "items") {
+            descriptions.push(range);
+        }
+        if let Some(scope) = self.unique {
+            descriptions.push(format!("unique per {}", scope.display_name()));
+        }
+        if self.is_flags() {
+            descriptions.push("flags".to_string());
+        }
+        descriptions
+    }
+}
+
+fn describe_range(min: Option<usize>, max: Option<usize>, unit: &str) -> Option<String> {
+    match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{}-{} {}", min, max, unit)),
+        (Some(min), None) => Some(format!(">= {} {}", min, unit)),
+        (None, Some(max)) => Some(format!("<= {} {}", max, unit)),
+        (None, None) => None,
+    }
+}
+
+/// Where the values of a `unique` property must not repeat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UniqueScope {
+    /// Across all instances in the project
+    Project,
+    /// Across the entities of each level (data instances count as one group)
+    Level,
+}
+
+impl UniqueScope {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            UniqueScope::Project => "project",
+            UniqueScope::Level => "level",
+        }
+    }
 }
 
 /// Property types supported by the schema
@@ -1,190 +1,827 @@
 //! Schema validation logic
 
-use crate::{Schema, SchemaError};
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::{
+    path_join, Condition, DiagnosticCode, Diagnostics, Literal, PropType, PropertyDef, Schema,
+    SchemaError, UniqueScope,
+};
+
+/// Array item types that aren't schema types
+const BUILTIN_ITEM_TYPES: [&str; 4] = ["String", "Int", "Float", "Bool"];
 
 /// Validate that the schema is internally consistent
+///
+/// Fails with the first error; use [`diagnose_schema`] to get all of them.
 pub fn validate_schema(schema: &Schema) -> Result<(), SchemaError> {
-    // Check that all enum references point to valid enums
+    diagnose_schema(schema).into_result()
+}
+
+/// Check the schema for consistency, collecting every problem
+///
+/// Paths are `/Type/property`.
+pub fn diagnose_schema(schema: &Schema) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    diagnose_inheritance(schema, &mut diagnostics);
+
     for (type_name, type_def) in schema.data_types.iter().chain(schema.embedded_types.iter()) {
+        let type_path = path_join("", type_name);
         for prop in &type_def.properties {
+            let path = path_join(&type_path, &prop.name);
+
+            // Check that all enum references point to valid enums
             if let Some(enum_type) = &prop.enum_type {
                 if !schema.enums.contains_key(enum_type) {
-                    return Err(SchemaError::ValidationError(format!(
-                        "Type '{}' property '{}' references unknown enum '{}'",
-                        type_name, prop.name, enum_type
-                    )));
+                    diagnostics.error(
+                        DiagnosticCode::UnknownEnum,
+                        path.clone(),
+                        format!(
+                            "Type '{}' property '{}' references unknown enum '{}'",
+                            type_name, prop.name, enum_type
+                        ),
+                    );
                 }
             }
 
             if let Some(ref_type) = &prop.ref_type {
                 if !schema.data_types.contains_key(ref_type) {
-                    return Err(SchemaError::ValidationError(format!(
-                        "Type '{}' property '{}' references unknown type '{}'",
-                        type_name, prop.name, ref_type
-                    )));
+                    diagnostics.error(
+                        DiagnosticCode::UnknownRefType,
+                        path.clone(),
+                        format!(
+                            "Type '{}' property '{}' references unknown type '{}'",
+                            type_name, prop.name, ref_type
+                        ),
+                    );
                 }
             }
 
             if let Some(embedded_type) = &prop.embedded_type {
                 if !schema.embedded_types.contains_key(embedded_type) {
-                    return Err(SchemaError::ValidationError(format!(
-                        "Type '{}' property '{}' references unknown embedded type '{}'",
-                        type_name, prop.name, embedded_type
-                    )));
+                    diagnostics.error(
+                        DiagnosticCode::UnknownEmbeddedType,
+                        path.clone(),
+                        format!(
+                            "Type '{}' property '{}' references unknown embedded type '{}'",
+                            type_name, prop.name, embedded_type
+                        ),
+                    );
+                }
+            }
+
+            if let Some(item_type) = &prop.item_type {
+                let known = BUILTIN_ITEM_TYPES.contains(&item_type.as_str())
+                    || schema.get_type(item_type).is_some()
+                    || schema.enums.contains_key(item_type);
+                if !known {
+                    diagnostics.warning(
+                        DiagnosticCode::UnknownItemType,
+                        path.clone(),
+                        format!(
+                            "Type '{}' property '{}' has unknown item type '{}', items aren't validated",
+                            type_name, prop.name, item_type
+                        ),
+                    );
                 }
             }
+
+            diagnose_constraints(schema, type_name, prop, &path, &mut diagnostics);
+            diagnostics
+                .items
+                .extend(diagnose_condition(schema, type_name, prop).items);
         }
     }
 
-    Ok(())
+    diagnostics
 }
 
-/// Validate an entity instance against the schema
-pub fn validate_instance(
+/// Check that a property's constraints are valid and apply to its type
+fn diagnose_constraints(
     schema: &Schema,
     type_name: &str,
-    properties: &std::collections::HashMap<String, serde_json::Value>,
-) -> Result<(), SchemaError> {
-    let type_def = schema
-        .get_type(type_name)
-        .ok_or_else(|| SchemaError::ValidationError(format!("Unknown type: {}", type_name)))?;
+    prop: &PropertyDef,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    let ignored = |diagnostics: &mut Diagnostics, constraint: &str| {
+        diagnostics.warning(
+            DiagnosticCode::InvalidConstraint,
+            path.to_string(),
+            format!(
+                "Type '{}' property '{}' is {}, '{}' is ignored",
+                type_name,
+                prop.name,
+                prop.prop_type.display_name(),
+                constraint
+            ),
+        );
+    };
+    let is_string = matches!(prop.prop_type, PropType::String | PropType::Multiline);
+    let has_items = prop.prop_type == PropType::Array || prop.is_flags();
 
-    // Check required properties are present
-    for prop_def in &type_def.properties {
-        if prop_def.required && !properties.contains_key(&prop_def.name) {
-            return Err(SchemaError::ValidationError(format!(
-                "Missing required property '{}' for type '{}'",
-                prop_def.name, type_name
-            )));
+    if let Some(pattern) = &prop.pattern {
+        if !is_string {
+            ignored(diagnostics, "pattern");
+        } else if let Err(e) = pattern_matches(pattern, "") {
+            diagnostics.error(
+                DiagnosticCode::InvalidConstraint,
+                path.to_string(),
+                format!(
+                    "Type '{}' property '{}' has an invalid pattern: {}",
+                    type_name, prop.name, e
+                ),
+            );
         }
     }
-
-    // Validate property values
-    for (prop_name, value) in properties {
-        if let Some(prop_def) = type_def.properties.iter().find(|p| &p.name == prop_name) {
-            validate_property_value(schema, prop_def, value)?;
+    if !is_string && (prop.min_length.is_some() || prop.max_length.is_some()) {
+        ignored(diagnostics, "minLength/maxLength");
+    }
+    if !has_items && (prop.min_items.is_some() || prop.max_items.is_some()) {
+        ignored(diagnostics, "minItems/maxItems");
+    }
+    for (min, max, what) in [
+        (prop.min_length, prop.max_length, "length"),
+        (prop.min_items, prop.max_items, "item count"),
+    ] {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                diagnostics.error(
+                    DiagnosticCode::InvalidConstraint,
+                    path.to_string(),
+                    format!(
+                        "Type '{}' property '{}' has a minimum {} above its maximum",
+                        type_name, prop.name, what
+                    ),
+                );
+            }
         }
     }
 
-    Ok(())
+    // Only single scalar values can be compared for uniqueness
+    let uniqueable = match prop.prop_type {
+        PropType::String | PropType::Int | PropType::Ref => true,
+        PropType::Enum => !prop.is_flags(),
+        _ => false,
+    };
+    if prop.unique.is_some() && !uniqueable {
+        ignored(diagnostics, "unique");
+    }
+
+    if prop.flags {
+        if prop.prop_type != PropType::Enum {
+            ignored(diagnostics, "flags");
+        } else if let Some(values) = prop.enum_type.as_ref().and_then(|e| schema.get_enum(e)) {
+            // Generated flag sets store one bit per value in a u64
+            if values.len() > 64 {
+                diagnostics.error(
+                    DiagnosticCode::InvalidConstraint,
+                    path.to_string(),
+                    format!(
+                        "Type '{}' property '{}' uses flags with {} enum values (at most 64)",
+                        type_name,
+                        prop.name,
+                        values.len()
+                    ),
+                );
+            }
+        }
+    }
 }
 
-/// Validate a single property value against its definition
-fn validate_property_value(
-    schema: &Schema,
-    prop_def: &crate::PropertyDef,
-    value: &serde_json::Value,
-) -> Result<(), SchemaError> {
-    use crate::PropType;
+/// Check a property's `showIf` against the properties of its type
+///
+/// Conditions that don't parse are errors. Unknown properties and comparisons
+/// that can never match are warnings, so schemas written when `showIf` was free
+/// text still load. Paths are `/Type/property`.
+pub fn diagnose_condition(schema: &Schema, type_name: &str, prop: &PropertyDef) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    let Some(source) = prop.show_if.as_deref().map(str::trim) else {
+        return diagnostics;
+    };
+    if source.is_empty() {
+        return diagnostics;
+    }
+    let path = path_join(&path_join("", type_name), &prop.name);
+    let mut report = |error: bool, message: String| {
+        let message = format!(
+            "Type '{}' property '{}' showIf: {}",
+            type_name, prop.name, message
+        );
+        if error {
+            diagnostics.error(DiagnosticCode::InvalidCondition, path.clone(), message);
+        } else {
+            diagnostics.warning(DiagnosticCode::InvalidCondition, path.clone(), message);
+        }
+    };
 
-    match prop_def.prop_type {
-        PropType::String | PropType::Multiline => {
-            if !value.is_string() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a string",
-                    prop_def.name
-                )));
-            }
+    let condition = match Condition::parse(source) {
+        Ok(condition) => condition,
+        Err(e) => {
+            report(true, e.to_string());
+            return diagnostics;
         }
-        PropType::Int => {
-            if let Some(n) = value.as_i64() {
-                if let Some(min) = prop_def.min {
-                    if (n as f64) < min {
-                        return Err(SchemaError::ValidationError(format!(
-                            "Property '{}' must be >= {}",
-                            prop_def.name, min
-                        )));
-                    }
-                }
-                if let Some(max) = prop_def.max {
-                    if (n as f64) > max {
-                        return Err(SchemaError::ValidationError(format!(
-                            "Property '{}' must be <= {}",
-                            prop_def.name, max
-                        )));
+    };
+
+    let properties = schema.all_properties(type_name);
+    for term in condition.terms() {
+        if term.property == prop.name {
+            report(
+                false,
+                "tests the property itself, so it can't be shown again once hidden".to_string(),
+            );
+            continue;
+        }
+        let Some(target) = properties.iter().find(|p| p.name == term.property) else {
+            report(false, format!("unknown property '{}'", term.property));
+            continue;
+        };
+        let Some(op) = term.op else {
+            continue;
+        };
+        if op.is_ordering() && !matches!(target.prop_type, PropType::Int | PropType::Float) {
+            report(
+                false,
+                format!(
+                    "'{}' is {}, only numbers can be compared with <, <=, > and >=",
+                    target.name,
+                    target.prop_type.display_name()
+                ),
+            );
+            continue;
+        }
+        let enum_values = target
+            .enum_type
+            .as_ref()
+            .filter(|_| target.prop_type == PropType::Enum && !target.is_flags())
+            .and_then(|e| schema.get_enum(e));
+        if let Some(enum_values) = enum_values {
+            for value in term.values {
+                if let Literal::String(value) = value {
+                    if !enum_values.contains(value) {
+                        report(
+                            false,
+                            format!("'{}' is not a value of '{}'", value, target.name),
+                        );
                     }
                 }
-            } else if !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be an integer",
-                    prop_def.name
-                )));
             }
         }
-        PropType::Float => {
-            if let Some(n) = value.as_f64() {
-                if let Some(min) = prop_def.min {
-                    if n < min {
-                        return Err(SchemaError::ValidationError(format!(
-                            "Property '{}' must be >= {}",
-                            prop_def.name, min
-                        )));
-                    }
+    }
+
+    diagnostics
+}
+
+/// Most patterns kept compiled at once; the cache is cleared when it fills up,
+/// so patterns typed into the editor one keystroke at a time don't pile up
+const MAX_CACHED_PATTERNS: usize = 256;
+
+thread_local! {
+    /// Compiled patterns, since instances are validated every frame in the editor
+    static PATTERNS: RefCell<HashMap<String, Result<Regex, String>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Check a string against a regex pattern, failing if the pattern is invalid
+fn pattern_matches(pattern: &str, value: &str) -> Result<bool, String> {
+    PATTERNS.with(|patterns| {
+        let mut patterns = patterns.borrow_mut();
+        if patterns.len() >= MAX_CACHED_PATTERNS && !patterns.contains_key(pattern) {
+            patterns.clear();
+        }
+        let regex = patterns
+            .entry(pattern.to_string())
+            .or_insert_with(|| Regex::new(pattern).map_err(|e| e.to_string()));
+        match regex {
+            Ok(regex) => Ok(regex.is_match(value)),
+            Err(e) => Err(e.clone()),
+        }
+    })
+}
+
+/// Check that `extends` chains resolve, don't loop and keep property types
+fn diagnose_inheritance(schema: &Schema, diagnostics: &mut Diagnostics) {
+    for types in [&schema.data_types, &schema.embedded_types] {
+        for (type_name, type_def) in types {
+            let Some(base) = &type_def.extends else {
+                continue;
+            };
+            let type_path = path_join("", type_name);
+            if !types.contains_key(base) {
+                diagnostics.error(
+                    DiagnosticCode::UnknownBaseType,
+                    type_path,
+                    format!("Type '{}' extends unknown type '{}'", type_name, base),
+                );
+                continue;
+            }
+
+            // Follow the chain until it ends or comes back to a visited type
+            let mut chain = vec![type_name.as_str()];
+            let mut current = type_def.extends.as_deref();
+            while let Some(name) = current {
+                if chain.contains(&name) {
+                    chain.push(name);
+                    diagnostics.error(
+                        DiagnosticCode::CyclicExtends,
+                        type_path.clone(),
+                        format!(
+                            "Type '{}' has a cyclic extends chain: {}",
+                            type_name,
+                            chain.join(" -> ")
+                        ),
+                    );
+                    break;
                 }
-                if let Some(max) = prop_def.max {
-                    if n > max {
-                        return Err(SchemaError::ValidationError(format!(
-                            "Property '{}' must be <= {}",
-                            prop_def.name, max
-                        )));
-                    }
+                chain.push(name);
+                current = types.get(name).and_then(|def| def.extends.as_deref());
+            }
+        }
+    }
+
+    // Overrides may change defaults and constraints, but not the property type
+    for (type_name, type_def) in schema.data_types.iter().chain(schema.embedded_types.iter()) {
+        let Some(base) = &type_def.extends else {
+            continue;
+        };
+        let inherited = schema.resolved_properties(base);
+        for prop in &type_def.properties {
+            if let Some((owner, base_prop)) = inherited.iter().find(|(_, p)| p.name == prop.name) {
+                if base_prop.prop_type != prop.prop_type {
+                    diagnostics.error(
+                        DiagnosticCode::OverrideTypeChanged,
+                        path_join(&path_join("", type_name), &prop.name),
+                        format!(
+                            "Type '{}' property '{}' overrides a {} property from '{}' as {}",
+                            type_name,
+                            prop.name,
+                            base_prop.prop_type.display_name(),
+                            owner,
+                            prop.prop_type.display_name()
+                        ),
+                    );
                 }
-            } else if !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a number",
-                    prop_def.name
-                )));
             }
         }
-        PropType::Bool => {
-            if !value.is_boolean() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a boolean",
-                    prop_def.name
-                )));
+    }
+}
+
+/// Validate an entity instance against the schema
+///
+/// Fails with the first error; use [`diagnose_instance`] to get all of them.
+pub fn validate_instance(
+    schema: &Schema,
+    type_name: &str,
+    properties: &HashMap<String, serde_json::Value>,
+) -> Result<(), SchemaError> {
+    diagnose_instance(schema, type_name, properties).into_result()
+}
+
+/// Check an entity instance against the schema, collecting every problem
+///
+/// Embedded objects and array items are checked recursively. Paths are
+/// `/Type/property`, extended with array indices and embedded property names
+/// (`/Type/loot/2/count`).
+pub fn diagnose_instance(
+    schema: &Schema,
+    type_name: &str,
+    properties: &HashMap<String, serde_json::Value>,
+) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    let path = path_join("", type_name);
+
+    match schema.get_type(type_name) {
+        None => diagnostics.error(
+            DiagnosticCode::UnknownType,
+            path,
+            format!("Unknown type: {}", type_name),
+        ),
+        Some(type_def) if type_def.is_abstract => diagnostics.error(
+            DiagnosticCode::AbstractType,
+            path,
+            format!("Type '{}' is abstract", type_name),
+        ),
+        Some(_) => check_properties(
+            schema,
+            type_name,
+            &path,
+            |name| properties.get(name),
+            &mut diagnostics,
+        ),
+    }
+
+    diagnostics
+}
+
+/// Check the properties of a type, looking values up with `get`
+fn check_properties<'a>(
+    schema: &Schema,
+    type_name: &str,
+    path: &str,
+    get: impl Fn(&str) -> Option<&'a serde_json::Value>,
+    diagnostics: &mut Diagnostics,
+) {
+    for prop_def in schema.all_properties(type_name) {
+        let prop_path = path_join(path, &prop_def.name);
+        match get(&prop_def.name) {
+            Some(value) => check_property_value(schema, prop_def, value, &prop_path, diagnostics),
+            // Hidden properties can't be filled in, so they aren't required
+            None if prop_def.required && prop_def.is_shown(|name| get(name).cloned()) => {
+                diagnostics.error(
+                    DiagnosticCode::MissingRequired,
+                    prop_path,
+                    format!(
+                        "Missing required property '{}' for type '{}'",
+                        prop_def.name, type_name
+                    ),
+                )
             }
+            None => {}
         }
-        PropType::Enum => {
-            if let Some(s) = value.as_str() {
-                if let Some(enum_type) = &prop_def.enum_type {
-                    if let Some(enum_values) = schema.get_enum(enum_type) {
-                        if !enum_values.contains(&s.to_string()) {
-                            return Err(SchemaError::ValidationError(format!(
-                                "Property '{}' must be one of: {:?}",
-                                prop_def.name, enum_values
-                            )));
+    }
+}
+
+/// Check a single property value against its definition
+fn check_property_value(
+    schema: &Schema,
+    prop_def: &PropertyDef,
+    value: &serde_json::Value,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    let name = prop_def.name.as_str();
+    match prop_def.prop_type {
+        PropType::String | PropType::Multiline => match value.as_str() {
+            Some(s) => check_string(prop_def, s, path, diagnostics),
+            None => expect_kind(false, value, name, "a string", path, diagnostics),
+        },
+        PropType::Int => match value.as_i64() {
+            Some(n) => check_range(prop_def, n as f64, path, diagnostics),
+            None => expect_kind(false, value, name, "an integer", path, diagnostics),
+        },
+        PropType::Float => match value.as_f64() {
+            Some(n) => check_range(prop_def, n, path, diagnostics),
+            None => expect_kind(false, value, name, "a number", path, diagnostics),
+        },
+        PropType::Bool => {
+            expect_kind(
+                value.is_boolean(),
+                value,
+                name,
+                "a boolean",
+                path,
+                diagnostics,
+            );
+        }
+        PropType::Enum if prop_def.is_flags() => match value.as_array() {
+            Some(items) => {
+                check_item_count(prop_def, items.len(), path, diagnostics);
+                for (index, item) in items.iter().enumerate() {
+                    let label = format!("{}[{}]", name, index);
+                    let item_path = path_join(path, &index.to_string());
+                    match (item.as_str(), &prop_def.enum_type) {
+                        (Some(s), Some(enum_type)) => {
+                            check_enum_value(schema, enum_type, s, &label, &item_path, diagnostics);
                         }
+                        (Some(_), None) => {}
+                        (None, _) => expect_kind(
+                            false,
+                            item,
+                            &label,
+                            "a string enum value",
+                            &item_path,
+                            diagnostics,
+                        ),
                     }
                 }
-            } else if !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a string enum value",
-                    prop_def.name
-                )));
             }
-        }
-        PropType::Array => {
-            if !value.is_array() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be an array",
-                    prop_def.name
-                )));
+            None => expect_kind(
+                false,
+                value,
+                name,
+                "an array of enum values",
+                path,
+                diagnostics,
+            ),
+        },
+        PropType::Enum => match value.as_str() {
+            Some(s) => {
+                if let Some(enum_type) = &prop_def.enum_type {
+                    check_enum_value(schema, enum_type, s, name, path, diagnostics);
+                }
             }
-        }
-        PropType::Ref => {
-            if !value.is_string() && !value.is_null() {
-                return Err(SchemaError::ValidationError(format!(
-                    "Property '{}' must be a reference string",
-                    prop_def.name
-                )));
+            None => expect_kind(false, value, name, "a string enum value", path, diagnostics),
+        },
+        PropType::Array => match value.as_array() {
+            Some(items) => {
+                check_item_count(prop_def, items.len(), path, diagnostics);
+                if let Some(item_type) = &prop_def.item_type {
+                    for (index, item) in items.iter().enumerate() {
+                        check_item(
+                            schema,
+                            item_type,
+                            item,
+                            &format!("{}[{}]", name, index),
+                            &path_join(path, &index.to_string()),
+                            diagnostics,
+                        );
+                    }
+                }
             }
+            None => expect_kind(false, value, name, "an array", path, diagnostics),
+        },
+        PropType::Ref => {
+            expect_kind(
+                value.is_string(),
+                value,
+                name,
+                "a reference string",
+                path,
+                diagnostics,
+            );
         }
-        // Other types (Point, Color, Sprite, Dialogue, Embedded) are more complex
+        PropType::Embedded => match value.as_object() {
+            Some(object) => {
+                if let Some(embedded_type) = &prop_def.embedded_type {
+                    check_properties(
+                        schema,
+                        embedded_type,
+                        path,
+                        |key| object.get(key),
+                        diagnostics,
+                    );
+                }
+            }
+            None => expect_kind(false, value, name, "an object", path, diagnostics),
+        },
+        // Other types (Point, Color, Sprite, Dialogue) are more complex
         // and validation is deferred to runtime
         _ => {}
     }
+}
+
+/// Check an array item against the array's item type
+fn check_item(
+    schema: &Schema,
+    item_type: &str,
+    item: &serde_json::Value,
+    label: &str,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    match item_type {
+        "String" => expect_kind(item.is_string(), item, label, "a string", path, diagnostics),
+        "Int" => expect_kind(item.is_i64(), item, label, "an integer", path, diagnostics),
+        "Float" => expect_kind(item.is_number(), item, label, "a number", path, diagnostics),
+        "Bool" => expect_kind(
+            item.is_boolean(),
+            item,
+            label,
+            "a boolean",
+            path,
+            diagnostics,
+        ),
+        _ if schema.embedded_types.contains_key(item_type) => match item.as_object() {
+            Some(object) => {
+                check_properties(schema, item_type, path, |key| object.get(key), diagnostics);
+            }
+            None => expect_kind(false, item, label, "an object", path, diagnostics),
+        },
+        // Items of data types are references to instances
+        _ if schema.data_types.contains_key(item_type) => {
+            expect_kind(
+                item.is_string(),
+                item,
+                label,
+                "a reference string",
+                path,
+                diagnostics,
+            );
+        }
+        _ => match item.as_str() {
+            Some(s) if schema.enums.contains_key(item_type) => {
+                check_enum_value(schema, item_type, s, label, path, diagnostics);
+            }
+            _ => {}
+        },
+    }
+}
+
+/// Record a wrong-type error unless the value has the expected kind or is null
+fn expect_kind(
+    ok: bool,
+    value: &serde_json::Value,
+    name: &str,
+    expected: &str,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    if !ok && !value.is_null() {
+        diagnostics.error(
+            DiagnosticCode::WrongType,
+            path.to_string(),
+            format!("Property '{}' must be {}", name, expected),
+        );
+    }
+}
+
+/// Check a number against the property's min/max
+fn check_range(prop_def: &PropertyDef, n: f64, path: &str, diagnostics: &mut Diagnostics) {
+    if let Some(min) = prop_def.min {
+        if n < min {
+            diagnostics.error(
+                DiagnosticCode::BelowMin,
+                path.to_string(),
+                format!("Property '{}' must be >= {}", prop_def.name, min),
+            );
+        }
+    }
+    if let Some(max) = prop_def.max {
+        if n > max {
+            diagnostics.error(
+                DiagnosticCode::AboveMax,
+                path.to_string(),
+                format!("Property '{}' must be <= {}", prop_def.name, max),
+            );
+        }
+    }
+}
+
+/// Check a string against the property's pattern and length limits
+fn check_string(prop_def: &PropertyDef, s: &str, path: &str, diagnostics: &mut Diagnostics) {
+    if let Some(pattern) = &prop_def.pattern {
+        // An invalid pattern is a schema problem, reported by diagnose_schema
+        if pattern_matches(pattern, s) == Ok(false) {
+            diagnostics.error(
+                DiagnosticCode::PatternMismatch,
+                path.to_string(),
+                format!("Property '{}' must match {}", prop_def.name, pattern),
+            );
+        }
+    }
+    let length = s.chars().count();
+    if let Some(min) = prop_def.min_length {
+        if length < min {
+            diagnostics.error(
+                DiagnosticCode::TooShort,
+                path.to_string(),
+                format!(
+                    "Property '{}' must be at least {} characters",
+                    prop_def.name, min
+                ),
+            );
+        }
+    }
+    if let Some(max) = prop_def.max_length {
+        if length > max {
+            diagnostics.error(
+                DiagnosticCode::TooLong,
+                path.to_string(),
+                format!(
+                    "Property '{}' must be at most {} characters",
+                    prop_def.name, max
+                ),
+            );
+        }
+    }
+}
+
+/// Check an array's length against the property's min/max items
+fn check_item_count(
+    prop_def: &PropertyDef,
+    count: usize,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    if let Some(min) = prop_def.min_items {
+        if count < min {
+            diagnostics.error(
+                DiagnosticCode::TooFewItems,
+                path.to_string(),
+                format!("Property '{}' needs at least {} items", prop_def.name, min),
+            );
+        }
+    }
+    if let Some(max) = prop_def.max_items {
+        if count > max {
+            diagnostics.error(
+                DiagnosticCode::TooManyItems,
+                path.to_string(),
+                format!("Property '{}' allows at most {} items", prop_def.name, max),
+            );
+        }
+    }
+}
+
+/// Check that a string is one of an enum's values (unknown enums are a schema problem)
+fn check_enum_value(
+    schema: &Schema,
+    enum_type: &str,
+    value: &str,
+    name: &str,
+    path: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    if let Some(enum_values) = schema.get_enum(enum_type) {
+        if !enum_values.iter().any(|v| v == value) {
+            diagnostics.error(
+                DiagnosticCode::InvalidEnumValue,
+                path.to_string(),
+                format!("Property '{}' must be one of: {:?}", name, enum_values),
+            );
+        }
+    }
+}
 
-    Ok(())
+/// Key of one value of a unique property: scope, declaring type, property, value
+type UniqueKey = (Option<String>, String, String, String);
+
+/// Finds values of `unique` properties that repeat within their scope
+///
+/// [`add`](Self::add) every instance first, then call
+/// [`diagnose`](Self::diagnose) for the instances to check. Values are grouped
+/// by the type declaring the property, so an inherited unique property must be
+/// unique across all derived types. Null and empty values are never duplicates.
+#[derive(Debug, Clone, Default)]
+pub struct UniqueValues {
+    counts: HashMap<UniqueKey, usize>,
+}
+
+impl UniqueValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an instance's unique values
+    ///
+    /// `level` identifies the level an entity is placed in; use `None` for
+    /// data instances.
+    pub fn add(
+        &mut self,
+        schema: &Schema,
+        level: Option<&str>,
+        type_name: &str,
+        properties: &HashMap<String, serde_json::Value>,
+    ) {
+        for (key, _) in unique_keys(schema, level, type_name, properties) {
+            *self.counts.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Report the instance's unique values that other instances also use
+    ///
+    /// Paths are `/Type/property`, like [`diagnose_instance`].
+    pub fn diagnose(
+        &self,
+        schema: &Schema,
+        level: Option<&str>,
+        type_name: &str,
+        properties: &HashMap<String, serde_json::Value>,
+    ) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        for (key, prop) in unique_keys(schema, level, type_name, properties) {
+            let count = self.counts.get(&key).copied().unwrap_or(0);
+            if count > 1 {
+                let scope = prop.unique.map_or("project", |s| s.display_name());
+                diagnostics.error(
+                    DiagnosticCode::Duplicate,
+                    path_join(&path_join("", type_name), &prop.name),
+                    format!(
+                        "Property '{}' value {} is used {} times in this {}",
+                        prop.name, key.3, count, scope
+                    ),
+                );
+            }
+        }
+        diagnostics
+    }
+}
+
+/// The unique values of an instance with their keys
+fn unique_keys<'a>(
+    schema: &'a Schema,
+    level: Option<&str>,
+    type_name: &str,
+    properties: &HashMap<String, serde_json::Value>,
+) -> Vec<(UniqueKey, &'a PropertyDef)> {
+    let mut keys = Vec::new();
+    for (owner, prop) in schema.resolved_properties(type_name) {
+        let Some(scope) = prop.unique else {
+            continue;
+        };
+        let value = match properties.get(&prop.name) {
+            None | Some(serde_json::Value::Null) => continue,
+            Some(serde_json::Value::String(s)) if s.is_empty() => continue,
+            Some(value) => value.to_string(),
+        };
+        let scope = match scope {
+            UniqueScope::Project => None,
+            UniqueScope::Level => level.map(str::to_string),
+        };
+        keys.push(((scope, owner.to_string(), prop.name.clone(), value), prop));
+    }
+    keys
 }
 
 #[cfg(test)]
@@ -291,4 +928,362 @@ mod tests {
         let result = validate_instance(&schema, "Item", &props);
         assert!(result.is_ok());
     }
+
+    const INHERITANCE_SCHEMA: &str = r#"{
+        "version": 1,
+        "project": { "name": "Test" },
+        "data_types": {
+            "Enemy": {
+                "abstract": true,
+                "properties": [
+                    { "name": "health", "type": "int", "default": 10, "min": 0 },
+                    { "name": "faction", "type": "string" }
+                ]
+            },
+            "Flyer": {
+                "extends": "Enemy",
+                "abstract": true,
+                "properties": [
+                    { "name": "altitude", "type": "float" }
+                ]
+            },
+            "Bat": {
+                "extends": "Flyer",
+                "placeable": true,
+                "properties": [
+                    { "name": "health", "type": "int", "default": 3, "max": 5 }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_inherited_properties() {
+        let schema = parse_schema(INHERITANCE_SCHEMA).unwrap();
+        assert_eq!(schema.base_types("Bat"), vec!["Flyer", "Enemy"]);
+
+        let resolved = schema.resolved_properties("Bat");
+        let names: Vec<_> = resolved
+            .iter()
+            .map(|(owner, prop)| (*owner, prop.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Bat", "health"),
+                ("Enemy", "faction"),
+                ("Flyer", "altitude")
+            ]
+        );
+        assert_eq!(schema.placeable_type_names(), vec!["Bat"]);
+
+        // The override's max applies, the base's min is replaced
+        let mut props = std::collections::HashMap::new();
+        props.insert("health".to_string(), serde_json::json!(6));
+        assert!(validate_instance(&schema, "Bat", &props).is_err());
+        props.insert("health".to_string(), serde_json::json!(-1));
+        assert!(validate_instance(&schema, "Bat", &props).is_ok());
+
+        assert!(validate_instance(&schema, "Enemy", &props).is_err());
+    }
+
+    #[test]
+    fn test_invalid_inheritance() {
+        let cyclic = INHERITANCE_SCHEMA.replace(
+            r#""abstract": true,
+                "properties": [
+                    { "name": "health""#,
+            r#""abstract": true,
+                "extends": "Bat",
+                "properties": [
+                    { "name": "health""#,
+        );
+        let err = parse_schema(&cyclic).unwrap_err().to_string();
+        assert!(err.contains("cyclic"), "{}", err);
+
+        let missing = INHERITANCE_SCHEMA.replace(r#""extends": "Enemy""#, r#""extends": "Boss""#);
+        let err = parse_schema(&missing).unwrap_err().to_string();
+        assert!(err.contains("unknown type 'Boss'"), "{}", err);
+
+        let retyped = INHERITANCE_SCHEMA.replace(
+            r#"{ "name": "health", "type": "int", "default": 3"#,
+            r#"{ "name": "health", "type": "string", "default": 3"#,
+        );
+        let err = parse_schema(&retyped).unwrap_err().to_string();
+        assert!(err.contains("overrides"), "{}", err);
+    }
+
+    #[test]
+    fn test_diagnose_instance_collects_all() {
+        let schema = parse_schema(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "enums": { "Rarity": ["Common", "Rare"] },
+            "data_types": {
+                "Chest": {
+                    "properties": [
+                        { "name": "name", "type": "string", "required": true },
+                        { "name": "gold", "type": "int", "min": 0 },
+                        { "name": "loot", "type": "array", "itemType": "Drop" },
+                        { "name": "tags", "type": "array", "itemType": "Int" },
+                        { "name": "lock", "type": "embedded", "embeddedType": "Lock" }
+                    ]
+                }
+            },
+            "embedded_types": {
+                "Drop": {
+                    "properties": [
+                        { "name": "count", "type": "int", "max": 10 },
+                        { "name": "rarity", "type": "enum", "enumType": "Rarity" }
+                    ]
+                },
+                "Lock": {
+                    "properties": [
+                        { "name": "key", "type": "ref", "refType": "Chest", "required": true }
+                    ]
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let mut props = std::collections::HashMap::new();
+        props.insert("gold".to_string(), serde_json::json!(-5));
+        props.insert(
+            "loot".to_string(),
+            serde_json::json!([{ "count": 3, "rarity": "Rare" }, { "count": 11, "rarity": "Epic" }, 4]),
+        );
+        props.insert("tags".to_string(), serde_json::json!([1, "two"]));
+        props.insert("lock".to_string(), serde_json::json!({}));
+
+        let diagnostics = diagnose_instance(&schema, "Chest", &props);
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.path.as_str(), d.code))
+            .collect();
+        assert_eq!(found.len(), 7, "{:?}", found);
+        for expected in [
+            ("/Chest/name", DiagnosticCode::MissingRequired),
+            ("/Chest/gold", DiagnosticCode::BelowMin),
+            ("/Chest/loot/1/count", DiagnosticCode::AboveMax),
+            ("/Chest/loot/1/rarity", DiagnosticCode::InvalidEnumValue),
+            ("/Chest/loot/2", DiagnosticCode::WrongType),
+            ("/Chest/tags/1", DiagnosticCode::WrongType),
+            ("/Chest/lock/key", DiagnosticCode::MissingRequired),
+        ] {
+            assert!(found.contains(&expected), "missing {:?}", expected);
+        }
+        assert_eq!(diagnostics.at("/Chest/loot").count(), 3);
+    }
+
+    #[test]
+    fn test_diagnose_schema_collects_all() {
+        let schema: crate::Schema = serde_json::from_str(
+            r#"{
+            "version": 1,
+            "project": { "name": "Test" },
+            "data_types": {
+                "Item": {
+                    "extends": "Missing",
+                    "properties": [
+                        { "name": "kind", "type": "enum", "enumType": "Nope" },
+                        { "name": "owner", "type": "ref", "refType": "Nobody" },
+                        { "name": "bag", "type": "array", "itemType": "Thing" }
+                    ]
+                }
+            }
+        }"#,
+        )
+        .unwrap();
+
+        let diagnostics = diagnose_schema(&schema);
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(diagnostics.errors().count(), 3);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.path == "/Item/bag" && d.code == DiagnosticCode::UnknownItemType));
+    }
+
+    const CONSTRAINT_SCHEMA: &str = r#"{
+        "version": 1,
+        "project": { "name": "Test" },
+        "enums": { "Element": ["Fire", "Ice", "Poison"] },
+        "data_types": {
+            "Door": {
+                "placeable": true,
+                "properties": [
+                    { "name": "doorId", "type": "string", "pattern": "^[A-Z][0-9]+$", "minLength": 2, "maxLength": 4, "unique": "level" },
+                    { "name": "code", "type": "int", "unique": "project" },
+                    { "name": "keys", "type": "array", "itemType": "String", "minItems": 1, "maxItems": 2 },
+                    { "name": "resists", "type": "enum", "enumType": "Element", "flags": true, "maxItems": 2 }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_validate_constraints() {
+        let schema = parse_schema(CONSTRAINT_SCHEMA).unwrap();
+        assert_eq!(schema.flag_enum_names(), vec!["Element"]);
+
+        let mut props = std::collections::HashMap::new();
+        props.insert("doorId".to_string(), serde_json::json!("A12"));
+        props.insert("keys".to_string(), serde_json::json!(["red"]));
+        props.insert("resists".to_string(), serde_json::json!(["Fire", "Ice"]));
+        assert!(diagnose_instance(&schema, "Door", &props).is_empty());
+
+        props.insert("doorId".to_string(), serde_json::json!("a12345"));
+        props.insert("keys".to_string(), serde_json::json!([]));
+        props.insert(
+            "resists".to_string(),
+            serde_json::json!(["Fire", "Ice", "Lava"]),
+        );
+        let codes: Vec<_> = diagnose_instance(&schema, "Door", &props)
+            .iter()
+            .map(|d| d.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                DiagnosticCode::PatternMismatch,
+                DiagnosticCode::TooLong,
+                DiagnosticCode::TooFewItems,
+                DiagnosticCode::TooManyItems,
+                DiagnosticCode::InvalidEnumValue,
+            ]
+        );
+
+        // A flags value must be an array
+        props.insert("resists".to_string(), serde_json::json!("Fire"));
+        assert!(diagnose_instance(&schema, "Door", &props)
+            .iter()
+            .any(|d| d.path == "/Door/resists" && d.code == DiagnosticCode::WrongType));
+    }
+
+    #[test]
+    fn test_invalid_constraints() {
+        let bad_pattern = CONSTRAINT_SCHEMA.replace("^[A-Z][0-9]+$", "([");
+        let err = parse_schema(&bad_pattern).unwrap_err().to_string();
+        assert!(err.contains("invalid pattern"), "{}", err);
+
+        let inverted = CONSTRAINT_SCHEMA.replace(r#""minItems": 1"#, r#""minItems": 3"#);
+        let err = parse_schema(&inverted).unwrap_err().to_string();
+        assert!(err.contains("minimum item count"), "{}", err);
+
+        // Constraints that don't apply to the type are only warned about
+        let misplaced = CONSTRAINT_SCHEMA.replace(
+            r#""type": "int", "unique": "project""#,
+            r#""type": "int", "unique": "project", "pattern": "x""#,
+        );
+        let schema: crate::Schema = serde_json::from_str(&misplaced).unwrap();
+        let diagnostics = diagnose_schema(&schema);
+        assert!(!diagnostics.has_errors());
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.path == "/Door/code" && d.code == DiagnosticCode::InvalidConstraint));
+    }
+
+    #[test]
+    fn test_pattern_cache_is_bounded() {
+        for i in 0..MAX_CACHED_PATTERNS * 2 {
+            assert_eq!(pattern_matches(&format!("^a{{{}}}$", i), "a"), Ok(i == 1));
+        }
+        assert!(PATTERNS.with(|patterns| patterns.borrow().len()) <= MAX_CACHED_PATTERNS);
+        assert!(pattern_matches("(", "").is_err());
+    }
+
+    #[test]
+    fn test_unique_values() {
+        let schema = parse_schema(CONSTRAINT_SCHEMA).unwrap();
+        let door = |id: &str, code: i64| {
+            let mut props = std::collections::HashMap::new();
+            props.insert("doorId".to_string(), serde_json::json!(id));
+            props.insert("code".to_string(), serde_json::json!(code));
+            props
+        };
+        let doors = [
+            (Some("level1"), door("A1", 1)),
+            (Some("level1"), door("A1", 2)),
+            (Some("level2"), door("A1", 2)),
+            (Some("level2"), door("", 3)),
+            (Some("level2"), door("", 4)),
+        ];
+
+        let mut unique = UniqueValues::new();
+        for (level, props) in &doors {
+            unique.add(&schema, *level, "Door", props);
+        }
+        let found: Vec<Vec<&str>> = doors
+            .iter()
+            .map(|(level, props)| {
+                unique
+                    .diagnose(&schema, *level, "Door", props)
+                    .items
+                    .into_iter()
+                    .map(|d| {
+                        if d.path.ends_with("doorId") {
+                            "id"
+                        } else {
+                            "code"
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![vec!["id"], vec!["id", "code"], vec!["code"], vec![], vec![]]
+        );
+    }
+
+    const SHOW_IF_SCHEMA: &str = r#"{
+        "version": 1,
+        "project": { "name": "Test" },
+        "enums": { "Kind": ["Weapon", "Armor"] },
+        "data_types": {
+            "Item": {
+                "properties": [
+                    { "name": "kind", "type": "enum", "enumType": "Kind" },
+                    { "name": "damage", "type": "int", "required": true, "showIf": "kind == Weapon" },
+                    { "name": "defense", "type": "int", "showIf": "kind in [Armor] && damage < 1" }
+                ]
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_show_if_required() {
+        let schema = parse_schema(SHOW_IF_SCHEMA).unwrap();
+
+        let mut props = std::collections::HashMap::new();
+        props.insert("kind".to_string(), serde_json::json!("Armor"));
+        assert!(validate_instance(&schema, "Item", &props).is_ok());
+
+        props.insert("kind".to_string(), serde_json::json!("Weapon"));
+        let diagnostics = diagnose_instance(&schema, "Item", &props);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics.items[0].path, "/Item/damage");
+    }
+
+    #[test]
+    fn test_diagnose_condition() {
+        for (show_if, error) in [
+            ("kind == Weapon &&", true),
+            ("weight > 3", false),
+            ("kind == Shield", false),
+            ("kind > 3", false),
+            ("defense", false),
+        ] {
+            let schema = SHOW_IF_SCHEMA.replace("kind in [Armor] && damage < 1", show_if);
+            // Only errors keep a schema from loading
+            assert_eq!(crate::parse_schema(&schema).is_ok(), !error, "{}", show_if);
+            let schema: crate::Schema = serde_json::from_str(&schema).unwrap();
+            let diagnostics = diagnose_schema(&schema);
+            assert_eq!(diagnostics.len(), 1, "{}: {:?}", show_if, diagnostics);
+            assert_eq!(diagnostics.has_errors(), error, "{}", show_if);
+            assert_eq!(diagnostics.items[0].path, "/Item/defense");
+            assert_eq!(diagnostics.items[0].code, DiagnosticCode::InvalidCondition);
+        }
+    }
 }
@@ -61,6 +61,10 @@ struct ManualLoadingState {
 #[derive(Component)]
 struct DialogueDisplay;
 
+/// The entity whose `DialogueRunner` this demo drives by hand
+#[derive(Component)]
+struct Speaker;
+
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -68,6 +72,9 @@ fn setup(
 ) {
     commands.spawn(Camera2d);
 
+    // Dialogue state lives on the speaker entity
+    commands.spawn((Speaker, DialogueRunner::default()));
+
     // =========================================================================
     // Load the MapProject asset
     // =========================================================================
@@ -133,7 +140,7 @@ fn setup_dialogue_when_ready(
 fn handle_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     state: Res<ManualLoadingState>,
-    mut runner: ResMut<DialogueRunner>,
+    mut runner: Single<&mut DialogueRunner, With<Speaker>>,
 ) {
     let Some(tree) = &state.tree else { return };
 
@@ -184,7 +191,7 @@ fn handle_input(
 
 fn update_display(
     state: Res<ManualLoadingState>,
-    runner: Res<DialogueRunner>,
+    runner: Single<&DialogueRunner, With<Speaker>>,
     mut display_query: Query<&mut Text, With<DialogueDisplay>>,
 ) {
     let Ok(mut text) = display_query.single_mut() else {